@@ -0,0 +1,111 @@
+use std::collections::HashMap;
+
+use async_trait::async_trait;
+use dapr::dapr::dapr::proto::runtime::v1::{dapr_client::DaprClient, InvokeBindingRequest};
+use tonic::{Code, Status, transport::Channel as TonicChannel};
+
+use crate::external::dapr::dapr_errors::to_app_error;
+use crate::internal::{
+    interfaces::blob_store::BlobStoreInterface,
+    shared_reconciler_rust_libraries::models::entities::app_errors::{AppError, AppErrorKind},
+};
+
+const CREATE_OPERATION: &str = "create";
+const GET_OPERATION: &str = "get";
+
+//the metadata key the dapr storage bindings (local storage, s3, azure blob storage) read the file name from
+const FILE_NAME_METADATA_KEY: &str = "fileName";
+
+//the sidecar reports every failed binding call as internal, a missing file included,
+//so one is told apart by what the storage component behind the binding said
+const FILE_NOT_FOUND_MESSAGES: [&str; 4] = [
+    //local storage
+    "no such file or directory",
+    //s3
+    "NoSuchKey",
+    //azure blob storage
+    "BlobNotFound",
+    //gcp storage
+    "object doesn't exist",
+];
+
+//saves files through a dapr output binding, so the actual store is whatever the binding component points at
+//each call works on its own clone of the client, the clones share one connection to the sidecar
+pub struct DaprBindingBlobStore {
+    pub binding_name: String,
+    pub client: DaprClient<TonicChannel>,
+    pub retryable_status_codes: Vec<Code>,
+}
+
+#[async_trait]
+impl BlobStoreInterface for DaprBindingBlobStore {
+    async fn save_file(&self, file_name: &str, content: &[u8]) -> Result<(), AppError> {
+        match self.invoke_binding(CREATE_OPERATION, file_name, content.to_vec()).await {
+            Ok(_) => Ok(()),
+            Err(e) => Err(self.to_app_error(e)),
+        }
+    }
+
+    async fn read_file(&self, file_name: &str) -> Result<Vec<u8>, AppError> {
+        match self.read_file_if_exists(file_name).await? {
+            Some(content) => Ok(content),
+            None => Err(AppError::new(
+                AppErrorKind::NotFound,
+                format!("no file [{}] in binding [{}]", file_name, self.binding_name),
+            )),
+        }
+    }
+
+    async fn read_file_if_exists(&self, file_name: &str) -> Result<Option<Vec<u8>>, AppError> {
+        match self.invoke_binding(GET_OPERATION, file_name, vec![]).await {
+            Ok(content) => Ok(Some(content)),
+            Err(e) if is_file_not_found(&e) => Ok(None),
+            Err(e) => Err(self.to_app_error(e)),
+        }
+    }
+}
+
+impl DaprBindingBlobStore {
+    pub(crate) fn new(
+        binding_name: String,
+        client: DaprClient<TonicChannel>,
+        retryable_status_codes: Vec<Code>,
+    ) -> Self {
+        DaprBindingBlobStore {
+            binding_name,
+            client,
+            retryable_status_codes,
+        }
+    }
+
+    async fn invoke_binding(
+        &self,
+        operation: &str,
+        file_name: &str,
+        data: Vec<u8>,
+    ) -> Result<Vec<u8>, Status> {
+        let request = InvokeBindingRequest {
+            name: self.binding_name.clone(),
+            data,
+            metadata: HashMap::from([(String::from(FILE_NAME_METADATA_KEY), file_name.to_string())]),
+            operation: String::from(operation),
+        };
+
+        let response = self.client.clone().invoke_binding(request).await?;
+        Ok(response.into_inner().data)
+    }
+
+    fn to_app_error(&self, status: Status) -> AppError {
+        to_app_error(status.into(), &self.retryable_status_codes, "invoke_binding")
+    }
+}
+
+fn is_file_not_found(status: &Status) -> bool {
+    match status.code() {
+        Code::NotFound => true,
+        Code::Internal => FILE_NOT_FOUND_MESSAGES
+            .iter()
+            .any(|message| status.message().contains(message)),
+        _ => false,
+    }
+}
@@ -0,0 +1,116 @@
+use tonic::Code;
+
+use crate::external::dapr::{
+    dapr_client::connect_to_dapr, dapr_errors::DEFAULT_RETRYABLE_STATUS_CODES,
+    fake_dapr_sidecar::FakeDaprSidecar,
+};
+use crate::internal::{
+    interfaces::blob_store::BlobStoreInterface,
+    shared_reconciler_rust_libraries::models::entities::app_errors::AppErrorKind,
+};
+
+use super::dapr_binding_blob_store::DaprBindingBlobStore;
+
+#[actix_web::test]
+async fn given_file_save_file_creates_it_through_the_binding_under_its_name() {
+    //setup
+    let (sidecar, blob_store) = setup_blob_store().await;
+
+    //act
+    blob_store
+        .save_file(&String::from("RECON-TASK-1234-primary.csv"), b"id,amount\n1,10\n")
+        .await
+        .unwrap();
+
+    //assert
+    assert_eq!(
+        sidecar.get_binding_file("blobstore", "RECON-TASK-1234-primary.csv"),
+        Some(b"id,amount\n1,10\n".to_vec())
+    );
+}
+
+#[actix_web::test]
+async fn given_saved_file_read_file_returns_its_content() {
+    //setup
+    let (_, blob_store) = setup_blob_store().await;
+    let file_name = String::from("UPLOAD-SESSION-1234-chunk-0");
+    blob_store.save_file(&file_name, b"id,amount\n").await.unwrap();
+
+    //act
+    let content = blob_store.read_file(&file_name).await;
+
+    //assert
+    assert_eq!(content.unwrap(), b"id,amount\n".to_vec());
+}
+
+#[actix_web::test]
+async fn given_unknown_file_read_file_if_exists_returns_none() {
+    //setup
+    let (_, blob_store) = setup_blob_store().await;
+
+    //act
+    let result = blob_store
+        .read_file_if_exists(&String::from("RECON-TASK-1234-payments.csv"))
+        .await;
+
+    //assert
+    assert_eq!(result.unwrap(), None);
+}
+
+#[actix_web::test]
+async fn given_sidecar_failing_with_internal_error_read_file_if_exists_returns_it() {
+    //setup
+    let (sidecar, blob_store) = setup_blob_store().await;
+    sidecar.fail_with(Some(Code::Internal));
+
+    //act
+    let result = blob_store
+        .read_file_if_exists(&String::from("RECON-TASK-1234-payments.csv"))
+        .await;
+
+    //assert
+    assert!(matches!(result.err().unwrap().kind, AppErrorKind::InternalError));
+}
+
+#[actix_web::test]
+async fn given_unknown_file_read_file_returns_not_found() {
+    //setup
+    let (_, blob_store) = setup_blob_store().await;
+
+    //act
+    let result = blob_store
+        .read_file(&String::from("UPLOAD-SESSION-1234-chunk-9"))
+        .await;
+
+    //assert
+    assert!(matches!(result.err().unwrap().kind, AppErrorKind::NotFound));
+}
+
+#[actix_web::test]
+async fn given_sidecar_unavailable_save_file_returns_connection_error() {
+    //setup
+    let (sidecar, blob_store) = setup_blob_store().await;
+    sidecar.fail_with(Some(Code::Unavailable));
+
+    //act
+    let result = blob_store
+        .save_file(&String::from("RECON-TASK-1234-primary.csv"), b"id,amount\n")
+        .await;
+
+    //assert
+    assert!(matches!(result.err().unwrap().kind, AppErrorKind::ConnectionError));
+}
+
+async fn setup_blob_store() -> (FakeDaprSidecar, DaprBindingBlobStore) {
+    let sidecar = FakeDaprSidecar::new();
+    let address = sidecar.start().await;
+    let client = connect_to_dapr(&address).await.unwrap();
+
+    let blob_store = DaprBindingBlobStore::new(
+        String::from("blobstore"),
+        client,
+        DEFAULT_RETRYABLE_STATUS_CODES.to_vec(),
+    );
+
+    (sidecar, blob_store)
+}
@@ -0,0 +1,6 @@
+pub mod dapr_binding_blob_store;
+pub mod resilient_blob_store;
+
+#[cfg(test)]
+#[path = "./dapr_binding_blob_store_tests.rs"]
+mod dapr_binding_blob_store_tests;
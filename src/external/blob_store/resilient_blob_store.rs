@@ -0,0 +1,46 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+
+use crate::external::resilience::{
+    call_with_resilience, circuit_breaker::CircuitBreaker, retry_policy::RetryPolicy,
+};
+use crate::internal::{
+    interfaces::blob_store::BlobStoreInterface,
+    shared_reconciler_rust_libraries::models::entities::app_errors::AppError,
+};
+
+pub struct ResilientBlobStore {
+    pub inner: Box<dyn BlobStoreInterface>,
+    pub retry_policy: RetryPolicy,
+    pub circuit_breaker: Arc<CircuitBreaker>,
+}
+
+#[async_trait]
+impl BlobStoreInterface for ResilientBlobStore {
+    async fn save_file(&self, file_name: &str, content: &[u8]) -> Result<(), AppError> {
+        call_with_resilience!(self, self.inner.save_file(file_name, content))
+    }
+
+    async fn read_file(&self, file_name: &str) -> Result<Vec<u8>, AppError> {
+        call_with_resilience!(self, self.inner.read_file(file_name))
+    }
+
+    async fn read_file_if_exists(&self, file_name: &str) -> Result<Option<Vec<u8>>, AppError> {
+        call_with_resilience!(self, self.inner.read_file_if_exists(file_name))
+    }
+}
+
+impl ResilientBlobStore {
+    pub(crate) fn new(
+        inner: Box<dyn BlobStoreInterface>,
+        retry_policy: RetryPolicy,
+        circuit_breaker: Arc<CircuitBreaker>,
+    ) -> Self {
+        ResilientBlobStore {
+            inner,
+            retry_policy,
+            circuit_breaker,
+        }
+    }
+}
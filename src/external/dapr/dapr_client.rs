@@ -1,21 +1,23 @@
-use dapr::{Client, dapr::dapr::proto::runtime::v1::dapr_client::DaprClient};
+use dapr::dapr::dapr::proto::runtime::v1::dapr_client::DaprClient;
 use tonic::transport::Channel as TonicChannel;
 
 use crate::internal::shared_reconciler_rust_libraries::models::entities::app_errors::{AppError, AppErrorKind};
 
-pub async fn connect_to_dapr(connection_url: &String) -> Result<Client<DaprClient<TonicChannel>>, AppError> {
+//the generated grpc client rather than the sdk's wrapper:
+//it exposes etags, binding operations and metadata, and it is cheap to clone,
+//so every repository can hold its own copy and call the sidecar without a lock
+pub async fn connect_to_dapr(connection_url: &str) -> Result<DaprClient<TonicChannel>, AppError> {
     // Create the client
-    let dapr_grpc_server_address = connection_url.clone();
+    let dapr_grpc_server_address = connection_url.to_string();
 
     //connect to dapr
-    let client_connect_result =
-        dapr::Client::<dapr::client::TonicClient>::connect(dapr_grpc_server_address).await;
+    let client_connect_result = DaprClient::connect(dapr_grpc_server_address).await;
 
     //handle the connection result
-    return match client_connect_result {
+    match client_connect_result {
         //connection succeeded
         Ok(s) => Ok(s),
         //connection failed
         Err(e) => Err(AppError::new(AppErrorKind::ConnectionError, e.to_string())),
-    };
-}
\ No newline at end of file
+    }
+}
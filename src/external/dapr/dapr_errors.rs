@@ -0,0 +1,79 @@
+use tonic::Code;
+
+use crate::internal::shared_reconciler_rust_libraries::models::entities::app_errors::{
+    AppError, AppErrorKind,
+};
+
+pub const DEFAULT_RETRYABLE_STATUS_CODES: [Code; 4] = [
+    Code::Unavailable,
+    Code::DeadlineExceeded,
+    Code::ResourceExhausted,
+    Code::Aborted,
+];
+
+//the dapr sdk keeps the tonic status private inside its GrpcError,
+//so the only way to get at the code is through the debug output
+pub fn grpc_status_code(error: &dapr::error::Error) -> Option<Code> {
+    if let dapr::error::Error::TransportError = error {
+        return Some(Code::Unavailable);
+    }
+
+    let error_details = format!("{:?}", error);
+    ALL_STATUS_CODES
+        .iter()
+        .find(|code| error_details.contains(&format!("code: {:?},", code)))
+        .cloned()
+}
+
+//converts a failed sidecar call into an AppError
+//connectivity failures and retryable status codes surface as ConnectionErrors
+//so the resilience layer can retry them
+pub fn to_app_error(
+    error: dapr::error::Error,
+    retryable_status_codes: &[Code],
+    operation: &str,
+) -> AppError {
+    let message = format!("{} failed: {}", operation, error);
+
+    match grpc_status_code(&error) {
+        Some(Code::Unavailable) => AppError::new(AppErrorKind::ConnectionError, message),
+        Some(code) if retryable_status_codes.contains(&code) => {
+            AppError::new(AppErrorKind::ConnectionError, message)
+        }
+        Some(Code::NotFound) => AppError::new(AppErrorKind::NotFound, message),
+        Some(Code::InvalidArgument) => AppError::new(AppErrorKind::BadClientRequest, message),
+        _ => AppError::new(AppErrorKind::InternalError, message),
+    }
+}
+
+pub fn parse_status_codes(raw_codes: &str) -> Vec<Code> {
+    raw_codes
+        .split(',')
+        .map(|raw_code| raw_code.trim())
+        .filter_map(|raw_code| {
+            ALL_STATUS_CODES
+                .iter()
+                .find(|code| format!("{:?}", code).eq_ignore_ascii_case(raw_code))
+                .cloned()
+        })
+        .collect()
+}
+
+const ALL_STATUS_CODES: [Code; 16] = [
+    Code::Cancelled,
+    Code::Unknown,
+    Code::InvalidArgument,
+    Code::DeadlineExceeded,
+    Code::NotFound,
+    Code::AlreadyExists,
+    Code::PermissionDenied,
+    Code::ResourceExhausted,
+    Code::FailedPrecondition,
+    Code::Aborted,
+    Code::OutOfRange,
+    Code::Unimplemented,
+    Code::Internal,
+    Code::Unavailable,
+    Code::DataLoss,
+    Code::Unauthenticated,
+];
@@ -0,0 +1,451 @@
+use chrono::{DateTime, Utc};
+use dapr::dapr::dapr::proto::{
+    common::v1::{state_options::StateConcurrency, Etag, StateItem, StateOptions},
+    runtime::v1::{dapr_client::DaprClient, DeleteStateRequest, GetStateRequest, SaveStateRequest},
+};
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::Value;
+use tonic::{Code, transport::Channel as TonicChannel};
+
+use crate::external::dapr::dapr_errors::to_app_error;
+use crate::internal::{
+    models::entities::versioned_record::{VersionedRecord, VersionedSchema},
+    shared_reconciler_rust_libraries::models::entities::app_errors::{AppError, AppErrorKind},
+};
+
+//how often an etag guarded update re-reads the key after losing a race with another writer
+const MAX_ETAG_WRITE_ATTEMPTS: usize = 10;
+
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct MigrationReport {
+    pub records_checked: usize,
+    pub records_migrated: usize,
+    pub failed_keys: Vec<String>,
+}
+
+//thin wrapper around the dapr client for everything this service keeps in the state store
+//each call works on its own clone of the client, the clones share one connection to the sidecar
+pub struct DaprStateStore {
+    pub store_name: String,
+    pub client: DaprClient<TonicChannel>,
+    pub retryable_status_codes: Vec<Code>,
+}
+
+impl DaprStateStore {
+    pub fn new(
+        store_name: String,
+        client: DaprClient<TonicChannel>,
+        retryable_status_codes: Vec<Code>,
+    ) -> Self {
+        DaprStateStore {
+            store_name,
+            client,
+            retryable_status_codes,
+        }
+    }
+
+    pub async fn get_raw(&self, key: &str) -> Result<Vec<u8>, AppError> {
+        let (data, _etag) = self.get_raw_with_etag(key).await?;
+        Ok(data)
+    }
+
+    //the etag is None when the key does not exist
+    pub async fn get_raw_with_etag(&self, key: &str) -> Result<(Vec<u8>, Option<String>), AppError> {
+        let request = GetStateRequest {
+            store_name: self.store_name.clone(),
+            key: String::from(key),
+            ..Default::default()
+        };
+
+        return match self.client.clone().get_state(request).await {
+            Ok(response) => {
+                let response = response.into_inner();
+                let etag = Some(response.etag).filter(|etag| !etag.is_empty());
+                Ok((response.data, etag))
+            }
+            Err(e) => Err(to_app_error(e.into(), &self.retryable_status_codes, "get_state")),
+        };
+    }
+
+    pub async fn save_raw(&self, key: &str, value: Vec<u8>) -> Result<(), AppError> {
+        let request = SaveStateRequest {
+            store_name: self.store_name.clone(),
+            states: vec![StateItem {
+                key: String::from(key),
+                value,
+                ..Default::default()
+            }],
+        };
+
+        return match self.client.clone().save_state(request).await {
+            Ok(_s) => Ok(()),
+            Err(e) => Err(to_app_error(e.into(), &self.retryable_status_codes, "save_state")),
+        };
+    }
+
+    //first-write-wins save: it only goes through if the key still holds what was read with `etag`,
+    //or, when there is no etag, if the key does not exist yet
+    //returns false when another writer got there first
+    pub async fn save_raw_if_unchanged(
+        &self,
+        key: &str,
+        value: Vec<u8>,
+        etag: Option<String>,
+    ) -> Result<bool, AppError> {
+        let request = SaveStateRequest {
+            store_name: self.store_name.clone(),
+            states: vec![StateItem {
+                key: String::from(key),
+                value,
+                etag: etag.map(|value| Etag { value }),
+                options: Some(StateOptions {
+                    concurrency: StateConcurrency::ConcurrencyFirstWrite as i32,
+                    ..Default::default()
+                }),
+                ..Default::default()
+            }],
+        };
+
+        return match self.client.clone().save_state(request).await {
+            Ok(_s) => Ok(true),
+            //the sidecar reports an etag mismatch as aborted
+            Err(e) if e.code() == Code::Aborted => Ok(false),
+            Err(e) => Err(to_app_error(e.into(), &self.retryable_status_codes, "save_state")),
+        };
+    }
+
+    pub async fn delete(&self, key: &str) -> Result<(), AppError> {
+        let request = DeleteStateRequest {
+            store_name: self.store_name.clone(),
+            key: String::from(key),
+            ..Default::default()
+        };
+
+        return match self.client.clone().delete_state(request).await {
+            Ok(_s) => Ok(()),
+            Err(e) => Err(to_app_error(e.into(), &self.retryable_status_codes, "delete_state")),
+        };
+    }
+
+    pub async fn get_record<T: VersionedSchema + DeserializeOwned>(
+        &self,
+        key: &str,
+    ) -> Result<VersionedRecord<T>, AppError> {
+        let raw = self.get_raw(key).await?;
+        decode_record::<T>(key, &raw)
+    }
+
+    //always writes the current schema version, stamping updated_at with the time of the write
+    pub async fn save_record<T: VersionedSchema + Serialize>(
+        &self,
+        key: &str,
+        data: &T,
+        created_at: DateTime<Utc>,
+    ) -> Result<(), AppError> {
+        let value = encode_record(key, data, created_at)?;
+        return self.save_raw(key, value).await;
+    }
+
+    //like get_record, but a record behind the current schema version is also rewritten and indexed
+    //records saved before the index existed predate versioning, so this is how they get into the index
+    pub async fn get_record_migrating<T: VersionedSchema + DeserializeOwned + Serialize>(
+        &self,
+        key: &str,
+        index_key: &str,
+    ) -> Result<VersionedRecord<T>, AppError> {
+        let (raw, etag) = self.get_raw_with_etag(key).await?;
+        let record = decode_record::<T>(key, &raw)?;
+
+        if record.schema_version == T::current_schema_version() {
+            return Ok(record);
+        }
+
+        //losing the race is fine, whoever won wrote the current schema version
+        let value = encode_record(key, &record.data, record.created_at)?;
+        self.save_raw_if_unchanged(key, value, etag).await?;
+        self.add_to_index(index_key, key).await?;
+
+        Ok(record)
+    }
+
+    //get_record_migrating plus the etag of the record as it is stored, for save_record_if_unchanged
+    //a record that was rewritten at the current schema version is read again, since the rewrite changed its etag
+    pub async fn get_record_migrating_with_etag<T: VersionedSchema + DeserializeOwned + Serialize>(
+        &self,
+        key: &str,
+        index_key: &str,
+    ) -> Result<(VersionedRecord<T>, Option<String>), AppError> {
+        let (raw, etag) = self.get_raw_with_etag(key).await?;
+        let record = decode_record::<T>(key, &raw)?;
+
+        if record.schema_version == T::current_schema_version() {
+            return Ok((record, etag));
+        }
+
+        let value = encode_record(key, &record.data, record.created_at)?;
+        self.save_raw_if_unchanged(key, value, etag).await?;
+        self.add_to_index(index_key, key).await?;
+
+        let (raw, etag) = self.get_raw_with_etag(key).await?;
+        Ok((decode_record::<T>(key, &raw)?, etag))
+    }
+
+    //save_record that only goes through while the key still holds what was read with `etag`
+    //returns false when another writer changed it since
+    pub async fn save_record_if_unchanged<T: VersionedSchema + Serialize>(
+        &self,
+        key: &str,
+        data: &T,
+        created_at: DateTime<Utc>,
+        etag: Option<String>,
+    ) -> Result<bool, AppError> {
+        let value = encode_record(key, data, created_at)?;
+        return self.save_raw_if_unchanged(key, value, etag).await;
+    }
+
+    //dapr has no way to list keys, so every record type keeps an index of its keys
+    pub async fn read_index(&self, index_key: &str) -> Result<Vec<String>, AppError> {
+        let raw = self.get_raw(index_key).await?;
+
+        if raw.is_empty() {
+            return Ok(vec![]);
+        }
+
+        decode_state::<Vec<String>>(index_key, &raw)
+    }
+
+    pub async fn add_to_index(&self, index_key: &str, key: &str) -> Result<(), AppError> {
+        let _ = self
+            .update_with_etag(index_key, |keys: &mut Vec<String>| {
+                if keys.iter().any(|k| k == key) {
+                    return false;
+                }
+
+                keys.push(String::from(key));
+                true
+            })
+            .await?;
+
+        Ok(())
+    }
+
+    pub async fn remove_from_index(&self, index_key: &str, key: &str) -> Result<(), AppError> {
+        let _ = self
+            .update_with_etag(index_key, |keys: &mut Vec<String>| {
+                let original_len = keys.len();
+                keys.retain(|k| k != key);
+                keys.len() != original_len
+            })
+            .await?;
+
+        Ok(())
+    }
+
+    //a read-modify-write guarded by the etag, so concurrent writers cannot undo each other's changes
+    //a missing key starts out as the default value, `change` returns false when nothing needs writing
+    //returns whether the change was written
+    pub async fn update_with_etag<V, F>(&self, key: &str, change: F) -> Result<bool, AppError>
+    where
+        V: DeserializeOwned + Serialize + Default,
+        F: Fn(&mut V) -> bool,
+    {
+        for _ in 0..MAX_ETAG_WRITE_ATTEMPTS {
+            let (raw, etag) = self.get_raw_with_etag(key).await?;
+            let mut value = match raw.is_empty() {
+                true => V::default(),
+                false => decode_state::<V>(key, &raw)?,
+            };
+
+            if !change(&mut value) {
+                return Ok(false);
+            }
+
+            let encoded_value = match serde_json::to_vec(&value) {
+                Ok(v) => v,
+                Err(e) => {
+                    return Err(AppError::new(
+                        AppErrorKind::InternalError,
+                        format!("unable to serialize value with key [{}]: {}", key, e),
+                    ))
+                }
+            };
+
+            if self.save_raw_if_unchanged(key, encoded_value, etag).await? {
+                return Ok(true);
+            }
+        }
+
+        //a connection error so the resilience layer backs off and retries the whole write
+        Err(AppError::new(
+            AppErrorKind::ConnectionError,
+            format!("key [{}] kept changing while it was being updated", key),
+        ))
+    }
+
+    //update_with_etag for a record that has to exist already, its created_at is kept as it was
+    //`change` returns false when nothing needs writing, the record is returned as it is stored afterwards
+    pub async fn update_record_with_etag<T, F>(&self, key: &str, change: F) -> Result<T, AppError>
+    where
+        T: VersionedSchema + DeserializeOwned + Serialize + Clone,
+        F: Fn(&mut T) -> bool,
+    {
+        return self.change_record_with_etag(key, None, change).await;
+    }
+
+    //update_record_with_etag for a record that may not exist yet, a missing record starts out as `initial`
+    pub async fn upsert_record_with_etag<T, F>(
+        &self,
+        key: &str,
+        initial: T,
+        change: F,
+    ) -> Result<T, AppError>
+    where
+        T: VersionedSchema + DeserializeOwned + Serialize + Clone,
+        F: Fn(&mut T) -> bool,
+    {
+        return self.change_record_with_etag(key, Some(initial), change).await;
+    }
+
+    async fn change_record_with_etag<T, F>(
+        &self,
+        key: &str,
+        initial: Option<T>,
+        change: F,
+    ) -> Result<T, AppError>
+    where
+        T: VersionedSchema + DeserializeOwned + Serialize + Clone,
+        F: Fn(&mut T) -> bool,
+    {
+        for _ in 0..MAX_ETAG_WRITE_ATTEMPTS {
+            let (raw, etag) = self.get_raw_with_etag(key).await?;
+            let (mut data, created_at) = match (&initial, raw.is_empty()) {
+                (Some(initial), true) => (initial.clone(), Utc::now()),
+                _ => {
+                    let record = decode_record::<T>(key, &raw)?;
+                    (record.data, record.created_at)
+                }
+            };
+
+            if !change(&mut data) {
+                return Ok(data);
+            }
+
+            let value = encode_record(key, &data, created_at)?;
+            if self.save_raw_if_unchanged(key, value, etag).await? {
+                return Ok(data);
+            }
+        }
+
+        //a connection error so the resilience layer backs off and retries the whole write
+        Err(AppError::new(
+            AppErrorKind::ConnectionError,
+            format!("key [{}] kept changing while it was being updated", key),
+        ))
+    }
+
+    //rewrites every indexed record that is behind the current schema version
+    pub async fn migrate_records<T: VersionedSchema + DeserializeOwned + Serialize>(
+        &self,
+        index_key: &str,
+    ) -> Result<MigrationReport, AppError> {
+        let mut report = MigrationReport::default();
+
+        for key in self.read_index(index_key).await? {
+            report.records_checked += 1;
+
+            let read_result = self.get_raw_with_etag(&key).await.and_then(|(raw, etag)| {
+                decode_record::<T>(&key, &raw).map(|record| (record, etag))
+            });
+
+            let (record, etag) = match read_result {
+                Ok(read) => read,
+                //the record was deleted but its key is still in the index
+                Err(e) if matches!(e.kind, AppErrorKind::NotFound) => continue,
+                Err(e) if matches!(e.kind, AppErrorKind::ConnectionError) => return Err(e),
+                Err(_) => {
+                    report.failed_keys.push(key);
+                    continue;
+                }
+            };
+
+            if record.schema_version == T::current_schema_version() {
+                continue;
+            }
+
+            //a record that changed since it was read was written by the service at the current version
+            let value = encode_record(&key, &record.data, record.created_at)?;
+            if self.save_raw_if_unchanged(&key, value, etag).await? {
+                report.records_migrated += 1;
+            }
+        }
+
+        Ok(report)
+    }
+}
+
+fn encode_record<T: VersionedSchema + Serialize>(
+    key: &str,
+    data: &T,
+    created_at: DateTime<Utc>,
+) -> Result<Vec<u8>, AppError> {
+    let record = VersionedRecord {
+        schema_version: T::current_schema_version(),
+        created_at,
+        updated_at: Utc::now(),
+        data,
+    };
+
+    match serde_json::to_vec(&record) {
+        Ok(value) => Ok(value),
+        Err(e) => Err(AppError::new(
+            AppErrorKind::InternalError,
+            format!("unable to serialize record with key [{}]: {}", key, e),
+        )),
+    }
+}
+
+//dapr returns an empty payload instead of an error when a key does not exist,
+//so an empty payload is a NotFound and anything we cannot parse is corrupt data
+pub fn decode_state<T: DeserializeOwned>(key: &str, data: &[u8]) -> Result<T, AppError> {
+    if data.is_empty() {
+        return Err(AppError::new(
+            AppErrorKind::NotFound,
+            format!("no record found with key [{}]", key),
+        ));
+    }
+
+    match serde_json::from_slice::<T>(data) {
+        Ok(decoded) => Ok(decoded),
+        Err(e) => Err(data_corruption_error(key, e.to_string())),
+    }
+}
+
+//reads a stored record, upgrading it to the current schema version on the way out
+//the returned schema_version is the one the record was stored with
+pub fn decode_record<T: VersionedSchema + DeserializeOwned>(
+    key: &str,
+    data: &[u8],
+) -> Result<VersionedRecord<T>, AppError> {
+    let raw = decode_state::<Value>(key, data)?;
+
+    let upgraded = VersionedRecord::from_stored_json(raw)
+        .and_then(|record| record.upgrade::<T>())
+        .map_err(|e| data_corruption_error(key, e))?;
+
+    match serde_json::from_value::<T>(upgraded.data) {
+        Ok(data) => Ok(VersionedRecord {
+            schema_version: upgraded.schema_version,
+            created_at: upgraded.created_at,
+            updated_at: upgraded.updated_at,
+            data,
+        }),
+        Err(e) => Err(data_corruption_error(key, e.to_string())),
+    }
+}
+
+fn data_corruption_error(key: &str, reason: String) -> AppError {
+    AppError::new(
+        AppErrorKind::ResponseUnmarshalError,
+        format!("data corruption: record with key [{}] could not be read: {}", key, reason),
+    )
+}
@@ -0,0 +1,493 @@
+use std::collections::HashMap;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+
+use dapr::dapr::dapr::proto::{
+    common::v1::{state_options::StateConcurrency, InvokeResponse, StateItem},
+    runtime::v1::{
+        dapr_server::{Dapr, DaprServer},
+        DeleteBulkStateRequest, DeleteStateRequest, ExecuteActorStateTransactionRequest,
+        ExecuteStateTransactionRequest, GetActorStateRequest, GetActorStateResponse,
+        GetBulkSecretRequest, GetBulkSecretResponse, GetBulkStateRequest, GetBulkStateResponse,
+        GetConfigurationRequest, GetConfigurationResponse,
+        GetMetadataResponse, GetSecretRequest, GetSecretResponse, GetStateRequest,
+        GetStateResponse, InvokeActorRequest, InvokeActorResponse, InvokeBindingRequest,
+        InvokeBindingResponse, InvokeServiceRequest, PublishEventRequest, QueryStateRequest,
+        QueryStateResponse, RegisterActorReminderRequest, RegisterActorTimerRequest,
+        RenameActorReminderRequest, SaveStateRequest, SetMetadataRequest,
+        SubscribeConfigurationRequest, SubscribeConfigurationResponse, TryLockRequest,
+        TryLockResponse, UnlockRequest, UnlockResponse, UnregisterActorReminderRequest,
+        UnregisterActorTimerRequest, UnsubscribeConfigurationRequest,
+        UnsubscribeConfigurationResponse,
+    },
+};
+use tokio::net::TcpListener;
+use tokio_stream::{wrappers::TcpListenerStream, Stream};
+use tonic::{Code, Request, Response, Status};
+
+//an in-process stand-in for the dapr sidecar so the real repository managers
+//can be exercised in `cargo test` without a network or a running daprd
+#[derive(Clone, Default)]
+pub struct FakeDaprSidecar {
+    pub state: Arc<Mutex<HashMap<String, Vec<u8>>>>,
+    //the etag of every key in the state store, bumped on each write
+    //only touched while holding the state lock
+    pub etags: Arc<Mutex<HashMap<String, u64>>>,
+    pub published_events: Arc<Mutex<Vec<PublishedEvent>>>,
+    //files saved through output bindings, keyed like the state store by binding and file name
+    pub binding_files: Arc<Mutex<HashMap<String, Vec<u8>>>>,
+    pub failure: Arc<Mutex<Option<Code>>>,
+}
+
+#[derive(Clone, Debug)]
+pub struct PublishedEvent {
+    pub pubsub_name: String,
+    pub topic: String,
+    pub data: Vec<u8>,
+    pub data_content_type: String,
+}
+
+impl FakeDaprSidecar {
+    pub fn new() -> Self {
+        FakeDaprSidecar::default()
+    }
+
+    //starts serving on a random local port and returns the grpc address to connect to
+    pub async fn start(&self) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = format!("http://{}", listener.local_addr().unwrap());
+        let service = DaprServer::new(self.clone());
+
+        tokio::spawn(async move {
+            tonic::transport::Server::builder()
+                .add_service(service)
+                .serve_with_incoming(TcpListenerStream::new(listener))
+                .await
+                .unwrap();
+        });
+
+        address
+    }
+
+    pub fn put_raw(&self, store_name: &str, key: &str, value: Vec<u8>) {
+        let mut state = self.state.lock().unwrap();
+        let key = state_key(store_name, key);
+        self.bump_etag(&key);
+        state.insert(key, value);
+    }
+
+    fn bump_etag(&self, key: &str) {
+        *self.etags.lock().unwrap().entry(String::from(key)).or_insert(0) += 1;
+    }
+
+    //like the real sidecar: a given etag has to match the stored one,
+    //and first-write-wins without an etag only saves keys that do not exist yet
+    fn etag_mismatch(&self, key: &str, item: &StateItem) -> Option<Status> {
+        let current_etag = self.etags.lock().unwrap().get(key).map(|etag| etag.to_string());
+        let is_first_write = item
+            .options
+            .as_ref()
+            .map(|options| options.concurrency == StateConcurrency::ConcurrencyFirstWrite as i32)
+            .unwrap_or(false);
+
+        let is_mismatch = match &item.etag {
+            Some(etag) => current_etag.as_ref() != Some(&etag.value),
+            None => is_first_write && current_etag.is_some(),
+        };
+
+        is_mismatch.then(|| {
+            Status::aborted(format!(
+                "failed saving state for key [{}]: possible etag mismatch",
+                item.key
+            ))
+        })
+    }
+
+    pub fn get_raw(&self, store_name: &str, key: &str) -> Option<Vec<u8>> {
+        self.state
+            .lock()
+            .unwrap()
+            .get(&state_key(store_name, key))
+            .cloned()
+    }
+
+    pub fn published_events_on(&self, topic: &str) -> Vec<PublishedEvent> {
+        self.published_events
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|event| event.topic == topic)
+            .cloned()
+            .collect()
+    }
+
+    pub fn put_binding_file(&self, binding_name: &str, file_name: &str, content: Vec<u8>) {
+        self.binding_files
+            .lock()
+            .unwrap()
+            .insert(state_key(binding_name, file_name), content);
+    }
+
+    pub fn get_binding_file(&self, binding_name: &str, file_name: &str) -> Option<Vec<u8>> {
+        self.binding_files
+            .lock()
+            .unwrap()
+            .get(&state_key(binding_name, file_name))
+            .cloned()
+    }
+
+    //every call fails with the given status code until cleared with None
+    pub fn fail_with(&self, code: Option<Code>) {
+        *self.failure.lock().unwrap() = code;
+    }
+
+    fn failure(&self) -> Option<Status> {
+        self.failure
+            .lock()
+            .unwrap()
+            .map(|code| Status::new(code, "fake dapr sidecar failure"))
+    }
+}
+
+fn state_key(store_name: &str, key: &str) -> String {
+    format!("{}||{}", store_name, key)
+}
+
+fn unimplemented(rpc: &str) -> Status {
+    Status::unimplemented(format!("{} is not supported by the fake dapr sidecar", rpc))
+}
+
+#[tonic::async_trait]
+impl Dapr for FakeDaprSidecar {
+    async fn get_state(
+        &self,
+        request: Request<GetStateRequest>,
+    ) -> Result<Response<GetStateResponse>, Status> {
+        if let Some(status) = self.failure() {
+            return Err(status);
+        }
+        let request = request.into_inner();
+
+        //like the real sidecar, a missing key is an empty payload rather than an error
+        let key = state_key(&request.store_name, &request.key);
+        let state = self.state.lock().unwrap();
+        let data = state.get(&key).cloned().unwrap_or_default();
+        let etag = self
+            .etags
+            .lock()
+            .unwrap()
+            .get(&key)
+            .map(|etag| etag.to_string())
+            .unwrap_or_default();
+
+        Ok(Response::new(GetStateResponse {
+            data,
+            etag,
+            ..Default::default()
+        }))
+    }
+
+    async fn save_state(&self, request: Request<SaveStateRequest>) -> Result<Response<()>, Status> {
+        if let Some(status) = self.failure() {
+            return Err(status);
+        }
+        let request = request.into_inner();
+
+        //check every etag first so a conflicting save leaves the store untouched
+        let mut state = self.state.lock().unwrap();
+        for item in &request.states {
+            if let Some(status) = self.etag_mismatch(&state_key(&request.store_name, &item.key), item) {
+                return Err(status);
+            }
+        }
+
+        for item in request.states {
+            let key = state_key(&request.store_name, &item.key);
+            self.bump_etag(&key);
+            state.insert(key, item.value);
+        }
+
+        Ok(Response::new(()))
+    }
+
+    async fn delete_state(
+        &self,
+        request: Request<DeleteStateRequest>,
+    ) -> Result<Response<()>, Status> {
+        if let Some(status) = self.failure() {
+            return Err(status);
+        }
+        let request = request.into_inner();
+
+        let mut state = self.state.lock().unwrap();
+        let key = state_key(&request.store_name, &request.key);
+        self.etags.lock().unwrap().remove(&key);
+        state.remove(&key);
+
+        Ok(Response::new(()))
+    }
+
+    async fn execute_state_transaction(
+        &self,
+        request: Request<ExecuteStateTransactionRequest>,
+    ) -> Result<Response<()>, Status> {
+        if let Some(status) = self.failure() {
+            return Err(status);
+        }
+        let request = request.into_inner();
+
+        //validate every operation first so a bad transaction leaves the store untouched
+        let mut operations = vec![];
+        for operation in request.operations {
+            let item = match operation.request {
+                Some(item) => item,
+                None => return Err(Status::invalid_argument("operation has no state item")),
+            };
+
+            match operation.operation_type.as_str() {
+                "upsert" | "delete" => operations.push((operation.operation_type, item)),
+                other => {
+                    return Err(Status::invalid_argument(format!(
+                        "unsupported operation type [{}]",
+                        other
+                    )))
+                }
+            }
+        }
+
+        let mut state = self.state.lock().unwrap();
+        for (operation_type, item) in operations {
+            let key = state_key(&request.store_name, &item.key);
+            if operation_type == "upsert" {
+                self.bump_etag(&key);
+                state.insert(key, item.value);
+            } else {
+                self.etags.lock().unwrap().remove(&key);
+                state.remove(&key);
+            }
+        }
+
+        Ok(Response::new(()))
+    }
+
+    async fn publish_event(
+        &self,
+        request: Request<PublishEventRequest>,
+    ) -> Result<Response<()>, Status> {
+        if let Some(status) = self.failure() {
+            return Err(status);
+        }
+        let request = request.into_inner();
+
+        self.published_events.lock().unwrap().push(PublishedEvent {
+            pubsub_name: request.pubsub_name,
+            topic: request.topic,
+            data: request.data,
+            data_content_type: request.data_content_type,
+        });
+
+        Ok(Response::new(()))
+    }
+
+    async fn invoke_service(
+        &self,
+        _request: Request<InvokeServiceRequest>,
+    ) -> Result<Response<InvokeResponse>, Status> {
+        Err(unimplemented("InvokeService"))
+    }
+
+    async fn query_state_alpha1(
+        &self,
+        _request: Request<QueryStateRequest>,
+    ) -> Result<Response<QueryStateResponse>, Status> {
+        Err(unimplemented("QueryStateAlpha1"))
+    }
+
+    async fn get_bulk_state(
+        &self,
+        _request: Request<GetBulkStateRequest>,
+    ) -> Result<Response<GetBulkStateResponse>, Status> {
+        Err(unimplemented("GetBulkState"))
+    }
+
+    async fn delete_bulk_state(
+        &self,
+        _request: Request<DeleteBulkStateRequest>,
+    ) -> Result<Response<()>, Status> {
+        Err(unimplemented("DeleteBulkState"))
+    }
+
+    //behaves like a storage binding: files are named by the fileName metadata entry
+    async fn invoke_binding(
+        &self,
+        request: Request<InvokeBindingRequest>,
+    ) -> Result<Response<InvokeBindingResponse>, Status> {
+        if let Some(status) = self.failure() {
+            return Err(status);
+        }
+        let request = request.into_inner();
+
+        let file_name = match request.metadata.get("fileName") {
+            Some(file_name) => file_name.clone(),
+            None => return Err(Status::invalid_argument("fileName metadata is required")),
+        };
+        let key = state_key(&request.name, &file_name);
+
+        let mut binding_files = self.binding_files.lock().unwrap();
+        let data = match request.operation.as_str() {
+            "create" => {
+                binding_files.insert(key, request.data);
+                vec![]
+            }
+            "get" => match binding_files.get(&key) {
+                Some(data) => data.clone(),
+                //what the real sidecar answers for a file missing from a local storage binding
+                None => {
+                    return Err(Status::internal(format!(
+                        "error invoking output binding {}: open /tmp/{}: no such file or directory",
+                        request.name, file_name
+                    )))
+                }
+            },
+            "delete" => {
+                binding_files.remove(&key);
+                vec![]
+            }
+            other => {
+                return Err(Status::invalid_argument(format!(
+                    "unsupported binding operation [{}]",
+                    other
+                )))
+            }
+        };
+
+        Ok(Response::new(InvokeBindingResponse {
+            data,
+            ..Default::default()
+        }))
+    }
+
+    async fn get_secret(
+        &self,
+        _request: Request<GetSecretRequest>,
+    ) -> Result<Response<GetSecretResponse>, Status> {
+        Err(unimplemented("GetSecret"))
+    }
+
+    async fn get_bulk_secret(
+        &self,
+        _request: Request<GetBulkSecretRequest>,
+    ) -> Result<Response<GetBulkSecretResponse>, Status> {
+        Err(unimplemented("GetBulkSecret"))
+    }
+
+    async fn register_actor_timer(
+        &self,
+        _request: Request<RegisterActorTimerRequest>,
+    ) -> Result<Response<()>, Status> {
+        Err(unimplemented("RegisterActorTimer"))
+    }
+
+    async fn unregister_actor_timer(
+        &self,
+        _request: Request<UnregisterActorTimerRequest>,
+    ) -> Result<Response<()>, Status> {
+        Err(unimplemented("UnregisterActorTimer"))
+    }
+
+    async fn register_actor_reminder(
+        &self,
+        _request: Request<RegisterActorReminderRequest>,
+    ) -> Result<Response<()>, Status> {
+        Err(unimplemented("RegisterActorReminder"))
+    }
+
+    async fn unregister_actor_reminder(
+        &self,
+        _request: Request<UnregisterActorReminderRequest>,
+    ) -> Result<Response<()>, Status> {
+        Err(unimplemented("UnregisterActorReminder"))
+    }
+
+    async fn rename_actor_reminder(
+        &self,
+        _request: Request<RenameActorReminderRequest>,
+    ) -> Result<Response<()>, Status> {
+        Err(unimplemented("RenameActorReminder"))
+    }
+
+    async fn get_actor_state(
+        &self,
+        _request: Request<GetActorStateRequest>,
+    ) -> Result<Response<GetActorStateResponse>, Status> {
+        Err(unimplemented("GetActorState"))
+    }
+
+    async fn execute_actor_state_transaction(
+        &self,
+        _request: Request<ExecuteActorStateTransactionRequest>,
+    ) -> Result<Response<()>, Status> {
+        Err(unimplemented("ExecuteActorStateTransaction"))
+    }
+
+    async fn invoke_actor(
+        &self,
+        _request: Request<InvokeActorRequest>,
+    ) -> Result<Response<InvokeActorResponse>, Status> {
+        Err(unimplemented("InvokeActor"))
+    }
+
+    async fn get_configuration_alpha1(
+        &self,
+        _request: Request<GetConfigurationRequest>,
+    ) -> Result<Response<GetConfigurationResponse>, Status> {
+        Err(unimplemented("GetConfigurationAlpha1"))
+    }
+
+    type SubscribeConfigurationAlpha1Stream = Pin<
+        Box<dyn Stream<Item = Result<SubscribeConfigurationResponse, Status>> + Send + Sync>,
+    >;
+
+    async fn subscribe_configuration_alpha1(
+        &self,
+        _request: Request<SubscribeConfigurationRequest>,
+    ) -> Result<Response<Self::SubscribeConfigurationAlpha1Stream>, Status> {
+        Err(unimplemented("SubscribeConfigurationAlpha1"))
+    }
+
+    async fn unsubscribe_configuration_alpha1(
+        &self,
+        _request: Request<UnsubscribeConfigurationRequest>,
+    ) -> Result<Response<UnsubscribeConfigurationResponse>, Status> {
+        Err(unimplemented("UnsubscribeConfigurationAlpha1"))
+    }
+
+    async fn try_lock_alpha1(
+        &self,
+        _request: Request<TryLockRequest>,
+    ) -> Result<Response<TryLockResponse>, Status> {
+        Err(unimplemented("TryLockAlpha1"))
+    }
+
+    async fn unlock_alpha1(
+        &self,
+        _request: Request<UnlockRequest>,
+    ) -> Result<Response<UnlockResponse>, Status> {
+        Err(unimplemented("UnlockAlpha1"))
+    }
+
+    async fn get_metadata(&self, _request: Request<()>) -> Result<Response<GetMetadataResponse>, Status> {
+        Err(unimplemented("GetMetadata"))
+    }
+
+    async fn set_metadata(
+        &self,
+        _request: Request<SetMetadataRequest>,
+    ) -> Result<Response<()>, Status> {
+        Err(unimplemented("SetMetadata"))
+    }
+
+    async fn shutdown(&self, _request: Request<()>) -> Result<Response<()>, Status> {
+        Err(unimplemented("Shutdown"))
+    }
+}
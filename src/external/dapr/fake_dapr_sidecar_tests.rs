@@ -0,0 +1,156 @@
+use dapr::dapr::dapr::proto::{
+    common::v1::{state_options::StateConcurrency, Etag, StateItem, StateOptions},
+    runtime::v1::{
+        dapr_server::Dapr, ExecuteStateTransactionRequest, GetStateRequest, PublishEventRequest,
+        SaveStateRequest, TransactionalStateOperation,
+    },
+};
+use tonic::{Code, Request};
+
+use super::fake_dapr_sidecar::FakeDaprSidecar;
+
+#[actix_web::test]
+async fn given_transaction_with_upserts_and_deletes_applies_all_operations() {
+    //setup
+    let sidecar = FakeDaprSidecar::new();
+    sidecar.put_raw("statestore", "stale-key", b"stale".to_vec());
+
+    let request = ExecuteStateTransactionRequest {
+        store_name: String::from("statestore"),
+        operations: vec![
+            transaction_operation("upsert", "new-key", b"fresh".to_vec()),
+            transaction_operation("delete", "stale-key", vec![]),
+        ],
+        ..Default::default()
+    };
+
+    //act
+    let result = sidecar.execute_state_transaction(Request::new(request)).await;
+
+    //assert
+    assert!(result.is_ok());
+    assert_eq!(sidecar.get_raw("statestore", "new-key"), Some(b"fresh".to_vec()));
+    assert_eq!(sidecar.get_raw("statestore", "stale-key"), None);
+}
+
+#[actix_web::test]
+async fn given_transaction_with_unknown_operation_leaves_store_untouched() {
+    //setup
+    let sidecar = FakeDaprSidecar::new();
+
+    let request = ExecuteStateTransactionRequest {
+        store_name: String::from("statestore"),
+        operations: vec![
+            transaction_operation("upsert", "new-key", b"fresh".to_vec()),
+            transaction_operation("merge", "other-key", vec![]),
+        ],
+        ..Default::default()
+    };
+
+    //act
+    let result = sidecar.execute_state_transaction(Request::new(request)).await;
+
+    //assert
+    assert!(result.is_err());
+    assert_eq!(sidecar.get_raw("statestore", "new-key"), None);
+}
+
+#[actix_web::test]
+async fn given_stale_etag_save_state_is_aborted_and_leaves_the_value() {
+    //setup
+    let sidecar = FakeDaprSidecar::new();
+    sidecar.put_raw("statestore", "key", b"first".to_vec());
+    let stale_etag = read_etag(&sidecar, "key").await;
+    sidecar.put_raw("statestore", "key", b"second".to_vec());
+
+    //act
+    let result = sidecar
+        .save_state(Request::new(save_request("key", b"third".to_vec(), Some(stale_etag))))
+        .await;
+
+    //assert
+    assert_eq!(result.err().unwrap().code(), Code::Aborted);
+    assert_eq!(sidecar.get_raw("statestore", "key"), Some(b"second".to_vec()));
+}
+
+#[actix_web::test]
+async fn given_first_write_without_etag_save_state_only_saves_new_keys() {
+    //setup
+    let sidecar = FakeDaprSidecar::new();
+    sidecar.put_raw("statestore", "existing-key", b"first".to_vec());
+
+    //act
+    let existing_key_result = sidecar
+        .save_state(Request::new(save_request("existing-key", b"second".to_vec(), None)))
+        .await;
+    let new_key_result = sidecar
+        .save_state(Request::new(save_request("new-key", b"first".to_vec(), None)))
+        .await;
+
+    //assert
+    assert_eq!(existing_key_result.err().unwrap().code(), Code::Aborted);
+    assert!(new_key_result.is_ok());
+    assert_eq!(sidecar.get_raw("statestore", "new-key"), Some(b"first".to_vec()));
+}
+
+#[actix_web::test]
+async fn given_published_event_records_it_against_its_topic() {
+    //setup
+    let sidecar = FakeDaprSidecar::new();
+
+    let request = PublishEventRequest {
+        pubsub_name: String::from("pubsub"),
+        topic: String::from("RECON-RESULTS-QUEUE-1234"),
+        data: b"{}".to_vec(),
+        data_content_type: String::from("application/json"),
+        ..Default::default()
+    };
+
+    //act
+    let result = sidecar.publish_event(Request::new(request)).await;
+
+    //assert
+    assert!(result.is_ok());
+    assert_eq!(sidecar.published_events_on("RECON-RESULTS-QUEUE-1234").len(), 1);
+}
+
+fn transaction_operation(
+    operation_type: &str,
+    key: &str,
+    value: Vec<u8>,
+) -> TransactionalStateOperation {
+    TransactionalStateOperation {
+        operation_type: String::from(operation_type),
+        request: Some(StateItem {
+            key: String::from(key),
+            value,
+            ..Default::default()
+        }),
+    }
+}
+
+async fn read_etag(sidecar: &FakeDaprSidecar, key: &str) -> String {
+    let request = GetStateRequest {
+        store_name: String::from("statestore"),
+        key: String::from(key),
+        ..Default::default()
+    };
+
+    return sidecar.get_state(Request::new(request)).await.unwrap().into_inner().etag;
+}
+
+fn save_request(key: &str, value: Vec<u8>, etag: Option<String>) -> SaveStateRequest {
+    SaveStateRequest {
+        store_name: String::from("statestore"),
+        states: vec![StateItem {
+            key: String::from(key),
+            value,
+            etag: etag.map(|value| Etag { value }),
+            options: Some(StateOptions {
+                concurrency: StateConcurrency::ConcurrencyFirstWrite as i32,
+                ..Default::default()
+            }),
+            ..Default::default()
+        }],
+    }
+}
@@ -1 +1,10 @@
-pub mod dapr_client;
\ No newline at end of file
+pub mod dapr_client;
+pub mod dapr_errors;
+pub mod dapr_state;
+
+#[cfg(test)]
+pub mod fake_dapr_sidecar;
+
+#[cfg(test)]
+#[path = "./fake_dapr_sidecar_tests.rs"]
+mod fake_dapr_sidecar_tests;
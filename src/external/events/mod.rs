@@ -0,0 +1,6 @@
+pub mod recon_task_event_publisher;
+pub mod resilient_recon_task_event_publisher;
+
+#[cfg(test)]
+#[path = "./recon_task_event_publisher_tests.rs"]
+mod recon_task_event_publisher_tests;
@@ -0,0 +1,64 @@
+use async_trait::async_trait;
+use dapr::dapr::dapr::proto::runtime::v1::{dapr_client::DaprClient, PublishEventRequest};
+use tonic::{Code, transport::Channel as TonicChannel};
+
+use crate::external::dapr::dapr_errors::to_app_error;
+use crate::internal::{
+    interfaces::recon_task_event_publisher::ReconTaskEventPublisherInterface,
+    models::entities::recon_task_event::ReconTaskEvent,
+    shared_reconciler_rust_libraries::models::entities::app_errors::{AppError, AppErrorKind},
+};
+
+const JSON_CONTENT_TYPE: &str = "application/json";
+
+pub struct ReconTaskEventPublisher {
+    pub pubsub_name: String,
+    pub client: DaprClient<TonicChannel>,
+    pub retryable_status_codes: Vec<Code>,
+}
+
+#[async_trait]
+impl ReconTaskEventPublisherInterface for ReconTaskEventPublisher {
+    async fn publish_task_event(
+        &self,
+        topic: &str,
+        event: &ReconTaskEvent,
+    ) -> Result<(), AppError> {
+        let data = match serde_json::to_vec(event) {
+            Ok(data) => data,
+            Err(e) => {
+                return Err(AppError::new(
+                    AppErrorKind::InternalError,
+                    format!("unable to serialize event for topic [{}]: {}", topic, e),
+                ))
+            }
+        };
+
+        let request = PublishEventRequest {
+            pubsub_name: self.pubsub_name.clone(),
+            topic: topic.to_string(),
+            data,
+            data_content_type: String::from(JSON_CONTENT_TYPE),
+            ..Default::default()
+        };
+
+        return match self.client.clone().publish_event(request).await {
+            Ok(_) => Ok(()),
+            Err(e) => Err(to_app_error(e.into(), &self.retryable_status_codes, "publish_event")),
+        };
+    }
+}
+
+impl ReconTaskEventPublisher {
+    pub(crate) fn new(
+        pubsub_name: String,
+        client: DaprClient<TonicChannel>,
+        retryable_status_codes: Vec<Code>,
+    ) -> Self {
+        ReconTaskEventPublisher {
+            pubsub_name,
+            client,
+            retryable_status_codes,
+        }
+    }
+}
@@ -0,0 +1,72 @@
+use chrono::Utc;
+use tonic::Code;
+
+use crate::external::dapr::{
+    dapr_client::connect_to_dapr, dapr_errors::DEFAULT_RETRYABLE_STATUS_CODES,
+    fake_dapr_sidecar::FakeDaprSidecar,
+};
+use crate::internal::{
+    interfaces::recon_task_event_publisher::ReconTaskEventPublisherInterface,
+    models::entities::recon_task_event::ReconTaskEvent,
+    shared_reconciler_rust_libraries::models::entities::app_errors::AppErrorKind,
+};
+
+use super::recon_task_event_publisher::ReconTaskEventPublisher;
+
+#[actix_web::test]
+async fn given_event_publish_task_event_sends_it_as_json_on_the_topic() {
+    //setup
+    let (sidecar, publisher) = setup_publisher().await;
+    let event = get_dummy_cancelled_event();
+
+    //act
+    publisher
+        .publish_task_event(&String::from("RECON-RESULTS-QUEUE-1234"), &event)
+        .await
+        .unwrap();
+
+    //assert
+    let published = sidecar.published_events_on("RECON-RESULTS-QUEUE-1234");
+    let decoded: ReconTaskEvent = serde_json::from_slice(&published[0].data).unwrap();
+    assert_eq!(published[0].pubsub_name, "pubsub");
+    assert_eq!(published[0].data_content_type, "application/json");
+    assert_eq!(decoded, event);
+}
+
+#[actix_web::test]
+async fn given_sidecar_unavailable_publish_task_event_returns_connection_error() {
+    //setup
+    let (sidecar, publisher) = setup_publisher().await;
+    sidecar.fail_with(Some(Code::Unavailable));
+
+    //act
+    let result = publisher
+        .publish_task_event(&String::from("RECON-RESULTS-QUEUE-1234"), &get_dummy_cancelled_event())
+        .await;
+
+    //assert
+    assert!(matches!(result.err().unwrap().kind, AppErrorKind::ConnectionError));
+}
+
+async fn setup_publisher() -> (FakeDaprSidecar, ReconTaskEventPublisher) {
+    let sidecar = FakeDaprSidecar::new();
+    let address = sidecar.start().await;
+    let client = connect_to_dapr(&address).await.unwrap();
+
+    let publisher = ReconTaskEventPublisher::new(
+        String::from("pubsub"),
+        client,
+        DEFAULT_RETRYABLE_STATUS_CODES.to_vec(),
+    );
+
+    (sidecar, publisher)
+}
+
+fn get_dummy_cancelled_event() -> ReconTaskEvent {
+    ReconTaskEvent::TaskCancelled {
+        task_id: String::from("RECON-TASK-1234"),
+        cancelled_by: String::from("test-user-id"),
+        reason: String::from("wrong file uploaded"),
+        cancelled_at: Utc::now(),
+    }
+}
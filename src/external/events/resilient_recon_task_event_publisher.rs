@@ -0,0 +1,43 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+
+use crate::external::resilience::{
+    call_with_resilience, circuit_breaker::CircuitBreaker, retry_policy::RetryPolicy,
+};
+use crate::internal::{
+    interfaces::recon_task_event_publisher::ReconTaskEventPublisherInterface,
+    models::entities::recon_task_event::ReconTaskEvent,
+    shared_reconciler_rust_libraries::models::entities::app_errors::AppError,
+};
+
+pub struct ResilientReconTaskEventPublisher {
+    pub inner: Box<dyn ReconTaskEventPublisherInterface>,
+    pub retry_policy: RetryPolicy,
+    pub circuit_breaker: Arc<CircuitBreaker>,
+}
+
+#[async_trait]
+impl ReconTaskEventPublisherInterface for ResilientReconTaskEventPublisher {
+    async fn publish_task_event(
+        &self,
+        topic: &str,
+        event: &ReconTaskEvent,
+    ) -> Result<(), AppError> {
+        call_with_resilience!(self, self.inner.publish_task_event(topic, event))
+    }
+}
+
+impl ResilientReconTaskEventPublisher {
+    pub(crate) fn new(
+        inner: Box<dyn ReconTaskEventPublisherInterface>,
+        retry_policy: RetryPolicy,
+        circuit_breaker: Arc<CircuitBreaker>,
+    ) -> Self {
+        ResilientReconTaskEventPublisher {
+            inner,
+            retry_policy,
+            circuit_breaker,
+        }
+    }
+}
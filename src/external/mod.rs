@@ -1,2 +1,5 @@
+pub mod blob_store;
 pub mod repositories;
 pub mod dapr;
+pub mod events;
+pub mod resilience;
@@ -1,2 +1,38 @@
 pub mod recon_file_details_repo;
+pub mod recon_task_audit_repo;
 pub mod recon_task_details_repo;
+pub mod recon_template_repo;
+pub mod resilient_recon_file_details_repo;
+pub mod resilient_recon_task_audit_repo;
+pub mod resilient_recon_task_details_repo;
+pub mod resilient_recon_template_repo;
+pub mod resilient_upload_session_repo;
+pub mod upload_session_repo;
+
+#[cfg(test)]
+#[path = "./recon_file_details_repo_tests.rs"]
+mod recon_file_details_repo_tests;
+
+#[cfg(test)]
+#[path = "./recon_task_audit_repo_tests.rs"]
+mod recon_task_audit_repo_tests;
+
+#[cfg(test)]
+#[path = "./recon_task_details_repo_tests.rs"]
+mod recon_task_details_repo_tests;
+
+#[cfg(test)]
+#[path = "./recon_template_repo_tests.rs"]
+mod recon_template_repo_tests;
+
+#[cfg(test)]
+#[path = "./resilient_recon_task_details_repo_tests.rs"]
+mod resilient_recon_task_details_repo_tests;
+
+#[cfg(test)]
+#[path = "./resilient_recon_template_repo_tests.rs"]
+mod resilient_recon_template_repo_tests;
+
+#[cfg(test)]
+#[path = "./upload_session_repo_tests.rs"]
+mod upload_session_repo_tests;
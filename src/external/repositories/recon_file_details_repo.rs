@@ -1,96 +1,103 @@
 use async_trait::async_trait;
-use dapr::{Client, dapr::dapr::proto::runtime::v1::dapr_client::DaprClient};
-use tonic::transport::Channel as TonicChannel;
+use chrono::Utc;
+use dapr::dapr::dapr::proto::runtime::v1::dapr_client::DaprClient;
+use tonic::{Code, transport::Channel as TonicChannel};
 
+use crate::external::dapr::dapr_state::{DaprStateStore, MigrationReport};
 use crate::internal::{
     interfaces::recon_files_repository::ReconFileDetailsRepositoryInterface,
-    shared_reconciler_rust_libraries::models::entities::{
-        app_errors::{AppError, AppErrorKind},
-        recon_tasks_models::ReconFileMetaData,
-    },
+    models::entities::recon_file::ReconFile,
+    shared_reconciler_rust_libraries::models::entities::app_errors::AppError,
 };
 
+const RECON_FILES_INDEX_KEY: &str = "RECON-FILES-INDEX";
+
 pub struct ReconFileDetailsRepositoryManager {
-    pub store_name: String,
-    pub client: Client<DaprClient<TonicChannel>>,
+    pub state_store: DaprStateStore,
 }
 
 #[async_trait]
 impl ReconFileDetailsRepositoryInterface for ReconFileDetailsRepositoryManager {
     async fn get_recon_file_details(
-        &mut self,
-        file_id: &String,
-    ) -> Result<ReconFileMetaData, AppError> {
-        let get_response = self.client
-            .get_state(self.store_name.clone(), String::from(file_id), None)
-            .await;
+        &self,
+        file_id: &str,
+    ) -> Result<ReconFile, AppError> {
+        let record = self
+            .state_store
+            .get_record_migrating::<ReconFile>(file_id, RECON_FILES_INDEX_KEY)
+            .await?;
 
-        return match get_response {
-            Ok(s) => {
-                let retrieval_result: Result<ReconFileMetaData, _> =
-                    serde_json::from_slice(&s.data);
-
-                match retrieval_result {
-                    Ok(unmarshalled_file_details) => Ok(unmarshalled_file_details),
-                    Err(e) => Err(AppError::new(AppErrorKind::NotFound, e.to_string())),
-                }
-            }
-            Err(e) => Err(AppError::new(AppErrorKind::NotFound, e.to_string())),
-        };
+        return Ok(record.data);
     }
 
     async fn create_recon_file_details(
-        &mut self,
-        file_details: &ReconFileMetaData,
+        &self,
+        file_details: &ReconFile,
     ) -> Result<String, AppError> {
-        let key = file_details.id.clone();
-        let val = serde_json::to_vec(&file_details).unwrap();
+        let key = file_details.metadata.id.clone();
 
         // save key-value pair in the state store
-        let save_result = self.client
-            .save_state(self.store_name.clone(), vec![(key.clone(), val)])
-            .await;
+        self.state_store
+            .save_record(&key, file_details, Utc::now())
+            .await?;
 
-        return match save_result {
-            Ok(_s) => Ok(key.clone()),
-            Err(e) => Err(AppError::new(AppErrorKind::InternalError, e.to_string())),
-        };
+        self.state_store
+            .add_to_index(RECON_FILES_INDEX_KEY, &key)
+            .await?;
+
+        return Ok(key);
     }
 
     async fn update_recon_file_details(
-        &mut self,
-        file_details: &ReconFileMetaData,
-    ) -> Result<ReconFileMetaData, AppError> {
-        //delete existing task task_details
-        let _ = self.delete_recon_file_details(&file_details.id);
+        &self,
+        file_details: &ReconFile,
+    ) -> Result<ReconFile, AppError> {
+        //keep the original creation time
+        let existing = self
+            .state_store
+            .get_record_migrating::<ReconFile>(&file_details.metadata.id, RECON_FILES_INDEX_KEY)
+            .await?;
 
         //save new details
-        let id = self.create_recon_file_details(file_details).await?;
+        self.state_store
+            .save_record(&file_details.metadata.id, file_details, existing.created_at)
+            .await?;
 
-        //return task details
-        return self.get_recon_file_details(&id).await;
+        //return file details
+        return self.get_recon_file_details(&file_details.metadata.id).await;
     }
 
-    async fn delete_recon_file_details(&mut self, file_id: &String) -> Result<bool, AppError> {
-
+    async fn delete_recon_file_details(&self, file_id: &str) -> Result<bool, AppError> {
         // delete a value from the state store
-        let delete_result = self.client
-            .delete_state(self.store_name.clone(), String::from(file_id), None)
-            .await;
+        self.state_store.delete(file_id).await?;
 
-        return match delete_result {
-            Ok(_s) => Ok(true),
-            Err(e) => Err(AppError::new(AppErrorKind::InternalError, e.to_string())),
-        };
+        self.state_store
+            .remove_from_index(RECON_FILES_INDEX_KEY, file_id)
+            .await?;
+
+        return Ok(true);
+    }
+
+    async fn list_recon_file_ids(&self) -> Result<Vec<String>, AppError> {
+        self.state_store.read_index(RECON_FILES_INDEX_KEY).await
     }
 }
 
 impl ReconFileDetailsRepositoryManager {
-    pub(crate) fn new(store_name: String, client: Client<DaprClient<TonicChannel>>) -> Self {
-        //handle the connection result
-        return ReconFileDetailsRepositoryManager {
-            store_name,
-            client,
-        };
+    pub(crate) fn new(
+        store_name: String,
+        client: DaprClient<TonicChannel>,
+        retryable_status_codes: Vec<Code>,
+    ) -> Self {
+        ReconFileDetailsRepositoryManager {
+            state_store: DaprStateStore::new(store_name, client, retryable_status_codes),
+        }
+    }
+
+    pub async fn migrate_records(&self) -> Result<MigrationReport, AppError> {
+        return self
+            .state_store
+            .migrate_records::<ReconFile>(RECON_FILES_INDEX_KEY)
+            .await;
     }
 }
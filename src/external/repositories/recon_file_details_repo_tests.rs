@@ -0,0 +1,117 @@
+use chrono::Utc;
+use tonic::Code;
+
+use crate::external::dapr::{
+    dapr_client::connect_to_dapr, dapr_errors::DEFAULT_RETRYABLE_STATUS_CODES,
+    fake_dapr_sidecar::FakeDaprSidecar,
+};
+use crate::internal::{
+    interfaces::recon_files_repository::ReconFileDetailsRepositoryInterface,
+    models::entities::{
+        file_format::FileFormatDescriptor, file_verification::FileVerification, recon_file::ReconFile,
+    },
+    shared_reconciler_rust_libraries::models::entities::{
+        app_errors::AppErrorKind,
+        recon_tasks_models::{ReconFileMetaData, ReconFileType},
+    },
+};
+
+use super::recon_file_details_repo::ReconFileDetailsRepositoryManager;
+
+const STORE_NAME: &str = "statestore";
+
+#[actix_web::test]
+async fn given_saved_file_details_get_recon_file_details_returns_them() {
+    //setup
+    let (_sidecar, repo) = setup_repo().await;
+    let recon_file = get_dummy_recon_file();
+
+    //act
+    let file_id = repo.create_recon_file_details(&recon_file).await.unwrap();
+    let result = repo.get_recon_file_details(&file_id).await.unwrap();
+
+    //assert
+    assert_eq!(result.metadata.file_hash, recon_file.metadata.file_hash);
+    assert_eq!(result.created_by, recon_file.created_by);
+}
+
+#[actix_web::test]
+async fn given_missing_key_get_recon_file_details_returns_not_found() {
+    //setup
+    let (_sidecar, repo) = setup_repo().await;
+
+    //act
+    let result = repo
+        .get_recon_file_details(&String::from("RECON-FILE-missing"))
+        .await;
+
+    //assert
+    assert!(matches!(result.err().unwrap().kind, AppErrorKind::NotFound));
+}
+
+#[actix_web::test]
+async fn given_corrupt_payload_get_recon_file_details_returns_data_corruption_error_with_key() {
+    //setup
+    let (sidecar, repo) = setup_repo().await;
+    sidecar.put_raw(STORE_NAME, "RECON-FILE-corrupt", b"{not json".to_vec());
+
+    //act
+    let result = repo
+        .get_recon_file_details(&String::from("RECON-FILE-corrupt"))
+        .await;
+
+    //assert
+    let error = result.err().unwrap();
+    assert!(matches!(error.kind, AppErrorKind::ResponseUnmarshalError));
+    assert!(error.message.contains("RECON-FILE-corrupt"));
+}
+
+#[actix_web::test]
+async fn given_sidecar_unavailable_get_recon_file_details_returns_connection_error() {
+    //setup
+    let (sidecar, repo) = setup_repo().await;
+    sidecar.fail_with(Some(Code::Unavailable));
+
+    //act
+    let result = repo
+        .get_recon_file_details(&String::from("RECON-FILE-1234"))
+        .await;
+
+    //assert
+    assert!(matches!(result.err().unwrap().kind, AppErrorKind::ConnectionError));
+}
+
+async fn setup_repo() -> (FakeDaprSidecar, ReconFileDetailsRepositoryManager) {
+    let sidecar = FakeDaprSidecar::new();
+    let address = sidecar.start().await;
+    let client = connect_to_dapr(&address).await.unwrap();
+
+    let repo = ReconFileDetailsRepositoryManager::new(
+        String::from(STORE_NAME),
+        client,
+        DEFAULT_RETRYABLE_STATUS_CODES.to_vec(),
+    );
+
+    (sidecar, repo)
+}
+
+fn get_dummy_recon_file() -> ReconFile {
+    ReconFile {
+        format: FileFormatDescriptor::default(),
+        column_schema: vec![],
+        stored_file_parts: vec![],
+        verification: FileVerification::default(),
+        metadata: ReconFileMetaData {
+            id: String::from("RECON-FILE-1234"),
+            file_name: String::from("src-file-1234"),
+            row_count: 1000,
+            column_delimiters: vec![','],
+            recon_file_type: ReconFileType::PrimaryFile,
+            column_headers: vec![String::from("header1"), String::from("header2")],
+            file_hash: String::from("src-file-hash-1234"),
+        },
+        created_by: String::from("test-user-id"),
+        created_at: Utc::now(),
+        updated_at: Utc::now(),
+    }
+}
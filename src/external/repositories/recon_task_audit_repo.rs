@@ -0,0 +1,75 @@
+use async_trait::async_trait;
+use dapr::dapr::dapr::proto::runtime::v1::dapr_client::DaprClient;
+use tonic::{Code, transport::Channel as TonicChannel};
+
+use crate::external::dapr::dapr_state::DaprStateStore;
+use crate::internal::{
+    interfaces::recon_task_audit_repository::ReconTaskAuditRepositoryInterface,
+    models::entities::recon_task_audit::{ReconTaskAuditEntry, ReconTaskAuditLog},
+    shared_reconciler_rust_libraries::models::entities::app_errors::{AppError, AppErrorKind},
+};
+
+const RECON_TASK_AUDIT_PREFIX: &str = "RECON-TASK-AUDIT";
+
+pub struct ReconTaskAuditRepositoryManager {
+    pub state_store: DaprStateStore,
+}
+
+#[async_trait]
+impl ReconTaskAuditRepositoryInterface for ReconTaskAuditRepositoryManager {
+    async fn append_audit_entry(&self, entry: &ReconTaskAuditEntry) -> Result<(), AppError> {
+        //the first entry for a task starts a new log
+        let new_audit_log = ReconTaskAuditLog {
+            task_id: entry.task_id.clone(),
+            entries: vec![],
+        };
+
+        //appended under the log's etag so entries written at the same time are all kept,
+        //an entry already in the log was saved by an earlier attempt whose response was lost
+        let _ = self
+            .state_store
+            .upsert_record_with_etag(&audit_log_key(&entry.task_id), new_audit_log, |audit_log| {
+                if audit_log.entries.contains(entry) {
+                    return false;
+                }
+
+                audit_log.entries.push(entry.clone());
+                true
+            })
+            .await?;
+
+        return Ok(());
+    }
+
+    async fn get_audit_entries(
+        &self,
+        task_id: &str,
+    ) -> Result<Vec<ReconTaskAuditEntry>, AppError> {
+        match self
+            .state_store
+            .get_record::<ReconTaskAuditLog>(&audit_log_key(task_id))
+            .await
+        {
+            Ok(record) => Ok(record.data.entries),
+            //tasks created before auditing existed have no history yet
+            Err(e) if matches!(e.kind, AppErrorKind::NotFound) => Ok(vec![]),
+            Err(e) => Err(e),
+        }
+    }
+}
+
+impl ReconTaskAuditRepositoryManager {
+    pub(crate) fn new(
+        store_name: String,
+        client: DaprClient<TonicChannel>,
+        retryable_status_codes: Vec<Code>,
+    ) -> Self {
+        ReconTaskAuditRepositoryManager {
+            state_store: DaprStateStore::new(store_name, client, retryable_status_codes),
+        }
+    }
+}
+
+fn audit_log_key(task_id: &str) -> String {
+    format!("{}-{}", RECON_TASK_AUDIT_PREFIX, task_id)
+}
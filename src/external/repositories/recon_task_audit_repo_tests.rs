@@ -0,0 +1,120 @@
+use crate::external::dapr::{
+    dapr_client::connect_to_dapr, dapr_errors::DEFAULT_RETRYABLE_STATUS_CODES,
+    fake_dapr_sidecar::FakeDaprSidecar,
+};
+use crate::internal::{
+    interfaces::recon_task_audit_repository::ReconTaskAuditRepositoryInterface,
+    models::entities::recon_task_audit::{ReconTaskAction, ReconTaskAuditEntry},
+};
+
+use super::recon_task_audit_repo::ReconTaskAuditRepositoryManager;
+
+#[actix_web::test]
+async fn given_appended_entries_get_audit_entries_returns_them_in_order() {
+    //setup
+    let repo = setup_repo().await;
+    let task_id = String::from("RECON-TASK-1234");
+    let user_id = String::from("test-user-id");
+
+    //act
+    repo.append_audit_entry(&ReconTaskAuditEntry::new(
+        &task_id,
+        ReconTaskAction::TaskCreated,
+        &user_id,
+        None,
+    ))
+    .await
+    .unwrap();
+    repo.append_audit_entry(&ReconTaskAuditEntry::new(
+        &task_id,
+        ReconTaskAction::PrimaryFileAttached,
+        &user_id,
+        Some(String::from("RECON-FILE-1234")),
+    ))
+    .await
+    .unwrap();
+
+    let entries = repo.get_audit_entries(&task_id).await.unwrap();
+
+    //assert
+    let actions: Vec<ReconTaskAction> = entries.into_iter().map(|e| e.action).collect();
+    assert_eq!(
+        actions,
+        vec![ReconTaskAction::TaskCreated, ReconTaskAction::PrimaryFileAttached]
+    );
+}
+
+#[actix_web::test]
+async fn given_concurrent_appends_get_audit_entries_returns_every_entry() {
+    //setup
+    let repo = setup_repo().await;
+    let task_id = String::from("RECON-TASK-1234");
+    let entries: Vec<ReconTaskAuditEntry> = (0..5)
+        .map(|i| {
+            ReconTaskAuditEntry::new(
+                &task_id,
+                ReconTaskAction::FilesVerified,
+                &format!("test-user-{}", i),
+                None,
+            )
+        })
+        .collect();
+
+    //act
+    let appends = entries.iter().map(|entry| repo.append_audit_entry(entry));
+    for result in futures_util::future::join_all(appends).await {
+        result.unwrap();
+    }
+
+    //assert
+    let stored = repo.get_audit_entries(&task_id).await.unwrap();
+    assert_eq!(stored.len(), 5);
+}
+
+#[actix_web::test]
+async fn given_entry_already_appended_append_audit_entry_does_not_add_it_again() {
+    //setup
+    let repo = setup_repo().await;
+    let task_id = String::from("RECON-TASK-1234");
+    let entry = ReconTaskAuditEntry::new(
+        &task_id,
+        ReconTaskAction::TaskCreated,
+        &String::from("test-user-id"),
+        None,
+    );
+    repo.append_audit_entry(&entry).await.unwrap();
+
+    //act
+    repo.append_audit_entry(&entry).await.unwrap();
+
+    //assert
+    let stored = repo.get_audit_entries(&task_id).await.unwrap();
+    assert_eq!(stored, vec![entry]);
+}
+
+#[actix_web::test]
+async fn given_task_without_history_get_audit_entries_returns_empty_list() {
+    //setup
+    let repo = setup_repo().await;
+
+    //act
+    let entries = repo
+        .get_audit_entries(&String::from("RECON-TASK-legacy"))
+        .await
+        .unwrap();
+
+    //assert
+    assert!(entries.is_empty());
+}
+
+async fn setup_repo() -> ReconTaskAuditRepositoryManager {
+    let sidecar = FakeDaprSidecar::new();
+    let address = sidecar.start().await;
+    let client = connect_to_dapr(&address).await.unwrap();
+
+    ReconTaskAuditRepositoryManager::new(
+        String::from("statestore"),
+        client,
+        DEFAULT_RETRYABLE_STATUS_CODES.to_vec(),
+    )
+}
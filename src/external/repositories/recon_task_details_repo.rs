@@ -1,98 +1,198 @@
+use std::collections::BTreeSet;
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Mutex,
+};
+
 use async_trait::async_trait;
-use dapr::{Client, dapr::dapr::proto::runtime::v1::dapr_client::DaprClient};
-use tonic::transport::Channel as TonicChannel;
+use chrono::Utc;
+use dapr::dapr::dapr::proto::runtime::v1::dapr_client::DaprClient;
+use tonic::{Code, transport::Channel as TonicChannel};
 
+use crate::external::dapr::dapr_state::{DaprStateStore, MigrationReport};
 use crate::internal::{
     interfaces::recon_tasks_repository::ReconTaskDetailsRepositoryInterface,
-    shared_reconciler_rust_libraries::models::entities::{
-        app_errors::{AppError, AppErrorKind},
-        recon_tasks_models::ReconTaskDetails,
+    models::entities::{
+        recon_results::ReconChunkResults,
+        recon_task::ReconTask,
     },
+    shared_reconciler_rust_libraries::models::entities::app_errors::{AppError, AppErrorKind},
 };
-use crate::internal::shared_reconciler_rust_libraries::common::utils::app_error;
+
+const RECON_TASKS_INDEX_KEY: &str = "RECON-TASKS-INDEX";
+const ACTIVE_TASKS_PREFIX: &str = "ACTIVE-TASKS";
 
 pub struct ReconTaskDetailsRepositoryManager {
-    pub store_name: String,
-    pub connection_string: String,
+    pub state_store: DaprStateStore,
 }
 
 #[async_trait]
 impl ReconTaskDetailsRepositoryInterface for ReconTaskDetailsRepositoryManager {
-    async fn get_task_details(&mut self, task_id: &String) -> Result<ReconTaskDetails, AppError> {
-        let get_response = self.client
-            .get_state(self.store_name.clone(), task_id.clone(), None)
-            .await;
-
-        return match get_response {
-            Ok(s) => {
-                let retrieval_result: Result<ReconTaskDetails, _> = serde_json::from_slice(&s.data);
+    async fn get_task_details(&self, task_id: &str) -> Result<ReconTask, AppError> {
+        let (record, etag) = self
+            .state_store
+            .get_record_migrating_with_etag::<ReconTask>(task_id, RECON_TASKS_INDEX_KEY)
+            .await?;
 
-                match retrieval_result {
-                    Ok(unmarshalled_task_details) => Ok(unmarshalled_task_details),
-                    Err(e) => app_error(AppErrorKind::ResponseUnmarshalError, Box::new(e)),
-                }
-            }
-            Err(e) => app_error(AppErrorKind::NotFound, Box::new(e))
-        };
+        let mut task = record.data;
+        task.etag = etag;
+        return Ok(task);
     }
 
     async fn create_task_details(
-        &mut self,
-        task_details: &ReconTaskDetails,
+        &self,
+        task_details: &ReconTask,
     ) -> Result<String, AppError> {
-        let key = task_details.id.clone();
-        let val = serde_json::to_vec(&task_details).unwrap();
+        let key = task_details.details.id.clone();
 
         // save key-value pair in the state store
-        let save_result = self.client
-            .save_state(self.store_name.clone(), vec![(key.clone(), val)])
-            .await;
+        self.state_store
+            .save_record(&key, task_details, Utc::now())
+            .await?;
 
-        return match save_result {
-            Ok(_s) => Ok(key.clone()),
-            Err(e) => app_error(AppErrorKind::InternalError, Box::new(e)),
-        };
+        self.state_store
+            .add_to_index(RECON_TASKS_INDEX_KEY, &key)
+            .await?;
+
+        return Ok(key);
     }
 
     async fn update_task_details(
-        &mut self,
-        task_details: &ReconTaskDetails,
-    ) -> Result<ReconTaskDetails, AppError> {
-        //delete existing task task_details
-        let _ = self.delete_task_details(&task_details.id);
+        &self,
+        task_details: &ReconTask,
+    ) -> Result<Option<ReconTask>, AppError> {
+        //keep the original creation time
+        let existing = self
+            .state_store
+            .get_record_migrating::<ReconTask>(&task_details.details.id, RECON_TASKS_INDEX_KEY)
+            .await?;
 
-        //save new details
-        let id = self.create_task_details(task_details).await?;
+        //save new details, unless the task changed since it was read
+        let is_saved = self
+            .state_store
+            .save_record_if_unchanged(
+                &task_details.details.id,
+                task_details,
+                existing.created_at,
+                task_details.etag.clone(),
+            )
+            .await?;
+
+        if !is_saved {
+            return Ok(None);
+        }
 
         //return task details
-        return self.get_task_details(&id).await;
+        return self.get_task_details(&task_details.details.id).await.map(Some);
     }
 
-    async fn delete_task_details(&mut self, task_details_id: &String) -> Result<bool, AppError> {
-        
-        // delete a value from the state store
-        let delete_result = self.client
-            .delete_state(self.store_name.clone(), String::from(task_details_id), None)
-            .await;
+    async fn list_task_details(&self) -> Result<Vec<ReconTask>, AppError> {
+        let mut tasks = vec![];
+
+        for key in self.state_store.read_index(RECON_TASKS_INDEX_KEY).await? {
+            match self.state_store.get_record::<ReconTask>(&key).await {
+                Ok(record) => tasks.push(record.data),
+                //the task was deleted but its key is still in the index
+                Err(e) if matches!(e.kind, AppErrorKind::NotFound) => continue,
+                Err(e) => return Err(e),
+            }
+        }
+
+        return Ok(tasks);
+    }
 
-        return match delete_result {
-            Ok(_s) => Ok(true),
-            Err(e) => app_error(AppErrorKind::InternalError, Box::new(e)),
+    async fn record_chunk_results(
+        &self,
+        results: &ReconChunkResults,
+    ) -> Result<ReconTask, AppError> {
+        //set on every attempt, results that can never be recorded leave the task as it is
+        let rejection = Mutex::new(None);
+
+        let task = self
+            .state_store
+            .update_record_with_etag(&results.task_id, |task: &mut ReconTask| {
+                let recorded = task.record_chunk_results(results, Utc::now());
+                let mut rejection = rejection.lock().unwrap();
+                *rejection = recorded.as_ref().err().cloned();
+                recorded.unwrap_or(false)
+            })
+            .await?;
+
+        return match rejection.into_inner().unwrap() {
+            Some(e) => Err(e),
+            None => Ok(task),
         };
     }
+
+    async fn mark_task_reconciling(&self, task_id: &str) -> Result<ReconTask, AppError> {
+        self
+            .state_store
+            .update_record_with_etag(task_id, |task: &mut ReconTask| {
+                task.mark_reconciling(Utc::now())
+            })
+            .await
+    }
+
+    async fn reserve_active_task_slot(
+        &self,
+        user_id: &str,
+        task_id: &str,
+        max_active_tasks: usize,
+    ) -> Result<bool, AppError> {
+        let is_reserved = AtomicBool::new(false);
+
+        let _ = self
+            .state_store
+            .update_with_etag(&active_tasks_key(user_id), |task_ids: &mut BTreeSet<String>| {
+                if task_ids.contains(task_id) {
+                    is_reserved.store(true, Ordering::SeqCst);
+                    return false;
+                }
+
+                let has_room = task_ids.len() < max_active_tasks;
+                is_reserved.store(has_room, Ordering::SeqCst);
+                has_room && task_ids.insert(task_id.to_string())
+            })
+            .await?;
+
+        return Ok(is_reserved.load(Ordering::SeqCst));
+    }
+
+    async fn release_active_task_slot(
+        &self,
+        user_id: &str,
+        task_id: &str,
+    ) -> Result<(), AppError> {
+        let _ = self
+            .state_store
+            .update_with_etag(&active_tasks_key(user_id), |task_ids: &mut BTreeSet<String>| {
+                task_ids.remove(task_id)
+            })
+            .await?;
+
+        return Ok(());
+    }
 }
 
 impl ReconTaskDetailsRepositoryManager {
-    pub(crate) fn new(store_name: String, connection_string: String) -> Self {
-        return ReconTaskDetailsRepositoryManager {
-            store_name,
-            connection_string,
-        };
+    pub(crate) fn new(
+        store_name: String,
+        client: DaprClient<TonicChannel>,
+        retryable_status_codes: Vec<Code>,
+    ) -> Self {
+        ReconTaskDetailsRepositoryManager {
+            state_store: DaprStateStore::new(store_name, client, retryable_status_codes),
+        }
+    }
+
+    pub async fn migrate_records(&self) -> Result<MigrationReport, AppError> {
+        return self
+            .state_store
+            .migrate_records::<ReconTask>(RECON_TASKS_INDEX_KEY)
+            .await;
     }
 }
 
-#[cfg(test)]
-mod tests {
-    #[actix_rt::test]
-    async fn given_valid_create_recon_task_request_calls_correct_dependencies_returns_success() {}
+fn active_tasks_key(user_id: &str) -> String {
+    format!("{}-{}", ACTIVE_TASKS_PREFIX, user_id)
 }
@@ -0,0 +1,578 @@
+use chrono::Utc;
+use serde_json::Value;
+use tonic::Code;
+
+use crate::external::dapr::{
+    dapr_client::connect_to_dapr, dapr_errors::DEFAULT_RETRYABLE_STATUS_CODES,
+    fake_dapr_sidecar::FakeDaprSidecar,
+};
+use crate::internal::{
+    interfaces::recon_tasks_repository::ReconTaskDetailsRepositoryInterface,
+    models::entities::{
+        recon_results::{ReconChunkResults, ReconResultCounts, ReconResultsSummary},
+        recon_task::{ReconTask, ReconTaskStatus},
+        upload_session::UploadedFileRole,
+        versioned_record::VersionedSchema,
+    },
+    shared_reconciler_rust_libraries::models::entities::{
+        app_errors::AppErrorKind,
+        file_chunk_queue::FileChunkQueue,
+        recon_tasks_models::{ComparisonPair, ReconciliationConfigs, ReconTaskDetails},
+    },
+};
+
+use super::recon_task_details_repo::ReconTaskDetailsRepositoryManager;
+
+const STORE_NAME: &str = "statestore";
+
+#[actix_web::test]
+async fn given_saved_task_details_get_task_details_returns_them() {
+    //setup
+    let (_sidecar, repo) = setup_repo().await;
+    let recon_task = get_dummy_recon_task();
+
+    //act
+    let task_id = repo.create_task_details(&recon_task).await.unwrap();
+    let result = repo.get_task_details(&task_id).await.unwrap();
+
+    //assert
+    assert_eq!(result.details.id, recon_task.details.id);
+    assert_eq!(result.created_by, recon_task.created_by);
+}
+
+#[actix_web::test]
+async fn given_missing_key_get_task_details_returns_not_found() {
+    //setup
+    let (_sidecar, repo) = setup_repo().await;
+
+    //act
+    let result = repo.get_task_details(&String::from("RECON-TASK-missing")).await;
+
+    //assert
+    assert!(matches!(result.err().unwrap().kind, AppErrorKind::NotFound));
+}
+
+#[actix_web::test]
+async fn given_corrupt_payload_get_task_details_returns_data_corruption_error_with_key() {
+    //setup
+    let (sidecar, repo) = setup_repo().await;
+    sidecar.put_raw(STORE_NAME, "RECON-TASK-corrupt", b"[1, 2".to_vec());
+
+    //act
+    let result = repo.get_task_details(&String::from("RECON-TASK-corrupt")).await;
+
+    //assert
+    let error = result.err().unwrap();
+    assert!(matches!(error.kind, AppErrorKind::ResponseUnmarshalError));
+    assert!(error.message.contains("RECON-TASK-corrupt"));
+}
+
+#[actix_web::test]
+async fn given_sidecar_unavailable_create_task_details_returns_connection_error() {
+    //setup
+    let (sidecar, repo) = setup_repo().await;
+    sidecar.fail_with(Some(Code::Unavailable));
+
+    //act
+    let result = repo
+        .create_task_details(&get_dummy_recon_task())
+        .await;
+
+    //assert
+    assert!(matches!(result.err().unwrap().kind, AppErrorKind::ConnectionError));
+}
+
+#[actix_web::test]
+async fn given_non_retryable_status_get_task_details_returns_internal_error() {
+    //setup
+    let (sidecar, repo) = setup_repo().await;
+    sidecar.fail_with(Some(Code::Internal));
+
+    //act
+    let result = repo.get_task_details(&String::from("RECON-TASK-1234")).await;
+
+    //assert
+    assert!(matches!(result.err().unwrap().kind, AppErrorKind::InternalError));
+}
+
+#[actix_web::test]
+async fn given_legacy_record_without_envelope_get_task_details_still_reads_it() {
+    //setup
+    let (sidecar, repo) = setup_repo().await;
+    let task_details = get_dummy_recon_task_details();
+    sidecar.put_raw(STORE_NAME, &task_details.id, serde_json::to_vec(&task_details).unwrap());
+
+    //act
+    let result = repo.get_task_details(&task_details.id).await;
+
+    //assert
+    assert_eq!(result.unwrap().details.id, task_details.id);
+}
+
+#[actix_web::test]
+async fn given_record_cancelled_before_the_cancelled_status_get_task_details_reads_it_as_cancelled() {
+    //setup
+    let (sidecar, repo) = setup_repo().await;
+    let mut stored = serde_json::to_value(get_dummy_recon_task()).unwrap();
+    stored["status"] = Value::from("ready");
+    stored["cancellation"] = serde_json::json!({
+        "cancelled_by": "test-user-id",
+        "cancelled_at": "2022-10-01T00:00:00Z",
+        "reason": "wrong file uploaded"
+    });
+    let task_id = stored["id"].as_str().unwrap().to_string();
+    sidecar.put_raw(STORE_NAME, &task_id, serde_json::to_vec(&stored).unwrap());
+
+    //act
+    let result = repo.get_task_details(&task_id).await.unwrap();
+
+    //assert
+    assert_eq!(result.status, ReconTaskStatus::Cancelled);
+    assert!(result.is_cancelled());
+}
+
+#[actix_web::test]
+async fn given_indexed_legacy_record_migrate_records_rewrites_it_at_latest_version() {
+    //setup
+    let (sidecar, repo) = setup_repo().await;
+    let task_details = get_dummy_recon_task_details();
+    sidecar.put_raw(STORE_NAME, &task_details.id, serde_json::to_vec(&task_details).unwrap());
+    sidecar.put_raw(
+        STORE_NAME,
+        "RECON-TASKS-INDEX",
+        serde_json::to_vec(&vec![task_details.id.clone()]).unwrap(),
+    );
+
+    //act
+    let report = repo.migrate_records().await.unwrap();
+
+    //assert
+    let stored: Value =
+        serde_json::from_slice(&sidecar.get_raw(STORE_NAME, &task_details.id).unwrap()).unwrap();
+    assert_eq!(report.records_migrated, 1);
+    assert_eq!(stored["schema_version"], ReconTask::current_schema_version());
+    assert_eq!(stored["data"]["id"], task_details.id);
+    assert_eq!(stored["data"]["created_at"], stored["created_at"]);
+}
+
+#[actix_web::test]
+async fn given_unindexed_legacy_record_get_task_details_rewrites_it_and_adds_it_to_the_index() {
+    //setup
+    let (sidecar, repo) = setup_repo().await;
+    let task_details = get_dummy_recon_task_details();
+    sidecar.put_raw(STORE_NAME, &task_details.id, serde_json::to_vec(&task_details).unwrap());
+
+    //act
+    repo.get_task_details(&task_details.id).await.unwrap();
+
+    //assert
+    let stored: Value =
+        serde_json::from_slice(&sidecar.get_raw(STORE_NAME, &task_details.id).unwrap()).unwrap();
+    let listed = repo.list_task_details().await.unwrap();
+    assert_eq!(stored["schema_version"], ReconTask::current_schema_version());
+    assert_eq!(listed.len(), 1);
+    assert_eq!(listed[0].details.id, task_details.id);
+}
+
+#[actix_web::test]
+async fn given_concurrent_creates_list_task_details_returns_every_task() {
+    //setup
+    let (_sidecar, repo) = setup_repo().await;
+    let recon_tasks: Vec<ReconTask> = (0..5)
+        .map(|i| {
+            let mut recon_task = get_dummy_recon_task();
+            recon_task.details.id = format!("RECON-TASK-{}", i);
+            recon_task
+        })
+        .collect();
+
+    //act
+    let creates = recon_tasks.iter().map(|task| repo.create_task_details(task));
+    for result in futures_util::future::join_all(creates).await {
+        result.unwrap();
+    }
+
+    //assert
+    let result = repo.list_task_details().await.unwrap();
+    assert_eq!(result.len(), 5);
+}
+
+#[actix_web::test]
+async fn given_created_task_update_task_details_keeps_created_at() {
+    //setup
+    let (sidecar, repo) = setup_repo().await;
+    let task_id = repo.create_task_details(&get_dummy_recon_task()).await.unwrap();
+    let created: Value =
+        serde_json::from_slice(&sidecar.get_raw(STORE_NAME, &task_id).unwrap()).unwrap();
+    let mut recon_task = repo.get_task_details(&task_id).await.unwrap();
+
+    //act
+    recon_task.details.is_done = true;
+    let saved = repo.update_task_details(&recon_task).await.unwrap();
+
+    //assert
+    let updated: Value =
+        serde_json::from_slice(&sidecar.get_raw(STORE_NAME, &task_id).unwrap()).unwrap();
+    assert!(saved.is_some());
+    assert_eq!(updated["created_at"], created["created_at"]);
+    assert_eq!(updated["data"]["is_done"], true);
+}
+
+#[actix_web::test]
+async fn given_task_changed_since_it_was_read_update_task_details_leaves_it_and_returns_none() {
+    //setup
+    let (_sidecar, repo) = setup_repo().await;
+    let task_id = repo.create_task_details(&get_dummy_recon_task()).await.unwrap();
+    let mut first_read = repo.get_task_details(&task_id).await.unwrap();
+    let mut second_read = repo.get_task_details(&task_id).await.unwrap();
+
+    first_read.row_key_separator = String::from(";");
+    repo.update_task_details(&first_read).await.unwrap();
+
+    //act
+    second_read.details.is_done = true;
+    let saved = repo.update_task_details(&second_read).await.unwrap();
+
+    //assert
+    let stored = repo.get_task_details(&task_id).await.unwrap();
+    assert!(saved.is_none());
+    assert_eq!(stored.row_key_separator, ";");
+    assert!(!stored.details.is_done);
+}
+
+#[actix_web::test]
+async fn given_task_that_was_never_read_update_task_details_returns_none() {
+    //setup
+    let (_sidecar, repo) = setup_repo().await;
+    let mut recon_task = get_dummy_recon_task();
+    repo.create_task_details(&recon_task).await.unwrap();
+
+    //act
+    recon_task.details.is_done = true;
+    let saved = repo.update_task_details(&recon_task).await.unwrap();
+
+    //assert
+    assert!(saved.is_none());
+}
+
+#[actix_web::test]
+async fn given_indexed_tasks_list_task_details_skips_deleted_ones() {
+    //setup
+    let (_sidecar, repo) = setup_repo().await;
+    let mut first_task = get_dummy_recon_task();
+    first_task.details.id = String::from("RECON-TASK-1");
+    let mut second_task = get_dummy_recon_task();
+    second_task.details.id = String::from("RECON-TASK-2");
+
+    repo.create_task_details(&first_task).await.unwrap();
+    repo.create_task_details(&second_task).await.unwrap();
+    repo.state_store.delete("RECON-TASK-1").await.unwrap();
+
+    //act
+    let result = repo.list_task_details().await.unwrap();
+
+    //assert
+    assert_eq!(result.len(), 1);
+    assert_eq!(result[0].details.id, "RECON-TASK-2");
+}
+
+#[actix_web::test]
+async fn given_concurrent_results_record_chunk_results_keeps_every_chunk_and_completes_the_task_once() {
+    //setup
+    let (_sidecar, repo) = setup_repo().await;
+    repo.create_task_details(&get_dummy_reconciling_recon_task()).await.unwrap();
+
+    let results: Vec<ReconChunkResults> = [
+        (UploadedFileRole::Primary, 1),
+        (UploadedFileRole::Primary, 2),
+        (UploadedFileRole::Comparison, 1),
+        (UploadedFileRole::Comparison, 2),
+    ]
+    .into_iter()
+    .map(|(file_role, chunk_sequence_number)| get_dummy_chunk_results(file_role, chunk_sequence_number))
+    .collect();
+
+    //act
+    let records = results.iter().map(|results| repo.record_chunk_results(results));
+    let recorded_tasks: Vec<ReconTask> = futures_util::future::join_all(records)
+        .await
+        .into_iter()
+        .map(|result| result.unwrap())
+        .collect();
+
+    //assert
+    let stored = repo.get_task_details(&String::from("RECON-TASK-1234")).await.unwrap();
+    let completed_at: Vec<_> = recorded_tasks
+        .iter()
+        .filter_map(|task| task.results_summary.completed_at)
+        .collect();
+    assert!(completed_at.iter().all(|completed_at| Some(*completed_at) == stored.results_summary.completed_at));
+    assert_eq!(stored.status, ReconTaskStatus::Completed);
+    assert!(stored.details.is_done);
+    assert_eq!(stored.results_summary.counts.matched, 40);
+}
+
+#[actix_web::test]
+async fn given_chunk_already_recorded_record_chunk_results_leaves_the_task_unchanged() {
+    //setup
+    let (_sidecar, repo) = setup_repo().await;
+    repo.create_task_details(&get_dummy_reconciling_recon_task()).await.unwrap();
+    let results = get_dummy_chunk_results(UploadedFileRole::Primary, 1);
+    repo.record_chunk_results(&results).await.unwrap();
+
+    //act
+    let recorded = repo.record_chunk_results(&results).await.unwrap();
+
+    //assert
+    let stored = repo.get_task_details(&String::from("RECON-TASK-1234")).await.unwrap();
+    assert_eq!(recorded.results_summary.counts.matched, 10);
+    assert_eq!(stored.status, ReconTaskStatus::Reconciling);
+    assert_eq!(stored.results_summary.counts.matched, 10);
+}
+
+#[actix_web::test]
+async fn given_task_not_reconciling_record_chunk_results_ignores_the_results() {
+    //setup
+    let (_sidecar, repo) = setup_repo().await;
+    repo.create_task_details(&get_dummy_recon_task()).await.unwrap();
+
+    //act
+    let recorded = repo
+        .record_chunk_results(&get_dummy_chunk_results(UploadedFileRole::Primary, 1))
+        .await
+        .unwrap();
+
+    //assert
+    let stored = repo.get_task_details(&String::from("RECON-TASK-1234")).await.unwrap();
+    assert_eq!(recorded.status, ReconTaskStatus::AwaitingFiles);
+    assert_eq!(stored.status, ReconTaskStatus::AwaitingFiles);
+    assert_eq!(stored.results_summary.counts.matched, 0);
+}
+
+#[actix_web::test]
+async fn given_chunk_past_the_total_record_chunk_results_returns_bad_client_request_and_leaves_the_task() {
+    //setup
+    let (_sidecar, repo) = setup_repo().await;
+    repo.create_task_details(&get_dummy_reconciling_recon_task()).await.unwrap();
+
+    //act
+    let result = repo
+        .record_chunk_results(&get_dummy_chunk_results(UploadedFileRole::Primary, 3))
+        .await;
+
+    //assert
+    let stored = repo.get_task_details(&String::from("RECON-TASK-1234")).await.unwrap();
+    assert!(matches!(result.err().unwrap().kind, AppErrorKind::BadClientRequest));
+    assert_eq!(stored.results_summary.counts.matched, 0);
+    assert!(stored.results_summary.primary_file_chunks.received_chunks.is_empty());
+}
+
+#[actix_web::test]
+async fn given_missing_task_record_chunk_results_returns_not_found() {
+    //setup
+    let (_sidecar, repo) = setup_repo().await;
+
+    //act
+    let result = repo
+        .record_chunk_results(&get_dummy_chunk_results(UploadedFileRole::Primary, 1))
+        .await;
+
+    //assert
+    assert!(matches!(result.err().unwrap().kind, AppErrorKind::NotFound));
+}
+
+#[actix_web::test]
+async fn given_starting_task_mark_task_reconciling_moves_it_on() {
+    //setup
+    let (_sidecar, repo) = setup_repo().await;
+    repo.create_task_details(&get_dummy_starting_recon_task()).await.unwrap();
+    repo.record_chunk_results(&get_dummy_chunk_results(UploadedFileRole::Primary, 1))
+        .await
+        .unwrap();
+
+    //act
+    let task = repo.mark_task_reconciling(&String::from("RECON-TASK-1234")).await.unwrap();
+
+    //assert
+    let stored = repo.get_task_details(&String::from("RECON-TASK-1234")).await.unwrap();
+    assert_eq!(task.status, ReconTaskStatus::Reconciling);
+    assert_eq!(stored.status, ReconTaskStatus::Reconciling);
+    assert_eq!(stored.results_summary.counts.matched, 10);
+}
+
+#[actix_web::test]
+async fn given_results_completed_the_task_while_starting_mark_task_reconciling_keeps_it_completed() {
+    //setup
+    let (_sidecar, repo) = setup_repo().await;
+    repo.create_task_details(&get_dummy_starting_recon_task()).await.unwrap();
+    for (file_role, chunk_sequence_number) in [
+        (UploadedFileRole::Primary, 1),
+        (UploadedFileRole::Primary, 2),
+        (UploadedFileRole::Comparison, 1),
+        (UploadedFileRole::Comparison, 2),
+    ] {
+        repo.record_chunk_results(&get_dummy_chunk_results(file_role, chunk_sequence_number))
+            .await
+            .unwrap();
+    }
+
+    //act
+    let task = repo.mark_task_reconciling(&String::from("RECON-TASK-1234")).await.unwrap();
+
+    //assert
+    let stored = repo.get_task_details(&String::from("RECON-TASK-1234")).await.unwrap();
+    assert_eq!(task.status, ReconTaskStatus::Completed);
+    assert_eq!(stored.status, ReconTaskStatus::Completed);
+    assert!(stored.details.is_done);
+    assert_eq!(stored.results_summary.counts.matched, 40);
+}
+
+#[actix_web::test]
+async fn given_slot_already_reserved_for_the_task_reserve_active_task_slot_does_not_take_another() {
+    //setup
+    let (_sidecar, repo) = setup_repo().await;
+    let user_id = String::from("test-user-id");
+    let task_id = String::from("RECON-TASK-1");
+    repo.reserve_active_task_slot(&user_id, &task_id, 2).await.unwrap();
+
+    //act
+    let reserved_again = repo.reserve_active_task_slot(&user_id, &task_id, 2).await.unwrap();
+    let reserved_other = repo
+        .reserve_active_task_slot(&user_id, &String::from("RECON-TASK-2"), 2)
+        .await
+        .unwrap();
+    let reserved_over_quota = repo
+        .reserve_active_task_slot(&user_id, &String::from("RECON-TASK-3"), 2)
+        .await
+        .unwrap();
+
+    //assert
+    assert!(reserved_again);
+    assert!(reserved_other);
+    assert!(!reserved_over_quota);
+}
+
+#[actix_web::test]
+async fn given_slot_released_twice_release_active_task_slot_frees_only_that_task() {
+    //setup
+    let (_sidecar, repo) = setup_repo().await;
+    let user_id = String::from("test-user-id");
+    let first_task_id = String::from("RECON-TASK-1");
+    let second_task_id = String::from("RECON-TASK-2");
+    repo.reserve_active_task_slot(&user_id, &first_task_id, 2).await.unwrap();
+    repo.reserve_active_task_slot(&user_id, &second_task_id, 2).await.unwrap();
+
+    //act
+    repo.release_active_task_slot(&user_id, &first_task_id).await.unwrap();
+    repo.release_active_task_slot(&user_id, &first_task_id).await.unwrap();
+
+    //assert
+    let reserved_third = repo
+        .reserve_active_task_slot(&user_id, &String::from("RECON-TASK-3"), 2)
+        .await
+        .unwrap();
+    let reserved_fourth = repo
+        .reserve_active_task_slot(&user_id, &String::from("RECON-TASK-4"), 2)
+        .await
+        .unwrap();
+    assert!(reserved_third);
+    assert!(!reserved_fourth);
+}
+
+async fn setup_repo() -> (FakeDaprSidecar, ReconTaskDetailsRepositoryManager) {
+    let sidecar = FakeDaprSidecar::new();
+    let address = sidecar.start().await;
+    let client = connect_to_dapr(&address).await.unwrap();
+
+    let repo = ReconTaskDetailsRepositoryManager::new(
+        String::from(STORE_NAME),
+        client,
+        DEFAULT_RETRYABLE_STATUS_CODES.to_vec(),
+    );
+
+    (sidecar, repo)
+}
+
+fn get_dummy_recon_task() -> ReconTask {
+    ReconTask {
+        details: get_dummy_recon_task_details(),
+        created_by: String::from("test-user-id"),
+        created_at: Utc::now(),
+        updated_at: Utc::now(),
+        cancellation: None,
+        reconciliation_start: None,
+        template: None,
+        pending_comparison_pairs: vec![],
+        resolved_comparison_pairs: vec![],
+        accepted_comparison_pairs: vec![],
+        comparison_pair_rules: vec![],
+        row_key_positions: vec![],
+        row_key_separator: String::from("|"),
+        column_type_mismatches: vec![],
+        status: ReconTaskStatus::AwaitingFiles,
+        results_summary: ReconResultsSummary::default(),
+        etag: None,
+    }
+}
+
+fn get_dummy_reconciling_recon_task() -> ReconTask {
+    let mut recon_task = get_dummy_recon_task();
+    recon_task.status = ReconTaskStatus::Reconciling;
+    recon_task
+}
+
+fn get_dummy_starting_recon_task() -> ReconTask {
+    let mut recon_task = get_dummy_recon_task();
+    recon_task.status = ReconTaskStatus::Starting;
+    recon_task
+}
+
+fn get_dummy_chunk_results(file_role: UploadedFileRole, chunk_sequence_number: u64) -> ReconChunkResults {
+    ReconChunkResults {
+        task_id: String::from("RECON-TASK-1234"),
+        file_role,
+        chunk_sequence_number,
+        total_chunks: 2,
+        counts: ReconResultCounts {
+            matched: 10,
+            unmatched_in_primary: 0,
+            unmatched_in_comparison: 0,
+            duplicates: 0,
+            field_mismatches: 0,
+        },
+        results_file_name: None,
+    }
+}
+
+fn get_dummy_recon_task_details() -> ReconTaskDetails {
+    ReconTaskDetails {
+        id: String::from("RECON-TASK-1234"),
+        primary_file_id: None,
+        comparison_file_id: None,
+        is_done: false,
+        has_begun: true,
+        comparison_pairs: vec![ComparisonPair {
+            primary_file_column_index: 0,
+            comparison_file_column_index: 0,
+            is_row_identifier: true,
+        }],
+        recon_config: ReconciliationConfigs {
+            should_check_for_duplicate_records_in_comparison_file: false,
+            should_reconciliation_be_case_sensitive: true,
+            should_ignore_white_space: true,
+            should_do_reverse_reconciliation: false,
+        },
+        recon_results_queue_info: FileChunkQueue {
+            topic_id: String::from("RECON-RESULTS-QUEUE-RECON-TASK-1234"),
+            last_acknowledged_id: None,
+        },
+        primary_file_chunks_queue_info: FileChunkQueue {
+            topic_id: String::from("PRIMARY-FILE-QUEUE-RECON-TASK-1234"),
+            last_acknowledged_id: None,
+        },
+        comparison_file_chunks_queue_info: FileChunkQueue {
+            topic_id: String::from("COMPARISON-FILE-QUEUE-RECON-TASK-1234"),
+            last_acknowledged_id: None,
+        },
+    }
+}
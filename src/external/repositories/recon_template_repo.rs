@@ -0,0 +1,106 @@
+use async_trait::async_trait;
+use dapr::dapr::dapr::proto::runtime::v1::dapr_client::DaprClient;
+use tonic::{Code, transport::Channel as TonicChannel};
+
+use crate::external::dapr::dapr_state::DaprStateStore;
+use crate::internal::{
+    interfaces::recon_templates_repository::ReconTemplateRepositoryInterface,
+    models::entities::recon_template::ReconTemplate,
+    shared_reconciler_rust_libraries::models::entities::app_errors::{AppError, AppErrorKind},
+};
+
+const RECON_TEMPLATES_INDEX_KEY: &str = "RECON-TEMPLATES-INDEX";
+
+pub struct ReconTemplateRepositoryManager {
+    pub state_store: DaprStateStore,
+}
+
+#[async_trait]
+impl ReconTemplateRepositoryInterface for ReconTemplateRepositoryManager {
+    async fn get_template(&self, template_id: &str) -> Result<ReconTemplate, AppError> {
+        let record = self
+            .state_store
+            .get_record::<ReconTemplate>(template_id)
+            .await?;
+
+        return Ok(record.data);
+    }
+
+    async fn get_template_version(
+        &self,
+        template_id: &str,
+        version: u32,
+    ) -> Result<ReconTemplate, AppError> {
+        let record = self
+            .state_store
+            .get_record::<ReconTemplate>(&template_version_key(template_id, version))
+            .await?;
+
+        return Ok(record.data);
+    }
+
+    async fn save_template(&self, template: &ReconTemplate) -> Result<String, AppError> {
+        let key = template.id.clone();
+
+        //the snapshot goes first so the latest version can always be looked up by number
+        self.state_store
+            .save_record(
+                &template_version_key(&key, template.version),
+                template,
+                template.updated_at,
+            )
+            .await?;
+
+        self.state_store
+            .save_record(&key, template, template.created_at)
+            .await?;
+
+        self.state_store
+            .add_to_index(RECON_TEMPLATES_INDEX_KEY, &key)
+            .await?;
+
+        return Ok(key);
+    }
+
+    //only the latest version is removed, tasks can still look up the versions they were created from
+    async fn delete_template(&self, template_id: &str) -> Result<bool, AppError> {
+        self.state_store.delete(template_id).await?;
+
+        self.state_store
+            .remove_from_index(RECON_TEMPLATES_INDEX_KEY, template_id)
+            .await?;
+
+        return Ok(true);
+    }
+
+    async fn list_templates(&self) -> Result<Vec<ReconTemplate>, AppError> {
+        let mut templates = vec![];
+
+        for key in self.state_store.read_index(RECON_TEMPLATES_INDEX_KEY).await? {
+            match self.state_store.get_record::<ReconTemplate>(&key).await {
+                Ok(record) => templates.push(record.data),
+                //the template was deleted but its key is still in the index
+                Err(e) if matches!(e.kind, AppErrorKind::NotFound) => continue,
+                Err(e) => return Err(e),
+            }
+        }
+
+        return Ok(templates);
+    }
+}
+
+impl ReconTemplateRepositoryManager {
+    pub(crate) fn new(
+        store_name: String,
+        client: DaprClient<TonicChannel>,
+        retryable_status_codes: Vec<Code>,
+    ) -> Self {
+        ReconTemplateRepositoryManager {
+            state_store: DaprStateStore::new(store_name, client, retryable_status_codes),
+        }
+    }
+}
+
+fn template_version_key(template_id: &str, version: u32) -> String {
+    format!("{}-V{}", template_id, version)
+}
@@ -0,0 +1,98 @@
+use chrono::Utc;
+
+use crate::external::dapr::{
+    dapr_client::connect_to_dapr, dapr_errors::DEFAULT_RETRYABLE_STATUS_CODES,
+    fake_dapr_sidecar::FakeDaprSidecar,
+};
+use crate::internal::{
+    interfaces::recon_templates_repository::ReconTemplateRepositoryInterface,
+    models::entities::{
+        named_comparison_pair::{ColumnReference, NamedComparisonPair},
+        recon_template::ReconTemplate,
+    },
+    shared_reconciler_rust_libraries::models::entities::recon_tasks_models::ReconciliationConfigs,
+};
+
+use super::recon_template_repo::ReconTemplateRepositoryManager;
+
+const STORE_NAME: &str = "statestore";
+
+#[actix_web::test]
+async fn given_updated_template_get_template_returns_latest_and_older_versions_stay_readable() {
+    //setup
+    let (_sidecar, repo) = setup_repo().await;
+    let first_version = get_dummy_recon_template();
+    let mut second_version = get_dummy_recon_template();
+    second_version.version = 2;
+    second_version.name = String::from("daily bank recon v2");
+
+    //act
+    repo.save_template(&first_version).await.unwrap();
+    repo.save_template(&second_version).await.unwrap();
+
+    let latest = repo.get_template(&first_version.id).await.unwrap();
+    let older = repo.get_template_version(&first_version.id, 1).await.unwrap();
+
+    //assert
+    assert_eq!(latest.version, 2);
+    assert_eq!(latest.name, "daily bank recon v2");
+    assert_eq!(older.name, "daily bank recon");
+}
+
+#[actix_web::test]
+async fn given_deleted_template_list_templates_skips_it_but_its_versions_remain() {
+    //setup
+    let (_sidecar, repo) = setup_repo().await;
+    let template = get_dummy_recon_template();
+    repo.save_template(&template).await.unwrap();
+
+    //act
+    repo.delete_template(&template.id).await.unwrap();
+
+    let templates = repo.list_templates().await.unwrap();
+    let version = repo.get_template_version(&template.id, 1).await;
+
+    //assert
+    assert!(templates.is_empty());
+    assert!(version.is_ok());
+}
+
+async fn setup_repo() -> (FakeDaprSidecar, ReconTemplateRepositoryManager) {
+    let sidecar = FakeDaprSidecar::new();
+    let address = sidecar.start().await;
+    let client = connect_to_dapr(&address).await.unwrap();
+
+    let repo = ReconTemplateRepositoryManager::new(
+        String::from(STORE_NAME),
+        client,
+        DEFAULT_RETRYABLE_STATUS_CODES.to_vec(),
+    );
+
+    (sidecar, repo)
+}
+
+fn get_dummy_recon_template() -> ReconTemplate {
+    ReconTemplate {
+        id: String::from("RECON-TEMPLATE-1234"),
+        name: String::from("daily bank recon"),
+        version: 1,
+        recon_configurations: ReconciliationConfigs {
+            should_check_for_duplicate_records_in_comparison_file: false,
+            should_reconciliation_be_case_sensitive: true,
+            should_ignore_white_space: true,
+            should_do_reverse_reconciliation: false,
+        },
+        comparison_pairs: vec![NamedComparisonPair {
+            primary_file_column: ColumnReference::Header(String::from("id")),
+            comparison_file_column: ColumnReference::Header(String::from("reference")),
+            is_row_identifier: true,
+            ignore_header_case: false,
+            row_key_position: None,
+            rules: None,
+        }],
+        row_key_separator: String::from("|"),
+        created_by: String::from("test-user-id"),
+        created_at: Utc::now(),
+        updated_at: Utc::now(),
+    }
+}
@@ -0,0 +1,64 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+
+use crate::external::resilience::{
+    call_with_resilience, circuit_breaker::CircuitBreaker, retry_policy::RetryPolicy,
+};
+use crate::internal::{
+    interfaces::recon_files_repository::ReconFileDetailsRepositoryInterface,
+    models::entities::recon_file::ReconFile,
+    shared_reconciler_rust_libraries::models::entities::app_errors::AppError,
+};
+
+pub struct ResilientReconFileDetailsRepository {
+    pub inner: Box<dyn ReconFileDetailsRepositoryInterface>,
+    pub retry_policy: RetryPolicy,
+    pub circuit_breaker: Arc<CircuitBreaker>,
+}
+
+#[async_trait]
+impl ReconFileDetailsRepositoryInterface for ResilientReconFileDetailsRepository {
+    async fn get_recon_file_details(
+        &self,
+        file_id: &str,
+    ) -> Result<ReconFile, AppError> {
+        call_with_resilience!(self, self.inner.get_recon_file_details(file_id))
+    }
+
+    async fn create_recon_file_details(
+        &self,
+        file_details: &ReconFile,
+    ) -> Result<String, AppError> {
+        call_with_resilience!(self, self.inner.create_recon_file_details(file_details))
+    }
+
+    async fn update_recon_file_details(
+        &self,
+        file_details: &ReconFile,
+    ) -> Result<ReconFile, AppError> {
+        call_with_resilience!(self, self.inner.update_recon_file_details(file_details))
+    }
+
+    async fn delete_recon_file_details(&self, file_id: &str) -> Result<bool, AppError> {
+        call_with_resilience!(self, self.inner.delete_recon_file_details(file_id))
+    }
+
+    async fn list_recon_file_ids(&self) -> Result<Vec<String>, AppError> {
+        call_with_resilience!(self, self.inner.list_recon_file_ids())
+    }
+}
+
+impl ResilientReconFileDetailsRepository {
+    pub(crate) fn new(
+        inner: Box<dyn ReconFileDetailsRepositoryInterface>,
+        retry_policy: RetryPolicy,
+        circuit_breaker: Arc<CircuitBreaker>,
+    ) -> Self {
+        ResilientReconFileDetailsRepository {
+            inner,
+            retry_policy,
+            circuit_breaker,
+        }
+    }
+}
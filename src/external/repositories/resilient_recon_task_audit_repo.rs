@@ -0,0 +1,46 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+
+use crate::external::resilience::{
+    call_with_resilience, circuit_breaker::CircuitBreaker, retry_policy::RetryPolicy,
+};
+use crate::internal::{
+    interfaces::recon_task_audit_repository::ReconTaskAuditRepositoryInterface,
+    models::entities::recon_task_audit::ReconTaskAuditEntry,
+    shared_reconciler_rust_libraries::models::entities::app_errors::AppError,
+};
+
+pub struct ResilientReconTaskAuditRepository {
+    pub inner: Box<dyn ReconTaskAuditRepositoryInterface>,
+    pub retry_policy: RetryPolicy,
+    pub circuit_breaker: Arc<CircuitBreaker>,
+}
+
+#[async_trait]
+impl ReconTaskAuditRepositoryInterface for ResilientReconTaskAuditRepository {
+    async fn append_audit_entry(&self, entry: &ReconTaskAuditEntry) -> Result<(), AppError> {
+        call_with_resilience!(self, self.inner.append_audit_entry(entry))
+    }
+
+    async fn get_audit_entries(
+        &self,
+        task_id: &str,
+    ) -> Result<Vec<ReconTaskAuditEntry>, AppError> {
+        call_with_resilience!(self, self.inner.get_audit_entries(task_id))
+    }
+}
+
+impl ResilientReconTaskAuditRepository {
+    pub(crate) fn new(
+        inner: Box<dyn ReconTaskAuditRepositoryInterface>,
+        retry_policy: RetryPolicy,
+        circuit_breaker: Arc<CircuitBreaker>,
+    ) -> Self {
+        ResilientReconTaskAuditRepository {
+            inner,
+            retry_policy,
+            circuit_breaker,
+        }
+    }
+}
@@ -0,0 +1,88 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+
+use crate::external::resilience::{
+    call_with_resilience, circuit_breaker::CircuitBreaker, retry_policy::RetryPolicy,
+};
+use crate::internal::{
+    interfaces::recon_tasks_repository::ReconTaskDetailsRepositoryInterface,
+    models::entities::{recon_results::ReconChunkResults, recon_task::ReconTask},
+    shared_reconciler_rust_libraries::models::entities::app_errors::AppError,
+};
+
+pub struct ResilientReconTaskDetailsRepository {
+    pub inner: Box<dyn ReconTaskDetailsRepositoryInterface>,
+    pub retry_policy: RetryPolicy,
+    pub circuit_breaker: Arc<CircuitBreaker>,
+}
+
+#[async_trait]
+impl ReconTaskDetailsRepositoryInterface for ResilientReconTaskDetailsRepository {
+    async fn get_task_details(&self, task_id: &str) -> Result<ReconTask, AppError> {
+        call_with_resilience!(self, self.inner.get_task_details(task_id))
+    }
+
+    async fn create_task_details(
+        &self,
+        task_details: &ReconTask,
+    ) -> Result<String, AppError> {
+        call_with_resilience!(self, self.inner.create_task_details(task_details))
+    }
+
+    async fn update_task_details(
+        &self,
+        task_details: &ReconTask,
+    ) -> Result<Option<ReconTask>, AppError> {
+        call_with_resilience!(self, self.inner.update_task_details(task_details))
+    }
+
+    async fn list_task_details(&self) -> Result<Vec<ReconTask>, AppError> {
+        call_with_resilience!(self, self.inner.list_task_details())
+    }
+
+    async fn record_chunk_results(
+        &self,
+        results: &ReconChunkResults,
+    ) -> Result<ReconTask, AppError> {
+        call_with_resilience!(self, self.inner.record_chunk_results(results))
+    }
+
+    async fn mark_task_reconciling(&self, task_id: &str) -> Result<ReconTask, AppError> {
+        call_with_resilience!(self, self.inner.mark_task_reconciling(task_id))
+    }
+
+    async fn reserve_active_task_slot(
+        &self,
+        user_id: &str,
+        task_id: &str,
+        max_active_tasks: usize,
+    ) -> Result<bool, AppError> {
+        call_with_resilience!(
+            self,
+            self.inner.reserve_active_task_slot(user_id, task_id, max_active_tasks)
+        )
+    }
+
+    async fn release_active_task_slot(
+        &self,
+        user_id: &str,
+        task_id: &str,
+    ) -> Result<(), AppError> {
+        call_with_resilience!(self, self.inner.release_active_task_slot(user_id, task_id))
+    }
+}
+
+impl ResilientReconTaskDetailsRepository {
+    pub(crate) fn new(
+        inner: Box<dyn ReconTaskDetailsRepositoryInterface>,
+        retry_policy: RetryPolicy,
+        circuit_breaker: Arc<CircuitBreaker>,
+    ) -> Self {
+        ResilientReconTaskDetailsRepository {
+            inner,
+            retry_policy,
+            circuit_breaker,
+        }
+    }
+}
@@ -0,0 +1,184 @@
+use std::sync::{
+    atomic::{AtomicU32, Ordering},
+    Arc,
+};
+use std::time::Duration;
+
+use crate::external::resilience::{
+    circuit_breaker::{CircuitBreaker, CircuitState},
+    retry_policy::RetryPolicy,
+};
+use crate::internal::{
+    interfaces::recon_tasks_repository::{
+        MockReconTaskDetailsRepositoryInterface, ReconTaskDetailsRepositoryInterface,
+    },
+    models::entities::{
+        recon_results::{ReconChunkResults, ReconResultCounts},
+        upload_session::UploadedFileRole,
+    },
+    shared_reconciler_rust_libraries::models::entities::app_errors::{AppError, AppErrorKind},
+};
+
+use super::resilient_recon_task_details_repo::ResilientReconTaskDetailsRepository;
+
+#[actix_web::test]
+async fn given_transient_errors_then_success_retries_and_returns_success() {
+    //setup
+    let calls = Arc::new(AtomicU32::new(0));
+    let calls_in_mock = calls.clone();
+
+    let mut mock_repo = Box::new(MockReconTaskDetailsRepositoryInterface::new());
+    mock_repo.expect_list_task_details().returning(move || {
+        match calls_in_mock.fetch_add(1, Ordering::SeqCst) {
+            0 | 1 => Err(unavailable_error()),
+            _ => Ok(vec![]),
+        }
+    });
+
+    let repo = ResilientReconTaskDetailsRepository::new(
+        mock_repo,
+        fast_retry_policy(3),
+        Arc::new(CircuitBreaker::new(10, Duration::from_secs(30))),
+    );
+
+    //act
+    let result = repo.list_task_details().await;
+
+    //assert
+    assert!(result.is_ok());
+    assert_eq!(calls.load(Ordering::SeqCst), 3);
+}
+
+#[actix_web::test]
+async fn given_non_retryable_error_returns_error_without_retrying() {
+    //setup
+    let mut mock_repo = Box::new(MockReconTaskDetailsRepositoryInterface::new());
+    mock_repo
+        .expect_list_task_details()
+        .times(1)
+        .returning(|| Err(AppError::new(AppErrorKind::NotFound, String::from("missing"))));
+
+    let repo = ResilientReconTaskDetailsRepository::new(
+        mock_repo,
+        fast_retry_policy(3),
+        Arc::new(CircuitBreaker::new(10, Duration::from_secs(30))),
+    );
+
+    //act
+    let result = repo.list_task_details().await;
+
+    //assert
+    assert!(matches!(result.err().unwrap().kind, AppErrorKind::NotFound));
+}
+
+#[actix_web::test]
+async fn given_sidecar_keeps_failing_gives_up_after_max_retries() {
+    //setup
+    let mut mock_repo = Box::new(MockReconTaskDetailsRepositoryInterface::new());
+    mock_repo
+        .expect_list_task_details()
+        .times(3)
+        .returning(|| Err(unavailable_error()));
+
+    let repo = ResilientReconTaskDetailsRepository::new(
+        mock_repo,
+        fast_retry_policy(2),
+        Arc::new(CircuitBreaker::new(10, Duration::from_secs(30))),
+    );
+
+    //act
+    let result = repo.list_task_details().await;
+
+    //assert
+    assert!(matches!(result.err().unwrap().kind, AppErrorKind::ConnectionError));
+}
+
+#[actix_web::test]
+async fn given_retries_outnumber_the_failure_threshold_returns_the_sidecars_error() {
+    //setup
+    let mut mock_repo = Box::new(MockReconTaskDetailsRepositoryInterface::new());
+    mock_repo
+        .expect_list_task_details()
+        .times(3)
+        .returning(|| Err(unavailable_error()));
+
+    let repo = ResilientReconTaskDetailsRepository::new(
+        mock_repo,
+        fast_retry_policy(2),
+        Arc::new(CircuitBreaker::new(1, Duration::from_secs(30))),
+    );
+
+    //act
+    let result = repo.list_task_details().await;
+
+    //assert
+    assert_eq!(result.err().unwrap().message, "unavailable");
+    assert_eq!(repo.circuit_breaker.state(), CircuitState::Open);
+}
+
+#[actix_web::test]
+async fn given_circuit_is_open_fails_fast_without_calling_sidecar() {
+    //setup
+    let mut mock_repo = Box::new(MockReconTaskDetailsRepositoryInterface::new());
+    mock_repo.expect_list_task_details().times(0);
+
+    let circuit_breaker = Arc::new(CircuitBreaker::new(1, Duration::from_secs(30)));
+    circuit_breaker.record_failure();
+
+    let repo =
+        ResilientReconTaskDetailsRepository::new(mock_repo, fast_retry_policy(3), circuit_breaker);
+
+    //act
+    let result = repo.list_task_details().await;
+
+    //assert
+    assert!(matches!(result.err().unwrap().kind, AppErrorKind::ConnectionError));
+    assert_eq!(repo.circuit_breaker.state(), CircuitState::Open);
+}
+
+#[actix_web::test]
+async fn given_transient_errors_recording_chunk_results_retries_since_the_stored_task_says_whether_it_completed() {
+    //setup
+    let mut mock_repo = Box::new(MockReconTaskDetailsRepositoryInterface::new());
+    mock_repo
+        .expect_record_chunk_results()
+        .times(3)
+        .returning(|_| Err(unavailable_error()));
+
+    let repo = ResilientReconTaskDetailsRepository::new(
+        mock_repo,
+        fast_retry_policy(2),
+        Arc::new(CircuitBreaker::new(10, Duration::from_secs(30))),
+    );
+
+    //act
+    let result = repo.record_chunk_results(&dummy_chunk_results()).await;
+
+    //assert
+    assert!(matches!(result.err().unwrap().kind, AppErrorKind::ConnectionError));
+}
+
+fn fast_retry_policy(max_retries: u32) -> RetryPolicy {
+    RetryPolicy::new(max_retries, Duration::from_millis(1), Duration::from_millis(1))
+}
+
+fn unavailable_error() -> AppError {
+    AppError::new(AppErrorKind::ConnectionError, String::from("unavailable"))
+}
+
+fn dummy_chunk_results() -> ReconChunkResults {
+    ReconChunkResults {
+        task_id: String::from("task-1234"),
+        file_role: UploadedFileRole::Primary,
+        chunk_sequence_number: 1,
+        total_chunks: 1,
+        counts: ReconResultCounts {
+            matched: 10,
+            unmatched_in_primary: 0,
+            unmatched_in_comparison: 0,
+            duplicates: 0,
+            field_mismatches: 0,
+        },
+        results_file_name: None,
+    }
+}
@@ -0,0 +1,60 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+
+use crate::external::resilience::{
+    call_once_with_resilience, call_with_resilience, circuit_breaker::CircuitBreaker,
+    retry_policy::RetryPolicy,
+};
+use crate::internal::{
+    interfaces::recon_templates_repository::ReconTemplateRepositoryInterface,
+    models::entities::recon_template::ReconTemplate,
+    shared_reconciler_rust_libraries::models::entities::app_errors::AppError,
+};
+
+pub struct ResilientReconTemplateRepository {
+    pub inner: Box<dyn ReconTemplateRepositoryInterface>,
+    pub retry_policy: RetryPolicy,
+    pub circuit_breaker: Arc<CircuitBreaker>,
+}
+
+#[async_trait]
+impl ReconTemplateRepositoryInterface for ResilientReconTemplateRepository {
+    async fn get_template(&self, template_id: &str) -> Result<ReconTemplate, AppError> {
+        call_with_resilience!(self, self.inner.get_template(template_id))
+    }
+
+    async fn get_template_version(
+        &self,
+        template_id: &str,
+        version: u32,
+    ) -> Result<ReconTemplate, AppError> {
+        call_with_resilience!(self, self.inner.get_template_version(template_id, version))
+    }
+
+    async fn save_template(&self, template: &ReconTemplate) -> Result<String, AppError> {
+        call_once_with_resilience!(self, self.inner.save_template(template))
+    }
+
+    async fn delete_template(&self, template_id: &str) -> Result<bool, AppError> {
+        call_with_resilience!(self, self.inner.delete_template(template_id))
+    }
+
+    async fn list_templates(&self) -> Result<Vec<ReconTemplate>, AppError> {
+        call_with_resilience!(self, self.inner.list_templates())
+    }
+}
+
+impl ResilientReconTemplateRepository {
+    pub(crate) fn new(
+        inner: Box<dyn ReconTemplateRepositoryInterface>,
+        retry_policy: RetryPolicy,
+        circuit_breaker: Arc<CircuitBreaker>,
+    ) -> Self {
+        ResilientReconTemplateRepository {
+            inner,
+            retry_policy,
+            circuit_breaker,
+        }
+    }
+}
@@ -0,0 +1,63 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use chrono::Utc;
+
+use crate::external::resilience::{
+    circuit_breaker::{CircuitBreaker, CircuitState},
+    retry_policy::RetryPolicy,
+};
+use crate::internal::{
+    interfaces::recon_templates_repository::{
+        MockReconTemplateRepositoryInterface, ReconTemplateRepositoryInterface,
+    },
+    models::entities::recon_template::ReconTemplate,
+    shared_reconciler_rust_libraries::models::entities::{
+        app_errors::{AppError, AppErrorKind},
+        recon_tasks_models::ReconciliationConfigs,
+    },
+};
+
+use super::resilient_recon_template_repo::ResilientReconTemplateRepository;
+
+#[actix_web::test]
+async fn given_transient_error_on_a_write_that_cannot_run_twice_returns_it_without_retrying() {
+    //setup
+    let mut mock_repo = Box::new(MockReconTemplateRepositoryInterface::new());
+    mock_repo
+        .expect_save_template()
+        .times(1)
+        .returning(|_| Err(AppError::new(AppErrorKind::ConnectionError, String::from("unavailable"))));
+
+    let repo = ResilientReconTemplateRepository::new(
+        mock_repo,
+        RetryPolicy::new(3, Duration::from_millis(1), Duration::from_millis(1)),
+        Arc::new(CircuitBreaker::new(1, Duration::from_secs(30))),
+    );
+
+    //act
+    let result = repo.save_template(&get_dummy_recon_template()).await;
+
+    //assert
+    assert!(matches!(result.err().unwrap().kind, AppErrorKind::ConnectionError));
+    assert_eq!(repo.circuit_breaker.state(), CircuitState::Open);
+}
+
+fn get_dummy_recon_template() -> ReconTemplate {
+    ReconTemplate {
+        id: String::from("RECON-TEMPLATE-1234"),
+        name: String::from("daily bank recon"),
+        version: 1,
+        recon_configurations: ReconciliationConfigs {
+            should_check_for_duplicate_records_in_comparison_file: false,
+            should_reconciliation_be_case_sensitive: true,
+            should_ignore_white_space: true,
+            should_do_reverse_reconciliation: false,
+        },
+        comparison_pairs: vec![],
+        row_key_separator: String::from("|"),
+        created_by: String::from("test-user-id"),
+        created_at: Utc::now(),
+        updated_at: Utc::now(),
+    }
+}
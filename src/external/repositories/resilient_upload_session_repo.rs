@@ -0,0 +1,59 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+
+use crate::external::resilience::{
+    call_with_resilience, circuit_breaker::CircuitBreaker, retry_policy::RetryPolicy,
+};
+use crate::internal::{
+    interfaces::upload_sessions_repository::UploadSessionRepositoryInterface,
+    models::entities::upload_session::UploadSession,
+    shared_reconciler_rust_libraries::models::entities::app_errors::AppError,
+};
+
+pub struct ResilientUploadSessionRepository {
+    pub inner: Box<dyn UploadSessionRepositoryInterface>,
+    pub retry_policy: RetryPolicy,
+    pub circuit_breaker: Arc<CircuitBreaker>,
+}
+
+#[async_trait]
+impl UploadSessionRepositoryInterface for ResilientUploadSessionRepository {
+    async fn get_upload_session(&self, upload_id: &str) -> Result<UploadSession, AppError> {
+        call_with_resilience!(self, self.inner.get_upload_session(upload_id))
+    }
+
+    async fn save_upload_session(&self, session: &UploadSession) -> Result<(), AppError> {
+        call_with_resilience!(self, self.inner.save_upload_session(session))
+    }
+
+    async fn record_upload_chunk(
+        &self,
+        upload_id: &str,
+        chunk_number: u64,
+    ) -> Result<UploadSession, AppError> {
+        call_with_resilience!(self, self.inner.record_upload_chunk(upload_id, chunk_number))
+    }
+
+    async fn complete_upload_session(
+        &self,
+        upload_id: &str,
+        file_id: &str,
+    ) -> Result<UploadSession, AppError> {
+        call_with_resilience!(self, self.inner.complete_upload_session(upload_id, file_id))
+    }
+}
+
+impl ResilientUploadSessionRepository {
+    pub(crate) fn new(
+        inner: Box<dyn UploadSessionRepositoryInterface>,
+        retry_policy: RetryPolicy,
+        circuit_breaker: Arc<CircuitBreaker>,
+    ) -> Self {
+        ResilientUploadSessionRepository {
+            inner,
+            retry_policy,
+            circuit_breaker,
+        }
+    }
+}
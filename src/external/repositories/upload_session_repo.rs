@@ -0,0 +1,85 @@
+use async_trait::async_trait;
+use chrono::Utc;
+use dapr::dapr::dapr::proto::runtime::v1::dapr_client::DaprClient;
+use tonic::{Code, transport::Channel as TonicChannel};
+
+use crate::external::dapr::dapr_state::DaprStateStore;
+use crate::internal::{
+    interfaces::upload_sessions_repository::UploadSessionRepositoryInterface,
+    models::entities::upload_session::{UploadSession, UploadSessionStatus},
+    shared_reconciler_rust_libraries::models::entities::app_errors::AppError,
+};
+
+pub struct UploadSessionRepositoryManager {
+    pub state_store: DaprStateStore,
+}
+
+#[async_trait]
+impl UploadSessionRepositoryInterface for UploadSessionRepositoryManager {
+    async fn get_upload_session(&self, upload_id: &str) -> Result<UploadSession, AppError> {
+        let record = self
+            .state_store
+            .get_record::<UploadSession>(upload_id)
+            .await?;
+
+        return Ok(record.data);
+    }
+
+    async fn save_upload_session(&self, session: &UploadSession) -> Result<(), AppError> {
+        self
+            .state_store
+            .save_record(&session.id, session, session.created_at)
+            .await
+    }
+
+    async fn record_upload_chunk(
+        &self,
+        upload_id: &str,
+        chunk_number: u64,
+    ) -> Result<UploadSession, AppError> {
+        self
+            .state_store
+            .update_record_with_etag(upload_id, |session: &mut UploadSession| {
+                if session.status != UploadSessionStatus::Open {
+                    return false;
+                }
+
+                session.record_chunk(chunk_number);
+                session.updated_at = Utc::now();
+                true
+            })
+            .await
+    }
+
+    async fn complete_upload_session(
+        &self,
+        upload_id: &str,
+        file_id: &str,
+    ) -> Result<UploadSession, AppError> {
+        self
+            .state_store
+            .update_record_with_etag(upload_id, |session: &mut UploadSession| {
+                if session.file_id.is_some() {
+                    return false;
+                }
+
+                session.status = UploadSessionStatus::Completed;
+                session.file_id = Some(file_id.to_string());
+                session.updated_at = Utc::now();
+                true
+            })
+            .await
+    }
+}
+
+impl UploadSessionRepositoryManager {
+    pub(crate) fn new(
+        store_name: String,
+        client: DaprClient<TonicChannel>,
+        retryable_status_codes: Vec<Code>,
+    ) -> Self {
+        UploadSessionRepositoryManager {
+            state_store: DaprStateStore::new(store_name, client, retryable_status_codes),
+        }
+    }
+}
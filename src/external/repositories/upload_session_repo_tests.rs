@@ -0,0 +1,143 @@
+use chrono::Utc;
+
+use crate::external::dapr::{
+    dapr_client::connect_to_dapr, dapr_errors::DEFAULT_RETRYABLE_STATUS_CODES,
+    fake_dapr_sidecar::FakeDaprSidecar,
+};
+use crate::internal::{
+    interfaces::upload_sessions_repository::UploadSessionRepositoryInterface,
+    models::entities::upload_session::{UploadSession, UploadSessionStatus, UploadedFileRole},
+    shared_reconciler_rust_libraries::models::entities::app_errors::AppErrorKind,
+};
+
+use super::upload_session_repo::UploadSessionRepositoryManager;
+
+const STORE_NAME: &str = "statestore";
+
+#[actix_web::test]
+async fn given_saved_session_with_received_chunks_a_new_repository_reads_it_back() {
+    //setup
+    let sidecar = FakeDaprSidecar::new();
+    let address = sidecar.start().await;
+    let repo = new_repo(&address).await;
+    let mut session = get_dummy_upload_session();
+    session.record_chunk(1);
+
+    //act
+    repo.save_upload_session(&session).await.unwrap();
+
+    //a second repository stands in for the service after a restart
+    let restarted_repo = new_repo(&address).await;
+    let stored = restarted_repo.get_upload_session(&session.id).await.unwrap();
+
+    //assert
+    assert_eq!(stored, session);
+}
+
+#[actix_web::test]
+async fn given_unknown_upload_id_get_upload_session_returns_not_found() {
+    //setup
+    let sidecar = FakeDaprSidecar::new();
+    let address = sidecar.start().await;
+    let repo = new_repo(&address).await;
+
+    //act
+    let result = repo
+        .get_upload_session(&String::from("UPLOAD-SESSION-unknown"))
+        .await;
+
+    //assert
+    assert!(matches!(result.err().unwrap().kind, AppErrorKind::NotFound));
+}
+
+#[actix_web::test]
+async fn given_chunks_recorded_at_the_same_time_record_upload_chunk_keeps_all_of_them() {
+    //setup
+    let sidecar = FakeDaprSidecar::new();
+    let address = sidecar.start().await;
+    let repo = new_repo(&address).await;
+    let session = get_dummy_upload_session();
+    repo.save_upload_session(&session).await.unwrap();
+
+    //act
+    let (first, second, third) = futures_util::join!(
+        repo.record_upload_chunk(&session.id, 0),
+        repo.record_upload_chunk(&session.id, 1),
+        repo.record_upload_chunk(&session.id, 2),
+    );
+
+    //assert
+    assert!(first.is_ok() && second.is_ok() && third.is_ok());
+    let stored = repo.get_upload_session(&session.id).await.unwrap();
+    assert_eq!(stored.received_chunks, vec![0, 1, 2]);
+}
+
+#[actix_web::test]
+async fn given_completed_session_record_upload_chunk_leaves_it_as_it_is() {
+    //setup
+    let sidecar = FakeDaprSidecar::new();
+    let address = sidecar.start().await;
+    let repo = new_repo(&address).await;
+    let mut session = get_dummy_upload_session();
+    session.status = UploadSessionStatus::Completed;
+    repo.save_upload_session(&session).await.unwrap();
+
+    //act
+    let stored = repo.record_upload_chunk(&session.id, 1).await.unwrap();
+
+    //assert
+    assert_eq!(stored.status, UploadSessionStatus::Completed);
+    assert!(stored.received_chunks.is_empty());
+}
+
+#[actix_web::test]
+async fn given_session_completed_twice_complete_upload_session_keeps_the_first_file() {
+    //setup
+    let sidecar = FakeDaprSidecar::new();
+    let address = sidecar.start().await;
+    let repo = new_repo(&address).await;
+    let session = get_dummy_upload_session();
+    repo.save_upload_session(&session).await.unwrap();
+
+    //act
+    let first = repo
+        .complete_upload_session(&session.id, &String::from("file-1"))
+        .await
+        .unwrap();
+    let second = repo
+        .complete_upload_session(&session.id, &String::from("file-2"))
+        .await
+        .unwrap();
+
+    //assert
+    assert_eq!(first.file_id, Some(String::from("file-1")));
+    assert_eq!(second.file_id, Some(String::from("file-1")));
+    assert_eq!(second.status, UploadSessionStatus::Completed);
+}
+
+async fn new_repo(address: &str) -> UploadSessionRepositoryManager {
+    let client = connect_to_dapr(address).await.unwrap();
+
+    UploadSessionRepositoryManager::new(
+        String::from(STORE_NAME),
+        client,
+        DEFAULT_RETRYABLE_STATUS_CODES.to_vec(),
+    )
+}
+
+fn get_dummy_upload_session() -> UploadSession {
+    UploadSession {
+        id: String::from("UPLOAD-SESSION-1234"),
+        task_id: String::from("RECON-TASK-1234"),
+        file_role: UploadedFileRole::Comparison,
+        file_name: String::from("ledger.csv"),
+        total_size_bytes: 30,
+        chunk_size_bytes: 10,
+        received_chunks: vec![],
+        status: UploadSessionStatus::Open,
+        file_id: None,
+        created_by: String::from("test-user-id"),
+        created_at: Utc::now(),
+        updated_at: Utc::now(),
+    }
+}
@@ -0,0 +1,138 @@
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use crate::internal::shared_reconciler_rust_libraries::models::entities::app_errors::{
+    AppError, AppErrorKind,
+};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CircuitState {
+    Closed,
+    Open,
+    HalfOpen,
+}
+
+#[derive(Debug)]
+struct CircuitBreakerState {
+    state: CircuitState,
+    consecutive_failures: u32,
+    opened_at: Option<Instant>,
+}
+
+//fails fast once the dapr sidecar has failed `failure_threshold` times in a row
+//after `open_duration` a single trial call is let through to probe the sidecar
+#[derive(Debug)]
+pub struct CircuitBreaker {
+    pub failure_threshold: u32,
+    pub open_duration: Duration,
+    inner: Mutex<CircuitBreakerState>,
+}
+
+impl CircuitBreaker {
+    pub fn new(failure_threshold: u32, open_duration: Duration) -> Self {
+        CircuitBreaker {
+            failure_threshold,
+            open_duration,
+            inner: Mutex::new(CircuitBreakerState {
+                state: CircuitState::Closed,
+                consecutive_failures: 0,
+                opened_at: None,
+            }),
+        }
+    }
+
+    #[cfg(test)]
+    pub fn state(&self) -> CircuitState {
+        return self.inner.lock().unwrap().state;
+    }
+
+    pub fn try_acquire(&self) -> Result<CircuitPermit<'_>, AppError> {
+        let mut inner = self.inner.lock().unwrap();
+
+        match inner.state {
+            CircuitState::Closed => Ok(CircuitPermit::new(self, false)),
+            CircuitState::HalfOpen => Err(circuit_open_error()),
+            CircuitState::Open => {
+                let open_for = inner.opened_at.map(|t| t.elapsed()).unwrap_or_default();
+
+                if open_for < self.open_duration {
+                    return Err(circuit_open_error());
+                }
+
+                //let one trial call through
+                inner.state = CircuitState::HalfOpen;
+                Ok(CircuitPermit::new(self, true))
+            }
+        }
+    }
+
+    pub fn record_success(&self) {
+        let mut inner = self.inner.lock().unwrap();
+        inner.state = CircuitState::Closed;
+        inner.consecutive_failures = 0;
+        inner.opened_at = None;
+    }
+
+    pub fn record_failure(&self) {
+        let mut inner = self.inner.lock().unwrap();
+        inner.consecutive_failures += 1;
+
+        let trial_call_failed = inner.state == CircuitState::HalfOpen;
+        if trial_call_failed || inner.consecutive_failures >= self.failure_threshold {
+            inner.state = CircuitState::Open;
+            inner.opened_at = Some(Instant::now());
+        }
+    }
+
+    //the trial call went away without an outcome, so the circuit stays open with its wait already over
+    //and the next caller gets to make the trial call instead
+    fn release_trial(&self) {
+        let mut inner = self.inner.lock().unwrap();
+        if inner.state == CircuitState::HalfOpen {
+            inner.state = CircuitState::Open;
+        }
+    }
+}
+
+//a call the circuit breaker let through, its outcome is reported when it ends
+//a trial call dropped before it reports (the request was cancelled or timed out) never blocks the circuit
+pub struct CircuitPermit<'a> {
+    breaker: &'a CircuitBreaker,
+    is_trial: bool,
+    is_reported: bool,
+}
+
+impl<'a> CircuitPermit<'a> {
+    fn new(breaker: &'a CircuitBreaker, is_trial: bool) -> Self {
+        CircuitPermit {
+            breaker,
+            is_trial,
+            is_reported: false,
+        }
+    }
+
+    pub fn record_success(mut self) {
+        self.is_reported = true;
+        self.breaker.record_success();
+    }
+
+    pub fn record_failure(mut self) {
+        self.is_reported = true;
+        self.breaker.record_failure();
+    }
+}
+
+impl Drop for CircuitPermit<'_> {
+    fn drop(&mut self) {
+        if self.is_trial && !self.is_reported {
+            self.breaker.release_trial();
+        }
+    }
+}
+
+fn circuit_open_error() -> AppError {
+    AppError::new(
+        AppErrorKind::ConnectionError,
+        String::from("the dapr sidecar is unavailable, circuit breaker is open"),
+    )
+}
@@ -0,0 +1,77 @@
+use std::time::Duration;
+
+use super::circuit_breaker::{CircuitBreaker, CircuitState};
+
+#[test]
+fn given_failures_below_threshold_circuit_stays_closed() {
+    //setup
+    let breaker = CircuitBreaker::new(3, Duration::from_secs(30));
+
+    //act
+    breaker.record_failure();
+    breaker.record_failure();
+
+    //assert
+    assert_eq!(breaker.state(), CircuitState::Closed);
+    assert!(breaker.try_acquire().is_ok());
+}
+
+#[test]
+fn given_failures_reach_threshold_circuit_opens_and_fails_fast() {
+    //setup
+    let breaker = CircuitBreaker::new(2, Duration::from_secs(30));
+
+    //act
+    breaker.record_failure();
+    breaker.record_failure();
+
+    //assert
+    assert_eq!(breaker.state(), CircuitState::Open);
+    assert!(breaker.try_acquire().is_err());
+}
+
+#[test]
+fn given_open_duration_elapsed_circuit_lets_one_trial_call_through() {
+    //setup
+    let breaker = CircuitBreaker::new(1, Duration::from_millis(0));
+    breaker.record_failure();
+
+    //act
+    let trial_call = breaker.try_acquire();
+    let concurrent_call = breaker.try_acquire();
+
+    //assert
+    assert!(trial_call.is_ok());
+    assert!(concurrent_call.is_err());
+    assert_eq!(breaker.state(), CircuitState::HalfOpen);
+}
+
+#[test]
+fn given_trial_call_succeeds_circuit_closes() {
+    //setup
+    let breaker = CircuitBreaker::new(1, Duration::from_millis(0));
+    breaker.record_failure();
+    let trial_call = breaker.try_acquire().unwrap();
+
+    //act
+    trial_call.record_success();
+
+    //assert
+    assert_eq!(breaker.state(), CircuitState::Closed);
+}
+
+#[test]
+fn given_trial_call_is_dropped_without_an_outcome_next_caller_gets_the_trial() {
+    //setup
+    let breaker = CircuitBreaker::new(1, Duration::from_millis(0));
+    breaker.record_failure();
+    let trial_call = breaker.try_acquire().unwrap();
+
+    //act
+    drop(trial_call);
+    let next_call = breaker.try_acquire();
+
+    //assert
+    assert!(next_call.is_ok());
+    assert_eq!(breaker.state(), CircuitState::HalfOpen);
+}
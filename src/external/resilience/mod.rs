@@ -0,0 +1,59 @@
+pub mod circuit_breaker;
+pub mod retry_policy;
+
+#[cfg(test)]
+#[path = "./circuit_breaker_tests.rs"]
+mod circuit_breaker_tests;
+
+#[cfg(test)]
+#[path = "./retry_policy_tests.rs"]
+mod retry_policy_tests;
+
+//runs a repository call through the circuit breaker, retrying transient failures with backoff
+//the breaker is asked once per call and told how the call ended once all its retries are done,
+//so a call that gave up returns the sidecar's last error rather than the breaker's
+//expects `$self` to have `retry_policy` and `circuit_breaker` fields
+macro_rules! call_with_resilience {
+    ($self:ident, $call:expr) => {{
+        let permit = $self.circuit_breaker.try_acquire()?;
+
+        let mut attempt: u32 = 0;
+        loop {
+            let result = $call.await;
+
+            match &result {
+                Err(e) if $self.retry_policy.is_retryable(e) => {
+                    if attempt >= $self.retry_policy.max_retries {
+                        permit.record_failure();
+                        break result;
+                    }
+
+                    tokio::time::sleep($self.retry_policy.backoff_delay(attempt)).await;
+                    attempt += 1;
+                }
+                _ => {
+                    permit.record_success();
+                    break result;
+                }
+            }
+        }
+    }};
+}
+
+//for writes that cannot safely run twice, e.g. one that was saved but whose response was lost,
+//they go through the circuit breaker like any other call but are never retried
+macro_rules! call_once_with_resilience {
+    ($self:ident, $call:expr) => {{
+        let permit = $self.circuit_breaker.try_acquire()?;
+
+        let result = $call.await;
+        match &result {
+            Err(e) if $self.retry_policy.is_retryable(e) => permit.record_failure(),
+            _ => permit.record_success(),
+        }
+        result
+    }};
+}
+
+pub(crate) use call_once_with_resilience;
+pub(crate) use call_with_resilience;
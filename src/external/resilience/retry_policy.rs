@@ -0,0 +1,34 @@
+use std::time::Duration;
+
+use crate::internal::shared_reconciler_rust_libraries::models::entities::app_errors::{
+    AppError, AppErrorKind,
+};
+
+#[derive(Clone, Debug)]
+pub struct RetryPolicy {
+    pub max_retries: u32,
+    pub base_delay: Duration,
+    pub max_delay: Duration,
+}
+
+impl RetryPolicy {
+    pub fn new(max_retries: u32, base_delay: Duration, max_delay: Duration) -> Self {
+        RetryPolicy {
+            max_retries,
+            base_delay,
+            max_delay,
+        }
+    }
+
+    //exponential backoff: base_delay * 2^attempt, capped at max_delay
+    pub fn backoff_delay(&self, attempt: u32) -> Duration {
+        let multiplier = 2u32.saturating_pow(attempt);
+        let delay = self.base_delay.saturating_mul(multiplier);
+        std::cmp::min(delay, self.max_delay)
+    }
+
+    //only connection errors are transient, everything else is returned as is
+    pub fn is_retryable(&self, error: &AppError) -> bool {
+        matches!(error.kind, AppErrorKind::ConnectionError)
+    }
+}
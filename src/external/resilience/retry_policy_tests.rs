@@ -0,0 +1,54 @@
+use std::time::Duration;
+
+use crate::internal::shared_reconciler_rust_libraries::models::entities::app_errors::{
+    AppError, AppErrorKind,
+};
+
+use super::retry_policy::RetryPolicy;
+
+#[test]
+fn given_increasing_attempts_backoff_delay_doubles_until_max_delay() {
+    //setup
+    let policy = RetryPolicy::new(5, Duration::from_millis(100), Duration::from_millis(500));
+
+    //act
+    let delays: Vec<Duration> = (0..5).map(|attempt| policy.backoff_delay(attempt)).collect();
+
+    //assert
+    assert_eq!(
+        delays,
+        vec![
+            Duration::from_millis(100),
+            Duration::from_millis(200),
+            Duration::from_millis(400),
+            Duration::from_millis(500),
+            Duration::from_millis(500),
+        ]
+    );
+}
+
+#[test]
+fn given_connection_error_is_retryable_returns_true() {
+    //setup
+    let policy = RetryPolicy::new(3, Duration::from_millis(1), Duration::from_millis(1));
+    let error = AppError::new(AppErrorKind::ConnectionError, String::from("unavailable"));
+
+    //act
+    let result = policy.is_retryable(&error);
+
+    //assert
+    assert!(result);
+}
+
+#[test]
+fn given_not_found_error_is_retryable_returns_false() {
+    //setup
+    let policy = RetryPolicy::new(3, Duration::from_millis(1), Duration::from_millis(1));
+    let error = AppError::new(AppErrorKind::NotFound, String::from("not found"));
+
+    //act
+    let result = policy.is_retryable(&error);
+
+    //assert
+    assert!(!result);
+}
@@ -0,0 +1,18 @@
+use async_trait::async_trait;
+use mockall::automock;
+
+use crate::internal::shared_reconciler_rust_libraries::models::entities::app_errors::AppError;
+
+//where the content of uploaded files is kept, files are addressed by name
+#[automock]
+#[async_trait]
+pub trait BlobStoreInterface: Send + Sync {
+    //overwrites any file already saved under the same name
+    async fn save_file(&self, file_name: &str, content: &[u8]) -> Result<(), AppError>;
+
+    //a NotFound error when nothing was saved under the name
+    async fn read_file(&self, file_name: &str) -> Result<Vec<u8>, AppError>;
+
+    //none when nothing was saved under the name, for files that may not have been put there yet
+    async fn read_file_if_exists(&self, file_name: &str) -> Result<Option<Vec<u8>>, AppError>;
+}
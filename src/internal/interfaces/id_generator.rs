@@ -0,0 +1,7 @@
+use mockall::automock;
+
+//hands out the part of an id after its prefix, see core_logic::id_generators for the strategies
+#[automock]
+pub trait IdGeneratorInterface: Send + Sync {
+    fn generate_id(&self) -> String;
+}
@@ -1,4 +1,10 @@
+pub mod blob_store;
+pub mod id_generator;
 pub mod recon_files_repository;
+pub mod recon_task_audit_repository;
+pub mod recon_task_event_publisher;
 pub mod recon_tasks_aggregator;
 pub mod recon_tasks_repository;
+pub mod recon_templates_repository;
 pub mod transformer;
+pub mod upload_sessions_repository;
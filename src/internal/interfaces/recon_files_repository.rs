@@ -1,22 +1,24 @@
 use async_trait::async_trait;
 use mockall::automock;
 
-use crate::internal::shared_reconciler_rust_libraries::models::entities::{
-    app_errors::AppError, recon_tasks_models::ReconFileMetaData,
+use crate::internal::{
+    models::entities::recon_file::ReconFile,
+    shared_reconciler_rust_libraries::models::entities::app_errors::AppError,
 };
 
 #[automock]
 #[async_trait]
 pub trait ReconFileDetailsRepositoryInterface: Send + Sync {
-    async fn get_recon_file_details(&mut self, file_id: &String)
-                                    -> Result<ReconFileMetaData, AppError>;
+    async fn get_recon_file_details(&self, file_id: &str)
+                                    -> Result<ReconFile, AppError>;
     async fn create_recon_file_details(
-        &mut self,
-        file_details: &ReconFileMetaData,
+        &self,
+        file_details: &ReconFile,
     ) -> Result<String, AppError>;
     async fn update_recon_file_details(
-        &mut self,
-        file_details: &ReconFileMetaData,
-    ) -> Result<ReconFileMetaData, AppError>;
-    async fn delete_recon_file_details(&mut self, file_id: &String) -> Result<bool, AppError>;
+        &self,
+        file_details: &ReconFile,
+    ) -> Result<ReconFile, AppError>;
+    async fn delete_recon_file_details(&self, file_id: &str) -> Result<bool, AppError>;
+    async fn list_recon_file_ids(&self) -> Result<Vec<String>, AppError>;
 }
@@ -0,0 +1,17 @@
+use async_trait::async_trait;
+use mockall::automock;
+
+use crate::internal::{
+    models::entities::recon_task_audit::ReconTaskAuditEntry,
+    shared_reconciler_rust_libraries::models::entities::app_errors::AppError,
+};
+
+#[automock]
+#[async_trait]
+pub trait ReconTaskAuditRepositoryInterface: Send + Sync {
+    async fn append_audit_entry(&self, entry: &ReconTaskAuditEntry) -> Result<(), AppError>;
+    async fn get_audit_entries(
+        &self,
+        task_id: &str,
+    ) -> Result<Vec<ReconTaskAuditEntry>, AppError>;
+}
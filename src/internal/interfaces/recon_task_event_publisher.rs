@@ -0,0 +1,17 @@
+use async_trait::async_trait;
+use mockall::automock;
+
+use crate::internal::{
+    models::entities::recon_task_event::ReconTaskEvent,
+    shared_reconciler_rust_libraries::models::entities::app_errors::AppError,
+};
+
+#[automock]
+#[async_trait]
+pub trait ReconTaskEventPublisherInterface: Send + Sync {
+    async fn publish_task_event(
+        &self,
+        topic: &str,
+        event: &ReconTaskEvent,
+    ) -> Result<(), AppError>;
+}
@@ -1,33 +1,231 @@
 use async_trait::async_trait;
+use futures_util::stream::BoxStream;
 use mockall::automock;
 
 use crate::internal::{
-    models::view_models::requests::{
-        AttachComparisonFileRequest, AttachPrimaryFileRequest, CreateReconTaskRequest,
-    },
-    shared_reconciler_rust_libraries::models::{
-        entities::app_errors::AppError,
-        view_models::recon_task_response_details::{FileResponseSummary, ReconTaskResponseDetails},
+    models::{
+        entities::{
+            authenticated_user::AuthenticatedUser,
+            recon_results::{ReconChunkResults, ReconResultsExport},
+            recon_task::ReconTask, recon_task_audit::ReconTaskAuditEntry, recon_template::ReconTemplate,
+            service_error::ServiceError, upload_session::UploadedFileRole,
+        },
+        view_models::{
+            requests::{
+                AttachComparisonFileRequest, AttachPrimaryFileRequest, CancelReconTaskRequest,
+                CloneReconTaskRequest, CompleteUploadRequest, CreateReconTaskRequest,
+                CreateUploadSessionRequest, ForceTaskStatusRequest, ReconTemplateRequest,
+            },
+            responses::{
+                FilePreviewResponse, OrphanedFilesCleanupResponse, ReconTaskResponseDetails,
+                SuggestedComparisonPairsResponse, UploadSessionResponse,
+            },
+        },
     },
+    shared_reconciler_rust_libraries::models::view_models::recon_task_response_details::FileResponseSummary,
 };
 
+//an uploaded file as it is read off the request, a piece at a time
+pub type UploadedContent = BoxStream<'static, Result<Vec<u8>, ServiceError>>;
+
 #[automock]
 #[async_trait]
-pub trait ReconTaskAggregationServiceInterface {
+pub trait ReconTaskAggregationServiceInterface: Send + Sync {
     async fn create_recon_task(
-        &mut self,
+        &self,
         request: &CreateReconTaskRequest,
-    ) -> Result<ReconTaskResponseDetails, AppError>;
+        caller: &AuthenticatedUser,
+    ) -> Result<ReconTaskResponseDetails, ServiceError>;
 
-    async fn get_recon_task(&mut self, task_id: &String) -> Result<ReconTaskResponseDetails, AppError>;
+    async fn get_recon_task(
+        &self,
+        task_id: &str,
+        caller: &AuthenticatedUser,
+    ) -> Result<ReconTaskResponseDetails, ServiceError>;
+
+    async fn get_recon_task_history(
+        &self,
+        task_id: &str,
+        caller: &AuthenticatedUser,
+    ) -> Result<Vec<ReconTaskAuditEntry>, ServiceError>;
 
     async fn attach_primary_file_to_task(
-        &mut self,
+        &self,
         request: &AttachPrimaryFileRequest,
-    ) -> Result<FileResponseSummary, AppError>;
+        caller: &AuthenticatedUser,
+    ) -> Result<FileResponseSummary, ServiceError>;
 
     async fn attach_comparison_file_to_task(
-        &mut self,
+        &self,
         request: &AttachComparisonFileRequest,
-    ) -> Result<FileResponseSummary, AppError>;
+        caller: &AuthenticatedUser,
+    ) -> Result<FileResponseSummary, ServiceError>;
+
+    //saves the content to the blob store as it arrives and attaches it with the metadata read from it
+    async fn upload_primary_file_to_task(
+        &self,
+        task_id: &str,
+        file_name: &str,
+        content: UploadedContent,
+        caller: &AuthenticatedUser,
+    ) -> Result<FileResponseSummary, ServiceError>;
+
+    async fn upload_comparison_file_to_task(
+        &self,
+        task_id: &str,
+        file_name: &str,
+        content: UploadedContent,
+        caller: &AuthenticatedUser,
+    ) -> Result<FileResponseSummary, ServiceError>;
+
+    //starts an upload sent in chunks, for files too large for a single request
+    async fn create_upload_session(
+        &self,
+        task_id: &str,
+        file_role: UploadedFileRole,
+        request: &CreateUploadSessionRequest,
+        caller: &AuthenticatedUser,
+    ) -> Result<UploadSessionResponse, ServiceError>;
+
+    //chunks can arrive in any order and sending one again replaces it
+    async fn upload_file_chunk(
+        &self,
+        task_id: &str,
+        upload_id: &str,
+        chunk_number: u64,
+        content: &[u8],
+        caller: &AuthenticatedUser,
+    ) -> Result<UploadSessionResponse, ServiceError>;
+
+    async fn get_upload_session(
+        &self,
+        task_id: &str,
+        upload_id: &str,
+        caller: &AuthenticatedUser,
+    ) -> Result<UploadSessionResponse, ServiceError>;
+
+    //attaches the file once every chunk is in and the chunks hash to the file_hash
+    async fn complete_upload_session(
+        &self,
+        task_id: &str,
+        upload_id: &str,
+        request: &CompleteUploadRequest,
+        caller: &AuthenticatedUser,
+    ) -> Result<FileResponseSummary, ServiceError>;
+
+    //reads the stored content of each attached file back and checks its declared file_hash and row_count
+    async fn verify_task_files(
+        &self,
+        task_id: &str,
+        caller: &AuthenticatedUser,
+    ) -> Result<ReconTaskResponseDetails, ServiceError>;
+
+    //the first rows of the stored file keyed by header, rows defaults to 20
+    async fn preview_recon_file(
+        &self,
+        task_id: &str,
+        file_id: &str,
+        rows: Option<usize>,
+        caller: &AuthenticatedUser,
+    ) -> Result<FilePreviewResponse, ServiceError>;
+
+    //publishes the start command on both file chunk topics once the task is ready
+    async fn start_recon_task(
+        &self,
+        task_id: &str,
+        caller: &AuthenticatedUser,
+    ) -> Result<ReconTaskResponseDetails, ServiceError>;
+
+    //sent by the workers through the results topic subscriber, so there is no caller to check
+    async fn record_recon_results(&self, results: &ReconChunkResults) -> Result<(), ServiceError>;
+
+    //only completed tasks have results to export
+    async fn get_recon_results_export(
+        &self,
+        task_id: &str,
+        caller: &AuthenticatedUser,
+    ) -> Result<ReconResultsExport, ServiceError>;
+
+    //one of the results_file_names of an export, read whole since a worker writes one per chunk
+    async fn read_recon_results_file(
+        &self,
+        results_file_name: &str,
+    ) -> Result<Vec<u8>, ServiceError>;
+
+    async fn cancel_recon_task(
+        &self,
+        task_id: &str,
+        request: &CancelReconTaskRequest,
+        caller: &AuthenticatedUser,
+    ) -> Result<ReconTaskResponseDetails, ServiceError>;
+
+    async fn clone_recon_task(
+        &self,
+        task_id: &str,
+        request: &CloneReconTaskRequest,
+        caller: &AuthenticatedUser,
+    ) -> Result<ReconTaskResponseDetails, ServiceError>;
+
+    //only available once both files are attached
+    async fn get_suggested_comparison_pairs(
+        &self,
+        task_id: &str,
+        caller: &AuthenticatedUser,
+    ) -> Result<SuggestedComparisonPairsResponse, ServiceError>;
+
+    //adds the suggestions to the task, skipping any column an existing pair already uses
+    async fn accept_suggested_comparison_pairs(
+        &self,
+        task_id: &str,
+        caller: &AuthenticatedUser,
+    ) -> Result<ReconTaskResponseDetails, ServiceError>;
+
+    async fn list_all_recon_tasks(
+        &self,
+        caller: &AuthenticatedUser,
+    ) -> Result<Vec<ReconTask>, ServiceError>;
+
+    async fn force_recon_task_status(
+        &self,
+        task_id: &str,
+        request: &ForceTaskStatusRequest,
+        caller: &AuthenticatedUser,
+    ) -> Result<ReconTaskResponseDetails, ServiceError>;
+
+    async fn delete_orphaned_recon_files(
+        &self,
+        caller: &AuthenticatedUser,
+    ) -> Result<OrphanedFilesCleanupResponse, ServiceError>;
+
+    async fn create_recon_template(
+        &self,
+        request: &ReconTemplateRequest,
+        caller: &AuthenticatedUser,
+    ) -> Result<ReconTemplate, ServiceError>;
+
+    //the latest version when no version is given
+    async fn get_recon_template(
+        &self,
+        template_id: &str,
+        version: Option<u32>,
+        caller: &AuthenticatedUser,
+    ) -> Result<ReconTemplate, ServiceError>;
+
+    async fn list_recon_templates(
+        &self,
+        caller: &AuthenticatedUser,
+    ) -> Result<Vec<ReconTemplate>, ServiceError>;
+
+    async fn update_recon_template(
+        &self,
+        template_id: &str,
+        request: &ReconTemplateRequest,
+        caller: &AuthenticatedUser,
+    ) -> Result<ReconTemplate, ServiceError>;
+
+    async fn delete_recon_template(
+        &self,
+        template_id: &str,
+        caller: &AuthenticatedUser,
+    ) -> Result<bool, ServiceError>;
 }
@@ -1,21 +1,49 @@
 use async_trait::async_trait;
 use mockall::automock;
 
-use crate::internal::shared_reconciler_rust_libraries::models::entities::{
-    app_errors::AppError, recon_tasks_models::ReconTaskDetails,
+use crate::internal::{
+    models::entities::{recon_results::ReconChunkResults, recon_task::ReconTask},
+    shared_reconciler_rust_libraries::models::entities::app_errors::AppError,
 };
 
 #[automock]
 #[async_trait]
 pub trait ReconTaskDetailsRepositoryInterface: Send + Sync {
-    async fn get_task_details(&mut self, task_id: &String) -> Result<ReconTaskDetails, AppError>;
+    async fn get_task_details(&self, task_id: &str) -> Result<ReconTask, AppError>;
     async fn create_task_details(
-        &mut self,
-        task_details: &ReconTaskDetails,
+        &self,
+        task_details: &ReconTask,
     ) -> Result<String, AppError>;
+    //only saved while the stored task still has the etag it was read with,
+    //returns None when another write got there first
     async fn update_task_details(
-        &mut self,
-        task_details: &ReconTaskDetails,
-    ) -> Result<ReconTaskDetails, AppError>;
-    async fn delete_task_details(&mut self, task_id: &String) -> Result<bool, AppError>;
+        &self,
+        task_details: &ReconTask,
+    ) -> Result<Option<ReconTask>, AppError>;
+    async fn list_task_details(&self) -> Result<Vec<ReconTask>, AppError>;
+
+    //applied to the stored task under its etag so results reported at the same time are all kept
+    //returns the task as it is stored, so results delivered again still find out it was completed
+    async fn record_chunk_results(
+        &self,
+        results: &ReconChunkResults,
+    ) -> Result<ReconTask, AppError>;
+
+    //applied to the stored task under its etag, so results recorded while the task was starting are kept
+    async fn mark_task_reconciling(&self, task_id: &str) -> Result<ReconTask, AppError>;
+
+    //a per user set of the tasks that are neither done nor cancelled
+    //a slot is only taken while the user has fewer than max_active_tasks, returns false otherwise
+    //both are keyed by the task, so running either twice for the same task changes nothing
+    async fn reserve_active_task_slot(
+        &self,
+        user_id: &str,
+        task_id: &str,
+        max_active_tasks: usize,
+    ) -> Result<bool, AppError>;
+    async fn release_active_task_slot(
+        &self,
+        user_id: &str,
+        task_id: &str,
+    ) -> Result<(), AppError>;
 }
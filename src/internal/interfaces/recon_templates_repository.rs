@@ -0,0 +1,23 @@
+use async_trait::async_trait;
+use mockall::automock;
+
+use crate::internal::{
+    models::entities::recon_template::ReconTemplate,
+    shared_reconciler_rust_libraries::models::entities::app_errors::AppError,
+};
+
+#[automock]
+#[async_trait]
+pub trait ReconTemplateRepositoryInterface: Send + Sync {
+    //returns the latest version of the template
+    async fn get_template(&self, template_id: &str) -> Result<ReconTemplate, AppError>;
+    async fn get_template_version(
+        &self,
+        template_id: &str,
+        version: u32,
+    ) -> Result<ReconTemplate, AppError>;
+    //saves the template as its latest version and keeps a copy under its version number
+    async fn save_template(&self, template: &ReconTemplate) -> Result<String, AppError>;
+    async fn delete_template(&self, template_id: &str) -> Result<bool, AppError>;
+    async fn list_templates(&self) -> Result<Vec<ReconTemplate>, AppError>;
+}
@@ -1,30 +1,60 @@
 use mockall::automock;
 
 use crate::internal::{
-    models::view_models::requests::{
-        AttachComparisonFileRequest, AttachPrimaryFileRequest, CreateReconTaskRequest,
-    },
-    shared_reconciler_rust_libraries::models::{
-        entities::recon_tasks_models::{ReconFileMetaData, ReconTaskDetails},
-        view_models::recon_task_response_details::ReconTaskResponseDetails,
+    models::{
+        entities::{
+            named_comparison_pair::NamedComparisonPair, recon_file::ReconFile,
+            recon_task::ReconTask, recon_template::ReconTemplate,
+            upload_session::{UploadSession, UploadedFileRole},
+        },
+        view_models::{
+            requests::{
+                AttachComparisonFileRequest, AttachPrimaryFileRequest, CreateUploadSessionRequest,
+                ReconTemplateRequest,
+            },
+            responses::ReconTaskResponseDetails,
+        },
     },
+    shared_reconciler_rust_libraries::models::entities::recon_tasks_models::ReconciliationConfigs,
 };
 
 #[automock]
 pub trait TransformerInterface: Send + Sync {
     fn build_recon_task_details_response(
         &self,
-        task_details: ReconTaskDetails,
-        primary_file_metadata: Option<ReconFileMetaData>,
-        comparison_file_metadata: Option<ReconFileMetaData>,
+        task_details: ReconTask,
+        primary_file_metadata: Option<ReconFile>,
+        comparison_file_metadata: Option<ReconFile>,
     ) -> ReconTaskResponseDetails;
 
-    fn get_primary_file_details(&self, request: &AttachPrimaryFileRequest) -> ReconFileMetaData;
+    fn get_primary_file_details(
+        &self,
+        request: &AttachPrimaryFileRequest,
+        created_by: &str,
+    ) -> ReconFile;
 
     fn get_comparison_file_details(
         &self,
         request: &AttachComparisonFileRequest,
-    ) -> ReconFileMetaData;
+        created_by: &str,
+    ) -> ReconFile;
+
+    fn get_recon_task_details(
+        &self,
+        created_by: &str,
+        recon_configurations: &ReconciliationConfigs,
+        comparison_pairs: &[NamedComparisonPair],
+    ) -> ReconTask;
 
-    fn get_recon_task_details(&self, request: &CreateReconTaskRequest) -> ReconTaskDetails;
+    fn get_recon_template(&self, request: &ReconTemplateRequest, created_by: &str) -> ReconTemplate;
+
+    fn copy_recon_file_details(&self, source: &ReconFile, created_by: &str) -> ReconFile;
+
+    fn get_upload_session(
+        &self,
+        task_id: &str,
+        file_role: UploadedFileRole,
+        request: &CreateUploadSessionRequest,
+        created_by: &str,
+    ) -> UploadSession;
 }
@@ -0,0 +1,29 @@
+use async_trait::async_trait;
+use mockall::automock;
+
+use crate::internal::{
+    models::entities::upload_session::UploadSession,
+    shared_reconciler_rust_libraries::models::entities::app_errors::AppError,
+};
+
+#[automock]
+#[async_trait]
+pub trait UploadSessionRepositoryInterface: Send + Sync {
+    async fn get_upload_session(&self, upload_id: &str) -> Result<UploadSession, AppError>;
+    //creates the session or replaces the stored one
+    async fn save_upload_session(&self, session: &UploadSession) -> Result<(), AppError>;
+    //chunks can arrive at the same time, so each is added to the stored session rather than
+    //the session being replaced, a session that is no longer open is left as it is
+    //returns the session as stored
+    async fn record_upload_chunk(
+        &self,
+        upload_id: &str,
+        chunk_number: u64,
+    ) -> Result<UploadSession, AppError>;
+    //only the first request to complete the session gets to, the others get back the file it was completed as
+    async fn complete_upload_session(
+        &self,
+        upload_id: &str,
+        file_id: &str,
+    ) -> Result<UploadSession, AppError>;
+}
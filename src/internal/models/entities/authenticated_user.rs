@@ -0,0 +1,49 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum UserRole {
+    //can read any task but not change anything
+    Viewer,
+    //can create tasks and read or change the tasks they created
+    Owner,
+    //can read and change any task and use the admin endpoints
+    Admin,
+}
+
+impl UserRole {
+    pub fn parse(role: &str) -> Option<UserRole> {
+        match role.to_lowercase().as_str() {
+            "viewer" => Some(UserRole::Viewer),
+            "owner" => Some(UserRole::Owner),
+            "admin" => Some(UserRole::Admin),
+            _ => None,
+        }
+    }
+}
+
+//the caller of a request as established by the bearer token, never by the request body
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct AuthenticatedUser {
+    pub user_id: String,
+
+    pub roles: Vec<UserRole>,
+}
+
+impl AuthenticatedUser {
+    //tokens without a roles claim are treated as plain task owners
+    pub fn new(user_id: &str) -> Self {
+        AuthenticatedUser::with_roles(user_id, vec![UserRole::Owner])
+    }
+
+    pub fn with_roles(user_id: &str, roles: Vec<UserRole>) -> Self {
+        AuthenticatedUser {
+            user_id: String::from(user_id),
+            roles,
+        }
+    }
+
+    pub fn has_role(&self, role: UserRole) -> bool {
+        self.roles.contains(&role)
+    }
+}
@@ -0,0 +1,116 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::internal::{
+    models::entities::comparison_rules::is_valid_date_format,
+    shared_reconciler_rust_libraries::models::entities::recon_tasks_models::ComparisonPair,
+};
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ColumnType {
+    String,
+    Integer,
+    Decimal,
+    Date,
+    Boolean,
+}
+
+impl ColumnType {
+    //integers and decimals are both numbers, every other type only matches itself
+    pub fn is_compatible_with(&self, other: &ColumnType) -> bool {
+        match (self, other) {
+            (ColumnType::Integer | ColumnType::Decimal, ColumnType::Integer | ColumnType::Decimal) => true,
+            _ => self == other,
+        }
+    }
+}
+
+impl fmt::Display for ColumnType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            ColumnType::String => "string",
+            ColumnType::Integer => "integer",
+            ColumnType::Decimal => "decimal",
+            ColumnType::Date => "date",
+            ColumnType::Boolean => "boolean",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+//what one column of a file holds, given in the same order as the file headers
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct ColumnSchema {
+    pub column_type: ColumnType,
+
+    #[serde(default)]
+    pub nullable: bool,
+
+    //how the values are written, e.g. a chrono strftime format for dates
+    #[serde(default)]
+    pub format: Option<String>,
+}
+
+//a comparison pair whose two columns hold values that cannot be compared
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct ColumnTypeMismatch {
+    pub primary_file_column_index: usize,
+
+    pub primary_file_column_type: ColumnType,
+
+    pub comparison_file_column_index: usize,
+
+    pub comparison_file_column_type: ColumnType,
+}
+
+//a file without a schema has nothing to check, otherwise there is one entry per header
+pub fn column_schema_errors(column_schema: &[ColumnSchema], column_headers: &[String]) -> Vec<String> {
+    let mut errors = vec![];
+
+    if !column_schema.is_empty() && column_schema.len() != column_headers.len() {
+        errors.push(format!(
+            "the column schema describes {} columns but the file has {} headers",
+            column_schema.len(),
+            column_headers.len()
+        ));
+    }
+
+    for column in column_schema {
+        match (&column.column_type, &column.format) {
+            (ColumnType::Date, Some(format)) if !is_valid_date_format(format) => {
+                errors.push(format!("[{}] is not a valid date format", format));
+            }
+            _ => (),
+        }
+    }
+
+    errors
+}
+
+//pairs pointing at a column either schema does not describe are left out, there is nothing to compare them with
+pub fn column_type_mismatches(
+    comparison_pairs: &[ComparisonPair],
+    primary_file_schema: &[ColumnSchema],
+    comparison_file_schema: &[ColumnSchema],
+) -> Vec<ColumnTypeMismatch> {
+    comparison_pairs
+        .iter()
+        .filter_map(|pair| {
+            let primary_column = primary_file_schema.get(pair.primary_file_column_index)?;
+            let comparison_column = comparison_file_schema.get(pair.comparison_file_column_index)?;
+
+            if primary_column.column_type.is_compatible_with(&comparison_column.column_type) {
+                return None;
+            }
+
+            Some(ColumnTypeMismatch {
+                primary_file_column_index: pair.primary_file_column_index,
+                primary_file_column_type: primary_column.column_type,
+                comparison_file_column_index: pair.comparison_file_column_index,
+                comparison_file_column_type: comparison_column.column_type,
+            })
+        })
+        .collect()
+}
@@ -0,0 +1,100 @@
+use crate::internal::{
+    models::entities::column_schema::{
+        column_schema_errors, column_type_mismatches, ColumnSchema, ColumnType, ColumnTypeMismatch,
+    },
+    shared_reconciler_rust_libraries::models::entities::recon_tasks_models::ComparisonPair,
+};
+
+#[test]
+fn given_numeric_types_is_compatible_with_treats_integers_and_decimals_alike() {
+    //assert
+    assert!(ColumnType::Integer.is_compatible_with(&ColumnType::Decimal));
+    assert!(ColumnType::Date.is_compatible_with(&ColumnType::Date));
+    assert!(!ColumnType::String.is_compatible_with(&ColumnType::Date));
+    assert!(!ColumnType::Boolean.is_compatible_with(&ColumnType::Integer));
+}
+
+#[test]
+fn given_schema_for_fewer_columns_than_headers_column_schema_errors_reports_it() {
+    //setup
+    let column_schema = vec![column(ColumnType::String)];
+    let column_headers = vec![String::from("id"), String::from("amount")];
+
+    //act
+    let errors = column_schema_errors(&column_schema, &column_headers);
+
+    //assert
+    assert_eq!(errors.len(), 1);
+}
+
+#[test]
+fn given_date_column_with_invalid_format_column_schema_errors_reports_it() {
+    //setup
+    let column_schema = vec![ColumnSchema {
+        column_type: ColumnType::Date,
+        nullable: true,
+        format: Some(String::from("%d/%Q")),
+    }];
+
+    //act
+    let errors = column_schema_errors(&column_schema, &[String::from("value_date")]);
+
+    //assert
+    assert_eq!(errors, vec![String::from("[%d/%Q] is not a valid date format")]);
+}
+
+#[test]
+fn given_no_schema_column_schema_errors_is_empty() {
+    //act
+    let errors = column_schema_errors(&[], &[String::from("id")]);
+
+    //assert
+    assert!(errors.is_empty());
+}
+
+#[test]
+fn given_pairs_column_type_mismatches_lists_only_incompatible_described_columns() {
+    //setup
+    let comparison_pairs = vec![pair(0, 0), pair(1, 2), pair(2, 1), pair(5, 0)];
+    let primary_file_schema = vec![
+        column(ColumnType::String),
+        column(ColumnType::Integer),
+        column(ColumnType::Decimal),
+    ];
+    let comparison_file_schema = vec![
+        column(ColumnType::String),
+        column(ColumnType::Date),
+        column(ColumnType::Decimal),
+    ];
+
+    //act
+    let mismatches =
+        column_type_mismatches(&comparison_pairs, &primary_file_schema, &comparison_file_schema);
+
+    //assert
+    assert_eq!(
+        mismatches,
+        vec![ColumnTypeMismatch {
+            primary_file_column_index: 2,
+            primary_file_column_type: ColumnType::Decimal,
+            comparison_file_column_index: 1,
+            comparison_file_column_type: ColumnType::Date,
+        }]
+    );
+}
+
+fn column(column_type: ColumnType) -> ColumnSchema {
+    ColumnSchema {
+        column_type,
+        nullable: false,
+        format: None,
+    }
+}
+
+fn pair(primary_file_column_index: usize, comparison_file_column_index: usize) -> ComparisonPair {
+    ComparisonPair {
+        primary_file_column_index,
+        comparison_file_column_index,
+        is_row_identifier: false,
+    }
+}
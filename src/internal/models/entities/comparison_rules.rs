@@ -0,0 +1,111 @@
+use chrono::format::{Item, StrftimeItems};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+//how the values of one comparison pair are prepared and compared, on top of the task wide ReconciliationConfigs
+//every rule is optional so pairs saved before rules existed read back as comparing values as they are
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+pub struct ComparisonRules {
+    #[serde(default)]
+    pub numeric_tolerance: Option<NumericTolerance>,
+
+    #[serde(default)]
+    pub date_format: Option<DateFormatRule>,
+
+    #[serde(default)]
+    pub trim_values: bool,
+
+    //applied in order, after trimming
+    #[serde(default)]
+    pub cleanup_patterns: Vec<RegexCleanup>,
+
+    #[serde(default)]
+    pub amount_sign: AmountSignHandling,
+}
+
+//two numbers match when they differ by no more than the tolerance
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum NumericTolerance {
+    Absolute { value: f64 },
+
+    //a percentage of the primary file value
+    Percent { value: f64 },
+}
+
+//both dates are parsed with their file's format before being compared, formats use chrono's strftime syntax
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct DateFormatRule {
+    pub primary_file_format: String,
+
+    pub comparison_file_format: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct RegexCleanup {
+    pub pattern: String,
+
+    //removes whatever the pattern matches when left out
+    #[serde(default)]
+    pub replacement: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum AmountSignHandling {
+    #[default]
+    AsIs,
+
+    //compares absolute values, for files that record debits and credits without a sign
+    IgnoreSign,
+
+    //flips the comparison file amount, for files that record the same movement from the other side
+    InvertComparisonSign,
+}
+
+impl ComparisonRules {
+    //every problem found, so a request can be fixed in one go
+    pub fn validation_errors(&self) -> Vec<String> {
+        let mut errors = vec![];
+
+        if let Some(numeric_tolerance) = &self.numeric_tolerance {
+            let (value, max_value) = match numeric_tolerance {
+                NumericTolerance::Absolute { value } => (*value, f64::MAX),
+                NumericTolerance::Percent { value } => (*value, 100.0),
+            };
+            if !value.is_finite() || value < 0.0 || value > max_value {
+                errors.push(format!("the numeric tolerance [{}] is out of range", value));
+            }
+        }
+
+        if let Some(date_format) = &self.date_format {
+            for format in [&date_format.primary_file_format, &date_format.comparison_file_format] {
+                if !is_valid_date_format(format) {
+                    errors.push(format!("[{}] is not a valid date format", format));
+                }
+            }
+        }
+
+        for cleanup in &self.cleanup_patterns {
+            if Regex::new(&cleanup.pattern).is_err() {
+                errors.push(format!("[{}] is not a valid cleanup pattern", cleanup.pattern));
+            }
+        }
+
+        errors
+    }
+}
+
+pub fn is_valid_date_format(format: &str) -> bool {
+    !format.is_empty() && !StrftimeItems::new(format).any(|item| item == Item::Error)
+}
+
+//the rules of a pair that has been resolved to column indexes, kept next to details.comparison_pairs
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ComparisonPairRules {
+    pub primary_file_column_index: usize,
+
+    pub comparison_file_column_index: usize,
+
+    pub rules: ComparisonRules,
+}
@@ -0,0 +1,105 @@
+use serde_json::json;
+
+use crate::internal::models::entities::{
+    comparison_rules::{
+        AmountSignHandling, ComparisonRules, DateFormatRule, NumericTolerance, RegexCleanup,
+    },
+    named_comparison_pair::NamedComparisonPair,
+};
+
+#[test]
+fn given_all_rules_serde_round_trips_them_unchanged() {
+    //setup
+    let rules = get_dummy_comparison_rules();
+
+    //act
+    let json = serde_json::to_string(&rules).unwrap();
+    let read_back: ComparisonRules = serde_json::from_str(&json).unwrap();
+
+    //assert
+    assert_eq!(read_back, rules);
+}
+
+#[test]
+fn given_rules_json_numeric_tolerance_and_amount_sign_use_snake_case_names() {
+    //setup
+    let json = json!({
+        "numeric_tolerance": {"type": "percent", "value": 0.5},
+        "amount_sign": "invert_comparison_sign"
+    });
+
+    //act
+    let rules: ComparisonRules = serde_json::from_value(json).unwrap();
+
+    //assert
+    assert_eq!(rules.numeric_tolerance, Some(NumericTolerance::Percent { value: 0.5 }));
+    assert_eq!(rules.amount_sign, AmountSignHandling::InvertComparisonSign);
+    assert!(!rules.trim_values);
+    assert!(rules.cleanup_patterns.is_empty());
+}
+
+#[test]
+fn given_pair_saved_without_rules_it_reads_back_and_serializes_without_rules() {
+    //setup
+    let json = json!({"primary_file_column_index": 0, "comparison_file_column_index": 1, "is_row_identifier": true});
+
+    //act
+    let pair: NamedComparisonPair = serde_json::from_value(json).unwrap();
+    let written_back = serde_json::to_value(&pair).unwrap();
+
+    //assert
+    assert!(pair.rules.is_none());
+    assert!(written_back.get("rules").is_none());
+}
+
+#[test]
+fn given_valid_rules_validation_errors_is_empty() {
+    //setup
+    let rules = get_dummy_comparison_rules();
+
+    //act
+    let errors = rules.validation_errors();
+
+    //assert
+    assert!(errors.is_empty());
+}
+
+#[test]
+fn given_invalid_rules_validation_errors_lists_every_problem() {
+    //setup
+    let rules = ComparisonRules {
+        numeric_tolerance: Some(NumericTolerance::Percent { value: 150.0 }),
+        date_format: Some(DateFormatRule {
+            primary_file_format: String::from("%Y-%m-%d"),
+            comparison_file_format: String::from("%Q"),
+        }),
+        trim_values: true,
+        cleanup_patterns: vec![RegexCleanup {
+            pattern: String::from("[0-9"),
+            replacement: String::new(),
+        }],
+        amount_sign: AmountSignHandling::AsIs,
+    };
+
+    //act
+    let errors = rules.validation_errors();
+
+    //assert
+    assert_eq!(errors.len(), 3);
+}
+
+fn get_dummy_comparison_rules() -> ComparisonRules {
+    ComparisonRules {
+        numeric_tolerance: Some(NumericTolerance::Absolute { value: 0.01 }),
+        date_format: Some(DateFormatRule {
+            primary_file_format: String::from("%d/%m/%Y"),
+            comparison_file_format: String::from("%Y-%m-%d"),
+        }),
+        trim_values: true,
+        cleanup_patterns: vec![RegexCleanup {
+            pattern: String::from("^REF-"),
+            replacement: String::new(),
+        }],
+        amount_sign: AmountSignHandling::IgnoreSign,
+    }
+}
@@ -0,0 +1,156 @@
+use serde::{Deserialize, Serialize};
+
+//the encodings the chunking workers know how to decode
+pub const SUPPORTED_ENCODINGS: [&str; 6] = [
+    "utf-8",
+    "utf-16le",
+    "utf-16be",
+    "iso-8859-1",
+    "windows-1252",
+    "ascii",
+];
+
+//everything a chunking worker needs to parse a file besides its delimiters and headers
+//files attached before descriptors existed read back as utf-8 csv with a header row
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct FileFormatDescriptor {
+    #[serde(default)]
+    pub layout: FileLayout,
+
+    #[serde(default = "default_encoding")]
+    pub encoding: String,
+
+    #[serde(default = "default_has_header_row")]
+    pub has_header_row: bool,
+
+    //rows before the header row, or before the first data row when there is no header row
+    #[serde(default)]
+    pub rows_to_skip: u64,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum FileLayout {
+    Csv {
+        #[serde(default = "default_quote_character")]
+        quote_character: char,
+
+        //a doubled quote character escapes itself when left out
+        #[serde(default)]
+        escape_character: Option<char>,
+    },
+
+    Tsv,
+
+    //the character offset each column starts at, in column order
+    FixedWidth { column_offsets: Vec<usize> },
+
+    JsonLines,
+}
+
+impl Default for FileLayout {
+    fn default() -> Self {
+        FileLayout::Csv {
+            quote_character: default_quote_character(),
+            escape_character: None,
+        }
+    }
+}
+
+impl Default for FileFormatDescriptor {
+    fn default() -> Self {
+        FileFormatDescriptor {
+            layout: FileLayout::default(),
+            encoding: default_encoding(),
+            has_header_row: default_has_header_row(),
+            rows_to_skip: 0,
+        }
+    }
+}
+
+fn default_quote_character() -> char {
+    '"'
+}
+
+fn default_encoding() -> String {
+    String::from("utf-8")
+}
+
+fn default_has_header_row() -> bool {
+    true
+}
+
+impl FileFormatDescriptor {
+    //checked against the rest of the attach request, every problem found is returned
+    pub fn validation_errors(
+        &self,
+        column_delimiters: &[char],
+        column_headers: &[String],
+        row_count: u64,
+    ) -> Vec<String> {
+        let mut errors = vec![];
+
+        if !SUPPORTED_ENCODINGS.contains(&self.encoding.to_lowercase().as_str()) {
+            errors.push(format!(
+                "the encoding [{}] is not supported, please use one of {}",
+                self.encoding,
+                SUPPORTED_ENCODINGS.join(", ")
+            ));
+        }
+
+        if self.rows_to_skip >= row_count {
+            errors.push(format!(
+                "cannot skip {} rows of a file with {} rows",
+                self.rows_to_skip, row_count
+            ));
+        }
+
+        match &self.layout {
+            FileLayout::Csv {
+                quote_character,
+                escape_character,
+            } => {
+                if column_delimiters.is_empty() {
+                    errors.push(String::from("a csv file needs at least one column delimiter"));
+                }
+                if column_delimiters.contains(quote_character) {
+                    errors.push(format!(
+                        "the quote character [{}] is also a column delimiter",
+                        quote_character
+                    ));
+                }
+                if let Some(escape_character) = escape_character {
+                    if column_delimiters.contains(escape_character) {
+                        errors.push(format!(
+                            "the escape character [{}] is also a column delimiter",
+                            escape_character
+                        ));
+                    }
+                }
+            }
+            FileLayout::Tsv => {
+                if column_delimiters.iter().any(|delimiter| *delimiter != '\t') {
+                    errors.push(String::from("a tsv file can only be delimited by tabs"));
+                }
+            }
+            FileLayout::FixedWidth { column_offsets } => {
+                if column_offsets.first() != Some(&0) {
+                    errors.push(String::from("the first fixed width column has to start at offset 0"));
+                }
+                if column_offsets.windows(2).any(|offsets| offsets[0] >= offsets[1]) {
+                    errors.push(String::from("fixed width column offsets have to be increasing"));
+                }
+                if column_offsets.len() != column_headers.len() {
+                    errors.push(format!(
+                        "there are {} column offsets for {} columns",
+                        column_offsets.len(),
+                        column_headers.len()
+                    ));
+                }
+            }
+            FileLayout::JsonLines => (),
+        }
+
+        errors
+    }
+}
@@ -0,0 +1,116 @@
+use serde_json::json;
+
+use crate::internal::models::entities::file_format::{FileFormatDescriptor, FileLayout};
+
+#[test]
+fn given_empty_json_file_format_descriptor_defaults_to_utf8_csv_with_header_row() {
+    //act
+    let format: FileFormatDescriptor = serde_json::from_value(json!({})).unwrap();
+
+    //assert
+    assert_eq!(format, FileFormatDescriptor::default());
+    assert_eq!(
+        format.layout,
+        FileLayout::Csv {
+            quote_character: '"',
+            escape_character: None,
+        }
+    );
+    assert_eq!(format.encoding, "utf-8");
+    assert!(format.has_header_row);
+    assert_eq!(format.rows_to_skip, 0);
+}
+
+#[test]
+fn given_fixed_width_json_file_format_descriptor_reads_the_column_offsets() {
+    //setup
+    let json = json!({
+        "layout": {"type": "fixed_width", "column_offsets": [0, 10, 25]},
+        "encoding": "iso-8859-1",
+        "has_header_row": false,
+        "rows_to_skip": 2
+    });
+
+    //act
+    let format: FileFormatDescriptor = serde_json::from_value(json.clone()).unwrap();
+
+    //assert
+    assert_eq!(
+        format.layout,
+        FileLayout::FixedWidth {
+            column_offsets: vec![0, 10, 25]
+        }
+    );
+    assert_eq!(serde_json::to_value(&format).unwrap(), json);
+}
+
+#[test]
+fn given_valid_json_lines_format_validation_errors_is_empty() {
+    //setup
+    let format = FileFormatDescriptor {
+        layout: FileLayout::JsonLines,
+        ..FileFormatDescriptor::default()
+    };
+
+    //act
+    let errors = format.validation_errors(&[], &headers(&["id", "amount"]), 10);
+
+    //assert
+    assert!(errors.is_empty());
+}
+
+#[test]
+fn given_csv_format_quoting_with_a_delimiter_validation_errors_reports_it() {
+    //setup
+    let format = FileFormatDescriptor {
+        layout: FileLayout::Csv {
+            quote_character: ',',
+            escape_character: Some('\\'),
+        },
+        ..FileFormatDescriptor::default()
+    };
+
+    //act
+    let errors = format.validation_errors(&[','], &headers(&["id", "amount"]), 10);
+
+    //assert
+    assert_eq!(errors, vec![String::from("the quote character [,] is also a column delimiter")]);
+}
+
+#[test]
+fn given_tsv_format_with_comma_delimiter_validation_errors_reports_it() {
+    //setup
+    let format = FileFormatDescriptor {
+        layout: FileLayout::Tsv,
+        ..FileFormatDescriptor::default()
+    };
+
+    //act
+    let errors = format.validation_errors(&[','], &headers(&["id", "amount"]), 10);
+
+    //assert
+    assert_eq!(errors.len(), 1);
+}
+
+#[test]
+fn given_bad_fixed_width_format_validation_errors_lists_every_problem() {
+    //setup
+    let format = FileFormatDescriptor {
+        layout: FileLayout::FixedWidth {
+            column_offsets: vec![5, 3],
+        },
+        encoding: String::from("ebcdic"),
+        has_header_row: true,
+        rows_to_skip: 10,
+    };
+
+    //act
+    let errors = format.validation_errors(&[], &headers(&["id", "amount", "date"]), 10);
+
+    //assert
+    assert_eq!(errors.len(), 5);
+}
+
+fn headers(names: &[&str]) -> Vec<String> {
+    names.iter().map(|name| String::from(*name)).collect()
+}
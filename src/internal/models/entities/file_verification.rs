@@ -0,0 +1,60 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum FileVerificationStatus {
+    //the content has not been read yet, e.g. only the metadata was attached
+    #[default]
+    Pending,
+    Verified,
+    Mismatch,
+}
+
+//whether the declared file_hash and row_count hold for the stored content
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+pub struct FileVerification {
+    pub status: FileVerificationStatus,
+
+    //what the stored content was found to hold, set once it has been read
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub actual_file_hash: Option<String>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub actual_row_count: Option<u64>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub verified_at: Option<DateTime<Utc>>,
+}
+
+impl FileVerification {
+    //hashes are compared ignoring case, clients send hex in either
+    pub fn compare(
+        declared_file_hash: &str,
+        declared_row_count: u64,
+        actual_file_hash: &str,
+        actual_row_count: u64,
+    ) -> Self {
+        let hash_matches = declared_file_hash.trim().to_lowercase() == actual_file_hash.to_lowercase();
+        let status = match hash_matches && declared_row_count == actual_row_count {
+            true => FileVerificationStatus::Verified,
+            false => FileVerificationStatus::Mismatch,
+        };
+
+        FileVerification {
+            status,
+            actual_file_hash: Some(actual_file_hash.to_string()),
+            actual_row_count: Some(actual_row_count),
+            verified_at: Some(Utc::now()),
+        }
+    }
+
+    //for metadata read from the content itself, which holds by construction
+    pub fn verified(file_hash: &str, row_count: u64) -> Self {
+        FileVerification::compare(file_hash, row_count, file_hash, row_count)
+    }
+
+    pub fn is_verified(&self) -> bool {
+        self.status == FileVerificationStatus::Verified
+    }
+}
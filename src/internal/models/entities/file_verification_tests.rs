@@ -0,0 +1,59 @@
+use crate::internal::models::entities::file_verification::{
+    FileVerification, FileVerificationStatus,
+};
+
+#[test]
+fn given_same_hash_in_another_case_and_same_row_count_compare_returns_verified() {
+    //act
+    let verification = FileVerification::compare(
+        &String::from("ABC123"),
+        3,
+        &String::from("abc123"),
+        3,
+    );
+
+    //assert
+    assert_eq!(verification.status, FileVerificationStatus::Verified);
+    assert_eq!(verification.actual_row_count, Some(3));
+    assert!(verification.verified_at.is_some());
+}
+
+#[test]
+fn given_different_row_count_compare_returns_mismatch_with_the_actual_values() {
+    //act
+    let verification = FileVerification::compare(
+        &String::from("abc123"),
+        3,
+        &String::from("abc123"),
+        4,
+    );
+
+    //assert
+    assert_eq!(verification.status, FileVerificationStatus::Mismatch);
+    assert_eq!(verification.actual_file_hash, Some(String::from("abc123")));
+    assert_eq!(verification.actual_row_count, Some(4));
+}
+
+#[test]
+fn given_different_hash_compare_returns_mismatch() {
+    //act
+    let verification = FileVerification::compare(
+        &String::from("abc123"),
+        3,
+        &String::from("def456"),
+        3,
+    );
+
+    //assert
+    assert!(!verification.is_verified());
+}
+
+#[test]
+fn given_only_a_pending_status_the_other_fields_are_read_as_unset() {
+    //act
+    let verification: FileVerification = serde_json::from_str("{\"status\": \"pending\"}").unwrap();
+
+    //assert
+    assert_eq!(verification, FileVerification::default());
+    assert_eq!(verification.status, FileVerificationStatus::Pending);
+}
@@ -0,0 +1,57 @@
+pub mod authenticated_user;
+pub mod column_schema;
+pub mod comparison_rules;
+pub mod file_format;
+pub mod file_verification;
+pub mod named_comparison_pair;
+pub mod recon_file;
+pub mod recon_results;
+pub mod recon_task;
+pub mod recon_task_audit;
+pub mod recon_task_event;
+pub mod recon_template;
+pub mod rejection_reason;
+pub mod row_key;
+pub mod service_error;
+pub mod upload_session;
+pub mod versioned_record;
+
+#[cfg(test)]
+#[path = "./column_schema_tests.rs"]
+mod column_schema_tests;
+
+#[cfg(test)]
+#[path = "./comparison_rules_tests.rs"]
+mod comparison_rules_tests;
+
+#[cfg(test)]
+#[path = "./file_format_tests.rs"]
+mod file_format_tests;
+
+#[cfg(test)]
+#[path = "./file_verification_tests.rs"]
+mod file_verification_tests;
+
+#[cfg(test)]
+#[path = "./named_comparison_pair_tests.rs"]
+mod named_comparison_pair_tests;
+
+#[cfg(test)]
+#[path = "./recon_results_tests.rs"]
+mod recon_results_tests;
+
+#[cfg(test)]
+#[path = "./rejection_reason_tests.rs"]
+mod rejection_reason_tests;
+
+#[cfg(test)]
+#[path = "./row_key_tests.rs"]
+mod row_key_tests;
+
+#[cfg(test)]
+#[path = "./upload_session_tests.rs"]
+mod upload_session_tests;
+
+#[cfg(test)]
+#[path = "./versioned_record_tests.rs"]
+mod versioned_record_tests;
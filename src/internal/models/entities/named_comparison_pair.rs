@@ -0,0 +1,131 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::internal::{
+    models::entities::{
+        comparison_rules::{ComparisonPairRules, ComparisonRules},
+        row_key::RowKeyPosition,
+    },
+    shared_reconciler_rust_libraries::models::entities::recon_tasks_models::ComparisonPair,
+};
+
+//a column given either by its position or by its header name
+//serialized untagged so a pair reads as {"primary_file_column": 0} or {"primary_file_column": "amount"}
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(untagged)]
+pub enum ColumnReference {
+    Index(usize),
+    Header(String),
+}
+
+impl ColumnReference {
+    //indexes are taken as they are, header names have to match one of the column headers
+    pub fn resolve(&self, column_headers: &[String], ignore_header_case: bool) -> Option<usize> {
+        match self {
+            ColumnReference::Index(index) => Some(*index),
+            ColumnReference::Header(header) if ignore_header_case => column_headers
+                .iter()
+                .position(|h| h.to_lowercase() == header.to_lowercase()),
+            ColumnReference::Header(header) => column_headers.iter().position(|h| h == header),
+        }
+    }
+}
+
+impl fmt::Display for ColumnReference {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ColumnReference::Index(index) => write!(f, "{}", index),
+            ColumnReference::Header(header) => write!(f, "{}", header),
+        }
+    }
+}
+
+//a comparison pair whose columns can only be turned into indexes once the file headers are known
+//the aliases keep requests written against the plain ComparisonPair working
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct NamedComparisonPair {
+    #[serde(alias = "primary_file_column_index")]
+    pub primary_file_column: ColumnReference,
+
+    #[serde(alias = "comparison_file_column_index")]
+    pub comparison_file_column: ColumnReference,
+
+    #[serde(default)]
+    pub is_row_identifier: bool,
+
+    #[serde(default)]
+    pub ignore_header_case: bool,
+
+    //where the value of a row identifier goes in the row key, required once there is more than one identifier
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub row_key_position: Option<usize>,
+
+    //left out of the json when there are none, like in pairs saved before rules existed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rules: Option<ComparisonRules>,
+}
+
+//a header based pair together with the column indexes it was resolved to
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ResolvedComparisonPair {
+    pub named_pair: NamedComparisonPair,
+
+    pub primary_file_column_index: usize,
+
+    pub comparison_file_column_index: usize,
+}
+
+impl From<&ComparisonPair> for NamedComparisonPair {
+    fn from(pair: &ComparisonPair) -> Self {
+        NamedComparisonPair {
+            primary_file_column: ColumnReference::Index(pair.primary_file_column_index),
+            comparison_file_column: ColumnReference::Index(pair.comparison_file_column_index),
+            is_row_identifier: pair.is_row_identifier,
+            ignore_header_case: false,
+            row_key_position: None,
+            rules: None,
+        }
+    }
+}
+
+impl NamedComparisonPair {
+    //pairs made up of indexes only need no file headers at all
+    pub fn to_comparison_pair(&self) -> Option<ComparisonPair> {
+        self.resolve(&[], &[])
+    }
+
+    pub fn resolve(
+        &self,
+        primary_file_headers: &[String],
+        comparison_file_headers: &[String],
+    ) -> Option<ComparisonPair> {
+        Some(ComparisonPair {
+            primary_file_column_index: self
+                .primary_file_column
+                .resolve(primary_file_headers, self.ignore_header_case)?,
+            comparison_file_column_index: self
+                .comparison_file_column
+                .resolve(comparison_file_headers, self.ignore_header_case)?,
+            is_row_identifier: self.is_row_identifier,
+        })
+    }
+
+    //the rules of this pair tied to the column indexes it resolved to
+    pub fn rules_for(&self, resolved_pair: &ComparisonPair) -> Option<ComparisonPairRules> {
+        self.rules.as_ref().map(|rules| ComparisonPairRules {
+            primary_file_column_index: resolved_pair.primary_file_column_index,
+            comparison_file_column_index: resolved_pair.comparison_file_column_index,
+            rules: rules.clone(),
+        })
+    }
+
+    //the row key position of this pair tied to the column indexes it resolved to
+    pub fn row_key_position_for(&self, resolved_pair: &ComparisonPair) -> Option<RowKeyPosition> {
+        self.row_key_position.map(|position| RowKeyPosition {
+            primary_file_column_index: resolved_pair.primary_file_column_index,
+            comparison_file_column_index: resolved_pair.comparison_file_column_index,
+            position,
+        })
+    }
+}
@@ -0,0 +1,142 @@
+use crate::internal::models::entities::named_comparison_pair::{
+    ColumnReference, NamedComparisonPair,
+};
+
+#[test]
+fn given_header_names_resolve_returns_their_column_indexes() {
+    //setup
+    let pair = NamedComparisonPair {
+        primary_file_column: ColumnReference::Header(String::from("amount")),
+        comparison_file_column: ColumnReference::Header(String::from("total")),
+        is_row_identifier: false,
+        ignore_header_case: false,
+        row_key_position: None,
+        rules: None,
+    };
+
+    //act
+    let resolved = pair
+        .resolve(&headers(&["id", "amount"]), &headers(&["total", "id"]))
+        .unwrap();
+
+    //assert
+    assert_eq!(resolved.primary_file_column_index, 1);
+    assert_eq!(resolved.comparison_file_column_index, 0);
+}
+
+#[test]
+fn given_unknown_header_name_resolve_returns_none() {
+    //setup
+    let pair = NamedComparisonPair {
+        primary_file_column: ColumnReference::Header(String::from("amount")),
+        comparison_file_column: ColumnReference::Index(0),
+        is_row_identifier: false,
+        ignore_header_case: false,
+        row_key_position: None,
+        rules: None,
+    };
+
+    //act
+    let resolved = pair.resolve(&headers(&["id", "total"]), &headers(&["id"]));
+
+    //assert
+    assert!(resolved.is_none());
+}
+
+#[test]
+fn given_index_only_pair_to_comparison_pair_needs_no_headers() {
+    //setup
+    let pair = NamedComparisonPair {
+        primary_file_column: ColumnReference::Index(2),
+        comparison_file_column: ColumnReference::Index(3),
+        is_row_identifier: true,
+        ignore_header_case: false,
+        row_key_position: None,
+        rules: None,
+    };
+
+    //act
+    let resolved = pair.to_comparison_pair().unwrap();
+
+    //assert
+    assert_eq!(resolved.primary_file_column_index, 2);
+    assert_eq!(resolved.comparison_file_column_index, 3);
+    assert!(resolved.is_row_identifier);
+}
+
+#[test]
+fn given_json_with_numbers_and_strings_column_references_deserialize_untagged() {
+    //setup
+    let json = r#"{"primary_file_column": 0, "comparison_file_column": "Amount"}"#;
+
+    //act
+    let pair: NamedComparisonPair = serde_json::from_str(json).unwrap();
+
+    //assert
+    assert_eq!(pair.primary_file_column, ColumnReference::Index(0));
+    assert_eq!(
+        pair.comparison_file_column,
+        ColumnReference::Header(String::from("Amount"))
+    );
+    assert!(!pair.is_row_identifier);
+}
+
+#[test]
+fn given_ignore_header_case_resolve_matches_headers_regardless_of_case() {
+    //setup
+    let pair = NamedComparisonPair {
+        primary_file_column: ColumnReference::Header(String::from("AMOUNT")),
+        comparison_file_column: ColumnReference::Header(String::from("Total")),
+        is_row_identifier: false,
+        ignore_header_case: true,
+        row_key_position: None,
+        rules: None,
+    };
+
+    //act
+    let resolved = pair
+        .resolve(&headers(&["id", "amount"]), &headers(&["total", "id"]))
+        .unwrap();
+
+    //assert
+    assert_eq!(resolved.primary_file_column_index, 1);
+    assert_eq!(resolved.comparison_file_column_index, 0);
+}
+
+#[test]
+fn given_case_sensitive_pair_resolve_does_not_match_differently_cased_headers() {
+    //setup
+    let pair = NamedComparisonPair {
+        primary_file_column: ColumnReference::Header(String::from("AMOUNT")),
+        comparison_file_column: ColumnReference::Index(0),
+        is_row_identifier: false,
+        ignore_header_case: false,
+        row_key_position: None,
+        rules: None,
+    };
+
+    //act
+    let resolved = pair.resolve(&headers(&["id", "amount"]), &headers(&["id"]));
+
+    //assert
+    assert!(resolved.is_none());
+}
+
+#[test]
+fn given_json_using_index_field_names_pair_still_deserializes() {
+    //setup
+    let json = r#"{"primary_file_column_index": 1, "comparison_file_column_index": 2, "is_row_identifier": true}"#;
+
+    //act
+    let pair: NamedComparisonPair = serde_json::from_str(json).unwrap();
+
+    //assert
+    assert_eq!(pair.primary_file_column, ColumnReference::Index(1));
+    assert_eq!(pair.comparison_file_column, ColumnReference::Index(2));
+    assert!(pair.is_row_identifier);
+    assert!(!pair.ignore_header_case);
+}
+
+fn headers(names: &[&str]) -> Vec<String> {
+    names.iter().map(|name| String::from(*name)).collect()
+}
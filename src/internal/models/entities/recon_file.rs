@@ -0,0 +1,52 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::internal::{
+    models::entities::{
+        column_schema::ColumnSchema,
+        file_format::FileFormatDescriptor,
+        file_verification::FileVerification,
+        versioned_record::{
+            copy_envelope_timestamps, wrap_legacy_record, SchemaUpgrade, VersionedSchema,
+        },
+    },
+    shared_reconciler_rust_libraries::models::entities::recon_tasks_models::ReconFileMetaData,
+};
+
+//the shared file metadata plus the fields only this service keeps track of
+//flattened so the stored json is still readable as a plain ReconFileMetaData
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ReconFile {
+    #[serde(flatten)]
+    pub metadata: ReconFileMetaData,
+
+    //how the chunking workers parse the file
+    #[serde(default)]
+    pub format: FileFormatDescriptor,
+
+    //one entry per header, empty when the file was attached without a schema
+    #[serde(default)]
+    pub column_schema: Vec<ColumnSchema>,
+
+    //the names the content was saved under in the blob store, in order
+    //one part for a file uploaded in one go, empty when only the metadata was attached
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub stored_file_parts: Vec<String>,
+
+    //pending until the stored content has been read back, files saved before verification existed included
+    #[serde(default)]
+    pub verification: FileVerification,
+
+    #[serde(default)]
+    pub created_by: String,
+
+    pub created_at: DateTime<Utc>,
+
+    pub updated_at: DateTime<Utc>,
+}
+
+impl VersionedSchema for ReconFile {
+    fn schema_upgrades() -> Vec<SchemaUpgrade> {
+        vec![wrap_legacy_record, copy_envelope_timestamps]
+    }
+}
@@ -0,0 +1,275 @@
+use std::collections::BTreeMap;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::internal::{
+    models::entities::upload_session::UploadedFileRole,
+    shared_reconciler_rust_libraries::models::entities::app_errors::{AppError, AppErrorKind},
+};
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct ReconResultCounts {
+    #[serde(default)]
+    pub matched: u64,
+
+    //rows of the primary file with no row of the same key in the comparison file
+    #[serde(default)]
+    pub unmatched_in_primary: u64,
+
+    //rows of the comparison file with no row of the same key in the primary file
+    #[serde(default)]
+    pub unmatched_in_comparison: u64,
+
+    #[serde(default)]
+    pub duplicates: u64,
+
+    //rows found in both files whose compared columns differ
+    #[serde(default)]
+    pub field_mismatches: u64,
+}
+
+impl ReconResultCounts {
+    pub fn add(&mut self, other: &ReconResultCounts) {
+        self.matched += other.matched;
+        self.unmatched_in_primary += other.unmatched_in_primary;
+        self.unmatched_in_comparison += other.unmatched_in_comparison;
+        self.duplicates += other.duplicates;
+        self.field_mismatches += other.field_mismatches;
+    }
+}
+
+//what a worker publishes on the task's results topic once it has reconciled a chunk of one of the files
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ReconChunkResults {
+    pub task_id: String,
+
+    pub file_role: UploadedFileRole,
+
+    //starts at 1, like the chunks of an upload
+    pub chunk_sequence_number: u64,
+
+    //how many chunks the worker split the file into
+    pub total_chunks: u64,
+
+    pub counts: ReconResultCounts,
+
+    //the JSON Lines file in the blob binding holding a ReconResultRow per reconciled row of the chunk,
+    //it can only be the one named by derived_results_file_name
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub results_file_name: Option<String>,
+}
+
+impl ReconChunkResults {
+    pub fn derived_results_file_name(&self) -> String {
+        chunk_results_file_name(&self.task_id, self.file_role, self.chunk_sequence_number)
+    }
+}
+
+//derived rather than taken from the results, so a results message cannot point an export at any other file
+//<task_id>-<file_role>-results-<chunk_sequence_number>
+pub fn chunk_results_file_name(
+    task_id: &str,
+    file_role: UploadedFileRole,
+    chunk_sequence_number: u64,
+) -> String {
+    let file_role = match file_role {
+        UploadedFileRole::Primary => "primary",
+        UploadedFileRole::Comparison => "comparison",
+    };
+    format!("{}-{}-results-{}", task_id, file_role, chunk_sequence_number)
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ReconOutcome {
+    Matched,
+    UnmatchedInPrimary,
+    UnmatchedInComparison,
+    Duplicate,
+    FieldMismatch,
+}
+
+//what the results of a task can be narrowed down to when they are exported
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ReconOutcomeFilter {
+    Matched,
+    //rows missing from either file
+    Unmatched,
+    UnmatchedInPrimary,
+    UnmatchedInComparison,
+    Duplicate,
+    FieldMismatch,
+}
+
+impl ReconOutcomeFilter {
+    pub fn includes(&self, outcome: ReconOutcome) -> bool {
+        match self {
+            ReconOutcomeFilter::Matched => outcome == ReconOutcome::Matched,
+            ReconOutcomeFilter::Unmatched => matches!(
+                outcome,
+                ReconOutcome::UnmatchedInPrimary | ReconOutcome::UnmatchedInComparison
+            ),
+            ReconOutcomeFilter::UnmatchedInPrimary => outcome == ReconOutcome::UnmatchedInPrimary,
+            ReconOutcomeFilter::UnmatchedInComparison => {
+                outcome == ReconOutcome::UnmatchedInComparison
+            }
+            ReconOutcomeFilter::Duplicate => outcome == ReconOutcome::Duplicate,
+            ReconOutcomeFilter::FieldMismatch => outcome == ReconOutcome::FieldMismatch,
+        }
+    }
+}
+
+//one line of a chunk's results file
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ReconResultRow {
+    pub outcome: ReconOutcome,
+
+    //the file the values were read from
+    pub file_role: UploadedFileRole,
+
+    pub row_key: String,
+
+    //in the order of the file's column_headers
+    pub values: Vec<String>,
+
+    //headers of the compared columns that differ, only for field mismatches
+    #[serde(default)]
+    pub mismatched_columns: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+pub struct ChunkProgress {
+    //unknown until the first chunk of the file is reported
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub total_chunks: Option<u64>,
+
+    #[serde(default)]
+    pub received_chunks: Vec<u64>,
+
+    //keyed by chunk sequence number, so results are exported in file order
+    #[serde(default)]
+    pub results_file_names: BTreeMap<u64, String>,
+}
+
+impl ChunkProgress {
+    //every chunk from 1 to total_chunks was reported, a file is never split into no chunks at all
+    pub fn is_complete(&self) -> bool {
+        match self.total_chunks {
+            Some(total_chunks) if total_chunks > 0 => (1..=total_chunks)
+                .all(|chunk_sequence_number| self.received_chunks.contains(&chunk_sequence_number)),
+            _ => false,
+        }
+    }
+}
+
+//the running totals of a task, kept with it so they survive restarts of the service
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+pub struct ReconResultsSummary {
+    #[serde(default)]
+    pub counts: ReconResultCounts,
+
+    #[serde(default)]
+    pub primary_file_chunks: ChunkProgress,
+
+    #[serde(default)]
+    pub comparison_file_chunks: ChunkProgress,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub completed_at: Option<DateTime<Utc>>,
+}
+
+impl ReconResultsSummary {
+    //pubsub delivers at least once, so a chunk already counted is ignored and false is returned
+    //results that can never fit the file's chunks, e.g. a chunk past total_chunks, are a bad client request
+    pub fn record(&mut self, results: &ReconChunkResults) -> Result<bool, AppError> {
+        let progress = match results.file_role {
+            UploadedFileRole::Primary => &mut self.primary_file_chunks,
+            UploadedFileRole::Comparison => &mut self.comparison_file_chunks,
+        };
+
+        if results.total_chunks == 0 {
+            return Err(AppError::new(
+                AppErrorKind::BadClientRequest,
+                format!("results of task [{}] must have at least one chunk", results.task_id),
+            ));
+        }
+
+        if !(1..=results.total_chunks).contains(&results.chunk_sequence_number) {
+            return Err(AppError::new(
+                AppErrorKind::BadClientRequest,
+                format!(
+                    "chunk [{}] of task [{}] is outside of its [{}] chunks",
+                    results.chunk_sequence_number, results.task_id, results.total_chunks
+                ),
+            ));
+        }
+
+        if let Some(total_chunks) = progress.total_chunks {
+            if total_chunks != results.total_chunks {
+                return Err(AppError::new(
+                    AppErrorKind::BadClientRequest,
+                    format!(
+                        "chunk [{}] of task [{}] is one of [{}] chunks, but the file was reported in [{}]",
+                        results.chunk_sequence_number, results.task_id, results.total_chunks, total_chunks
+                    ),
+                ));
+            }
+        }
+
+        if progress.received_chunks.contains(&results.chunk_sequence_number) {
+            return Ok(false);
+        }
+
+        progress.total_chunks = Some(results.total_chunks);
+        progress.received_chunks.push(results.chunk_sequence_number);
+        if results.results_file_name.is_some() {
+            progress
+                .results_file_names
+                .insert(results.chunk_sequence_number, results.derived_results_file_name());
+        }
+        self.counts.add(&results.counts);
+        Ok(true)
+    }
+
+    pub fn all_chunks_accounted_for(&self) -> bool {
+        self.primary_file_chunks.is_complete() && self.comparison_file_chunks.is_complete()
+    }
+
+    //those of the primary file first, derived again from the chunks so a name stored before they were derived is never read
+    pub fn results_file_names(&self, task_id: &str) -> Vec<String> {
+        let derived_names = |file_role: UploadedFileRole, progress: &ChunkProgress| {
+            progress
+                .results_file_names
+                .keys()
+                .map(|chunk_sequence_number| chunk_results_file_name(task_id, file_role, *chunk_sequence_number))
+                .collect::<Vec<String>>()
+        };
+
+        derived_names(UploadedFileRole::Primary, &self.primary_file_chunks)
+            .into_iter()
+            .chain(derived_names(UploadedFileRole::Comparison, &self.comparison_file_chunks))
+            .collect()
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum ReconResultsFormat {
+    #[default]
+    Csv,
+    Jsonl,
+}
+
+//what an export of a task's results is read from
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ReconResultsExport {
+    pub task_id: String,
+
+    pub primary_file_headers: Vec<String>,
+
+    pub comparison_file_headers: Vec<String>,
+
+    pub results_file_names: Vec<String>,
+}
@@ -0,0 +1,220 @@
+use crate::internal::{
+    models::entities::{
+        recon_results::{
+            ChunkProgress, ReconChunkResults, ReconOutcome, ReconOutcomeFilter, ReconResultCounts,
+            ReconResultsSummary,
+        },
+        upload_session::UploadedFileRole,
+    },
+    shared_reconciler_rust_libraries::models::entities::app_errors::AppErrorKind,
+};
+
+#[test]
+fn given_new_chunk_record_adds_its_counts_to_the_totals() {
+    //setup
+    let mut summary = ReconResultsSummary::default();
+
+    //act
+    let first = summary.record(&get_dummy_chunk_results(UploadedFileRole::Primary, 1, 2)).unwrap();
+    let second = summary.record(&get_dummy_chunk_results(UploadedFileRole::Primary, 2, 2)).unwrap();
+
+    //assert
+    assert!(first && second);
+    assert_eq!(summary.counts.matched, 20);
+    assert_eq!(summary.counts.field_mismatches, 2);
+    assert_eq!(summary.primary_file_chunks.received_chunks, vec![1, 2]);
+}
+
+#[test]
+fn given_chunk_already_recorded_record_ignores_it() {
+    //setup
+    let mut summary = ReconResultsSummary::default();
+    summary.record(&get_dummy_chunk_results(UploadedFileRole::Primary, 1, 2)).unwrap();
+
+    //act
+    let recorded = summary.record(&get_dummy_chunk_results(UploadedFileRole::Primary, 1, 2)).unwrap();
+
+    //assert
+    assert!(!recorded);
+    assert_eq!(summary.counts.matched, 10);
+}
+
+#[test]
+fn given_no_chunks_in_total_record_returns_bad_client_request() {
+    //setup
+    let mut summary = ReconResultsSummary::default();
+
+    //act
+    let result = summary.record(&get_dummy_chunk_results(UploadedFileRole::Primary, 1, 0));
+
+    //assert
+    assert!(matches!(result.err().unwrap().kind, AppErrorKind::BadClientRequest));
+    assert_eq!(summary, ReconResultsSummary::default());
+}
+
+#[test]
+fn given_chunk_outside_of_the_total_record_returns_bad_client_request() {
+    //setup
+    let mut summary = ReconResultsSummary::default();
+
+    //act
+    let past_the_total = summary.record(&get_dummy_chunk_results(UploadedFileRole::Primary, 3, 2));
+    let before_the_first = summary.record(&get_dummy_chunk_results(UploadedFileRole::Primary, 0, 2));
+
+    //assert
+    assert!(matches!(past_the_total.err().unwrap().kind, AppErrorKind::BadClientRequest));
+    assert!(matches!(before_the_first.err().unwrap().kind, AppErrorKind::BadClientRequest));
+    assert_eq!(summary, ReconResultsSummary::default());
+}
+
+#[test]
+fn given_total_other_than_the_recorded_one_record_returns_bad_client_request() {
+    //setup
+    let mut summary = ReconResultsSummary::default();
+    summary.record(&get_dummy_chunk_results(UploadedFileRole::Primary, 1, 2)).unwrap();
+
+    //act
+    let result = summary.record(&get_dummy_chunk_results(UploadedFileRole::Primary, 2, 3));
+
+    //assert
+    assert!(matches!(result.err().unwrap().kind, AppErrorKind::BadClientRequest));
+    assert_eq!(summary.primary_file_chunks.total_chunks, Some(2));
+    assert_eq!(summary.counts.matched, 10);
+}
+
+#[test]
+fn given_no_chunks_in_total_is_complete_returns_false() {
+    //setup
+    let progress = ChunkProgress {
+        total_chunks: Some(0),
+        ..ChunkProgress::default()
+    };
+
+    //act
+    let complete = progress.is_complete();
+
+    //assert
+    assert!(!complete);
+}
+
+#[test]
+fn given_as_many_chunks_as_the_total_but_not_every_one_of_them_is_complete_returns_false() {
+    //setup
+    let progress = ChunkProgress {
+        total_chunks: Some(2),
+        received_chunks: vec![1, 3],
+        ..ChunkProgress::default()
+    };
+
+    //act
+    let complete = progress.is_complete();
+
+    //assert
+    assert!(!complete);
+}
+
+#[test]
+fn given_all_chunks_of_only_one_file_all_chunks_accounted_for_returns_false() {
+    //setup
+    let mut summary = ReconResultsSummary::default();
+    summary.record(&get_dummy_chunk_results(UploadedFileRole::Primary, 1, 1)).unwrap();
+
+    //act
+    let accounted_for = summary.all_chunks_accounted_for();
+
+    //assert
+    assert!(!accounted_for);
+}
+
+#[test]
+fn given_all_chunks_of_both_files_all_chunks_accounted_for_returns_true() {
+    //setup
+    let mut summary = ReconResultsSummary::default();
+    summary.record(&get_dummy_chunk_results(UploadedFileRole::Primary, 1, 1)).unwrap();
+    summary.record(&get_dummy_chunk_results(UploadedFileRole::Comparison, 2, 2)).unwrap();
+    summary.record(&get_dummy_chunk_results(UploadedFileRole::Comparison, 1, 2)).unwrap();
+
+    //act
+    let accounted_for = summary.all_chunks_accounted_for();
+
+    //assert
+    assert!(accounted_for);
+}
+
+#[test]
+fn given_chunks_recorded_out_of_order_results_file_names_lists_primary_chunks_first_in_file_order() {
+    //setup
+    let mut summary = ReconResultsSummary::default();
+    for (file_role, chunk_sequence_number) in [
+        (UploadedFileRole::Comparison, 1),
+        (UploadedFileRole::Primary, 2),
+        (UploadedFileRole::Primary, 1),
+    ] {
+        let mut results = get_dummy_chunk_results(file_role, chunk_sequence_number, 2);
+        results.results_file_name = Some(results.derived_results_file_name());
+        summary.record(&results).unwrap();
+    }
+
+    //act
+    let results_file_names = summary.results_file_names("task-1234");
+
+    //assert
+    assert_eq!(
+        results_file_names,
+        vec![
+            "task-1234-primary-results-1",
+            "task-1234-primary-results-2",
+            "task-1234-comparison-results-1"
+        ]
+    );
+}
+
+#[test]
+fn given_name_of_another_file_stored_for_a_chunk_results_file_names_lists_the_derived_name() {
+    //setup
+    let mut summary = ReconResultsSummary::default();
+    summary.record(&get_dummy_chunk_results(UploadedFileRole::Primary, 1, 1)).unwrap();
+    summary
+        .primary_file_chunks
+        .results_file_names
+        .insert(1, String::from("other-task-src-file-1234"));
+
+    //act
+    let results_file_names = summary.results_file_names("task-1234");
+
+    //assert
+    assert_eq!(results_file_names, vec!["task-1234-primary-results-1"]);
+}
+
+#[test]
+fn given_unmatched_filter_includes_returns_true_for_rows_missing_from_either_file() {
+    //act
+    let filter = ReconOutcomeFilter::Unmatched;
+
+    //assert
+    assert!(filter.includes(ReconOutcome::UnmatchedInPrimary));
+    assert!(filter.includes(ReconOutcome::UnmatchedInComparison));
+    assert!(!filter.includes(ReconOutcome::Matched));
+    assert!(!filter.includes(ReconOutcome::FieldMismatch));
+}
+
+fn get_dummy_chunk_results(
+    file_role: UploadedFileRole,
+    chunk_sequence_number: u64,
+    total_chunks: u64,
+) -> ReconChunkResults {
+    ReconChunkResults {
+        task_id: String::from("task-1234"),
+        file_role,
+        chunk_sequence_number,
+        total_chunks,
+        counts: ReconResultCounts {
+            matched: 10,
+            unmatched_in_primary: 1,
+            unmatched_in_comparison: 0,
+            duplicates: 0,
+            field_mismatches: 1,
+        },
+        results_file_name: None,
+    }
+}
@@ -0,0 +1,310 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::internal::{
+    models::entities::{
+        column_schema::ColumnTypeMismatch,
+        comparison_rules::{ComparisonPairRules, ComparisonRules},
+        named_comparison_pair::{NamedComparisonPair, ResolvedComparisonPair},
+        recon_file::ReconFile,
+        recon_results::{ReconChunkResults, ReconResultsSummary},
+        recon_template::ReconTemplateReference,
+        row_key::{default_row_key_separator, RowKeyDefinition, RowKeyPosition},
+        upload_session::UploadedFileRole,
+        versioned_record::{
+            copy_envelope_timestamps, wrap_legacy_record, SchemaUpgrade, VersionedRecord,
+            VersionedSchema,
+        },
+    },
+    shared_reconciler_rust_libraries::models::entities::{
+        app_errors::AppError,
+        recon_tasks_models::{ComparisonPair, ReconTaskDetails},
+    },
+};
+
+//the shared task details plus the fields only this service keeps track of
+//flattened so the stored json is still readable as a plain ReconTaskDetails
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ReconTask {
+    #[serde(flatten)]
+    pub details: ReconTaskDetails,
+
+    #[serde(default)]
+    pub created_by: String,
+
+    pub created_at: DateTime<Utc>,
+
+    pub updated_at: DateTime<Utc>,
+
+    //set together with the cancelled status, a cancelled task never accepts files again
+    #[serde(default)]
+    pub cancellation: Option<ReconTaskCancellation>,
+
+    //set together with the starting status, before any start command is published
+    #[serde(default)]
+    pub reconciliation_start: Option<ReconTaskStart>,
+
+    #[serde(default)]
+    pub template: Option<ReconTemplateReference>,
+
+    //pairs that name columns by header, moved into resolved_comparison_pairs once both files are attached
+    #[serde(default)]
+    pub pending_comparison_pairs: Vec<NamedComparisonPair>,
+
+    //the header based pairs against the current files, resolved again every time a file is attached
+    //in the order they were appended to details.comparison_pairs
+    #[serde(default)]
+    pub resolved_comparison_pairs: Vec<ResolvedComparisonPair>,
+
+    //suggested pairs the owner accepted, kept apart from the pairs given with the task
+    //they name their columns by header and are resolved after the named pairs into details.comparison_pairs
+    #[serde(default)]
+    pub accepted_comparison_pairs: Vec<NamedComparisonPair>,
+
+    //only pairs that have rules are listed, the others compare their values as they are
+    #[serde(default)]
+    pub comparison_pair_rules: Vec<ComparisonPairRules>,
+
+    //only identifier pairs that were given a position are listed, see row_key_definition
+    #[serde(default)]
+    pub row_key_positions: Vec<RowKeyPosition>,
+
+    //joins the identifier column values into a row key, see row_key_definition
+    #[serde(default = "default_row_key_separator")]
+    pub row_key_separator: String,
+
+    //pairs comparing columns of incompatible types, checked once both files are attached
+    #[serde(default)]
+    pub column_type_mismatches: Vec<ColumnTypeMismatch>,
+
+    //tasks saved before the status existed are read as awaiting files until their files are verified
+    #[serde(default)]
+    pub status: ReconTaskStatus,
+
+    //what the workers have reported on the results topic since the task started
+    #[serde(default)]
+    pub results_summary: ReconResultsSummary,
+
+    //the etag of the stored task when it was read, an update only goes through while it still matches
+    //never stored or returned to clients
+    #[serde(skip)]
+    pub etag: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum ReconTaskStatus {
+    #[default]
+    AwaitingFiles,
+    //both files are attached and their stored content matches the declared hash and row count
+    Ready,
+    //saved before the start commands are published, starting again sends the same commands again
+    Starting,
+    //the start command has been published, the files and pairs can no longer change
+    Reconciling,
+    //every chunk of both files has been reported on the results topic
+    Completed,
+    //see cancellation for who cancelled it and why
+    Cancelled,
+}
+
+impl ReconTaskStatus {
+    pub fn has_started(&self) -> bool {
+        !matches!(self, ReconTaskStatus::AwaitingFiles | ReconTaskStatus::Ready)
+    }
+
+    //a task is only ready once both of its files have been verified
+    pub fn for_files(
+        primary_file: Option<&ReconFile>,
+        comparison_file: Option<&ReconFile>,
+    ) -> ReconTaskStatus {
+        match (primary_file, comparison_file) {
+            (Some(primary_file), Some(comparison_file))
+                if primary_file.verification.is_verified()
+                    && comparison_file.verification.is_verified() =>
+            {
+                ReconTaskStatus::Ready
+            }
+            _ => ReconTaskStatus::AwaitingFiles,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ReconTaskCancellation {
+    pub cancelled_by: String,
+
+    pub cancelled_at: DateTime<Utc>,
+
+    pub reason: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ReconTaskStart {
+    //the same for every attempt at publishing the start commands, see idempotency_key
+    pub start_id: String,
+
+    pub started_by: String,
+
+    pub started_at: DateTime<Utc>,
+}
+
+impl ReconTaskStart {
+    pub fn new(task_id: &str, started_by: &str, started_at: DateTime<Utc>) -> Self {
+        ReconTaskStart {
+            start_id: format!("{}-start-{}", task_id, started_at.timestamp_millis()),
+            started_by: started_by.to_string(),
+            started_at,
+        }
+    }
+
+    //one per start command, a worker that already took a command with the key ignores it when it comes again
+    pub fn idempotency_key(&self, file_role: UploadedFileRole) -> String {
+        match file_role {
+            UploadedFileRole::Primary => format!("{}-primary", self.start_id),
+            UploadedFileRole::Comparison => format!("{}-comparison", self.start_id),
+        }
+    }
+}
+
+impl ReconTask {
+    pub fn is_cancelled(&self) -> bool {
+        self.status == ReconTaskStatus::Cancelled
+    }
+
+    //active tasks count towards their owner's quota
+    pub fn is_active(&self) -> bool {
+        !self.details.is_done && !self.is_cancelled()
+    }
+
+    //returns false when there is nothing to update, e.g. the task was cancelled or the chunk was already reported,
+    //a worker can report before the task has been saved as reconciling
+    pub fn record_chunk_results(
+        &mut self,
+        results: &ReconChunkResults,
+        recorded_at: DateTime<Utc>,
+    ) -> Result<bool, AppError> {
+        if !matches!(self.status, ReconTaskStatus::Starting | ReconTaskStatus::Reconciling) {
+            return Ok(false);
+        }
+
+        if !self.results_summary.record(results)? {
+            return Ok(false);
+        }
+
+        self.updated_at = recorded_at;
+        if self.results_summary.all_chunks_accounted_for() {
+            self.status = ReconTaskStatus::Completed;
+            self.details.is_done = true;
+            self.results_summary.completed_at = Some(recorded_at);
+        }
+        Ok(true)
+    }
+
+    //only a task that is still starting moves on, results recorded in the meantime are kept
+    //returns false when there is nothing to update, e.g. the results already completed the task
+    pub fn mark_reconciling(&mut self, marked_at: DateTime<Utc>) -> bool {
+        if self.status != ReconTaskStatus::Starting {
+            return false;
+        }
+
+        self.status = ReconTaskStatus::Reconciling;
+        self.updated_at = marked_at;
+        true
+    }
+
+    //the pairs as they were originally given, header names included, e.g. to set up a copy of the task
+    pub fn comparison_pair_definitions(&self) -> Vec<NamedComparisonPair> {
+        self
+            .index_only_comparison_pairs()
+            .iter()
+            .map(|pair| NamedComparisonPair {
+                rules: self.rules_of(pair.primary_file_column_index, pair.comparison_file_column_index),
+                row_key_position: self.row_key_position_of(pair),
+                ..NamedComparisonPair::from(pair)
+            })
+            .chain(self.named_comparison_pairs())
+            .chain(self.accepted_comparison_pairs.iter().cloned())
+            .collect()
+    }
+
+    //the pairs given by index, they come before the resolved and accepted pairs in details.comparison_pairs
+    pub fn index_only_comparison_pairs(&self) -> &[ComparisonPair] {
+        let index_only_pairs = self
+            .details
+            .comparison_pairs
+            .len()
+            .saturating_sub(self.resolved_comparison_pairs.len())
+            .saturating_sub(self.accepted_comparison_pairs.len());
+
+        &self.details.comparison_pairs[..index_only_pairs]
+    }
+
+    //the pairs that name their columns by header, whether or not both files are attached yet
+    pub fn named_comparison_pairs(&self) -> Vec<NamedComparisonPair> {
+        self
+            .resolved_comparison_pairs
+            .iter()
+            .map(|pair| pair.named_pair.clone())
+            .chain(self.pending_comparison_pairs.iter().cloned())
+            .collect()
+    }
+
+    //the identifier pairs ordered by their row key position, derived every time so it never drifts from the pairs
+    pub fn row_key_definition(&self) -> RowKeyDefinition {
+        RowKeyDefinition::new(
+            &self.row_key_separator,
+            &self.details.comparison_pairs,
+            &self.row_key_positions,
+            &self.pending_comparison_pairs,
+        )
+    }
+
+    pub fn row_key_position_of(&self, pair: &ComparisonPair) -> Option<usize> {
+        self
+            .row_key_positions
+            .iter()
+            .find(|row_key_position| {
+                row_key_position.primary_file_column_index == pair.primary_file_column_index
+                    && row_key_position.comparison_file_column_index == pair.comparison_file_column_index
+            })
+            .map(|row_key_position| row_key_position.position)
+    }
+
+    pub fn rules_of(
+        &self,
+        primary_file_column_index: usize,
+        comparison_file_column_index: usize,
+    ) -> Option<ComparisonRules> {
+        self
+            .comparison_pair_rules
+            .iter()
+            .find(|pair_rules| {
+                pair_rules.primary_file_column_index == primary_file_column_index
+                    && pair_rules.comparison_file_column_index == comparison_file_column_index
+            })
+            .map(|pair_rules| pair_rules.rules.clone())
+    }
+}
+
+impl VersionedSchema for ReconTask {
+    fn schema_upgrades() -> Vec<SchemaUpgrade> {
+        vec![wrap_legacy_record, copy_envelope_timestamps, mark_cancelled_tasks]
+    }
+}
+
+//version 2 -> 3: tasks cancelled before the cancelled status existed only carry their cancellation
+fn mark_cancelled_tasks(mut record: VersionedRecord<Value>) -> Result<VersionedRecord<Value>, String> {
+    let fields = match record.data.as_object_mut() {
+        Some(fields) => fields,
+        None => return Err(String::from("record data is not a json object")),
+    };
+
+    let is_cancelled = fields.get("cancellation").is_some_and(|cancellation| !cancellation.is_null());
+    if is_cancelled {
+        fields.insert(String::from("status"), Value::from("cancelled"));
+    }
+
+    Ok(record)
+}
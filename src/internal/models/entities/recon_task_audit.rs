@@ -0,0 +1,63 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::internal::models::entities::versioned_record::{
+    wrap_legacy_record, SchemaUpgrade, VersionedSchema,
+};
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub enum ReconTaskAction {
+    TaskCreated,
+    PrimaryFileAttached,
+    ComparisonFileAttached,
+    StatusForced,
+    TaskCancelled,
+    SuggestedPairsAccepted,
+    FilesVerified,
+    ReconciliationStarted,
+    ReconciliationCompleted,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ReconTaskAuditEntry {
+    pub task_id: String,
+
+    pub action: ReconTaskAction,
+
+    pub performed_by: String,
+
+    pub performed_at: DateTime<Utc>,
+
+    pub details: Option<String>,
+}
+
+//the append-only history of everything that happened to a task
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct ReconTaskAuditLog {
+    pub task_id: String,
+
+    pub entries: Vec<ReconTaskAuditEntry>,
+}
+
+impl VersionedSchema for ReconTaskAuditLog {
+    fn schema_upgrades() -> Vec<SchemaUpgrade> {
+        vec![wrap_legacy_record]
+    }
+}
+
+impl ReconTaskAuditEntry {
+    pub fn new(
+        task_id: &str,
+        action: ReconTaskAction,
+        performed_by: &str,
+        details: Option<String>,
+    ) -> Self {
+        ReconTaskAuditEntry {
+            task_id: task_id.to_string(),
+            action,
+            performed_by: performed_by.to_string(),
+            performed_at: Utc::now(),
+            details,
+        }
+    }
+}
@@ -0,0 +1,71 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::internal::{
+    models::entities::{
+        comparison_rules::ComparisonPairRules, file_format::FileFormatDescriptor,
+        recon_file::ReconFile, row_key::RowKeyColumn,
+    },
+    shared_reconciler_rust_libraries::models::entities::recon_tasks_models::{
+        ComparisonPair, ReconciliationConfigs,
+    },
+};
+
+//events this service publishes on a task's topics for the reconciliation workers
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(tag = "event_type")]
+pub enum ReconTaskEvent {
+    TaskCancelled {
+        task_id: String,
+        cancelled_by: String,
+        reason: String,
+        cancelled_at: DateTime<Utc>,
+    },
+
+    //sent on each file's chunks topic, everything a chunking worker needs to split the file and queue its rows
+    ReconciliationStarted {
+        task_id: String,
+        //the same when the command is sent again, so a worker can tell it has already taken it
+        idempotency_key: String,
+        file: Box<FileToReconcile>,
+        recon_config: ReconciliationConfigs,
+        comparison_pairs: Vec<ComparisonPair>,
+        comparison_pair_rules: Vec<ComparisonPairRules>,
+        row_key_separator: String,
+        //the identifier columns in the order their values are joined into the row key
+        row_key_columns: Vec<RowKeyColumn>,
+        //where the workers send what they find
+        recon_results_topic: String,
+        started_by: String,
+        started_at: DateTime<Utc>,
+    },
+}
+
+//the metadata of a file and where its content is kept
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct FileToReconcile {
+    pub file_id: String,
+    pub file_name: String,
+    pub file_hash: String,
+    pub row_count: u64,
+    pub column_delimiters: Vec<char>,
+    pub column_headers: Vec<String>,
+    pub format: FileFormatDescriptor,
+    //read in order, a file attached with its metadata only is the one part it was put under within the task
+    pub stored_file_parts: Vec<String>,
+}
+
+impl From<&ReconFile> for FileToReconcile {
+    fn from(file: &ReconFile) -> Self {
+        FileToReconcile {
+            file_id: file.metadata.id.clone(),
+            file_name: file.metadata.file_name.clone(),
+            file_hash: file.metadata.file_hash.clone(),
+            row_count: file.metadata.row_count,
+            column_delimiters: file.metadata.column_delimiters.clone(),
+            column_headers: file.metadata.column_headers.clone(),
+            format: file.format.clone(),
+            stored_file_parts: file.stored_file_parts.clone(),
+        }
+    }
+}
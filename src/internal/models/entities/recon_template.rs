@@ -0,0 +1,49 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::internal::{
+    models::entities::{
+        named_comparison_pair::NamedComparisonPair,
+        row_key::default_row_key_separator,
+        versioned_record::{wrap_legacy_record, SchemaUpgrade, VersionedSchema},
+    },
+    shared_reconciler_rust_libraries::models::entities::recon_tasks_models::ReconciliationConfigs,
+};
+
+//a named, reusable set of reconciliation settings that tasks can be created from
+//every update bumps the version, older versions are kept so tasks can refer back to them
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ReconTemplate {
+    pub id: String,
+
+    pub name: String,
+
+    pub version: u32,
+
+    pub recon_configurations: ReconciliationConfigs,
+
+    pub comparison_pairs: Vec<NamedComparisonPair>,
+
+    #[serde(default = "default_row_key_separator")]
+    pub row_key_separator: String,
+
+    pub created_by: String,
+
+    pub created_at: DateTime<Utc>,
+
+    pub updated_at: DateTime<Utc>,
+}
+
+//which template, and which version of it, a task was created from
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct ReconTemplateReference {
+    pub template_id: String,
+
+    pub template_version: u32,
+}
+
+impl VersionedSchema for ReconTemplate {
+    fn schema_upgrades() -> Vec<SchemaUpgrade> {
+        vec![wrap_legacy_record]
+    }
+}
@@ -0,0 +1,35 @@
+use serde::Serialize;
+
+use crate::internal::{
+    models::entities::service_error::ServiceError,
+    shared_reconciler_rust_libraries::models::entities::app_errors::{AppError, AppErrorKind},
+};
+
+//the shared AppErrorKind only covers what every reconciler service needs,
+//so the rejections particular to this service travel on a ServiceError next to it
+//ok_or_error picks the status code from the reason and clients see the reason as the error's kind
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum RejectionReason {
+    Forbidden,
+
+    //the user already has as many tasks going as their quota allows
+    TooManyActiveTasks,
+
+    //a file with more rows than the quota allows
+    FileTooLarge,
+
+    //too many mutating requests in a short time
+    RateLimited,
+
+    //the task was changed by another request after this one read it
+    Conflict,
+}
+
+impl RejectionReason {
+    pub fn error(&self, message: String) -> ServiceError {
+        ServiceError {
+            error: AppError::new(AppErrorKind::BadClientRequest, message),
+            rejection: Some(*self),
+        }
+    }
+}
@@ -0,0 +1,50 @@
+use crate::internal::{
+    models::entities::{rejection_reason::RejectionReason, service_error::ServiceError},
+    shared_reconciler_rust_libraries::models::entities::app_errors::{AppError, AppErrorKind},
+};
+
+#[test]
+fn given_error_made_from_a_reason_it_carries_that_reason_and_the_untouched_message() {
+    //setup
+    let message = String::from("not your task");
+
+    //act
+    let error = RejectionReason::Forbidden.error(message.clone());
+
+    //assert
+    assert_eq!(error.rejection, Some(RejectionReason::Forbidden));
+    assert!(matches!(error.kind, AppErrorKind::BadClientRequest));
+    assert_eq!(error.message, message);
+}
+
+#[test]
+fn given_error_made_from_a_reason_it_serializes_the_reason_as_its_kind() {
+    //setup
+    let error = RejectionReason::TooManyActiveTasks.error(String::from("quota reached"));
+
+    //act
+    let body = serde_json::to_value(&error).unwrap();
+
+    //assert
+    assert_eq!(
+        body,
+        serde_json::json!({"kind": "TooManyActiveTasks", "message": "quota reached"})
+    );
+}
+
+#[test]
+fn given_shared_app_error_it_carries_no_reason_and_serializes_its_own_kind() {
+    //setup
+    let app_error = AppError::new(AppErrorKind::BadClientRequest, String::from("forbidden: not a rejection"));
+
+    //act
+    let error = ServiceError::from(app_error);
+    let body = serde_json::to_value(&error).unwrap();
+
+    //assert
+    assert_eq!(error.rejection, None);
+    assert_eq!(
+        body,
+        serde_json::json!({"kind": "BadClientRequest", "message": "forbidden: not a rejection"})
+    );
+}
@@ -0,0 +1,194 @@
+use std::collections::HashSet;
+
+use serde::{Deserialize, Serialize};
+
+use crate::internal::{
+    models::entities::named_comparison_pair::{ColumnReference, NamedComparisonPair},
+    shared_reconciler_rust_libraries::models::entities::recon_tasks_models::ComparisonPair,
+};
+
+pub const DEFAULT_ROW_KEY_SEPARATOR: &str = "|";
+
+pub fn default_row_key_separator() -> String {
+    String::from(DEFAULT_ROW_KEY_SEPARATOR)
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct RowKeyColumn {
+    pub primary_file_column_index: usize,
+
+    pub comparison_file_column_index: usize,
+}
+
+//where the value of an identifier pair goes in the row key, tied to the column indexes the pair resolved to
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct RowKeyPosition {
+    pub primary_file_column_index: usize,
+
+    pub comparison_file_column_index: usize,
+
+    pub position: usize,
+}
+
+//how a worker builds the key of a row: the values of the identifier columns, in this order, joined by the separator
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct RowKeyDefinition {
+    pub key_separator: String,
+
+    pub identifier_columns: Vec<RowKeyColumn>,
+
+    //false while identifier pairs that name their columns by header wait for the files to be attached
+    pub is_complete: bool,
+}
+
+impl RowKeyDefinition {
+    pub fn new(
+        key_separator: &str,
+        comparison_pairs: &[ComparisonPair],
+        row_key_positions: &[RowKeyPosition],
+        pending_comparison_pairs: &[NamedComparisonPair],
+    ) -> Self {
+        let mut identifier_columns: Vec<RowKeyColumn> = comparison_pairs
+            .iter()
+            .filter(|pair| pair.is_row_identifier)
+            .map(|pair| RowKeyColumn {
+                primary_file_column_index: pair.primary_file_column_index,
+                comparison_file_column_index: pair.comparison_file_column_index,
+            })
+            .collect();
+
+        //the sort is stable, so tasks saved before positions existed keep their pair order
+        identifier_columns.sort_by_key(|column| {
+            row_key_positions
+                .iter()
+                .find(|row_key_position| {
+                    row_key_position.primary_file_column_index == column.primary_file_column_index
+                        && row_key_position.comparison_file_column_index
+                            == column.comparison_file_column_index
+                })
+                .map_or(usize::MAX, |row_key_position| row_key_position.position)
+        });
+
+        RowKeyDefinition {
+            key_separator: key_separator.to_string(),
+            identifier_columns,
+            is_complete: !pending_comparison_pairs
+                .iter()
+                .any(|pair| pair.is_row_identifier),
+        }
+    }
+}
+
+//checked before a task or template is saved, while header names may still be unresolved
+pub fn row_identifier_errors(comparison_pairs: &[NamedComparisonPair], key_separator: &str) -> Vec<String> {
+    let mut errors = vec![];
+
+    if key_separator.is_empty() {
+        errors.push(String::from("please supply a row_key_separator"));
+    }
+
+    let identifier_pairs: Vec<&NamedComparisonPair> = comparison_pairs
+        .iter()
+        .filter(|pair| pair.is_row_identifier)
+        .collect();
+
+    if identifier_pairs.is_empty() {
+        errors.push(String::from(
+            "at least one comparison pair has to be a row identifier",
+        ));
+    }
+
+    let primary_columns = identifier_pairs
+        .iter()
+        .map(|pair| column_key(&pair.primary_file_column, pair.ignore_header_case));
+    let comparison_columns = identifier_pairs
+        .iter()
+        .map(|pair| column_key(&pair.comparison_file_column, pair.ignore_header_case));
+
+    errors.extend(duplicate_column_errors(primary_columns, "primary"));
+    errors.extend(duplicate_column_errors(comparison_columns, "comparison"));
+    errors.extend(row_key_position_errors(comparison_pairs, &identifier_pairs));
+    errors
+}
+
+//a composite key needs every identifier to say where its value goes, as 0, 1, 2 and so on
+fn row_key_position_errors(
+    comparison_pairs: &[NamedComparisonPair],
+    identifier_pairs: &[&NamedComparisonPair],
+) -> Vec<String> {
+    let mut errors: Vec<String> = comparison_pairs
+        .iter()
+        .filter(|pair| !pair.is_row_identifier && pair.row_key_position.is_some())
+        .map(|pair| {
+            format!(
+                "the comparison pair [{}] and [{}] is not a row identifier and cannot have a row_key_position",
+                pair.primary_file_column, pair.comparison_file_column
+            )
+        })
+        .collect();
+
+    let mut positions: Vec<usize> = identifier_pairs
+        .iter()
+        .filter_map(|pair| pair.row_key_position)
+        .collect();
+
+    if identifier_pairs.len() > 1 && positions.len() < identifier_pairs.len() {
+        errors.push(String::from(
+            "please supply a row_key_position for every row identifier when there is more than one",
+        ));
+        return errors;
+    }
+
+    positions.sort();
+    if positions.iter().enumerate().any(|(expected, position)| *position != expected) {
+        errors.push(format!(
+            "the row_key_position of the row identifiers have to be 0 to {}, each used once",
+            positions.len().saturating_sub(1)
+        ));
+    }
+    errors
+}
+
+//checked again once header names have been turned into indexes, as a name and an index can point at the same column
+pub fn resolved_row_identifier_errors(comparison_pairs: &[ComparisonPair]) -> Vec<String> {
+    let identifier_pairs: Vec<&ComparisonPair> = comparison_pairs
+        .iter()
+        .filter(|pair| pair.is_row_identifier)
+        .collect();
+
+    let primary_columns = identifier_pairs
+        .iter()
+        .map(|pair| pair.primary_file_column_index.to_string());
+    let comparison_columns = identifier_pairs
+        .iter()
+        .map(|pair| pair.comparison_file_column_index.to_string());
+
+    let mut errors = duplicate_column_errors(primary_columns, "primary");
+    errors.extend(duplicate_column_errors(comparison_columns, "comparison"));
+    errors
+}
+
+fn column_key(column: &ColumnReference, ignore_header_case: bool) -> String {
+    match column {
+        ColumnReference::Header(header) if ignore_header_case => header.to_lowercase(),
+        _ => column.to_string(),
+    }
+}
+
+fn duplicate_column_errors(
+    columns: impl Iterator<Item = String>,
+    file_description: &str,
+) -> Vec<String> {
+    let mut seen_columns = HashSet::new();
+    let mut errors = vec![];
+
+    for column in columns {
+        if !seen_columns.insert(column.clone()) {
+            errors.push(format!(
+                "the {} file column [{}] is used by more than one row identifier",
+                file_description, column
+            ));
+        }
+    }
+    errors
+}
@@ -0,0 +1,238 @@
+use crate::internal::{
+    models::entities::{
+        named_comparison_pair::{ColumnReference, NamedComparisonPair},
+        row_key::{
+            resolved_row_identifier_errors, row_identifier_errors, RowKeyColumn, RowKeyDefinition,
+            RowKeyPosition,
+        },
+    },
+    shared_reconciler_rust_libraries::models::entities::recon_tasks_models::ComparisonPair,
+};
+
+#[test]
+fn given_identifier_pairs_row_key_definition_keeps_them_in_pair_order() {
+    //setup
+    let comparison_pairs = vec![
+        comparison_pair(3, 1, true),
+        comparison_pair(0, 0, false),
+        comparison_pair(1, 2, true),
+    ];
+
+    //act
+    let row_key = RowKeyDefinition::new(&String::from("#"), &comparison_pairs, &[], &[]);
+
+    //assert
+    assert_eq!(row_key.key_separator, "#");
+    assert_eq!(
+        row_key.identifier_columns,
+        vec![
+            RowKeyColumn {
+                primary_file_column_index: 3,
+                comparison_file_column_index: 1,
+            },
+            RowKeyColumn {
+                primary_file_column_index: 1,
+                comparison_file_column_index: 2,
+            },
+        ]
+    );
+    assert!(row_key.is_complete);
+}
+
+#[test]
+fn given_row_key_positions_row_key_definition_orders_identifiers_by_them() {
+    //setup
+    let comparison_pairs = vec![comparison_pair(3, 1, true), comparison_pair(1, 2, true)];
+    let row_key_positions = vec![
+        RowKeyPosition {
+            primary_file_column_index: 3,
+            comparison_file_column_index: 1,
+            position: 1,
+        },
+        RowKeyPosition {
+            primary_file_column_index: 1,
+            comparison_file_column_index: 2,
+            position: 0,
+        },
+    ];
+
+    //act
+    let row_key = RowKeyDefinition::new(&String::from("|"), &comparison_pairs, &row_key_positions, &[]);
+
+    //assert
+    assert_eq!(row_key.identifier_columns[0].primary_file_column_index, 1);
+    assert_eq!(row_key.identifier_columns[1].primary_file_column_index, 3);
+}
+
+#[test]
+fn given_pending_identifier_pair_row_key_definition_is_not_complete() {
+    //setup
+    let pending_pairs = vec![named_pair("reference", "bank_reference", true, false)];
+
+    //act
+    let row_key = RowKeyDefinition::new(
+        &String::from("|"),
+        &[comparison_pair(0, 0, true)],
+        &[],
+        &pending_pairs,
+    );
+
+    //assert
+    assert!(!row_key.is_complete);
+}
+
+#[test]
+fn given_no_identifier_pairs_row_identifier_errors_asks_for_one() {
+    //setup
+    let comparison_pairs = vec![named_pair("amount", "total", false, false)];
+
+    //act
+    let errors = row_identifier_errors(&comparison_pairs, "|");
+
+    //assert
+    assert_eq!(errors.len(), 1);
+}
+
+#[test]
+fn given_identifiers_sharing_a_column_row_identifier_errors_reports_the_duplicate() {
+    //setup
+    let comparison_pairs = vec![
+        positioned(named_pair("Reference", "bank_reference", true, true), 0),
+        positioned(named_pair("reference", "narration", true, true), 1),
+    ];
+
+    //act
+    let errors = row_identifier_errors(&comparison_pairs, "|");
+
+    //assert
+    assert_eq!(
+        errors,
+        vec![String::from(
+            "the primary file column [reference] is used by more than one row identifier"
+        )]
+    );
+}
+
+#[test]
+fn given_identifiers_without_row_key_positions_row_identifier_errors_asks_for_them() {
+    //setup
+    let comparison_pairs = vec![
+        positioned(named_pair("reference", "bank_reference", true, false), 0),
+        named_pair("date", "value_date", true, false),
+    ];
+
+    //act
+    let errors = row_identifier_errors(&comparison_pairs, "|");
+
+    //assert
+    assert_eq!(
+        errors,
+        vec![String::from(
+            "please supply a row_key_position for every row identifier when there is more than one"
+        )]
+    );
+}
+
+#[test]
+fn given_row_key_positions_with_a_gap_row_identifier_errors_reports_them() {
+    //setup
+    let comparison_pairs = vec![
+        positioned(named_pair("reference", "bank_reference", true, false), 0),
+        positioned(named_pair("date", "value_date", true, false), 2),
+    ];
+
+    //act
+    let errors = row_identifier_errors(&comparison_pairs, "|");
+
+    //assert
+    assert_eq!(errors.len(), 1);
+}
+
+#[test]
+fn given_row_key_position_on_a_plain_pair_row_identifier_errors_reports_it() {
+    //setup
+    let comparison_pairs = vec![
+        named_pair("reference", "bank_reference", true, false),
+        positioned(named_pair("amount", "total", false, false), 1),
+    ];
+
+    //act
+    let errors = row_identifier_errors(&comparison_pairs, "|");
+
+    //assert
+    assert_eq!(errors.len(), 1);
+}
+
+#[test]
+fn given_single_identifier_without_row_key_position_row_identifier_errors_is_empty() {
+    //setup
+    let comparison_pairs = vec![
+        named_pair("reference", "bank_reference", true, false),
+        named_pair("amount", "total", false, false),
+    ];
+
+    //act
+    let errors = row_identifier_errors(&comparison_pairs, "|");
+
+    //assert
+    assert!(errors.is_empty());
+}
+
+#[test]
+fn given_empty_separator_row_identifier_errors_reports_it() {
+    //setup
+    let comparison_pairs = vec![named_pair("reference", "bank_reference", true, false)];
+
+    //act
+    let errors = row_identifier_errors(&comparison_pairs, "");
+
+    //assert
+    assert_eq!(errors.len(), 1);
+}
+
+#[test]
+fn given_header_and_index_resolving_to_the_same_column_resolved_row_identifier_errors_reports_it() {
+    //setup
+    let comparison_pairs = vec![comparison_pair(0, 0, true), comparison_pair(1, 0, true)];
+
+    //act
+    let errors = resolved_row_identifier_errors(&comparison_pairs);
+
+    //assert
+    assert_eq!(errors.len(), 1);
+}
+
+fn comparison_pair(
+    primary_file_column_index: usize,
+    comparison_file_column_index: usize,
+    is_row_identifier: bool,
+) -> ComparisonPair {
+    ComparisonPair {
+        primary_file_column_index,
+        comparison_file_column_index,
+        is_row_identifier,
+    }
+}
+
+fn named_pair(
+    primary_header: &str,
+    comparison_header: &str,
+    is_row_identifier: bool,
+    ignore_header_case: bool,
+) -> NamedComparisonPair {
+    NamedComparisonPair {
+        primary_file_column: ColumnReference::Header(String::from(primary_header)),
+        comparison_file_column: ColumnReference::Header(String::from(comparison_header)),
+        is_row_identifier,
+        ignore_header_case,
+        row_key_position: None,
+        rules: None,
+    }
+}
+
+fn positioned(pair: NamedComparisonPair, row_key_position: usize) -> NamedComparisonPair {
+    NamedComparisonPair {
+        row_key_position: Some(row_key_position),
+        ..pair
+    }
+}
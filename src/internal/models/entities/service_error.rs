@@ -0,0 +1,61 @@
+use std::{fmt, ops::Deref};
+
+use serde::{ser::SerializeStruct, Serialize, Serializer};
+
+use crate::internal::{
+    models::entities::rejection_reason::RejectionReason,
+    shared_reconciler_rust_libraries::models::entities::app_errors::{AppError, AppErrorKind},
+};
+
+//what the service fails with, the shared AppError plus why the request was turned away when it was
+//it derefs to the AppError, so its kind and message read the same as before
+#[derive(Debug, Clone, PartialEq)]
+pub struct ServiceError {
+    pub error: AppError,
+    pub rejection: Option<RejectionReason>,
+}
+
+impl ServiceError {
+    pub fn new(kind: AppErrorKind, message: String) -> ServiceError {
+        ServiceError::from(AppError::new(kind, message))
+    }
+}
+
+impl From<AppError> for ServiceError {
+    fn from(error: AppError) -> Self {
+        ServiceError {
+            error,
+            rejection: None,
+        }
+    }
+}
+
+impl Deref for ServiceError {
+    type Target = AppError;
+
+    fn deref(&self) -> &AppError {
+        &self.error
+    }
+}
+
+impl fmt::Display for ServiceError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match &self.rejection {
+            Some(reason) => write!(f, "{:?}: {}", reason, self.error.message),
+            None => self.error.fmt(f),
+        }
+    }
+}
+
+//the same shape as an AppError, with the rejection reason in place of the kind when there is one
+impl Serialize for ServiceError {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut state = serializer.serialize_struct("ServiceError", 2)?;
+        match &self.rejection {
+            Some(reason) => state.serialize_field("kind", reason)?,
+            None => state.serialize_field("kind", &self.error.kind)?,
+        }
+        state.serialize_field("message", &self.error.message)?;
+        state.end()
+    }
+}
@@ -0,0 +1,135 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::internal::models::entities::versioned_record::{
+    wrap_legacy_record, SchemaUpgrade, VersionedSchema,
+};
+
+//the most chunks one upload can be split into, the same limit s3 puts on multipart uploads
+pub const MAX_UPLOAD_CHUNK_COUNT: u64 = 10_000;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum UploadedFileRole {
+    Primary,
+    Comparison,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum UploadSessionStatus {
+    Open,
+    Completed,
+}
+
+//a file being uploaded to a task in numbered chunks, kept in the state store so an upload survives a restart
+//chunk n holds bytes n * chunk_size_bytes onwards, every chunk is chunk_size_bytes long except the last
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct UploadSession {
+    pub id: String,
+
+    pub task_id: String,
+
+    pub file_role: UploadedFileRole,
+
+    pub file_name: String,
+
+    pub total_size_bytes: u64,
+
+    pub chunk_size_bytes: u64,
+
+    //in ascending order, each number at most once
+    pub received_chunks: Vec<u64>,
+
+    pub status: UploadSessionStatus,
+
+    //the file the upload was attached as once completed
+    pub file_id: Option<String>,
+
+    pub created_by: String,
+
+    pub created_at: DateTime<Utc>,
+
+    pub updated_at: DateTime<Utc>,
+}
+
+//inclusive on both ends, like an http content-range
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct ByteRange {
+    pub first_byte: u64,
+
+    pub last_byte: u64,
+}
+
+impl VersionedSchema for UploadSession {
+    fn schema_upgrades() -> Vec<SchemaUpgrade> {
+        vec![wrap_legacy_record]
+    }
+}
+
+pub fn upload_chunk_count(total_size_bytes: u64, chunk_size_bytes: u64) -> u64 {
+    if chunk_size_bytes == 0 {
+        return 0;
+    }
+    total_size_bytes.div_ceil(chunk_size_bytes)
+}
+
+impl UploadSession {
+    pub fn chunk_count(&self) -> u64 {
+        upload_chunk_count(self.total_size_bytes, self.chunk_size_bytes)
+    }
+
+    //none when the upload has no chunk with that number
+    pub fn expected_chunk_size(&self, chunk_number: u64) -> Option<u64> {
+        if chunk_number >= self.chunk_count() {
+            return None;
+        }
+
+        let chunk_start = chunk_number * self.chunk_size_bytes;
+        Some(self.chunk_size_bytes.min(self.total_size_bytes - chunk_start))
+    }
+
+    //receiving a chunk again replaces it, so it is only recorded once
+    pub fn record_chunk(&mut self, chunk_number: u64) {
+        if let Err(position) = self.received_chunks.binary_search(&chunk_number) {
+            self.received_chunks.insert(position, chunk_number);
+        }
+    }
+
+    pub fn missing_chunks(&self) -> Vec<u64> {
+        (0..self.chunk_count())
+            .filter(|chunk_number| self.received_chunks.binary_search(chunk_number).is_err())
+            .collect()
+    }
+
+    //runs of consecutive chunks merged into the bytes they cover
+    pub fn received_ranges(&self) -> Vec<ByteRange> {
+        let mut ranges: Vec<ByteRange> = vec![];
+
+        for chunk_number in &self.received_chunks {
+            let first_byte = chunk_number * self.chunk_size_bytes;
+            let last_byte = first_byte + self.expected_chunk_size(*chunk_number).unwrap_or(0) - 1;
+
+            match ranges.last_mut() {
+                Some(range) if range.last_byte + 1 == first_byte => range.last_byte = last_byte,
+                _ => ranges.push(ByteRange {
+                    first_byte,
+                    last_byte,
+                }),
+            }
+        }
+
+        ranges
+    }
+
+    //where the blob store keeps a chunk, the chunks in order are the content of the file
+    pub fn chunk_file_name(&self, chunk_number: u64) -> String {
+        format!("{}-chunk-{}", self.id, chunk_number)
+    }
+
+    pub fn chunk_file_names(&self) -> Vec<String> {
+        (0..self.chunk_count())
+            .map(|chunk_number| self.chunk_file_name(chunk_number))
+            .collect()
+    }
+}
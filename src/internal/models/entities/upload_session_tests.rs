@@ -0,0 +1,102 @@
+use chrono::Utc;
+
+use crate::internal::models::entities::upload_session::{
+    upload_chunk_count, ByteRange, UploadSession, UploadSessionStatus, UploadedFileRole,
+};
+
+#[test]
+fn given_size_not_divisible_by_chunk_size_the_last_chunk_holds_the_rest() {
+    //setup
+    let session = get_dummy_upload_session(25, 10);
+
+    //assert
+    assert_eq!(session.chunk_count(), 3);
+    assert_eq!(session.expected_chunk_size(0), Some(10));
+    assert_eq!(session.expected_chunk_size(2), Some(5));
+    assert_eq!(session.expected_chunk_size(3), None);
+}
+
+#[test]
+fn given_chunk_received_twice_record_chunk_keeps_it_once_in_order() {
+    //setup
+    let mut session = get_dummy_upload_session(50, 10);
+
+    //act
+    session.record_chunk(3);
+    session.record_chunk(0);
+    session.record_chunk(3);
+
+    //assert
+    assert_eq!(session.received_chunks, vec![0, 3]);
+    assert_eq!(session.missing_chunks(), vec![1, 2, 4]);
+}
+
+#[test]
+fn given_consecutive_chunks_received_ranges_merges_them() {
+    //setup
+    let mut session = get_dummy_upload_session(45, 10);
+    for chunk_number in [0, 1, 3, 4] {
+        session.record_chunk(chunk_number);
+    }
+
+    //act
+    let ranges = session.received_ranges();
+
+    //assert
+    assert_eq!(
+        ranges,
+        vec![
+            ByteRange {
+                first_byte: 0,
+                last_byte: 19,
+            },
+            ByteRange {
+                first_byte: 30,
+                last_byte: 44,
+            },
+        ]
+    );
+}
+
+#[test]
+fn given_zero_chunk_size_upload_chunk_count_is_zero() {
+    //assert
+    assert_eq!(upload_chunk_count(100, 0), 0);
+    assert_eq!(upload_chunk_count(100, 100), 1);
+}
+
+#[test]
+fn given_session_chunk_file_names_lists_every_chunk_in_order() {
+    //setup
+    let session = get_dummy_upload_session(25, 10);
+
+    //act
+    let file_names = session.chunk_file_names();
+
+    //assert
+    assert_eq!(
+        file_names,
+        vec![
+            "UPLOAD-SESSION-1234-chunk-0",
+            "UPLOAD-SESSION-1234-chunk-1",
+            "UPLOAD-SESSION-1234-chunk-2",
+        ]
+    );
+}
+
+fn get_dummy_upload_session(total_size_bytes: u64, chunk_size_bytes: u64) -> UploadSession {
+    UploadSession {
+        id: String::from("UPLOAD-SESSION-1234"),
+        task_id: String::from("RECON-TASK-1234"),
+        file_role: UploadedFileRole::Primary,
+        file_name: String::from("payments.csv"),
+        total_size_bytes,
+        chunk_size_bytes,
+        received_chunks: vec![],
+        status: UploadSessionStatus::Open,
+        file_id: None,
+        created_by: String::from("test-user-id"),
+        created_at: Utc::now(),
+        updated_at: Utc::now(),
+    }
+}
@@ -0,0 +1,95 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+//the envelope every record is stored in
+//records written before the envelope existed are read as schema_version 0
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct VersionedRecord<T> {
+    pub schema_version: u32,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub data: T,
+}
+
+//upgrades the raw json of a record from one schema version to the next
+//upgrades get the whole envelope so they can backfill fields from its timestamps
+pub type SchemaUpgrade = fn(VersionedRecord<Value>) -> Result<VersionedRecord<Value>, String>;
+
+pub trait VersionedSchema {
+    //schema_upgrades()[n] takes a record stored at version n to version n + 1
+    //so registering a new upgrade is all it takes to bump the schema version
+    fn schema_upgrades() -> Vec<SchemaUpgrade>;
+
+    fn current_schema_version() -> u32 {
+        Self::schema_upgrades().len() as u32
+    }
+}
+
+impl VersionedRecord<Value> {
+    //reads either an envelope or a pre-envelope record
+    pub fn from_stored_json(raw: Value) -> Result<VersionedRecord<Value>, String> {
+        let is_envelope = raw.get("schema_version").is_some() && raw.get("data").is_some();
+
+        if is_envelope {
+            return serde_json::from_value(raw).map_err(|e| e.to_string());
+        }
+
+        //legacy records carry no timestamps so the best we can do is the time we first see them
+        let now = Utc::now();
+        Ok(VersionedRecord {
+            schema_version: 0,
+            created_at: now,
+            updated_at: now,
+            data: raw,
+        })
+    }
+
+    //applies every registered upgrade from the stored version up to the current one
+    pub fn upgrade<T: VersionedSchema>(self) -> Result<VersionedRecord<Value>, String> {
+        let upgrades = T::schema_upgrades();
+
+        if self.schema_version > T::current_schema_version() {
+            return Err(format!(
+                "record has schema version {} but the latest known version is {}",
+                self.schema_version,
+                T::current_schema_version()
+            ));
+        }
+
+        let stored_schema_version = self.schema_version;
+        let mut record = self;
+        for upgrade in &upgrades[stored_schema_version as usize..] {
+            record = upgrade(record)?;
+        }
+
+        //callers still get to see which version the record was stored with
+        Ok(VersionedRecord {
+            schema_version: stored_schema_version,
+            ..record
+        })
+    }
+}
+
+//version 0 -> 1: the record was stored bare, its fields are unchanged inside the envelope
+pub fn wrap_legacy_record(record: VersionedRecord<Value>) -> Result<VersionedRecord<Value>, String> {
+    Ok(record)
+}
+
+//backfills created_at/updated_at on the record itself from the envelope timestamps
+pub fn copy_envelope_timestamps(
+    mut record: VersionedRecord<Value>,
+) -> Result<VersionedRecord<Value>, String> {
+    let created_at = serde_json::to_value(record.created_at).map_err(|e| e.to_string())?;
+    let updated_at = serde_json::to_value(record.updated_at).map_err(|e| e.to_string())?;
+
+    match record.data.as_object_mut() {
+        Some(fields) => {
+            fields.entry("created_at").or_insert(created_at);
+            fields.entry("updated_at").or_insert(updated_at);
+        }
+        None => return Err(String::from("record data is not a json object")),
+    }
+
+    Ok(record)
+}
@@ -0,0 +1,103 @@
+use serde_json::{json, Value};
+
+use super::versioned_record::{
+    copy_envelope_timestamps, SchemaUpgrade, VersionedRecord, VersionedSchema,
+};
+
+struct TestRecord {}
+
+impl VersionedSchema for TestRecord {
+    fn schema_upgrades() -> Vec<SchemaUpgrade> {
+        vec![keep_data, rename_name_to_full_name]
+    }
+}
+
+fn keep_data(record: VersionedRecord<Value>) -> Result<VersionedRecord<Value>, String> {
+    Ok(record)
+}
+
+fn rename_name_to_full_name(
+    mut record: VersionedRecord<Value>,
+) -> Result<VersionedRecord<Value>, String> {
+    let name = record.data["name"].take();
+    record.data["full_name"] = name;
+    Ok(record)
+}
+
+#[test]
+fn given_bare_legacy_record_reads_it_as_schema_version_zero() {
+    //setup
+    let raw = json!({"name": "task-1234"});
+
+    //act
+    let record = VersionedRecord::from_stored_json(raw.clone()).unwrap();
+
+    //assert
+    assert_eq!(record.schema_version, 0);
+    assert_eq!(record.data, raw);
+}
+
+#[test]
+fn given_legacy_record_upgrade_applies_every_registered_upgrade() {
+    //setup
+    let record = VersionedRecord::from_stored_json(json!({"name": "task-1234"})).unwrap();
+
+    //act
+    let upgraded = record.upgrade::<TestRecord>().unwrap();
+
+    //assert
+    assert_eq!(upgraded.data["full_name"], "task-1234");
+}
+
+#[test]
+fn given_enveloped_record_upgrade_only_applies_newer_upgrades() {
+    //setup
+    let raw = json!({
+        "schema_version": 2,
+        "created_at": "2022-10-01T00:00:00Z",
+        "updated_at": "2022-10-02T00:00:00Z",
+        "data": {"full_name": "task-1234"}
+    });
+
+    //act
+    let upgraded = VersionedRecord::from_stored_json(raw)
+        .unwrap()
+        .upgrade::<TestRecord>()
+        .unwrap();
+
+    //assert
+    assert_eq!(upgraded.schema_version, 2);
+    assert_eq!(upgraded.data, json!({"full_name": "task-1234"}));
+}
+
+#[test]
+fn given_legacy_record_copy_envelope_timestamps_backfills_them() {
+    //setup
+    let record = VersionedRecord::from_stored_json(json!({"name": "task-1234"})).unwrap();
+    let created_at = record.created_at;
+
+    //act
+    let upgraded = copy_envelope_timestamps(record).unwrap();
+
+    //assert
+    assert_eq!(upgraded.data["created_at"], serde_json::to_value(created_at).unwrap());
+}
+
+#[test]
+fn given_record_from_newer_schema_upgrade_returns_error() {
+    //setup
+    let raw = json!({
+        "schema_version": 3,
+        "created_at": "2022-10-01T00:00:00Z",
+        "updated_at": "2022-10-02T00:00:00Z",
+        "data": {}
+    });
+
+    //act
+    let result = VersionedRecord::from_stored_json(raw)
+        .unwrap()
+        .upgrade::<TestRecord>();
+
+    //assert
+    assert!(result.is_err());
+}
@@ -1,27 +1,81 @@
-use serde::{Deserialize, Serialize};
+use serde::{de::Error, Deserialize, Deserializer, Serialize};
 use validator::Validate;
 
-use crate::internal::shared_reconciler_rust_libraries::models::entities::recon_tasks_models::{
-    ComparisonPair, ReconciliationConfigs,
+use crate::internal::{
+    models::entities::{
+        column_schema::ColumnSchema, file_format::FileFormatDescriptor,
+        named_comparison_pair::NamedComparisonPair,
+        recon_results::{ReconChunkResults, ReconOutcomeFilter, ReconResultsFormat},
+        row_key::default_row_key_separator, upload_session::UploadedFileRole,
+    },
+    services::core_logic::id_generators::is_well_formed_id,
+    shared_reconciler_rust_libraries::models::entities::recon_tasks_models::ReconciliationConfigs,
 };
 
+//ids in paths and bodies are checked before they reach the store, so a malformed one is a 400 rather than a 404
+fn deserialize_id<'de, D: Deserializer<'de>>(deserializer: D) -> Result<String, D::Error> {
+    let id = String::deserialize(deserializer)?;
+    if !is_well_formed_id(&id) {
+        return Err(D::Error::custom(format!("[{}] is not a well formed id", id)));
+    }
+    Ok(id)
+}
+
+//the same check for an id a body may leave out
+fn deserialize_optional_id<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<String>, D::Error> {
+    let id = Option::<String>::deserialize(deserializer)?;
+    if let Some(id) = &id {
+        if !is_well_formed_id(id) {
+            return Err(D::Error::custom(format!("[{}] is not a well formed id", id)));
+        }
+    }
+    Ok(id)
+}
+
 #[derive(Serialize, Deserialize, Debug)]
 pub struct GetTaskDetailsRequest {
+    #[serde(deserialize_with = "deserialize_id")]
     pub task_id: String,
 }
 
+#[derive(Serialize, Deserialize, Debug)]
+pub struct GetTemplateDetailsRequest {
+    #[serde(deserialize_with = "deserialize_id")]
+    pub template_id: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Default)]
+pub struct GetTemplateVersionQuery {
+    //the latest version when absent
+    pub version: Option<u32>,
+}
+
 #[derive(Serialize, Deserialize, Validate, Debug)]
 pub struct CreateReconTaskRequest {
+    //always overwritten with the subject of the bearer token
+    #[serde(default)]
     #[validate(length(min = 1, message = "please supply a user_id"))]
     pub user_id: String,
 
-    pub recon_configurations: ReconciliationConfigs,
+    //either a template to take the settings from, or the settings themselves
+    #[serde(default, deserialize_with = "deserialize_optional_id")]
+    pub template_id: Option<String>,
+
+    #[serde(default)]
+    pub recon_configurations: Option<ReconciliationConfigs>,
+
+    //columns may be given by index or by header name, header names are resolved once the files are attached
+    #[serde(default)]
+    pub comparison_pairs: Vec<NamedComparisonPair>,
 
-    pub comparison_pairs: Vec<ComparisonPair>,
+    //"|" when left out, identifier columns are joined in the order of comparison_pairs
+    #[serde(default)]
+    pub row_key_separator: Option<String>,
 }
 
 #[derive(Serialize, Deserialize, Validate, Debug)]
 pub struct AttachPrimaryFileRequest {
+    #[serde(deserialize_with = "deserialize_id")]
     #[validate(length(min = 1, message = "please supply a task_id"))]
     pub task_id: String,
 
@@ -39,10 +93,19 @@ pub struct AttachPrimaryFileRequest {
 
     #[validate(length(min = 1, message = "please supply the primary_file_delimiters"))]
     pub primary_file_delimiters: Vec<char>,
+
+    //a utf-8 csv file with a header row when left out
+    #[serde(default)]
+    pub primary_file_format: FileFormatDescriptor,
+
+    //optional, one entry per header
+    #[serde(default)]
+    pub primary_file_column_schema: Vec<ColumnSchema>,
 }
 
 #[derive(Serialize, Deserialize, Validate, Debug)]
 pub struct AttachComparisonFileRequest {
+    #[serde(deserialize_with = "deserialize_id")]
     #[validate(length(min = 1, message = "please supply a task_id"))]
     pub task_id: String,
 
@@ -60,4 +123,124 @@ pub struct AttachComparisonFileRequest {
 
     #[validate(length(min = 1, message = "please supply the comparison_file_delimiters"))]
     pub comparison_file_delimiters: Vec<char>,
+
+    //a utf-8 csv file with a header row when left out
+    #[serde(default)]
+    pub comparison_file_format: FileFormatDescriptor,
+
+    //optional, one entry per header
+    #[serde(default)]
+    pub comparison_file_column_schema: Vec<ColumnSchema>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct UploadFilePath {
+    #[serde(deserialize_with = "deserialize_id")]
+    pub task_id: String,
+
+    pub file_role: UploadedFileRole,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct UploadSessionPath {
+    #[serde(deserialize_with = "deserialize_id")]
+    pub task_id: String,
+
+    #[serde(deserialize_with = "deserialize_id")]
+    pub upload_id: String,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct FilePreviewPath {
+    #[serde(deserialize_with = "deserialize_id")]
+    pub task_id: String,
+
+    #[serde(deserialize_with = "deserialize_id")]
+    pub file_id: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Default)]
+pub struct FilePreviewQuery {
+    //20 when absent
+    pub rows: Option<usize>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct UploadChunkPath {
+    #[serde(deserialize_with = "deserialize_id")]
+    pub task_id: String,
+
+    #[serde(deserialize_with = "deserialize_id")]
+    pub upload_id: String,
+
+    pub chunk_number: u64,
+}
+
+#[derive(Serialize, Deserialize, Validate, Debug, Clone)]
+pub struct CreateUploadSessionRequest {
+    #[validate(length(min = 1, message = "please supply a file_name"))]
+    pub file_name: String,
+
+    #[validate(range(min = 1, message = "please supply the total_size_bytes of the file"))]
+    pub total_size_bytes: u64,
+
+    #[validate(range(min = 1, message = "please supply a chunk_size_bytes"))]
+    pub chunk_size_bytes: u64,
+}
+
+#[derive(Serialize, Deserialize, Validate, Debug, Clone)]
+pub struct CompleteUploadRequest {
+    //lowercase or uppercase hex sha-256 of the whole file, checked against the chunks received
+    #[validate(length(min = 1, message = "please supply the file_hash"))]
+    pub file_hash: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ForceTaskStatusRequest {
+    pub has_begun: bool,
+
+    pub is_done: bool,
+}
+
+#[derive(Serialize, Deserialize, Validate, Debug, Clone)]
+pub struct CancelReconTaskRequest {
+    #[validate(length(min = 1, message = "please supply a reason for cancelling the task"))]
+    pub reason: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct CloneReconTaskRequest {
+    //file metadata is only carried over when asked for, since the files usually change every run
+    #[serde(default)]
+    pub copy_file_metadata: bool,
+}
+
+#[derive(Serialize, Deserialize, Validate, Debug, Clone)]
+pub struct ReconTemplateRequest {
+    #[validate(length(min = 1, message = "please supply a name"))]
+    pub name: String,
+
+    pub recon_configurations: ReconciliationConfigs,
+
+    #[validate(length(min = 1, message = "please supply at least one comparison pair"))]
+    pub comparison_pairs: Vec<NamedComparisonPair>,
+
+    #[serde(default = "default_row_key_separator")]
+    pub row_key_separator: String,
+}
+
+//the cloud event envelope the sidecar delivers pubsub messages in, only its data is read
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ReconResultsCloudEvent {
+    pub data: ReconChunkResults,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ReconResultsQuery {
+    //csv when absent
+    #[serde(default)]
+    pub format: ReconResultsFormat,
+
+    //every outcome when absent
+    pub status: Option<ReconOutcomeFilter>,
 }
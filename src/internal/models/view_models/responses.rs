@@ -0,0 +1,171 @@
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use crate::internal::{
+    models::entities::{
+        named_comparison_pair::{ColumnReference, NamedComparisonPair},
+        recon_file::ReconFile,
+        recon_task::ReconTask,
+        row_key::RowKeyDefinition,
+        upload_session::{ByteRange, UploadSession, UploadSessionStatus, UploadedFileRole},
+    },
+    shared_reconciler_rust_libraries::models::entities::recon_tasks_models::ComparisonPair,
+};
+
+//same shape as the shared ReconTaskResponseDetails, with this service's extra task and file fields
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ReconTaskResponseDetails {
+    pub task_id: String,
+
+    pub task_details: ReconTask,
+
+    pub primary_file_metadata: Option<ReconFile>,
+
+    pub comparison_file_metadata: Option<ReconFile>,
+
+    pub row_key: RowKeyDefinition,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct OrphanedFilesCleanupResponse {
+    pub deleted_file_ids: Vec<String>,
+}
+
+//a comparison pair proposed from the file headers, with the headers shown so it can be checked at a glance
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct SuggestedComparisonPair {
+    pub primary_file_column_index: usize,
+
+    pub primary_file_column_header: String,
+
+    pub comparison_file_column_index: usize,
+
+    pub comparison_file_column_header: String,
+
+    //1.0 when the headers are the same once case, whitespace and underscores are ignored
+    pub similarity_score: f64,
+
+    pub is_row_identifier: bool,
+}
+
+impl SuggestedComparisonPair {
+    pub fn to_comparison_pair(&self) -> ComparisonPair {
+        ComparisonPair {
+            primary_file_column_index: self.primary_file_column_index,
+            comparison_file_column_index: self.comparison_file_column_index,
+            is_row_identifier: self.is_row_identifier,
+        }
+    }
+
+    //named by header so the pair follows its columns if a file is replaced
+    pub fn to_named_comparison_pair(&self, row_key_position: Option<usize>) -> NamedComparisonPair {
+        NamedComparisonPair {
+            primary_file_column: ColumnReference::Header(self.primary_file_column_header.clone()),
+            comparison_file_column: ColumnReference::Header(self.comparison_file_column_header.clone()),
+            is_row_identifier: self.is_row_identifier,
+            ignore_header_case: false,
+            row_key_position,
+            rules: None,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct SuggestedComparisonPairsResponse {
+    pub task_id: String,
+
+    pub suggested_pairs: Vec<SuggestedComparisonPair>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct PreviewColumn {
+    pub header: String,
+
+    //highlighted, the column is compared by one of the task's comparison pairs
+    pub is_compared: bool,
+
+    pub is_row_identifier: bool,
+}
+
+//the first rows of an attached file, to check it is the right one before reconciling
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct FilePreviewResponse {
+    pub task_id: String,
+
+    pub file_id: String,
+
+    pub file_name: String,
+
+    //in file order, the rows are keyed by these headers
+    pub columns: Vec<PreviewColumn>,
+
+    pub rows: Vec<Map<String, Value>>,
+}
+
+//what a client needs to carry on with an upload: which chunks to send and which bytes are already here
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct UploadSessionResponse {
+    pub upload_id: String,
+
+    pub task_id: String,
+
+    pub file_role: UploadedFileRole,
+
+    pub file_name: String,
+
+    pub total_size_bytes: u64,
+
+    pub chunk_size_bytes: u64,
+
+    pub chunk_count: u64,
+
+    pub received_ranges: Vec<ByteRange>,
+
+    pub missing_chunks: Vec<u64>,
+
+    pub status: UploadSessionStatus,
+
+    pub file_id: Option<String>,
+}
+
+impl UploadSessionResponse {
+    pub fn new(session: &UploadSession) -> Self {
+        UploadSessionResponse {
+            upload_id: session.id.clone(),
+            task_id: session.task_id.clone(),
+            file_role: session.file_role,
+            file_name: session.file_name.clone(),
+            total_size_bytes: session.total_size_bytes,
+            chunk_size_bytes: session.chunk_size_bytes,
+            chunk_count: session.chunk_count(),
+            received_ranges: session.received_ranges(),
+            missing_chunks: session.missing_chunks(),
+            status: session.status,
+            file_id: session.file_id.clone(),
+        }
+    }
+}
+
+//one entry of the programmatic subscription the sidecar reads from /dapr/subscribe
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct DaprSubscription {
+    pub pubsubname: String,
+
+    pub topic: String,
+
+    pub route: String,
+}
+
+//tells the sidecar whether to acknowledge, redeliver or discard a message
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum DaprEventStatus {
+    Success,
+    Retry,
+    Drop,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct DaprEventResponse {
+    pub status: DaprEventStatus,
+}
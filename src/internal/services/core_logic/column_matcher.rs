@@ -0,0 +1,126 @@
+use crate::internal::models::view_models::responses::SuggestedComparisonPair;
+
+//header pairs scoring below this are too far apart to be worth suggesting
+pub const MIN_SIMILARITY_SCORE: f64 = 0.7;
+
+//the last word of a header that marks a column as identifying a row, e.g. "Transaction Id" or "bank_ref"
+const ROW_IDENTIFIER_WORDS: [&str; 8] = ["id", "uuid", "ref", "reference", "key", "code", "no", "number"];
+
+//pairs every primary file column with its closest comparison file column
+//the best scoring pairs are taken first so every column ends up in at most one suggestion
+pub fn suggest_comparison_pairs(
+    primary_file_headers: &[String],
+    comparison_file_headers: &[String],
+) -> Vec<SuggestedComparisonPair> {
+    let mut candidates: Vec<(usize, usize, f64)> = vec![];
+    for (primary_index, primary_header) in primary_file_headers.iter().enumerate() {
+        for (comparison_index, comparison_header) in comparison_file_headers.iter().enumerate() {
+            let score = similarity_score(primary_header, comparison_header);
+            if score >= MIN_SIMILARITY_SCORE {
+                candidates.push((primary_index, comparison_index, score));
+            }
+        }
+    }
+
+    //stable sort, so equally good candidates keep the order of the file columns
+    candidates.sort_by(|a, b| b.2.total_cmp(&a.2));
+
+    let mut used_primary_columns = vec![false; primary_file_headers.len()];
+    let mut used_comparison_columns = vec![false; comparison_file_headers.len()];
+    let mut suggestions = vec![];
+
+    for (primary_index, comparison_index, score) in candidates {
+        if used_primary_columns[primary_index] || used_comparison_columns[comparison_index] {
+            continue;
+        }
+        used_primary_columns[primary_index] = true;
+        used_comparison_columns[comparison_index] = true;
+
+        let primary_header = &primary_file_headers[primary_index];
+        let comparison_header = &comparison_file_headers[comparison_index];
+
+        suggestions.push(SuggestedComparisonPair {
+            primary_file_column_index: primary_index,
+            primary_file_column_header: primary_header.clone(),
+            comparison_file_column_index: comparison_index,
+            comparison_file_column_header: comparison_header.clone(),
+            similarity_score: score,
+            is_row_identifier: looks_like_row_identifier(primary_header)
+                && looks_like_row_identifier(comparison_header),
+        });
+    }
+
+    suggestions.sort_by_key(|suggestion| suggestion.primary_file_column_index);
+    suggestions
+}
+
+//case, whitespace, underscores and the like make no difference to what a column holds
+pub fn normalize_header(header: &str) -> String {
+    header
+        .chars()
+        .filter(|c| c.is_alphanumeric())
+        .flat_map(|c| c.to_lowercase())
+        .collect()
+}
+
+//1.0 for headers that are the same once normalized, going down to 0.0 as more edits are needed
+pub fn similarity_score(first_header: &str, second_header: &str) -> f64 {
+    let first: Vec<char> = normalize_header(first_header).chars().collect();
+    let second: Vec<char> = normalize_header(second_header).chars().collect();
+
+    let longest = first.len().max(second.len());
+    if longest == 0 {
+        return 0.0;
+    }
+
+    1.0 - (edit_distance(&first, &second) as f64 / longest as f64)
+}
+
+pub fn looks_like_row_identifier(header: &str) -> bool {
+    match header_words(header).last() {
+        Some(word) => ROW_IDENTIFIER_WORDS.contains(&word.as_str()),
+        None => false,
+    }
+}
+
+//splits on anything that is not a letter or digit and on camelCase boundaries
+fn header_words(header: &str) -> Vec<String> {
+    let mut words: Vec<String> = vec![];
+    let mut current_word = String::new();
+    let mut previous_was_lowercase = false;
+
+    for c in header.chars() {
+        let starts_new_word = !c.is_alphanumeric() || (c.is_uppercase() && previous_was_lowercase);
+        if starts_new_word && !current_word.is_empty() {
+            words.push(current_word.clone());
+            current_word.clear();
+        }
+        if c.is_alphanumeric() {
+            current_word.extend(c.to_lowercase());
+        }
+        previous_was_lowercase = c.is_lowercase();
+    }
+
+    if !current_word.is_empty() {
+        words.push(current_word);
+    }
+    words
+}
+
+//levenshtein distance, keeping only the previous row of the table
+fn edit_distance(first: &[char], second: &[char]) -> usize {
+    let mut previous_row: Vec<usize> = (0..=second.len()).collect();
+
+    for (i, first_char) in first.iter().enumerate() {
+        let mut current_row = vec![i + 1; second.len() + 1];
+        for (j, second_char) in second.iter().enumerate() {
+            let substitution_cost = if first_char == second_char { 0 } else { 1 };
+            current_row[j + 1] = (previous_row[j] + substitution_cost)
+                .min(previous_row[j + 1] + 1)
+                .min(current_row[j] + 1);
+        }
+        previous_row = current_row;
+    }
+
+    previous_row[second.len()]
+}
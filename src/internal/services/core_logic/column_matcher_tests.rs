@@ -0,0 +1,63 @@
+use crate::internal::services::core_logic::column_matcher::{
+    looks_like_row_identifier, normalize_header, similarity_score, suggest_comparison_pairs,
+};
+
+#[test]
+fn given_headers_differing_in_case_spaces_and_underscores_normalize_header_makes_them_equal() {
+    //setup
+    let headers = ["Transaction Id", "transaction_id", " TRANSACTION-ID "];
+
+    //act
+    let normalized: Vec<String> = headers.iter().map(|header| normalize_header(header)).collect();
+
+    //assert
+    assert!(normalized.iter().all(|header| header == "transactionid"));
+}
+
+#[test]
+fn given_similar_headers_similarity_score_is_between_the_threshold_and_one() {
+    //setup
+    let (first_header, second_header) = ("Amount", "amounts");
+
+    //act
+    let score = similarity_score(first_header, second_header);
+
+    //assert
+    assert!(score > 0.8 && score < 1.0);
+}
+
+#[test]
+fn given_headers_ending_in_identifier_words_looks_like_row_identifier_flags_them() {
+    //assert
+    assert!(looks_like_row_identifier("Transaction ID"));
+    assert!(looks_like_row_identifier("bank_ref"));
+    assert!(looks_like_row_identifier("customerId"));
+    assert!(!looks_like_row_identifier("paid"));
+    assert!(!looks_like_row_identifier("amount"));
+}
+
+#[test]
+fn given_two_files_suggest_comparison_pairs_matches_each_column_at_most_once() {
+    //setup
+    let primary_file_headers = headers(&["Transaction ID", "Amount", "Narration"]);
+    let comparison_file_headers = headers(&["total", "transaction_id", "amount", "value_date"]);
+
+    //act
+    let suggestions = suggest_comparison_pairs(&primary_file_headers, &comparison_file_headers);
+
+    //assert
+    assert_eq!(suggestions.len(), 2);
+
+    assert_eq!(suggestions[0].primary_file_column_index, 0);
+    assert_eq!(suggestions[0].comparison_file_column_index, 1);
+    assert_eq!(suggestions[0].similarity_score, 1.0);
+    assert!(suggestions[0].is_row_identifier);
+
+    assert_eq!(suggestions[1].primary_file_column_index, 1);
+    assert_eq!(suggestions[1].comparison_file_column_index, 2);
+    assert!(!suggestions[1].is_row_identifier);
+}
+
+fn headers(names: &[&str]) -> Vec<String> {
+    names.iter().map(|name| String::from(*name)).collect()
+}
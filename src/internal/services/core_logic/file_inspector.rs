@@ -0,0 +1,226 @@
+use sha2::{Digest, Sha256};
+
+//the delimiters tried when sniffing, earlier ones win when two fit equally well
+const CANDIDATE_DELIMITERS: [char; 4] = [',', ';', '\t', '|'];
+
+//the delimiter and header row are sniffed from the first lines only
+const SAMPLE_LINE_COUNT: usize = 20;
+
+//stops a file without line breaks from being held in memory just for sniffing
+const MAX_SAMPLE_BYTES: usize = 64 * 1024;
+
+const BYTE_ORDER_MARK: char = '\u{feff}';
+
+//what can be told about a file from its content alone
+#[derive(Debug, Clone, PartialEq)]
+pub struct FileInspection {
+    //lowercase hex sha-256 of the whole content
+    pub file_hash: String,
+
+    //every line holding something, the header row included
+    pub row_count: u64,
+
+    pub column_delimiters: Vec<char>,
+
+    //column_1, column_2, ... when the file has no header row
+    pub column_headers: Vec<String>,
+
+    pub has_header_row: bool,
+}
+
+//reads a file a chunk at a time, so it can sit next to whatever is streaming the file somewhere else
+#[derive(Default)]
+pub struct FileInspector {
+    hasher: Sha256,
+    row_count: u64,
+    current_line_has_content: bool,
+    sample: Vec<u8>,
+    sample_line_count: usize,
+}
+
+impl FileInspector {
+    pub fn new() -> Self {
+        FileInspector::default()
+    }
+
+    pub fn update(&mut self, chunk: &[u8]) {
+        self.hasher.update(chunk);
+
+        for byte in chunk {
+            match byte {
+                b'\n' => {
+                    if self.current_line_has_content {
+                        self.row_count += 1;
+                    }
+                    self.current_line_has_content = false;
+                }
+                b'\r' => (),
+                _ => self.current_line_has_content = true,
+            }
+        }
+
+        self.keep_sample(chunk);
+    }
+
+    //rows seen so far, the last one only counts once its line break has been read
+    pub fn row_count(&self) -> u64 {
+        self.row_count
+    }
+
+    pub fn finish(mut self) -> FileInspection {
+        //the last line does not have to end with a line break
+        if self.current_line_has_content {
+            self.row_count += 1;
+        }
+
+        let sample = String::from_utf8_lossy(&self.sample).to_string();
+        let sample_lines: Vec<&str> = sample
+            .lines()
+            .take(SAMPLE_LINE_COUNT)
+            .map(|line| line.trim_end_matches('\r'))
+            .filter(|line| !line.trim().is_empty())
+            .collect();
+
+        let delimiter = sniff_delimiter(&sample_lines);
+        let first_row = match sample_lines.first() {
+            Some(line) => split_fields(line.trim_start_matches(BYTE_ORDER_MARK), delimiter),
+            None => vec![],
+        };
+
+        let has_header_row = looks_like_header_row(&first_row);
+        let column_headers = match has_header_row {
+            true => first_row.iter().map(|field| field.trim().to_string()).collect(),
+            false => (1..=first_row.len())
+                .map(|column| format!("column_{}", column))
+                .collect(),
+        };
+
+        FileInspection {
+            file_hash: format!("{:x}", self.hasher.finalize()),
+            row_count: self.row_count,
+            column_delimiters: vec![delimiter],
+            column_headers,
+            has_header_row,
+        }
+    }
+
+    fn keep_sample(&mut self, chunk: &[u8]) {
+        for byte in chunk {
+            if self.sample_line_count >= SAMPLE_LINE_COUNT || self.sample.len() >= MAX_SAMPLE_BYTES {
+                return;
+            }
+            self.sample.push(*byte);
+            if *byte == b'\n' {
+                self.sample_line_count += 1;
+            }
+        }
+    }
+}
+
+//inspects a file that is already fully in memory
+#[cfg(test)]
+pub fn inspect_file(content: &[u8]) -> FileInspection {
+    let mut inspector = FileInspector::new();
+    inspector.update(content);
+    inspector.finish()
+}
+
+//the candidate splitting every sample line into the same, largest number of columns
+//falls back to the one splitting the first line the most when none is consistent, and to a comma when none is found
+pub fn sniff_delimiter(sample_lines: &[&str]) -> char {
+    let column_counts = |delimiter: char| -> Vec<usize> {
+        sample_lines
+            .iter()
+            .map(|line| split_fields(line, delimiter).len())
+            .collect()
+    };
+
+    let mut best_consistent: Option<(char, usize)> = None;
+    let mut best_first_line: Option<(char, usize)> = None;
+
+    for delimiter in CANDIDATE_DELIMITERS {
+        let counts = column_counts(delimiter);
+        let first_line_count = match counts.first() {
+            Some(count) if *count > 1 => *count,
+            _ => continue,
+        };
+
+        let is_consistent = counts.iter().all(|count| *count == first_line_count);
+        if is_consistent && splits_into_more_columns(first_line_count, best_consistent) {
+            best_consistent = Some((delimiter, first_line_count));
+        }
+        if splits_into_more_columns(first_line_count, best_first_line) {
+            best_first_line = Some((delimiter, first_line_count));
+        }
+    }
+
+    best_consistent
+        .or(best_first_line)
+        .map(|(delimiter, _)| delimiter)
+        .unwrap_or(CANDIDATE_DELIMITERS[0])
+}
+
+fn splits_into_more_columns(column_count: usize, best_so_far: Option<(char, usize)>) -> bool {
+    match best_so_far {
+        Some((_, best_column_count)) => column_count > best_column_count,
+        None => true,
+    }
+}
+
+//a header row names every column once and none of its names is a number
+pub fn looks_like_header_row(first_row: &[String]) -> bool {
+    if first_row.is_empty() {
+        return false;
+    }
+
+    let mut names: Vec<String> = first_row
+        .iter()
+        .map(|field| field.trim().to_lowercase())
+        .collect();
+
+    if names
+        .iter()
+        .any(|name| name.is_empty() || name.parse::<f64>().is_ok())
+    {
+        return false;
+    }
+
+    names.sort();
+    names.dedup();
+    names.len() == first_row.len()
+}
+
+//splits on the delimiter outside double quotes, a doubled quote inside quotes is a literal quote
+pub fn split_fields(line: &str, delimiter: char) -> Vec<String> {
+    split_quoted_fields(line, delimiter, '"')
+}
+
+pub fn split_quoted_fields(line: &str, delimiter: char, quote_character: char) -> Vec<String> {
+    let mut fields = vec![];
+    let mut current_field = String::new();
+    let mut in_quotes = false;
+    let mut chars = line.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            c if c == quote_character && in_quotes && chars.peek() == Some(&quote_character) => {
+                current_field.push(quote_character);
+                chars.next();
+            }
+            c if c == quote_character => in_quotes = !in_quotes,
+            c if c == delimiter && !in_quotes => {
+                fields.push(current_field.clone());
+                current_field.clear();
+            }
+            c => current_field.push(c),
+        }
+    }
+
+    fields.push(current_field);
+    fields
+}
+
+//lowercase hex sha-256, the same format as a file_hash
+pub fn content_hash(content: &[u8]) -> String {
+    format!("{:x}", Sha256::digest(content))
+}
@@ -0,0 +1,100 @@
+use crate::internal::services::core_logic::file_inspector::{
+    inspect_file, looks_like_header_row, sniff_delimiter, split_fields, FileInspector,
+};
+
+#[test]
+fn given_csv_with_header_row_inspect_file_reads_its_headers_delimiter_and_rows() {
+    //setup
+    let content = b"id,amount,currency\n1,10.50,UGX\n2,20.00,USD\n";
+
+    //act
+    let inspection = inspect_file(content);
+
+    //assert
+    assert_eq!(inspection.row_count, 3);
+    assert_eq!(inspection.column_delimiters, vec![',']);
+    assert_eq!(inspection.column_headers, vec!["id", "amount", "currency"]);
+    assert!(inspection.has_header_row);
+}
+
+#[test]
+fn given_content_inspect_file_hashes_it_with_sha256() {
+    //act
+    let inspection = inspect_file(b"abc");
+
+    //assert
+    assert_eq!(
+        inspection.file_hash,
+        "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+    );
+}
+
+#[test]
+fn given_file_fed_in_chunks_inspector_gives_the_same_result_as_reading_it_at_once() {
+    //setup
+    let content = b"id;name\r\n1;\"Doe; John\"\r\n\r\n2;Jane";
+    let mut inspector = FileInspector::new();
+
+    //act
+    for chunk in content.chunks(3) {
+        inspector.update(chunk);
+    }
+    let inspection = inspector.finish();
+
+    //assert
+    assert_eq!(inspection, inspect_file(content));
+    assert_eq!(inspection.row_count, 3);
+    assert_eq!(inspection.column_delimiters, vec![';']);
+}
+
+#[test]
+fn given_first_row_with_numbers_inspect_file_names_the_columns_itself() {
+    //setup
+    let content = b"1|10.50|UGX\n2|20.00|USD";
+
+    //act
+    let inspection = inspect_file(content);
+
+    //assert
+    assert!(!inspection.has_header_row);
+    assert_eq!(inspection.column_delimiters, vec!['|']);
+    assert_eq!(inspection.column_headers, vec!["column_1", "column_2", "column_3"]);
+}
+
+#[test]
+fn given_lines_split_evenly_by_one_delimiter_only_sniff_delimiter_picks_it() {
+    //setup
+    let lines = ["name\tnote", "a\tx, y and z", "b\tplain"];
+
+    //act
+    let delimiter = sniff_delimiter(&lines);
+
+    //assert
+    assert_eq!(delimiter, '\t');
+}
+
+#[test]
+fn given_single_column_lines_sniff_delimiter_falls_back_to_comma() {
+    //act
+    let delimiter = sniff_delimiter(&["id", "1", "2"]);
+
+    //assert
+    assert_eq!(delimiter, ',');
+}
+
+#[test]
+fn given_repeated_or_empty_names_looks_like_header_row_is_false() {
+    //assert
+    assert!(!looks_like_header_row(&[String::from("id"), String::from("ID")]));
+    assert!(!looks_like_header_row(&[String::from("id"), String::from("")]));
+    assert!(looks_like_header_row(&[String::from("id"), String::from("amount")]));
+}
+
+#[test]
+fn given_quoted_fields_split_fields_keeps_their_delimiters_and_escaped_quotes() {
+    //act
+    let fields = split_fields("1,\"Doe, John\",\"say \"\"hi\"\"\"", ',');
+
+    //assert
+    assert_eq!(fields, vec!["1", "Doe, John", "say \"hi\""]);
+}
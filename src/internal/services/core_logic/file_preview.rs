@@ -0,0 +1,95 @@
+use serde_json::{Map, Value};
+
+use crate::internal::{
+    models::entities::{file_format::FileLayout, recon_file::ReconFile},
+    services::core_logic::file_inspector::split_quoted_fields,
+};
+
+//how many lines of the file have to be read to preview the given number of rows
+pub fn preview_line_count(file: &ReconFile, max_rows: usize) -> usize {
+    leading_line_count(file) + max_rows
+}
+
+//the skipped rows and the header row, none of which is data
+fn leading_line_count(file: &ReconFile) -> usize {
+    let header_rows = match file.format.has_header_row {
+        true => 1,
+        false => 0,
+    };
+    file.format.rows_to_skip as usize + header_rows
+}
+
+pub fn complete_line_count(content: &[u8]) -> usize {
+    content.iter().filter(|byte| **byte == b'\n').count()
+}
+
+//the first rows of the file as json objects keyed by the file's headers
+//the content is read as utf-8 whatever the declared encoding, it is only for a look at the file
+pub fn preview_rows(content: &[u8], file: &ReconFile, max_rows: usize) -> Vec<Map<String, Value>> {
+    let content = String::from_utf8_lossy(content);
+    content
+        .lines()
+        .map(|line| line.trim_end_matches('\r').trim_start_matches('\u{feff}'))
+        .skip(leading_line_count(file))
+        .filter(|line| !line.trim().is_empty())
+        .take(max_rows)
+        .map(|line| preview_row(line, file))
+        .collect()
+}
+
+fn preview_row(line: &str, file: &ReconFile) -> Map<String, Value> {
+    let delimiter = file.metadata.column_delimiters.first().copied().unwrap_or(',');
+
+    let fields = match &file.format.layout {
+        FileLayout::Csv {
+            quote_character, ..
+        } => split_quoted_fields(line, delimiter, *quote_character),
+        FileLayout::Tsv => split_quoted_fields(line, '\t', '"'),
+        FileLayout::FixedWidth { column_offsets } => split_fixed_width(line, column_offsets),
+        FileLayout::JsonLines => {
+            //a line that is not a json object is shown as it is
+            if let Ok(Value::Object(row)) = serde_json::from_str::<Value>(line) {
+                return row;
+            }
+            let mut row = Map::new();
+            row.insert(String::from("value"), Value::String(line.to_string()));
+            return row;
+        }
+    };
+
+    keyed_by_header(fields, &file.metadata.column_headers)
+}
+
+fn split_fixed_width(line: &str, column_offsets: &[usize]) -> Vec<String> {
+    let chars: Vec<char> = line.chars().collect();
+
+    column_offsets
+        .iter()
+        .enumerate()
+        .map(|(column, start)| {
+            let end = column_offsets.get(column + 1).copied().unwrap_or(chars.len());
+            let start = (*start).min(chars.len());
+            let end = end.clamp(start, chars.len());
+            chars[start..end].iter().collect::<String>().trim().to_string()
+        })
+        .collect()
+}
+
+//a column missing from the row is null, a column past the headers is named by its position
+fn keyed_by_header(fields: Vec<String>, column_headers: &[String]) -> Map<String, Value> {
+    let mut row = Map::new();
+
+    for (column, header) in column_headers.iter().enumerate() {
+        let value = match fields.get(column) {
+            Some(field) => Value::String(field.clone()),
+            None => Value::Null,
+        };
+        row.insert(header.clone(), value);
+    }
+
+    for (column, field) in fields.iter().enumerate().skip(column_headers.len()) {
+        row.insert(format!("column_{}", column + 1), Value::String(field.clone()));
+    }
+
+    row
+}
@@ -0,0 +1,107 @@
+use chrono::Utc;
+use serde_json::{json, Value};
+
+use crate::internal::{
+    models::entities::{
+        file_format::{FileFormatDescriptor, FileLayout},
+        file_verification::FileVerification,
+        recon_file::ReconFile,
+    },
+    services::core_logic::file_preview::{complete_line_count, preview_line_count, preview_rows},
+    shared_reconciler_rust_libraries::models::entities::recon_tasks_models::{
+        ReconFileMetaData, ReconFileType,
+    },
+};
+
+#[test]
+fn given_csv_with_header_row_preview_rows_keys_the_first_rows_by_header() {
+    //setup
+    let file = get_dummy_recon_file(FileFormatDescriptor::default(), vec![';']);
+    let content = b"id;amount\r\n1;\"10;50\"\r\n\r\n2;20\r\n3;30\r\n";
+
+    //act
+    let rows = preview_rows(content, &file, 2);
+
+    //assert
+    assert_eq!(
+        rows.into_iter().map(Value::Object).collect::<Vec<Value>>(),
+        vec![
+            json!({ "id": "1", "amount": "10;50" }),
+            json!({ "id": "2", "amount": "20" }),
+        ]
+    );
+}
+
+#[test]
+fn given_rows_to_skip_and_short_rows_preview_rows_skips_them_and_fills_missing_columns_with_null() {
+    //setup
+    let format = FileFormatDescriptor {
+        has_header_row: false,
+        rows_to_skip: 1,
+        ..FileFormatDescriptor::default()
+    };
+    let file = get_dummy_recon_file(format, vec![',']);
+    let content = b"exported on 2022-01-01\n1\n2,20,extra\n";
+
+    //act
+    let rows = preview_rows(content, &file, 20);
+
+    //assert
+    assert_eq!(
+        rows.into_iter().map(Value::Object).collect::<Vec<Value>>(),
+        vec![
+            json!({ "id": "1", "amount": null }),
+            json!({ "id": "2", "amount": "20", "column_3": "extra" }),
+        ]
+    );
+}
+
+#[test]
+fn given_fixed_width_file_preview_rows_cuts_columns_at_their_offsets() {
+    //setup
+    let format = FileFormatDescriptor {
+        layout: FileLayout::FixedWidth {
+            column_offsets: vec![0, 4],
+        },
+        ..FileFormatDescriptor::default()
+    };
+    let file = get_dummy_recon_file(format, vec![]);
+    let content = b"id  amount\n1   10\n";
+
+    //act
+    let rows = preview_rows(content, &file, 20);
+
+    //assert
+    assert_eq!(Value::Object(rows[0].clone()), json!({ "id": "1", "amount": "10" }));
+}
+
+#[test]
+fn given_header_row_preview_line_count_includes_it() {
+    //setup
+    let file = get_dummy_recon_file(FileFormatDescriptor::default(), vec![',']);
+
+    //assert
+    assert_eq!(preview_line_count(&file, 20), 21);
+    assert_eq!(complete_line_count(b"id,amount\n1,10\n2,2"), 2);
+}
+
+fn get_dummy_recon_file(format: FileFormatDescriptor, column_delimiters: Vec<char>) -> ReconFile {
+    ReconFile {
+        metadata: ReconFileMetaData {
+            id: String::from("file-1234"),
+            file_name: String::from("payments.csv"),
+            row_count: 3,
+            column_delimiters,
+            recon_file_type: ReconFileType::PrimaryFile,
+            column_headers: vec![String::from("id"), String::from("amount")],
+            file_hash: String::from("hash"),
+        },
+        format,
+        column_schema: vec![],
+        stored_file_parts: vec![],
+        verification: FileVerification::default(),
+        created_by: String::from("test-user-id"),
+        created_at: Utc::now(),
+        updated_at: Utc::now(),
+    }
+}
@@ -0,0 +1,221 @@
+#[cfg(test)]
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use chrono::Utc;
+use uuid::Uuid;
+
+use crate::internal::{
+    interfaces::id_generator::IdGeneratorInterface,
+    shared_reconciler_rust_libraries::models::entities::app_errors::{AppError, AppErrorKind},
+};
+
+const UUID_LENGTH: usize = 36;
+const ULID_LENGTH: usize = 26;
+const MAX_ID_LENGTH: usize = 128;
+
+//crockford's base32, without I, L, O and U
+const ULID_ALPHABET: &[u8; 32] = b"0123456789ABCDEFGHJKMNPQRSTVWXYZ";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum IdGeneratorKind {
+    #[default]
+    UuidV4,
+    UuidV7,
+    Ulid,
+    //hands out predictable ids, so it can never be picked through ID_GENERATOR
+    #[cfg(test)]
+    Seeded(u64),
+}
+
+impl IdGeneratorKind {
+    //as set in the ID_GENERATOR env variable
+    pub fn parse(value: &str) -> Option<IdGeneratorKind> {
+        match value.trim().to_lowercase().as_str() {
+            "uuid_v4" => Some(IdGeneratorKind::UuidV4),
+            "uuid_v7" => Some(IdGeneratorKind::UuidV7),
+            "ulid" => Some(IdGeneratorKind::Ulid),
+            _ => None,
+        }
+    }
+
+    //a generator that is not known would otherwise quietly fall back to another one
+    pub fn from_setting(value: &str) -> Result<IdGeneratorKind, AppError> {
+        IdGeneratorKind::parse(value).ok_or_else(|| {
+            AppError::new(
+                AppErrorKind::InternalError,
+                format!(
+                    "id generator [{}] is not one of uuid_v4, uuid_v7 or ulid",
+                    value
+                ),
+            )
+        })
+    }
+}
+
+pub fn new_id_generator(kind: IdGeneratorKind) -> Box<dyn IdGeneratorInterface> {
+    match kind {
+        IdGeneratorKind::UuidV4 => Box::new(UuidV4IdGenerator {}),
+        IdGeneratorKind::UuidV7 => Box::new(UuidV7IdGenerator {}),
+        IdGeneratorKind::Ulid => Box::new(UlidIdGenerator {}),
+        #[cfg(test)]
+        IdGeneratorKind::Seeded(seed) => Box::new(SeededIdGenerator::new(seed)),
+    }
+}
+
+pub struct UuidV4IdGenerator {}
+
+impl IdGeneratorInterface for UuidV4IdGenerator {
+    fn generate_id(&self) -> String {
+        Uuid::new_v4().to_string()
+    }
+}
+
+//the first 48 bits are the unix time in milliseconds, so ids sort by when they were made
+pub struct UuidV7IdGenerator {}
+
+impl IdGeneratorInterface for UuidV7IdGenerator {
+    fn generate_id(&self) -> String {
+        Uuid::now_v7().to_string()
+    }
+}
+
+//48 bits of unix time in milliseconds then 80 random bits, in 26 characters of crockford's base32
+pub struct UlidIdGenerator {}
+
+impl IdGeneratorInterface for UlidIdGenerator {
+    fn generate_id(&self) -> String {
+        //only the bits of a v4 uuid that are not its version or variant
+        let random_high = Uuid::new_v4().as_u128() >> 80;
+        let random_low = Uuid::new_v4().as_u128() & 0xffff_ffff;
+        let value = ((unix_millis() as u128) << 80) | (random_high << 32) | random_low;
+
+        (0..ULID_LENGTH)
+            .map(|position| {
+                let index = (value >> (5 * (ULID_LENGTH - 1 - position))) & 0x1f;
+                ULID_ALPHABET[index as usize] as char
+            })
+            .collect()
+    }
+}
+
+//the same seed hands out the same ids in the same order, for tests
+#[cfg(test)]
+pub struct SeededIdGenerator {
+    seed: u64,
+    next: AtomicU64,
+}
+
+#[cfg(test)]
+impl SeededIdGenerator {
+    pub fn new(seed: u64) -> Self {
+        SeededIdGenerator {
+            seed,
+            next: AtomicU64::new(0),
+        }
+    }
+}
+
+#[cfg(test)]
+impl IdGeneratorInterface for SeededIdGenerator {
+    fn generate_id(&self) -> String {
+        let sequence_number = self.next.fetch_add(1, Ordering::Relaxed);
+        Uuid::from_u128(((self.seed as u128) << 64) | sequence_number as u128).to_string()
+    }
+}
+
+fn unix_millis() -> u64 {
+    Utc::now().timestamp_millis().max(0) as u64
+}
+
+//what each kind of id starts with
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IdPrefixes {
+    pub recon_task: String,
+    pub recon_file: String,
+    pub recon_template: String,
+    pub upload_session: String,
+    pub primary_file_queue: String,
+    pub comparison_file_queue: String,
+    pub recon_results_queue: String,
+}
+
+impl Default for IdPrefixes {
+    fn default() -> Self {
+        IdPrefixes {
+            recon_task: String::from("RECON-TASK"),
+            recon_file: String::from("RECON-FILE"),
+            recon_template: String::from("RECON-TEMPLATE"),
+            upload_session: String::from("UPLOAD-SESSION"),
+            primary_file_queue: String::from("PRIMARY-FILE-QUEUE"),
+            comparison_file_queue: String::from("COMPARISON-FILE-QUEUE"),
+            recon_results_queue: String::from("RECON-RESULTS-QUEUE"),
+        }
+    }
+}
+
+impl IdPrefixes {
+    //a prefix that fails the check would make every id it starts fail validation on the way back in
+    pub fn ensure_well_formed(&self) -> Result<(), AppError> {
+        let prefixes = [
+            &self.recon_task,
+            &self.recon_file,
+            &self.recon_template,
+            &self.upload_session,
+            &self.primary_file_queue,
+            &self.comparison_file_queue,
+            &self.recon_results_queue,
+        ];
+
+        for prefix in prefixes {
+            if !is_well_formed_prefix(prefix) {
+                return Err(AppError::new(
+                    AppErrorKind::InternalError,
+                    format!(
+                        "id prefix [{}] can only hold letters and digits separated by single dashes",
+                        prefix
+                    ),
+                ));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+//a prefix followed by a dash and either a uuid or a ulid, whichever generator made it
+pub fn is_well_formed_id(id: &str) -> bool {
+    if id.len() > MAX_ID_LENGTH || !id.is_ascii() {
+        return false;
+    }
+
+    has_prefix_before(id, UUID_LENGTH, is_uuid) || has_prefix_before(id, ULID_LENGTH, is_ulid)
+}
+
+fn has_prefix_before(id: &str, suffix_length: usize, is_suffix: fn(&str) -> bool) -> bool {
+    if id.len() <= suffix_length {
+        return false;
+    }
+
+    let (prefix, suffix) = id.split_at(id.len() - suffix_length);
+    match prefix.strip_suffix('-') {
+        Some(prefix) => is_well_formed_prefix(prefix) && is_suffix(suffix),
+        None => false,
+    }
+}
+
+fn is_well_formed_prefix(prefix: &str) -> bool {
+    prefix
+        .split('-')
+        .all(|part| !part.is_empty() && part.chars().all(|c| c.is_ascii_alphanumeric()))
+}
+
+//only the hyphenated form is as long as UUID_LENGTH
+fn is_uuid(suffix: &str) -> bool {
+    Uuid::parse_str(suffix).is_ok()
+}
+
+//the first character holds only 3 bits of the 128, so it can be no larger than 7
+fn is_ulid(suffix: &str) -> bool {
+    let suffix = suffix.to_ascii_uppercase();
+    suffix.bytes().all(|c| ULID_ALPHABET.contains(&c)) && suffix.as_bytes()[0] <= b'7'
+}
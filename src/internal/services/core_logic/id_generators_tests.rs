@@ -0,0 +1,134 @@
+use crate::internal::{
+    interfaces::id_generator::IdGeneratorInterface,
+    services::core_logic::id_generators::{
+        is_well_formed_id, new_id_generator, IdGeneratorKind, IdPrefixes, SeededIdGenerator,
+        UlidIdGenerator, UuidV7IdGenerator,
+    },
+    shared_reconciler_rust_libraries::models::entities::app_errors::AppErrorKind,
+};
+
+#[test]
+fn given_the_same_seed_seeded_generators_hand_out_the_same_ids_in_the_same_order() {
+    //setup
+    let first_generator = SeededIdGenerator::new(42);
+    let second_generator = SeededIdGenerator::new(42);
+
+    //act
+    let first_ids: Vec<String> = (0..3).map(|_| first_generator.generate_id()).collect();
+    let second_ids: Vec<String> = (0..3).map(|_| second_generator.generate_id()).collect();
+
+    //assert
+    assert_eq!(first_ids, second_ids);
+    assert_ne!(first_ids[0], first_ids[1]);
+    assert_eq!(first_ids[0], "00000000-0000-002a-0000-000000000000");
+}
+
+#[test]
+fn given_ulid_generator_generate_id_returns_26_characters_of_crockfords_base32() {
+    //setup
+    let generator = UlidIdGenerator {};
+
+    //act
+    let id = generator.generate_id();
+
+    //assert
+    assert_eq!(id.len(), 26);
+    assert!(is_well_formed_id(&format!("RECON-TASK-{}", id)));
+}
+
+#[test]
+fn given_uuid_v7_generator_generate_id_returns_version_7_ids_that_sort_by_time() {
+    //setup
+    let generator = UuidV7IdGenerator {};
+    let earlier_id = generator.generate_id();
+    std::thread::sleep(std::time::Duration::from_millis(2));
+
+    //act
+    let later_id = generator.generate_id();
+
+    //assert
+    assert_eq!(later_id.chars().nth(14), Some('7'));
+    assert!(earlier_id < later_id);
+}
+
+#[test]
+fn given_ids_made_by_each_generator_is_well_formed_id_accepts_them() {
+    //setup
+    let kinds = [
+        IdGeneratorKind::UuidV4,
+        IdGeneratorKind::UuidV7,
+        IdGeneratorKind::Ulid,
+        IdGeneratorKind::Seeded(7),
+    ];
+
+    for kind in kinds {
+        //act
+        let id = format!("UPLOAD-SESSION-{}", new_id_generator(kind).generate_id());
+
+        //assert
+        assert!(is_well_formed_id(&id), "{:?} made [{}]", kind, id);
+    }
+}
+
+#[test]
+fn given_malformed_ids_is_well_formed_id_rejects_them() {
+    //setup
+    let too_long_id = format!(
+        "{}-123e4567-e89b-12d3-a456-426614174000",
+        "RECON".repeat(20)
+    );
+    let ids = [
+        "task-1234",
+        "RECON-TASK-unknown",
+        "123e4567-e89b-12d3-a456-426614174000",
+        "RECON--TASK-123e4567-e89b-12d3-a456-426614174000",
+        "RECON TASK-123e4567-e89b-12d3-a456-426614174000",
+        "RECON-TASK-81ARZ3NDEKTSV4RRFFQ69G5FAV",
+        too_long_id.as_str(),
+    ];
+
+    for id in ids {
+        //act
+        let is_well_formed = is_well_formed_id(id);
+
+        //assert
+        assert!(!is_well_formed, "[{}] was accepted", id);
+    }
+}
+
+#[test]
+fn given_a_prefix_with_a_space_ensure_well_formed_returns_error() {
+    //setup
+    let prefixes = IdPrefixes {
+        recon_task: String::from("RECON TASK"),
+        ..IdPrefixes::default()
+    };
+
+    //act
+    let result = prefixes.ensure_well_formed();
+
+    //assert
+    assert!(result.is_err());
+    assert!(IdPrefixes::default().ensure_well_formed().is_ok());
+}
+
+#[test]
+fn given_generator_names_parse_returns_their_kind() {
+    //assert
+    assert_eq!(IdGeneratorKind::parse("uuid_v4"), Some(IdGeneratorKind::UuidV4));
+    assert_eq!(IdGeneratorKind::parse("UUID_V7"), Some(IdGeneratorKind::UuidV7));
+    assert_eq!(IdGeneratorKind::parse(" ulid "), Some(IdGeneratorKind::Ulid));
+    assert_eq!(IdGeneratorKind::parse("seeded"), None);
+    assert_eq!(IdGeneratorKind::parse("snowflake"), None);
+}
+
+#[test]
+fn given_unknown_or_seeded_generator_from_setting_returns_internal_error() {
+    for value in ["snowflake", "seeded", ""] {
+        //act
+        let result = IdGeneratorKind::from_setting(value);
+
+        //assert
+        assert!(matches!(result.err().unwrap().kind, AppErrorKind::InternalError), "[{}] was accepted", value);
+    }
+}
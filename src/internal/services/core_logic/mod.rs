@@ -1,5 +1,30 @@
+pub mod column_matcher;
+pub mod file_inspector;
+pub mod file_preview;
+pub mod id_generators;
+pub mod results_export;
 pub mod transfomer;
 
+#[cfg(test)]
+#[path = "./column_matcher_tests.rs"]
+mod column_matcher_tests;
+
+#[cfg(test)]
+#[path = "./file_inspector_tests.rs"]
+mod file_inspector_tests;
+
+#[cfg(test)]
+#[path = "./file_preview_tests.rs"]
+mod file_preview_tests;
+
+#[cfg(test)]
+#[path = "./id_generators_tests.rs"]
+mod id_generators_tests;
+
+#[cfg(test)]
+#[path = "./results_export_tests.rs"]
+mod results_export_tests;
+
 #[cfg(test)]
 #[path = "./transfomer_tests.rs"]
 mod transfomer_tests;
@@ -0,0 +1,172 @@
+use serde_json::{json, Map, Value};
+
+use crate::internal::{
+    models::entities::{
+        recon_results::{
+            ReconOutcomeFilter, ReconResultRow, ReconResultsExport, ReconResultsFormat,
+        },
+        upload_session::UploadedFileRole,
+    },
+    shared_reconciler_rust_libraries::models::entities::app_errors::{AppError, AppErrorKind},
+};
+
+//every exported csv row starts with these, followed by the headers of both files
+const LEADING_CSV_COLUMNS: [&str; 3] = ["outcome", "file_role", "row_key"];
+const MISMATCHED_COLUMNS_CSV_COLUMN: &str = "mismatched_columns";
+
+//turns the results files of a task into export lines, one results file at a time
+#[derive(Debug, Clone)]
+pub struct ResultsExportWriter {
+    format: ReconResultsFormat,
+
+    outcome_filter: Option<ReconOutcomeFilter>,
+
+    primary_file_headers: Vec<String>,
+
+    comparison_file_headers: Vec<String>,
+
+    //the headers of both files in file order, a header the files share is one column
+    value_columns: Vec<String>,
+}
+
+impl ResultsExportWriter {
+    pub fn new(
+        format: ReconResultsFormat,
+        outcome_filter: Option<ReconOutcomeFilter>,
+        export: &ReconResultsExport,
+    ) -> Self {
+        let mut value_columns = export.primary_file_headers.clone();
+        for header in &export.comparison_file_headers {
+            if !value_columns.contains(header) {
+                value_columns.push(header.clone());
+            }
+        }
+
+        ResultsExportWriter {
+            format,
+            outcome_filter,
+            primary_file_headers: export.primary_file_headers.clone(),
+            comparison_file_headers: export.comparison_file_headers.clone(),
+            value_columns,
+        }
+    }
+
+    pub fn content_type(&self) -> &'static str {
+        match self.format {
+            ReconResultsFormat::Csv => "text/csv; charset=utf-8",
+            ReconResultsFormat::Jsonl => "application/x-ndjson",
+        }
+    }
+
+    //json lines have no header line
+    pub fn header(&self) -> Option<String> {
+        if self.format != ReconResultsFormat::Csv {
+            return None;
+        }
+
+        let columns: Vec<String> = LEADING_CSV_COLUMNS
+            .iter()
+            .map(|column| column.to_string())
+            .chain(self.value_columns.iter().cloned())
+            .chain([MISMATCHED_COLUMNS_CSV_COLUMN.to_string()])
+            .map(|column| csv_field(&column))
+            .collect();
+        Some(format!("{}\n", columns.join(",")))
+    }
+
+    //the rows of one results file that pass the outcome filter
+    pub fn write_rows(&self, results_file: &[u8]) -> Result<String, AppError> {
+        let content = std::str::from_utf8(results_file).map_err(|e| {
+            AppError::new(
+                AppErrorKind::InternalError,
+                format!("results file is not valid utf-8: {}", e),
+            )
+        })?;
+
+        let mut lines = String::new();
+        for line in content.lines().filter(|line| !line.trim().is_empty()) {
+            let row: ReconResultRow = serde_json::from_str(line).map_err(|e| {
+                AppError::new(
+                    AppErrorKind::InternalError,
+                    format!("results file has a row that can not be read: {}", e),
+                )
+            })?;
+
+            let is_included = self
+                .outcome_filter
+                .is_none_or(|outcome_filter| outcome_filter.includes(row.outcome));
+            if !is_included {
+                continue;
+            }
+
+            let values_by_header = self.values_by_header(&row);
+            let line = match self.format {
+                ReconResultsFormat::Csv => self.csv_line(&row, &values_by_header),
+                ReconResultsFormat::Jsonl => jsonl_line(&row, values_by_header),
+            };
+            lines.push_str(&line);
+            lines.push('\n');
+        }
+
+        Ok(lines)
+    }
+
+    //the values of a row keyed by the headers of the file it was read from
+    fn values_by_header(&self, row: &ReconResultRow) -> Map<String, Value> {
+        let headers = match row.file_role {
+            UploadedFileRole::Primary => &self.primary_file_headers,
+            UploadedFileRole::Comparison => &self.comparison_file_headers,
+        };
+
+        headers
+            .iter()
+            .zip(row.values.iter())
+            .map(|(header, value)| (header.clone(), Value::String(value.clone())))
+            .collect()
+    }
+
+    fn csv_line(&self, row: &ReconResultRow, values_by_header: &Map<String, Value>) -> String {
+        let values = self.value_columns.iter().map(|column| {
+            values_by_header
+                .get(column)
+                .and_then(|value| value.as_str())
+                .unwrap_or_default()
+                .to_string()
+        });
+
+        let fields: Vec<String> = [
+            snake_case_name(&json!(row.outcome)),
+            snake_case_name(&json!(row.file_role)),
+            row.row_key.clone(),
+        ]
+        .into_iter()
+        .chain(values)
+        .chain([row.mismatched_columns.join(";")])
+        .map(|field| csv_field(&field))
+        .collect();
+        fields.join(",")
+    }
+}
+
+fn jsonl_line(row: &ReconResultRow, values_by_header: Map<String, Value>) -> String {
+    json!({
+        "outcome": row.outcome,
+        "file_role": row.file_role,
+        "row_key": row.row_key,
+        "values": values_by_header,
+        "mismatched_columns": row.mismatched_columns,
+    })
+    .to_string()
+}
+
+fn snake_case_name(value: &Value) -> String {
+    value.as_str().unwrap_or_default().to_string()
+}
+
+//quoted only when it has to be, with any quotes inside doubled
+fn csv_field(value: &str) -> String {
+    if !value.contains([',', '"', '\n', '\r']) {
+        return value.to_string();
+    }
+    format!("\"{}\"", value.replace('"', "\"\""))
+}
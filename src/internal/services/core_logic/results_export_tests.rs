@@ -0,0 +1,108 @@
+use serde_json::{json, Value};
+
+use crate::internal::{
+    models::entities::recon_results::{
+        ReconOutcomeFilter, ReconResultsExport, ReconResultsFormat,
+    },
+    services::core_logic::results_export::ResultsExportWriter,
+};
+
+#[test]
+fn given_csv_format_header_lists_the_headers_of_both_files_once() {
+    //setup
+    let writer = ResultsExportWriter::new(ReconResultsFormat::Csv, None, &get_dummy_export());
+
+    //act
+    let header = writer.header();
+
+    //assert
+    assert_eq!(
+        header,
+        Some(String::from(
+            "outcome,file_role,row_key,id,amount,currency,mismatched_columns\n"
+        ))
+    );
+}
+
+#[test]
+fn given_unmatched_filter_write_rows_writes_only_unmatched_rows_under_their_file_headers() {
+    //setup
+    let writer = ResultsExportWriter::new(
+        ReconResultsFormat::Csv,
+        Some(ReconOutcomeFilter::Unmatched),
+        &get_dummy_export(),
+    );
+
+    //act
+    let lines = writer.write_rows(get_dummy_results_file().as_bytes()).unwrap();
+
+    //assert
+    assert_eq!(
+        lines,
+        "unmatched_in_primary,primary,2,2,\"1,000\",,\n\
+         unmatched_in_comparison,comparison,3,3,,UGX,\n"
+    );
+}
+
+#[test]
+fn given_jsonl_format_write_rows_keys_values_by_the_headers_of_their_file() {
+    //setup
+    let writer = ResultsExportWriter::new(
+        ReconResultsFormat::Jsonl,
+        Some(ReconOutcomeFilter::FieldMismatch),
+        &get_dummy_export(),
+    );
+
+    //act
+    let lines = writer.write_rows(get_dummy_results_file().as_bytes()).unwrap();
+
+    //assert
+    let rows: Vec<Value> = lines
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect();
+    assert_eq!(writer.header(), None);
+    assert_eq!(
+        rows,
+        vec![json!({
+            "outcome": "field_mismatch",
+            "file_role": "primary",
+            "row_key": "1",
+            "values": { "id": "1", "amount": "10" },
+            "mismatched_columns": ["amount"]
+        })]
+    );
+}
+
+#[test]
+fn given_row_that_is_not_a_result_write_rows_returns_error() {
+    //setup
+    let writer = ResultsExportWriter::new(ReconResultsFormat::Csv, None, &get_dummy_export());
+
+    //act
+    let result = writer.write_rows(b"{\"outcome\": \"unknown\"}\n");
+
+    //assert
+    assert!(result.is_err());
+}
+
+fn get_dummy_export() -> ReconResultsExport {
+    ReconResultsExport {
+        task_id: String::from("task-1234"),
+        primary_file_headers: vec![String::from("id"), String::from("amount")],
+        comparison_file_headers: vec![String::from("id"), String::from("currency")],
+        results_file_names: vec![String::from("task-1234-results-1")],
+    }
+}
+
+fn get_dummy_results_file() -> String {
+    [
+        json!({ "outcome": "field_mismatch", "file_role": "primary", "row_key": "1", "values": ["1", "10"], "mismatched_columns": ["amount"] }),
+        json!({ "outcome": "unmatched_in_primary", "file_role": "primary", "row_key": "2", "values": ["2", "1,000"] }),
+        json!({ "outcome": "unmatched_in_comparison", "file_role": "comparison", "row_key": "3", "values": ["3", "UGX"] }),
+        json!({ "outcome": "matched", "file_role": "primary", "row_key": "4", "values": ["4", "40"] }),
+    ]
+    .iter()
+    .map(|row| format!("{}\n", row))
+    .collect()
+}
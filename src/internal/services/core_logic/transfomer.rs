@@ -1,45 +1,64 @@
-use uuid::Uuid;
+use chrono::Utc;
 
 use crate::internal::{
-    interfaces::transformer::TransformerInterface,
-    models::view_models::requests::{
-        AttachComparisonFileRequest, AttachPrimaryFileRequest, CreateReconTaskRequest,
-    },
-    shared_reconciler_rust_libraries::models::{
+    interfaces::{id_generator::IdGeneratorInterface, transformer::TransformerInterface},
+    models::{
         entities::{
-            file_chunk_queue::FileChunkQueue,
-            recon_tasks_models::{ReconFileMetaData, ReconFileType, ReconTaskDetails},
+            column_schema::ColumnSchema, file_format::FileFormatDescriptor,
+            file_verification::FileVerification,
+            named_comparison_pair::NamedComparisonPair, recon_file::ReconFile,
+            recon_results::ReconResultsSummary,
+            recon_task::{ReconTask, ReconTaskStatus}, recon_template::ReconTemplate,
+            row_key::default_row_key_separator,
+            upload_session::{UploadSession, UploadSessionStatus, UploadedFileRole},
+        },
+        view_models::{
+            requests::{
+                AttachComparisonFileRequest, AttachPrimaryFileRequest, CreateUploadSessionRequest,
+                ReconTemplateRequest,
+            },
+            responses::ReconTaskResponseDetails,
+        },
+    },
+    services::core_logic::id_generators::IdPrefixes,
+    shared_reconciler_rust_libraries::models::entities::{
+        file_chunk_queue::FileChunkQueue,
+        recon_tasks_models::{
+            ReconciliationConfigs, ReconFileMetaData, ReconFileType, ReconTaskDetails,
         },
-        view_models::recon_task_response_details::ReconTaskResponseDetails,
     },
 };
 
-const RECON_FILE_STORE_PREFIX: &'static str = "RECON-FILE";
-const RECON_TASKS_STORE_PREFIX: &'static str = "RECON-TASK";
-const PRIMARY_FILE_QUEUE_PREFIX: &'static str = "PRIMARY-FILE-QUEUE";
-const COMPARISON_FILE_QUEUE_PREFIX: &'static str = "COMPARISON-FILE-QUEUE";
-const RECON_RESULTS_QUEUE_PREFIX: &'static str = "RECON-RESULTS-QUEUE";
+//ids are the prefix of what they identify followed by whatever the id generator hands out
+pub struct Transformer {
+    pub id_generator: Box<dyn IdGeneratorInterface>,
 
-pub struct Transformer {}
+    pub id_prefixes: IdPrefixes,
+}
 
 impl TransformerInterface for Transformer {
     fn build_recon_task_details_response(
         &self,
-        task_details: ReconTaskDetails,
-        primary_file_metadata: Option<ReconFileMetaData>,
-        comparison_file_metadata: Option<ReconFileMetaData>,
+        task_details: ReconTask,
+        primary_file_metadata: Option<ReconFile>,
+        comparison_file_metadata: Option<ReconFile>,
     ) -> ReconTaskResponseDetails {
-        return ReconTaskResponseDetails {
-            task_id: task_details.id.clone(),
+        ReconTaskResponseDetails {
+            task_id: task_details.details.id.clone(),
+            row_key: task_details.row_key_definition(),
             task_details,
             primary_file_metadata,
             comparison_file_metadata,
-        };
+        }
     }
 
-    fn get_primary_file_details(&self, request: &AttachPrimaryFileRequest) -> ReconFileMetaData {
-        return ReconFileMetaData {
-            id: self.generate_uuid(RECON_FILE_STORE_PREFIX),
+    fn get_primary_file_details(
+        &self,
+        request: &AttachPrimaryFileRequest,
+        created_by: &str,
+    ) -> ReconFile {
+        let metadata = ReconFileMetaData {
+            id: self.generate_id(&self.id_prefixes.recon_file),
             file_name: request.primary_file_name.clone(),
             row_count: request.primary_file_row_count,
             recon_file_type: ReconFileType::PrimaryFile,
@@ -47,14 +66,21 @@ impl TransformerInterface for Transformer {
             column_delimiters: request.primary_file_delimiters.clone(),
             column_headers: request.primary_file_headers.clone(),
         };
+        self.new_recon_file(
+            metadata,
+            &request.primary_file_format,
+            &request.primary_file_column_schema,
+            created_by,
+        )
     }
 
     fn get_comparison_file_details(
         &self,
         request: &AttachComparisonFileRequest,
-    ) -> ReconFileMetaData {
-        return ReconFileMetaData {
-            id: self.generate_uuid(RECON_FILE_STORE_PREFIX),
+        created_by: &str,
+    ) -> ReconFile {
+        let metadata = ReconFileMetaData {
+            id: self.generate_id(&self.id_prefixes.recon_file),
             file_name: request.comparison_file_name.clone(),
             row_count: request.comparison_file_row_count,
             recon_file_type: ReconFileType::ComparisonFile,
@@ -62,34 +88,156 @@ impl TransformerInterface for Transformer {
             column_delimiters: request.comparison_file_delimiters.clone(),
             column_headers: request.comparison_file_headers.clone(),
         };
+        self.new_recon_file(
+            metadata,
+            &request.comparison_file_format,
+            &request.comparison_file_column_schema,
+            created_by,
+        )
     }
 
-    fn get_recon_task_details(&self, request: &CreateReconTaskRequest) -> ReconTaskDetails {
-        let task_id = self.generate_uuid(RECON_TASKS_STORE_PREFIX);
-        return ReconTaskDetails {
+    fn get_recon_task_details(
+        &self,
+        created_by: &str,
+        recon_configurations: &ReconciliationConfigs,
+        comparison_pairs: &[NamedComparisonPair],
+    ) -> ReconTask {
+        let task_id = self.generate_id(&self.id_prefixes.recon_task);
+
+        //pairs that name columns by header have to wait until the files are attached
+        let mut resolved_pairs = vec![];
+        let mut pending_pairs = vec![];
+        let mut comparison_pair_rules = vec![];
+        let mut row_key_positions = vec![];
+        for pair in comparison_pairs {
+            match pair.to_comparison_pair() {
+                Some(resolved_pair) => {
+                    comparison_pair_rules.extend(pair.rules_for(&resolved_pair));
+                    row_key_positions.extend(pair.row_key_position_for(&resolved_pair));
+                    resolved_pairs.push(resolved_pair);
+                }
+                None => pending_pairs.push(pair.clone()),
+            }
+        }
+
+        let details = ReconTaskDetails {
             id: task_id.clone(),
             primary_file_id: None,
             comparison_file_id: None,
             is_done: false,
             has_begun: true,
-            comparison_pairs: request.comparison_pairs.clone(),
-            recon_config: request.recon_configurations.clone(),
-            recon_results_queue_info: self.generate_queue_topic(RECON_RESULTS_QUEUE_PREFIX, &task_id),
-            primary_file_chunks_queue_info: self.generate_queue_topic(PRIMARY_FILE_QUEUE_PREFIX, &task_id),
-            comparison_file_chunks_queue_info: self.generate_queue_topic(COMPARISON_FILE_QUEUE_PREFIX, &task_id),
+            comparison_pairs: resolved_pairs,
+            recon_config: recon_configurations.clone(),
+            //the shared task details still need a results queue, though results come in on the one results topic
+            recon_results_queue_info: self
+                .generate_queue_topic(&self.id_prefixes.recon_results_queue, &task_id),
+            primary_file_chunks_queue_info: self
+                .generate_queue_topic(&self.id_prefixes.primary_file_queue, &task_id),
+            comparison_file_chunks_queue_info: self
+                .generate_queue_topic(&self.id_prefixes.comparison_file_queue, &task_id),
         };
+
+        let now = Utc::now();
+        ReconTask {
+            details,
+            created_by: created_by.to_string(),
+            created_at: now,
+            updated_at: now,
+            cancellation: None,
+            reconciliation_start: None,
+            template: None,
+            pending_comparison_pairs: pending_pairs,
+            resolved_comparison_pairs: vec![],
+            accepted_comparison_pairs: vec![],
+            comparison_pair_rules,
+            row_key_positions,
+            row_key_separator: default_row_key_separator(),
+            column_type_mismatches: vec![],
+            status: ReconTaskStatus::AwaitingFiles,
+            results_summary: ReconResultsSummary::default(),
+            etag: None,
+        }
+    }
+
+    fn get_recon_template(&self, request: &ReconTemplateRequest, created_by: &str) -> ReconTemplate {
+        let now = Utc::now();
+        ReconTemplate {
+            id: self.generate_id(&self.id_prefixes.recon_template),
+            name: request.name.clone(),
+            version: 1,
+            recon_configurations: request.recon_configurations.clone(),
+            comparison_pairs: request.comparison_pairs.clone(),
+            row_key_separator: request.row_key_separator.clone(),
+            created_by: created_by.to_string(),
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    fn copy_recon_file_details(&self, source: &ReconFile, created_by: &str) -> ReconFile {
+        let mut metadata = source.metadata.clone();
+        metadata.id = self.generate_id(&self.id_prefixes.recon_file);
+        let mut copied_file =
+            self.new_recon_file(metadata, &source.format, &source.column_schema, created_by);
+        //the copy keeps the verification of the source, the service points it at the source's stored content
+        copied_file.verification = source.verification.clone();
+        copied_file
+    }
+
+    fn get_upload_session(
+        &self,
+        task_id: &str,
+        file_role: UploadedFileRole,
+        request: &CreateUploadSessionRequest,
+        created_by: &str,
+    ) -> UploadSession {
+        let now = Utc::now();
+        UploadSession {
+            id: self.generate_id(&self.id_prefixes.upload_session),
+            task_id: task_id.to_string(),
+            file_role,
+            file_name: request.file_name.clone(),
+            total_size_bytes: request.total_size_bytes,
+            chunk_size_bytes: request.chunk_size_bytes,
+            received_chunks: vec![],
+            status: UploadSessionStatus::Open,
+            file_id: None,
+            created_by: created_by.to_string(),
+            created_at: now,
+            updated_at: now,
+        }
     }
 }
 
 impl Transformer {
-    fn generate_uuid(&self, prefix: &str) -> String {
-        let id = Uuid::new_v4().to_string();
-        let full_id = String::from(format!("{}-{}", prefix, id));
-        return full_id;
+    fn new_recon_file(
+        &self,
+        metadata: ReconFileMetaData,
+        format: &FileFormatDescriptor,
+        column_schema: &[ColumnSchema],
+        created_by: &str,
+    ) -> ReconFile {
+        let now = Utc::now();
+        ReconFile {
+            metadata,
+            format: format.clone(),
+            column_schema: column_schema.to_vec(),
+            stored_file_parts: vec![],
+            verification: FileVerification::default(),
+            created_by: created_by.to_string(),
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    fn generate_id(&self, prefix: &str) -> String {
+        let id = self.id_generator.generate_id();
+        let full_id = format!("{}-{}", prefix, id);
+        full_id
     }
 
-    fn generate_queue_topic(&self, prefix: &str, task_id: &String) -> FileChunkQueue {
-        let uuid = String::from(format!("{}-{}", prefix, task_id));
+    fn generate_queue_topic(&self, prefix: &str, task_id: &str) -> FileChunkQueue {
+        let uuid = format!("{}-{}", prefix, task_id);
         FileChunkQueue {
             topic_id: uuid,
             last_acknowledged_id: Option::None,
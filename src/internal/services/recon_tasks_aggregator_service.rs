@@ -1,61 +1,167 @@
+use std::collections::HashSet;
+
 use async_trait::async_trait;
+use chrono::Utc;
+use futures_util::StreamExt;
 use validator::Validate;
 
 use crate::internal::{
     interfaces::{
+        blob_store::BlobStoreInterface,
         recon_files_repository::ReconFileDetailsRepositoryInterface,
-        recon_tasks_aggregator::ReconTaskAggregationServiceInterface,
+        recon_task_audit_repository::ReconTaskAuditRepositoryInterface,
+        recon_task_event_publisher::ReconTaskEventPublisherInterface,
+        recon_tasks_aggregator::{ReconTaskAggregationServiceInterface, UploadedContent},
         recon_tasks_repository::ReconTaskDetailsRepositoryInterface,
+        recon_templates_repository::ReconTemplateRepositoryInterface,
         transformer::TransformerInterface,
+        upload_sessions_repository::UploadSessionRepositoryInterface,
+    },
+    models::{
+        entities::{
+            authenticated_user::{AuthenticatedUser, UserRole},
+            column_schema::{column_schema_errors, column_type_mismatches, ColumnSchema},
+            file_format::{FileFormatDescriptor, FileLayout},
+            file_verification::FileVerification,
+            named_comparison_pair::{ColumnReference, NamedComparisonPair, ResolvedComparisonPair},
+            recon_file::ReconFile,
+            recon_results::{ReconChunkResults, ReconResultsExport},
+            recon_task::{ReconTask, ReconTaskCancellation, ReconTaskStart, ReconTaskStatus},
+            recon_task_audit::{ReconTaskAction, ReconTaskAuditEntry},
+            recon_task_event::{FileToReconcile, ReconTaskEvent},
+            recon_template::{ReconTemplate, ReconTemplateReference},
+            rejection_reason::RejectionReason,
+            service_error::ServiceError,
+            row_key::{default_row_key_separator, resolved_row_identifier_errors, row_identifier_errors},
+            upload_session::{
+                upload_chunk_count, UploadSession, UploadSessionStatus, UploadedFileRole,
+                MAX_UPLOAD_CHUNK_COUNT,
+            },
+        },
+        view_models::{
+            requests::{
+                AttachComparisonFileRequest, AttachPrimaryFileRequest, CancelReconTaskRequest,
+                CloneReconTaskRequest, CompleteUploadRequest, CreateReconTaskRequest,
+                CreateUploadSessionRequest, ForceTaskStatusRequest, ReconTemplateRequest,
+            },
+            responses::{
+                FilePreviewResponse, OrphanedFilesCleanupResponse, PreviewColumn,
+                ReconTaskResponseDetails, SuggestedComparisonPair, SuggestedComparisonPairsResponse,
+                UploadSessionResponse,
+            },
+        },
     },
-    models::view_models::requests::{
-        AttachComparisonFileRequest, AttachPrimaryFileRequest, CreateReconTaskRequest,
+    services::core_logic::{
+        column_matcher::suggest_comparison_pairs,
+        file_inspector::{content_hash, FileInspection, FileInspector},
+        file_preview::{complete_line_count, preview_line_count, preview_rows},
     },
     shared_reconciler_rust_libraries::models::{
-        entities::app_errors::{AppError, AppErrorKind},
-        view_models::recon_task_response_details::{FileResponseSummary, ReconTaskResponseDetails},
+        entities::{
+            app_errors::{AppError, AppErrorKind},
+            recon_tasks_models::ReconciliationConfigs,
+        },
+        view_models::recon_task_response_details::FileResponseSummary,
     },
 };
 
+const DEFAULT_PREVIEW_ROWS: usize = 20;
+
+//previews are read into memory whole, so they are kept small
+const MAX_PREVIEW_ROWS: usize = 100;
+
+//recorded in the history as performing the changes the workers report
+const RECON_RESULTS_SUBSCRIBER: &str = "recon-results-subscriber";
+
+//an upload is saved in parts of this size as it arrives
+const UPLOADED_FILE_PART_SIZE_BYTES: usize = 4 * 1024 * 1024;
+
 pub struct ReconTaskAggregationService {
     pub recon_task_details_repo: Box<dyn ReconTaskDetailsRepositoryInterface>,
     pub recon_file_details_repo: Box<dyn ReconFileDetailsRepositoryInterface>,
+    pub recon_task_audit_repo: Box<dyn ReconTaskAuditRepositoryInterface>,
+    pub recon_template_repo: Box<dyn ReconTemplateRepositoryInterface>,
     pub transformer: Box<dyn TransformerInterface>,
+    pub event_publisher: Box<dyn ReconTaskEventPublisherInterface>,
+    pub upload_session_repo: Box<dyn UploadSessionRepositoryInterface>,
+    pub blob_store: Box<dyn BlobStoreInterface>,
+    pub quotas: ReconTaskQuotas,
+    //the one topic every worker reports on, the results name the task they belong to
+    pub recon_results_topic: String,
+}
+
+//what a new task is set up with, read either from the request or from a template
+struct ReconTaskSettings {
+    recon_configurations: ReconciliationConfigs,
+    comparison_pairs: Vec<NamedComparisonPair>,
+    row_key_separator: String,
+    template: Option<ReconTemplateReference>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ReconTaskQuotas {
+    //tasks that are neither done nor cancelled
+    pub max_active_tasks_per_user: usize,
+    pub max_file_row_count: u64,
 }
 
 #[async_trait]
 impl ReconTaskAggregationServiceInterface for ReconTaskAggregationService {
     async fn create_recon_task(
-        &mut self,
+        &self,
         request: &CreateReconTaskRequest,
-    ) -> Result<ReconTaskResponseDetails, AppError> {
+        caller: &AuthenticatedUser,
+    ) -> Result<ReconTaskResponseDetails, ServiceError> {
+        ensure_can_create_tasks(caller)?;
+
         //validate request
         match request.validate() {
             Ok(_) => (),
             Err(e) => {
-                return Err(AppError::new(
+                return Err(ServiceError::new(
                     AppErrorKind::BadClientRequest,
                     e.to_string().replace("\n", " , "),
                 ));
             }
         }
 
+        let settings = self.read_task_settings(request).await?;
+        validate_comparison_rules(&settings.comparison_pairs)?;
+        validate_row_identifiers(&settings.comparison_pairs, &settings.row_key_separator)?;
+
         //save recon task details
-        let recon_task_details = self.transformer.get_recon_task_details(&request);
+        let mut recon_task_details = self.transformer.get_recon_task_details(
+            &request.user_id,
+            &settings.recon_configurations,
+            &settings.comparison_pairs,
+        );
+        recon_task_details.template = settings.template;
+        recon_task_details.row_key_separator = settings.row_key_separator;
 
-        let task_id = self
-            .recon_task_details_repo
-            .create_task_details(&recon_task_details)
+        let task_id = self.create_active_task(&recon_task_details).await?;
+
+        //record who created the task
+        self.recon_task_audit_repo
+            .append_audit_entry(&ReconTaskAuditEntry::new(
+                &task_id,
+                ReconTaskAction::TaskCreated,
+                &request.user_id,
+                None,
+            ))
             .await?;
 
         //retrieve saved details
-        return self.get_recon_task(&task_id).await;
+        return self.get_recon_task(&task_id, caller).await;
     }
 
-    async fn get_recon_task(&mut self, task_id: &String) -> Result<ReconTaskResponseDetails, AppError> {
+    async fn get_recon_task(
+        &self,
+        task_id: &str,
+        caller: &AuthenticatedUser,
+    ) -> Result<ReconTaskResponseDetails, ServiceError> {
         //validate request
         if task_id.is_empty() {
-            return Err(AppError::new(
+            return Err(ServiceError::new(
                 AppErrorKind::BadClientRequest,
                 String::from("please supply a taskID"),
             ));
@@ -67,8 +173,10 @@ impl ReconTaskAggregationServiceInterface for ReconTaskAggregationService {
             .get_task_details(task_id)
             .await?;
 
+        ensure_can_read_task(&task_details, caller)?;
+
         //fetch src file from repository
-        let src_file_metadata  = match task_details.primary_file_id.clone() {
+        let src_file_metadata  = match task_details.details.primary_file_id.clone() {
             Some(primary_file_id) => {
                 let primary_file_metadata = self.recon_file_details_repo.get_recon_file_details(&primary_file_id).await?;
                 Some(primary_file_metadata)
@@ -77,7 +185,7 @@ impl ReconTaskAggregationServiceInterface for ReconTaskAggregationService {
         };
 
         //fetch cmp file from repository
-        let cmp_file_metadata = match task_details.comparison_file_id.clone() {
+        let cmp_file_metadata = match task_details.details.comparison_file_id.clone() {
             Some(comparison_file_id) => {
                 let comparison_file_metadata = self.recon_file_details_repo.get_recon_file_details(&comparison_file_id).await?;
                 Some(comparison_file_metadata)
@@ -96,63 +204,1929 @@ impl ReconTaskAggregationServiceInterface for ReconTaskAggregationService {
         return Ok(task_details_response);
     }
 
+    async fn get_recon_task_history(
+        &self,
+        task_id: &str,
+        caller: &AuthenticatedUser,
+    ) -> Result<Vec<ReconTaskAuditEntry>, ServiceError> {
+        //make sure the task exists so unknown ids are a NotFound rather than an empty history
+        let task_details = self
+            .recon_task_details_repo
+            .get_task_details(task_id)
+            .await?;
+
+        ensure_can_read_task(&task_details, caller)?;
+
+        return Ok(self.recon_task_audit_repo.get_audit_entries(task_id).await?);
+    }
+
     async fn attach_primary_file_to_task(
-        &mut self,
+        &self,
         request: &AttachPrimaryFileRequest,
-    ) -> Result<FileResponseSummary, AppError> {
-        //transform into primary file details
-        let primary_file_details = self.transformer.get_primary_file_details(&request);
+        caller: &AuthenticatedUser,
+    ) -> Result<FileResponseSummary, ServiceError> {
+        let _ = attached_file_blob_name(&request.task_id, &request.primary_file_name)?;
+        return self
+            .attach_primary_file(request, vec![], FileVerification::default(), caller)
+            .await;
+    }
 
-        //save the file details
-        let primary_file_id = self
-            .recon_file_details_repo
-            .create_recon_file_details(&primary_file_details)
+    async fn attach_comparison_file_to_task(
+        &self,
+        request: &AttachComparisonFileRequest,
+        caller: &AuthenticatedUser,
+    ) -> Result<FileResponseSummary, ServiceError> {
+        let _ = attached_file_blob_name(&request.task_id, &request.comparison_file_name)?;
+        return self
+            .attach_comparison_file(request, vec![], FileVerification::default(), caller)
+            .await;
+    }
+
+    async fn upload_primary_file_to_task(
+        &self,
+        task_id: &str,
+        file_name: &str,
+        content: UploadedContent,
+        caller: &AuthenticatedUser,
+    ) -> Result<FileResponseSummary, ServiceError> {
+        self
+            .upload_file(task_id, UploadedFileRole::Primary, file_name, content, caller)
+            .await
+    }
+
+    async fn upload_comparison_file_to_task(
+        &self,
+        task_id: &str,
+        file_name: &str,
+        content: UploadedContent,
+        caller: &AuthenticatedUser,
+    ) -> Result<FileResponseSummary, ServiceError> {
+        self
+            .upload_file(task_id, UploadedFileRole::Comparison, file_name, content, caller)
+            .await
+    }
+
+    async fn create_upload_session(
+        &self,
+        task_id: &str,
+        file_role: UploadedFileRole,
+        request: &CreateUploadSessionRequest,
+        caller: &AuthenticatedUser,
+    ) -> Result<UploadSessionResponse, ServiceError> {
+        //validate request
+        match request.validate() {
+            Ok(_) => (),
+            Err(e) => {
+                return Err(ServiceError::new(
+                    AppErrorKind::BadClientRequest,
+                    e.to_string().replace("\n", " , "),
+                ));
+            }
+        }
+
+        let chunk_count = upload_chunk_count(request.total_size_bytes, request.chunk_size_bytes);
+        if chunk_count > MAX_UPLOAD_CHUNK_COUNT {
+            return Err(ServiceError::new(
+                AppErrorKind::BadClientRequest,
+                format!(
+                    "the upload would take {} chunks, please use a chunk_size_bytes that keeps it at {} or fewer",
+                    chunk_count, MAX_UPLOAD_CHUNK_COUNT
+                ),
+            ));
+        }
+
+        self.ensure_task_can_take_upload(task_id, caller).await?;
+
+        let session = self
+            .transformer
+            .get_upload_session(task_id, file_role, request, &caller.user_id);
+
+        self.upload_session_repo.save_upload_session(&session).await?;
+
+        return Ok(UploadSessionResponse::new(&session));
+    }
+
+    async fn upload_file_chunk(
+        &self,
+        task_id: &str,
+        upload_id: &str,
+        chunk_number: u64,
+        content: &[u8],
+        caller: &AuthenticatedUser,
+    ) -> Result<UploadSessionResponse, ServiceError> {
+        let session = self.get_task_upload_session(task_id, upload_id).await?;
+        ensure_upload_session_is_open(&session)?;
+        self.ensure_task_can_take_upload(task_id, caller).await?;
+
+        match session.expected_chunk_size(chunk_number) {
+            None => {
+                return Err(ServiceError::new(
+                    AppErrorKind::BadClientRequest,
+                    format!(
+                        "there is no chunk {}, the upload has chunks 0 to {}",
+                        chunk_number,
+                        session.chunk_count() - 1
+                    ),
+                ));
+            }
+            Some(expected_size) if expected_size != content.len() as u64 => {
+                return Err(ServiceError::new(
+                    AppErrorKind::BadClientRequest,
+                    format!(
+                        "chunk {} should be {} bytes long but is {}",
+                        chunk_number,
+                        expected_size,
+                        content.len()
+                    ),
+                ));
+            }
+            Some(_) => (),
+        }
+
+        self.blob_store
+            .save_file(&session.chunk_file_name(chunk_number), content)
             .await?;
 
-        //retrieve saved task details
-        let recon_task_details = self.get_recon_task(&request.task_id.clone()).await?;
+        let session = self
+            .upload_session_repo
+            .record_upload_chunk(upload_id, chunk_number)
+            .await?;
+
+        //another request completed the upload while this chunk was being saved
+        ensure_upload_session_is_open(&session)?;
+
+        return Ok(UploadSessionResponse::new(&session));
+    }
+
+    async fn get_upload_session(
+        &self,
+        task_id: &str,
+        upload_id: &str,
+        caller: &AuthenticatedUser,
+    ) -> Result<UploadSessionResponse, ServiceError> {
+        let session = self.get_task_upload_session(task_id, upload_id).await?;
+
+        //whoever can read the task can follow its uploads
+        let _ = self.get_recon_task(task_id, caller).await?;
+
+        return Ok(UploadSessionResponse::new(&session));
+    }
+
+    async fn complete_upload_session(
+        &self,
+        task_id: &str,
+        upload_id: &str,
+        request: &CompleteUploadRequest,
+        caller: &AuthenticatedUser,
+    ) -> Result<FileResponseSummary, ServiceError> {
+        //validate request
+        match request.validate() {
+            Ok(_) => (),
+            Err(e) => {
+                return Err(ServiceError::new(
+                    AppErrorKind::BadClientRequest,
+                    e.to_string().replace("\n", " , "),
+                ));
+            }
+        }
+
+        let session = self.get_task_upload_session(task_id, upload_id).await?;
+
+        //completing again returns the file attached the first time, so a retried request is harmless
+        if let Some(file_id) = &session.file_id {
+            return Ok(FileResponseSummary {
+                file_id: file_id.clone(),
+                task_id: task_id.to_string(),
+            });
+        }
+
+        self.ensure_task_can_take_upload(task_id, caller).await?;
+
+        let missing_chunks = session.missing_chunks();
+        if let Some(first_missing_chunk) = missing_chunks.first() {
+            return Err(ServiceError::new(
+                AppErrorKind::BadClientRequest,
+                format!(
+                    "{} of {} chunks have not been uploaded yet, starting with chunk {}",
+                    missing_chunks.len(),
+                    session.chunk_count(),
+                    first_missing_chunk
+                ),
+            ));
+        }
+
+        //chunks are read back one at a time, so completing a large upload does not hold the whole file in memory
+        let stored_file_parts = session.chunk_file_names();
+        let mut inspector = FileInspector::new();
+        for part in &stored_file_parts {
+            let content = self.blob_store.read_file(part).await?;
+            inspector.update(&content);
+        }
+        let inspection = inspector.finish();
+
+        if inspection.file_hash != request.file_hash.trim().to_lowercase() {
+            return Err(ServiceError::new(
+                AppErrorKind::BadClientRequest,
+                format!(
+                    "the uploaded chunks hash to [{}] and not to the file_hash [{}], please re-upload the chunks that changed",
+                    inspection.file_hash, request.file_hash
+                ),
+            ));
+        }
+
+        ensure_uploaded_file_is_not_empty(&session.file_name, &inspection)?;
+        self.ensure_file_row_count_within_quota(inspection.row_count)?;
+
+        let file_summary = self
+            .attach_uploaded_file(
+                task_id,
+                session.file_role,
+                &session.file_name,
+                &inspection,
+                stored_file_parts,
+                caller,
+            )
+            .await?;
+
+        //a complete request racing this one may have finished first, its file is the one the upload is
+        let session = self
+            .upload_session_repo
+            .complete_upload_session(upload_id, &file_summary.file_id)
+            .await?;
+
+        return Ok(FileResponseSummary {
+            file_id: session.file_id.unwrap_or(file_summary.file_id),
+            task_id: task_id.to_string(),
+        });
+    }
+
+    async fn verify_task_files(
+        &self,
+        task_id: &str,
+        caller: &AuthenticatedUser,
+    ) -> Result<ReconTaskResponseDetails, ServiceError> {
+        let recon_task_details = self.get_recon_task(task_id, caller).await?;
         let mut recon_task = recon_task_details.task_details;
-        recon_task.primary_file_id = Some(primary_file_id.clone());
 
-        //update the task
-        let _ = self
-            .recon_task_details_repo
-            .update_task_details(&recon_task)
+        ensure_can_change_task(&recon_task, caller)?;
+
+        if recon_task_details.primary_file_metadata.is_none()
+            && recon_task_details.comparison_file_metadata.is_none()
+        {
+            return Err(ServiceError::new(
+                AppErrorKind::BadClientRequest,
+                format!("task [{}] has no files to verify", task_id),
+            ));
+        }
+
+        let primary_file = self
+            .verify_file(task_id, recon_task_details.primary_file_metadata)
+            .await?;
+        let comparison_file = self
+            .verify_file(task_id, recon_task_details.comparison_file_metadata)
             .await?;
 
-        Ok(FileResponseSummary {
-            file_id: primary_file_id.clone(),
-            task_id: request.task_id.clone(),
-        })
+        let outcomes: Vec<String> = [("primary", &primary_file), ("comparison", &comparison_file)]
+            .iter()
+            .filter_map(|(file_role, file)| {
+                file.as_ref()
+                    .map(|file| format!("{} file {:?}", file_role, file.verification.status))
+            })
+            .collect();
+
+        //verifying again after the start leaves the task reconciling
+        let status = ReconTaskStatus::for_files(primary_file.as_ref(), comparison_file.as_ref());
+        if !recon_task.status.has_started() && recon_task.status != status {
+            recon_task.status = status;
+            recon_task.updated_at = Utc::now();
+            let _ = self.save_task_details(&recon_task).await?;
+        }
+
+        self.recon_task_audit_repo
+            .append_audit_entry(&ReconTaskAuditEntry::new(
+                task_id,
+                ReconTaskAction::FilesVerified,
+                &caller.user_id,
+                Some(outcomes.join(", ")),
+            ))
+            .await?;
+
+        return self.get_recon_task(task_id, caller).await;
     }
 
-    async fn attach_comparison_file_to_task(
-        &mut self,
-        request: &AttachComparisonFileRequest,
-    ) -> Result<FileResponseSummary, AppError> {
-        //transform into primary file details
-        let comparison_file_details = self.transformer.get_comparison_file_details(&request);
+    async fn preview_recon_file(
+        &self,
+        task_id: &str,
+        file_id: &str,
+        rows: Option<usize>,
+        caller: &AuthenticatedUser,
+    ) -> Result<FilePreviewResponse, ServiceError> {
+        let max_rows = rows.unwrap_or(DEFAULT_PREVIEW_ROWS);
+        if max_rows == 0 || max_rows > MAX_PREVIEW_ROWS {
+            return Err(ServiceError::new(
+                AppErrorKind::BadClientRequest,
+                format!("please ask for between 1 and {} rows", MAX_PREVIEW_ROWS),
+            ));
+        }
 
-        //save the file details
-        let comparison_file_id = self
-            .recon_file_details_repo
-            .create_recon_file_details(&comparison_file_details)
+        let recon_task_details = self.get_recon_task(task_id, caller).await?;
+        let task_details = &recon_task_details.task_details.details;
+
+        //each file sees the pairs through its own column indexes
+        let (file, compared_columns): (Option<&ReconFile>, Vec<(usize, bool)>) =
+            if task_details.primary_file_id.as_deref() == Some(file_id) {
+                (
+                    recon_task_details.primary_file_metadata.as_ref(),
+                    task_details
+                        .comparison_pairs
+                        .iter()
+                        .map(|pair| (pair.primary_file_column_index, pair.is_row_identifier))
+                        .collect(),
+                )
+            } else if task_details.comparison_file_id.as_deref() == Some(file_id) {
+                (
+                    recon_task_details.comparison_file_metadata.as_ref(),
+                    task_details
+                        .comparison_pairs
+                        .iter()
+                        .map(|pair| (pair.comparison_file_column_index, pair.is_row_identifier))
+                        .collect(),
+                )
+            } else {
+                (None, vec![])
+            };
+
+        let file = match file {
+            Some(file) => file,
+            None => {
+                return Err(ServiceError::new(
+                    AppErrorKind::NotFound,
+                    format!("task [{}] has no file [{}]", task_id, file_id),
+                ));
+            }
+        };
+
+        let content = self
+            .read_file_lines(task_id, file, preview_line_count(file, max_rows))
             .await?;
 
-        //retrieve saved task details
-        let recon_task_details = self.get_recon_task(&request.task_id.clone()).await?;
+        let columns = file
+            .metadata
+            .column_headers
+            .iter()
+            .enumerate()
+            .map(|(column_index, header)| PreviewColumn {
+                header: header.clone(),
+                is_compared: compared_columns.iter().any(|(index, _)| *index == column_index),
+                is_row_identifier: compared_columns
+                    .iter()
+                    .any(|(index, is_row_identifier)| *index == column_index && *is_row_identifier),
+            })
+            .collect();
+
+        return Ok(FilePreviewResponse {
+            task_id: task_id.to_string(),
+            file_id: file_id.to_string(),
+            file_name: file.metadata.file_name.clone(),
+            columns,
+            rows: preview_rows(&content, file, max_rows),
+        });
+    }
+
+    async fn start_recon_task(
+        &self,
+        task_id: &str,
+        caller: &AuthenticatedUser,
+    ) -> Result<ReconTaskResponseDetails, ServiceError> {
+        let recon_task_details = self.get_recon_task(task_id, caller).await?;
         let mut recon_task = recon_task_details.task_details;
-        recon_task.comparison_file_id = Some(comparison_file_id.clone());
 
-        //update the task
+        ensure_can_change_task(&recon_task, caller)?;
+        ensure_task_is_not_cancelled(&recon_task)?;
+
+        //a start that failed part way through is finished by sending the same commands again
+        let resumed_start = match (&recon_task.status, &recon_task.reconciliation_start) {
+            (ReconTaskStatus::Starting, Some(start)) => Some(start.clone()),
+            _ => None,
+        };
+        if resumed_start.is_none() {
+            ensure_task_has_not_started(&recon_task)?;
+        }
+
+        let (primary_file, comparison_file) = match (
+            &recon_task_details.primary_file_metadata,
+            &recon_task_details.comparison_file_metadata,
+        ) {
+            (Some(primary_file), Some(comparison_file)) => (primary_file, comparison_file),
+            _ => {
+                return Err(ServiceError::new(
+                    AppErrorKind::BadClientRequest,
+                    format!("task [{}] needs both files attached before it can start", task_id),
+                ));
+            }
+        };
+
+        for (file_role, file) in [("primary", primary_file), ("comparison", comparison_file)] {
+            if !file.verification.is_verified() {
+                return Err(ServiceError::new(
+                    AppErrorKind::BadClientRequest,
+                    format!(
+                        "the {} file [{}] is {:?}, only verified files can be reconciled",
+                        file_role, file.metadata.id, file.verification.status
+                    ),
+                ));
+            }
+        }
+
+        //saved before anything is published, so the commands of a failed start are sent again as they were
+        let start = match resumed_start {
+            Some(start) => start,
+            None => {
+                let start = ReconTaskStart::new(task_id, &caller.user_id, Utc::now());
+                recon_task.status = ReconTaskStatus::Starting;
+                recon_task.reconciliation_start = Some(start.clone());
+                recon_task.updated_at = start.started_at;
+                recon_task = self.save_task_details(&recon_task).await?;
+                start
+            }
+        };
+
+        for (topic, file_role, file) in [
+            (
+                &recon_task.details.primary_file_chunks_queue_info.topic_id,
+                UploadedFileRole::Primary,
+                primary_file,
+            ),
+            (
+                &recon_task.details.comparison_file_chunks_queue_info.topic_id,
+                UploadedFileRole::Comparison,
+                comparison_file,
+            ),
+        ] {
+            let event = ReconTaskEvent::ReconciliationStarted {
+                task_id: task_id.to_string(),
+                idempotency_key: start.idempotency_key(file_role),
+                file: Box::new(FileToReconcile {
+                    stored_file_parts: stored_content_parts(task_id, file)?,
+                    ..FileToReconcile::from(file)
+                }),
+                recon_config: recon_task.details.recon_config.clone(),
+                comparison_pairs: recon_task.details.comparison_pairs.clone(),
+                comparison_pair_rules: recon_task.comparison_pair_rules.clone(),
+                row_key_separator: recon_task.row_key_separator.clone(),
+                row_key_columns: recon_task.row_key_definition().identifier_columns,
+                recon_results_topic: self.recon_results_topic.clone(),
+                started_by: start.started_by.clone(),
+                started_at: start.started_at,
+            };
+            self.event_publisher.publish_task_event(topic, &event).await?;
+        }
+
+        //workers can report results before this, so the task is changed as it is stored now
         let _ = self
             .recon_task_details_repo
-            .update_task_details(&recon_task)
+            .mark_task_reconciling(task_id)
             .await?;
 
-        Ok(FileResponseSummary {
-            file_id: comparison_file_id.clone(),
-            task_id: request.task_id.clone(),
-        })
+        self.recon_task_audit_repo
+            .append_audit_entry(&ReconTaskAuditEntry::new(
+                task_id,
+                ReconTaskAction::ReconciliationStarted,
+                &caller.user_id,
+                None,
+            ))
+            .await?;
+
+        return self.get_recon_task(task_id, caller).await;
+    }
+
+    async fn record_recon_results(&self, results: &ReconChunkResults) -> Result<(), ServiceError> {
+        ensure_results_file_name_is_derived(results)?;
+
+        let recon_task = self
+            .recon_task_details_repo
+            .record_chunk_results(results)
+            .await?;
+
+        //read from the stored task rather than from whether this delivery completed it,
+        //so a delivery whose completion was lost still releases the slot and records the completion
+        let completed_at = match (&recon_task.status, recon_task.results_summary.completed_at) {
+            (ReconTaskStatus::Completed, Some(completed_at)) => completed_at,
+            _ => return Ok(()),
+        };
+
+        self.recon_task_details_repo
+            .release_active_task_slot(&recon_task.created_by, &recon_task.details.id)
+            .await?;
+
+        //dated when the task completed, so every delivery appends the same entry and it is only kept once
+        self.recon_task_audit_repo
+            .append_audit_entry(&ReconTaskAuditEntry {
+                performed_at: completed_at,
+                ..ReconTaskAuditEntry::new(
+                    &results.task_id,
+                    ReconTaskAction::ReconciliationCompleted,
+                    &String::from(RECON_RESULTS_SUBSCRIBER),
+                    None,
+                )
+            })
+            .await?;
+
+        return Ok(());
+    }
+
+    async fn get_recon_results_export(
+        &self,
+        task_id: &str,
+        caller: &AuthenticatedUser,
+    ) -> Result<ReconResultsExport, ServiceError> {
+        let recon_task_details = self.get_recon_task(task_id, caller).await?;
+        let recon_task = &recon_task_details.task_details;
+
+        if recon_task.status != ReconTaskStatus::Completed {
+            return Err(ServiceError::new(
+                AppErrorKind::BadClientRequest,
+                format!("task [{}] has no results to export until it is completed", task_id),
+            ));
+        }
+
+        let column_headers = |file: &Option<ReconFile>| {
+            file.as_ref()
+                .map(|file| file.metadata.column_headers.clone())
+                .unwrap_or_default()
+        };
+
+        return Ok(ReconResultsExport {
+            task_id: task_id.to_string(),
+            primary_file_headers: column_headers(&recon_task_details.primary_file_metadata),
+            comparison_file_headers: column_headers(&recon_task_details.comparison_file_metadata),
+            results_file_names: recon_task.results_summary.results_file_names(task_id),
+        });
+    }
+
+    async fn read_recon_results_file(
+        &self,
+        results_file_name: &str,
+    ) -> Result<Vec<u8>, ServiceError> {
+        Ok(self.blob_store.read_file(results_file_name).await?)
+    }
+
+    async fn cancel_recon_task(
+        &self,
+        task_id: &str,
+        request: &CancelReconTaskRequest,
+        caller: &AuthenticatedUser,
+    ) -> Result<ReconTaskResponseDetails, ServiceError> {
+        //validate request
+        match request.validate() {
+            Ok(_) => (),
+            Err(e) => {
+                return Err(ServiceError::new(
+                    AppErrorKind::BadClientRequest,
+                    e.to_string().replace("\n", " , "),
+                ));
+            }
+        }
+
+        let mut recon_task = self
+            .recon_task_details_repo
+            .get_task_details(task_id)
+            .await?;
+
+        ensure_can_change_task(&recon_task, caller)?;
+
+        //cancelling twice keeps the original cancellation but publishes the event again
+        //so a publish that failed the first time can be retried by the client
+        if let Some(cancellation) = recon_task.cancellation.clone() {
+            self.publish_cancellation(&recon_task, &cancellation).await?;
+            return self.get_recon_task(task_id, caller).await;
+        }
+
+        if recon_task.details.is_done {
+            return Err(ServiceError::new(
+                AppErrorKind::BadClientRequest,
+                format!("task [{}] is already done and cannot be cancelled", task_id),
+            ));
+        }
+
+        let cancellation = ReconTaskCancellation {
+            cancelled_by: caller.user_id.clone(),
+            cancelled_at: Utc::now(),
+            reason: request.reason.clone(),
+        };
+
+        recon_task.cancellation = Some(cancellation.clone());
+        recon_task.status = ReconTaskStatus::Cancelled;
+        recon_task.updated_at = cancellation.cancelled_at;
+
+        //persist first so no file can be attached while the workers are being told to stop
+        //saved under the etag it was read with, so a task completed in the meantime is a conflict rather than overwritten
+        let _ = self.save_task_details(&recon_task).await?;
+
+        //the task was neither done nor cancelled when it was read and the save went through under that etag,
+        //so it is this write that took the task out of the active ones
+        self.recon_task_details_repo
+            .release_active_task_slot(&recon_task.created_by, task_id)
+            .await?;
+
+        self.recon_task_audit_repo
+            .append_audit_entry(&ReconTaskAuditEntry::new(
+                task_id,
+                ReconTaskAction::TaskCancelled,
+                &caller.user_id,
+                Some(request.reason.clone()),
+            ))
+            .await?;
+
+        self.publish_cancellation(&recon_task, &cancellation).await?;
+
+        return self.get_recon_task(task_id, caller).await;
+    }
+
+    async fn clone_recon_task(
+        &self,
+        task_id: &str,
+        request: &CloneReconTaskRequest,
+        caller: &AuthenticatedUser,
+    ) -> Result<ReconTaskResponseDetails, ServiceError> {
+        ensure_can_create_tasks(caller)?;
+
+        let source_task = self
+            .recon_task_details_repo
+            .get_task_details(task_id)
+            .await?;
+
+        ensure_can_read_task(&source_task, caller)?;
+
+        //the transformer hands out fresh ids and queue topics, only the configuration is reused
+        let mut cloned_task = self.transformer.get_recon_task_details(
+            &caller.user_id,
+            &source_task.details.recon_config,
+            &source_task.comparison_pair_definitions(),
+        );
+        cloned_task.template = source_task.template.clone();
+        cloned_task.row_key_separator = source_task.row_key_separator.clone();
+
+        let cloned_task_id = self.create_active_task(&cloned_task).await?;
+
+        self.recon_task_audit_repo
+            .append_audit_entry(&ReconTaskAuditEntry::new(
+                &cloned_task_id,
+                ReconTaskAction::TaskCreated,
+                &caller.user_id,
+                Some(format!("cloned from task [{}]", task_id)),
+            ))
+            .await?;
+
+        if !request.copy_file_metadata {
+            return self.get_recon_task(&cloned_task_id, caller).await;
+        }
+
+        let primary_file = self
+            .copy_recon_file_to_task(
+                task_id,
+                &source_task.details.primary_file_id,
+                &cloned_task_id,
+                ReconTaskAction::PrimaryFileAttached,
+                caller,
+            )
+            .await?;
+
+        let comparison_file = self
+            .copy_recon_file_to_task(
+                task_id,
+                &source_task.details.comparison_file_id,
+                &cloned_task_id,
+                ReconTaskAction::ComparisonFileAttached,
+                caller,
+            )
+            .await?;
+
+        //read back so the update carries the etag of the task as it was created
+        let mut cloned_task = self
+            .recon_task_details_repo
+            .get_task_details(&cloned_task_id)
+            .await?;
+
+        //the copies keep the verification of the files they were copied from
+        cloned_task.details.primary_file_id = primary_file.as_ref().map(|file| file.metadata.id.clone());
+        cloned_task.details.comparison_file_id =
+            comparison_file.as_ref().map(|file| file.metadata.id.clone());
+        cloned_task.status =
+            ReconTaskStatus::for_files(primary_file.as_ref(), comparison_file.as_ref());
+        cloned_task.updated_at = Utc::now();
+
+        if let (Some(primary_file), Some(comparison_file)) = (&primary_file, &comparison_file) {
+            resolve_comparison_pairs(
+                &mut cloned_task,
+                &primary_file.metadata.column_headers,
+                &comparison_file.metadata.column_headers,
+            )?;
+            cloned_task.column_type_mismatches = column_type_mismatches(
+                &cloned_task.details.comparison_pairs,
+                &primary_file.column_schema,
+                &comparison_file.column_schema,
+            );
+        }
+
+        let _ = self.save_task_details(&cloned_task).await?;
+
+        return self.get_recon_task(&cloned_task_id, caller).await;
     }
+
+    async fn get_suggested_comparison_pairs(
+        &self,
+        task_id: &str,
+        caller: &AuthenticatedUser,
+    ) -> Result<SuggestedComparisonPairsResponse, ServiceError> {
+        let recon_task_details = self.get_recon_task(task_id, caller).await?;
+
+        let suggested_pairs = suggest_pairs_for_attached_files(&recon_task_details)?;
+
+        return Ok(SuggestedComparisonPairsResponse {
+            task_id: recon_task_details.task_id,
+            suggested_pairs,
+        });
+    }
+
+    async fn accept_suggested_comparison_pairs(
+        &self,
+        task_id: &str,
+        caller: &AuthenticatedUser,
+    ) -> Result<ReconTaskResponseDetails, ServiceError> {
+        let recon_task_details = self.get_recon_task(task_id, caller).await?;
+        let suggested_pairs = suggest_pairs_for_attached_files(&recon_task_details)?;
+        let mut recon_task = recon_task_details.task_details;
+
+        ensure_can_change_task(&recon_task, caller)?;
+        ensure_task_is_not_cancelled(&recon_task)?;
+        ensure_task_has_not_started(&recon_task)?;
+
+        //pairs the user set up themselves win over any suggestion for the same columns
+        let accepted_pairs: Vec<&SuggestedComparisonPair> = suggested_pairs
+            .iter()
+            .filter(|suggestion| {
+                !recon_task.details.comparison_pairs.iter().any(|pair| {
+                    pair.primary_file_column_index == suggestion.primary_file_column_index
+                        || pair.comparison_file_column_index == suggestion.comparison_file_column_index
+                })
+            })
+            .collect();
+
+        //the resolved pairs go after every other pair, matching the order of accepted_comparison_pairs
+        //accepted identifiers are appended to the end of the row key
+        let mut next_row_key_position = recon_task.row_key_definition().identifier_columns.len();
+        for suggestion in &accepted_pairs {
+            let row_key_position = match suggestion.is_row_identifier {
+                true => Some(next_row_key_position),
+                false => None,
+            };
+            next_row_key_position += row_key_position.map_or(0, |_| 1);
+
+            let named_pair = suggestion.to_named_comparison_pair(row_key_position);
+            let resolved_pair = suggestion.to_comparison_pair();
+            recon_task.row_key_positions.extend(named_pair.row_key_position_for(&resolved_pair));
+            recon_task.details.comparison_pairs.push(resolved_pair);
+            recon_task.accepted_comparison_pairs.push(named_pair);
+        }
+
+        let accepted_pairs_count = accepted_pairs.len();
+        recon_task.updated_at = Utc::now();
+
+        //suggestions are only made once both files are attached, so both are known here
+        if let (Some(primary_file), Some(comparison_file)) = (
+            &recon_task_details.primary_file_metadata,
+            &recon_task_details.comparison_file_metadata,
+        ) {
+            recon_task.column_type_mismatches = column_type_mismatches(
+                &recon_task.details.comparison_pairs,
+                &primary_file.column_schema,
+                &comparison_file.column_schema,
+            );
+        }
+
+        let _ = self.save_task_details(&recon_task).await?;
+
+        self.recon_task_audit_repo
+            .append_audit_entry(&ReconTaskAuditEntry::new(
+                task_id,
+                ReconTaskAction::SuggestedPairsAccepted,
+                &caller.user_id,
+                Some(format!("{} suggested comparison pairs accepted", accepted_pairs_count)),
+            ))
+            .await?;
+
+        return self.get_recon_task(task_id, caller).await;
+    }
+
+    async fn list_all_recon_tasks(
+        &self,
+        caller: &AuthenticatedUser,
+    ) -> Result<Vec<ReconTask>, ServiceError> {
+        ensure_is_admin(caller)?;
+
+        return Ok(self.recon_task_details_repo.list_task_details().await?);
+    }
+
+    async fn force_recon_task_status(
+        &self,
+        task_id: &str,
+        request: &ForceTaskStatusRequest,
+        caller: &AuthenticatedUser,
+    ) -> Result<ReconTaskResponseDetails, ServiceError> {
+        ensure_is_admin(caller)?;
+
+        let recon_task_details = self.get_recon_task(task_id, caller).await?;
+        let mut recon_task = recon_task_details.task_details;
+
+        //a cancelled task stays cancelled, forcing it would bring it back without anyone noticing
+        ensure_task_is_not_cancelled(&recon_task)?;
+
+        //the status is set along with the flags, so a forced task reads the same whichever of them is looked at
+        let was_active = recon_task.is_active();
+        recon_task.details.has_begun = request.has_begun || request.is_done;
+        recon_task.details.is_done = request.is_done;
+        recon_task.status = match (recon_task.details.has_begun, recon_task.details.is_done) {
+            (_, true) => ReconTaskStatus::Completed,
+            (true, false) => ReconTaskStatus::Reconciling,
+            (false, false) => ReconTaskStatus::for_files(
+                recon_task_details.primary_file_metadata.as_ref(),
+                recon_task_details.comparison_file_metadata.as_ref(),
+            ),
+        };
+        recon_task.updated_at = Utc::now();
+
+        let _ = self.save_task_details(&recon_task).await?;
+
+        self.sync_active_task_slot(&recon_task, was_active).await?;
+
+        self.recon_task_audit_repo
+            .append_audit_entry(&ReconTaskAuditEntry::new(
+                task_id,
+                ReconTaskAction::StatusForced,
+                &caller.user_id,
+                Some(format!(
+                    "has_begun={}, is_done={}",
+                    request.has_begun, request.is_done
+                )),
+            ))
+            .await?;
+
+        return self.get_recon_task(task_id, caller).await;
+    }
+
+    async fn delete_orphaned_recon_files(
+        &self,
+        caller: &AuthenticatedUser,
+    ) -> Result<OrphanedFilesCleanupResponse, ServiceError> {
+        ensure_is_admin(caller)?;
+
+        //a file is orphaned when no task points at it, e.g. the task update failed after the file was saved
+        let referenced_file_ids: HashSet<String> = self
+            .recon_task_details_repo
+            .list_task_details()
+            .await?
+            .into_iter()
+            .flat_map(|task| vec![task.details.primary_file_id, task.details.comparison_file_id])
+            .flatten()
+            .collect();
+
+        let mut deleted_file_ids = vec![];
+
+        for file_id in self.recon_file_details_repo.list_recon_file_ids().await? {
+            if referenced_file_ids.contains(&file_id) {
+                continue;
+            }
+
+            self.recon_file_details_repo
+                .delete_recon_file_details(&file_id)
+                .await?;
+
+            deleted_file_ids.push(file_id);
+        }
+
+        return Ok(OrphanedFilesCleanupResponse { deleted_file_ids });
+    }
+
+    async fn create_recon_template(
+        &self,
+        request: &ReconTemplateRequest,
+        caller: &AuthenticatedUser,
+    ) -> Result<ReconTemplate, ServiceError> {
+        ensure_can_create_tasks(caller)?;
+        validate_template_request(request)?;
+
+        let template = self.transformer.get_recon_template(request, &caller.user_id);
+
+        let _ = self.recon_template_repo.save_template(&template).await?;
+
+        return Ok(template);
+    }
+
+    //templates are shared, anyone who can sign in can read them
+    async fn get_recon_template(
+        &self,
+        template_id: &str,
+        version: Option<u32>,
+        _caller: &AuthenticatedUser,
+    ) -> Result<ReconTemplate, ServiceError> {
+        match version {
+            Some(version) => Ok(self
+                .recon_template_repo
+                .get_template_version(template_id, version)
+                .await?),
+            None => Ok(self.recon_template_repo.get_template(template_id).await?),
+        }
+    }
+
+    async fn list_recon_templates(
+        &self,
+        _caller: &AuthenticatedUser,
+    ) -> Result<Vec<ReconTemplate>, ServiceError> {
+        Ok(self.recon_template_repo.list_templates().await?)
+    }
+
+    async fn update_recon_template(
+        &self,
+        template_id: &str,
+        request: &ReconTemplateRequest,
+        caller: &AuthenticatedUser,
+    ) -> Result<ReconTemplate, ServiceError> {
+        let existing = self.recon_template_repo.get_template(template_id).await?;
+
+        ensure_can_change_template(&existing, caller)?;
+        validate_template_request(request)?;
+
+        //tasks keep pointing at the version they were created from
+        let template = ReconTemplate {
+            name: request.name.clone(),
+            version: existing.version + 1,
+            recon_configurations: request.recon_configurations.clone(),
+            comparison_pairs: request.comparison_pairs.clone(),
+            row_key_separator: request.row_key_separator.clone(),
+            updated_at: Utc::now(),
+            ..existing
+        };
+
+        let _ = self.recon_template_repo.save_template(&template).await?;
+
+        return Ok(template);
+    }
+
+    async fn delete_recon_template(
+        &self,
+        template_id: &str,
+        caller: &AuthenticatedUser,
+    ) -> Result<bool, ServiceError> {
+        let existing = self.recon_template_repo.get_template(template_id).await?;
+
+        ensure_can_change_template(&existing, caller)?;
+
+        return Ok(self.recon_template_repo.delete_template(template_id).await?);
+    }
+}
+
+impl ReconTaskAggregationService {
+    async fn attach_primary_file(
+        &self,
+        request: &AttachPrimaryFileRequest,
+        stored_file_parts: Vec<String>,
+        verification: FileVerification,
+        caller: &AuthenticatedUser,
+    ) -> Result<FileResponseSummary, ServiceError> {
+        //retrieve saved task details
+        let recon_task_details = self.get_recon_task(&request.task_id.clone(), caller).await?;
+        let mut recon_task = recon_task_details.task_details;
+
+        ensure_can_change_task(&recon_task, caller)?;
+        ensure_task_is_not_cancelled(&recon_task)?;
+        ensure_task_has_not_started(&recon_task)?;
+        self.ensure_file_row_count_within_quota(request.primary_file_row_count)?;
+        validate_file_format(
+            &request.primary_file_format,
+            &request.primary_file_delimiters,
+            &request.primary_file_headers,
+            request.primary_file_row_count,
+        )?;
+        validate_column_schema(&request.primary_file_column_schema, &request.primary_file_headers)?;
+        ensure_named_pairs_find_columns(
+            &recon_task,
+            &request.primary_file_headers,
+            |pair| &pair.primary_file_column,
+            "primary",
+        )?;
+
+        //transform into primary file details
+        let mut primary_file_details = self
+            .transformer
+            .get_primary_file_details(request, &caller.user_id);
+        primary_file_details.stored_file_parts = stored_file_parts;
+        primary_file_details.verification = verification;
+
+        //save the file details
+        let primary_file_id = self
+            .recon_file_details_repo
+            .create_recon_file_details(&primary_file_details)
+            .await?;
+
+        recon_task.details.primary_file_id = Some(primary_file_id.clone());
+        recon_task.status = ReconTaskStatus::for_files(
+            Some(&primary_file_details),
+            recon_task_details.comparison_file_metadata.as_ref(),
+        );
+        recon_task.updated_at = Utc::now();
+
+        if let Some(comparison_file) = &recon_task_details.comparison_file_metadata {
+            resolve_comparison_pairs(
+                &mut recon_task,
+                &request.primary_file_headers,
+                &comparison_file.metadata.column_headers,
+            )?;
+            recon_task.column_type_mismatches = column_type_mismatches(
+                &recon_task.details.comparison_pairs,
+                &request.primary_file_column_schema,
+                &comparison_file.column_schema,
+            );
+        }
+
+        //update the task
+        let _ = self.save_task_details(&recon_task).await?;
+
+        self.recon_task_audit_repo
+            .append_audit_entry(&ReconTaskAuditEntry::new(
+                &request.task_id,
+                ReconTaskAction::PrimaryFileAttached,
+                &caller.user_id,
+                Some(primary_file_id.clone()),
+            ))
+            .await?;
+
+        Ok(FileResponseSummary {
+            file_id: primary_file_id.clone(),
+            task_id: request.task_id.clone(),
+        })
+    }
+
+    async fn attach_comparison_file(
+        &self,
+        request: &AttachComparisonFileRequest,
+        stored_file_parts: Vec<String>,
+        verification: FileVerification,
+        caller: &AuthenticatedUser,
+    ) -> Result<FileResponseSummary, ServiceError> {
+        //retrieve saved task details
+        let recon_task_details = self.get_recon_task(&request.task_id.clone(), caller).await?;
+        let mut recon_task = recon_task_details.task_details;
+
+        ensure_can_change_task(&recon_task, caller)?;
+        ensure_task_is_not_cancelled(&recon_task)?;
+        ensure_task_has_not_started(&recon_task)?;
+        self.ensure_file_row_count_within_quota(request.comparison_file_row_count)?;
+        validate_file_format(
+            &request.comparison_file_format,
+            &request.comparison_file_delimiters,
+            &request.comparison_file_headers,
+            request.comparison_file_row_count,
+        )?;
+        validate_column_schema(
+            &request.comparison_file_column_schema,
+            &request.comparison_file_headers,
+        )?;
+        ensure_named_pairs_find_columns(
+            &recon_task,
+            &request.comparison_file_headers,
+            |pair| &pair.comparison_file_column,
+            "comparison",
+        )?;
+
+        //transform into comparison file details
+        let mut comparison_file_details = self
+            .transformer
+            .get_comparison_file_details(request, &caller.user_id);
+        comparison_file_details.stored_file_parts = stored_file_parts;
+        comparison_file_details.verification = verification;
+
+        //save the file details
+        let comparison_file_id = self
+            .recon_file_details_repo
+            .create_recon_file_details(&comparison_file_details)
+            .await?;
+
+        recon_task.details.comparison_file_id = Some(comparison_file_id.clone());
+        recon_task.status = ReconTaskStatus::for_files(
+            recon_task_details.primary_file_metadata.as_ref(),
+            Some(&comparison_file_details),
+        );
+        recon_task.updated_at = Utc::now();
+
+        if let Some(primary_file) = &recon_task_details.primary_file_metadata {
+            resolve_comparison_pairs(
+                &mut recon_task,
+                &primary_file.metadata.column_headers,
+                &request.comparison_file_headers,
+            )?;
+            recon_task.column_type_mismatches = column_type_mismatches(
+                &recon_task.details.comparison_pairs,
+                &primary_file.column_schema,
+                &request.comparison_file_column_schema,
+            );
+        }
+
+        //update the task
+        let _ = self.save_task_details(&recon_task).await?;
+
+        self.recon_task_audit_repo
+            .append_audit_entry(&ReconTaskAuditEntry::new(
+                &request.task_id,
+                ReconTaskAction::ComparisonFileAttached,
+                &caller.user_id,
+                Some(comparison_file_id.clone()),
+            ))
+            .await?;
+
+        Ok(FileResponseSummary {
+            file_id: comparison_file_id.clone(),
+            task_id: request.task_id.clone(),
+        })
+    }
+
+    //checks the task can take a file before any content is saved, then saves the content a part at a time
+    //as it arrives, so an upload is never held in memory whole
+    async fn upload_file(
+        &self,
+        task_id: &str,
+        file_role: UploadedFileRole,
+        file_name: &str,
+        mut content: UploadedContent,
+        caller: &AuthenticatedUser,
+    ) -> Result<FileResponseSummary, ServiceError> {
+        self.ensure_task_can_take_upload(task_id, caller).await?;
+
+        if file_name.trim().is_empty() {
+            return Err(ServiceError::new(
+                AppErrorKind::BadClientRequest,
+                String::from("please supply a file name"),
+            ));
+        }
+
+        let mut inspector = FileInspector::new();
+        let mut stored_file_parts = vec![];
+        let mut part = Vec::with_capacity(UPLOADED_FILE_PART_SIZE_BYTES);
+
+        while let Some(piece) = content.next().await {
+            let piece = piece?;
+            inspector.update(&piece);
+            part.extend_from_slice(&piece);
+
+            //stops reading a file that is already over the quota rather than saving all of it first
+            self.ensure_file_row_count_within_quota(inspector.row_count())?;
+
+            if part.len() >= UPLOADED_FILE_PART_SIZE_BYTES {
+                stored_file_parts.push(self.save_uploaded_file_part(task_id, &part).await?);
+                part.clear();
+            }
+        }
+
+        let inspection = inspector.finish();
+        ensure_uploaded_file_is_not_empty(file_name, &inspection)?;
+        self.ensure_file_row_count_within_quota(inspection.row_count)?;
+
+        if !part.is_empty() {
+            stored_file_parts.push(self.save_uploaded_file_part(task_id, &part).await?);
+        }
+
+        return self
+            .attach_uploaded_file(
+                task_id,
+                file_role,
+                file_name,
+                &inspection,
+                stored_file_parts,
+                caller,
+            )
+            .await;
+    }
+
+    //named by content, so uploading the same file to a task again overwrites rather than duplicates it
+    async fn save_uploaded_file_part(
+        &self,
+        task_id: &str,
+        part: &[u8],
+    ) -> Result<String, ServiceError> {
+        let stored_file_name = format!("{}-{}", task_id, content_hash(part));
+        self.blob_store.save_file(&stored_file_name, part).await?;
+        Ok(stored_file_name)
+    }
+
+    //attaches an uploaded file with the metadata read from its content
+    async fn attach_uploaded_file(
+        &self,
+        task_id: &str,
+        file_role: UploadedFileRole,
+        file_name: &str,
+        inspection: &FileInspection,
+        stored_file_parts: Vec<String>,
+        caller: &AuthenticatedUser,
+    ) -> Result<FileResponseSummary, ServiceError> {
+        //the metadata was read from the stored content, so it holds by construction
+        let verification = FileVerification::verified(&inspection.file_hash, inspection.row_count);
+
+        match file_role {
+            UploadedFileRole::Primary => {
+                let request = AttachPrimaryFileRequest {
+                    task_id: task_id.to_string(),
+                    primary_file_name: file_name.to_string(),
+                    primary_file_hash: inspection.file_hash.clone(),
+                    primary_file_row_count: inspection.row_count,
+                    primary_file_headers: inspection.column_headers.clone(),
+                    primary_file_delimiters: inspection.column_delimiters.clone(),
+                    primary_file_format: uploaded_file_format(inspection),
+                    primary_file_column_schema: vec![],
+                };
+                self.attach_primary_file(&request, stored_file_parts, verification, caller)
+                    .await
+            }
+            UploadedFileRole::Comparison => {
+                let request = AttachComparisonFileRequest {
+                    task_id: task_id.to_string(),
+                    comparison_file_name: file_name.to_string(),
+                    comparison_file_hash: inspection.file_hash.clone(),
+                    comparison_file_row_count: inspection.row_count,
+                    comparison_file_headers: inspection.column_headers.clone(),
+                    comparison_file_delimiters: inspection.column_delimiters.clone(),
+                    comparison_file_format: uploaded_file_format(inspection),
+                    comparison_file_column_schema: vec![],
+                };
+                self.attach_comparison_file(&request, stored_file_parts, verification, caller)
+                    .await
+            }
+        }
+    }
+
+    async fn ensure_task_can_take_upload(
+        &self,
+        task_id: &str,
+        caller: &AuthenticatedUser,
+    ) -> Result<(), ServiceError> {
+        let recon_task = self.get_recon_task(task_id, caller).await?.task_details;
+        ensure_can_change_task(&recon_task, caller)?;
+        ensure_task_is_not_cancelled(&recon_task)?;
+        ensure_task_has_not_started(&recon_task)
+    }
+
+    //files already verified are left alone, a file whose content is not in the blob store stays pending
+    async fn verify_file(
+        &self,
+        task_id: &str,
+        file: Option<ReconFile>,
+    ) -> Result<Option<ReconFile>, ServiceError> {
+        let mut file = match file {
+            Some(file) if !file.verification.is_verified() => file,
+            file => return Ok(file),
+        };
+
+        let mut inspector = FileInspector::new();
+        for part in &stored_content_parts(task_id, &file)? {
+            match self.blob_store.read_file_if_exists(part).await? {
+                Some(content) => inspector.update(&content),
+                None => return Ok(Some(file)),
+            }
+        }
+        let inspection = inspector.finish();
+
+        file.verification = FileVerification::compare(
+            &file.metadata.file_hash,
+            file.metadata.row_count,
+            &inspection.file_hash,
+            inspection.row_count,
+        );
+        file.updated_at = Utc::now();
+
+        let file = self
+            .recon_file_details_repo
+            .update_recon_file_details(&file)
+            .await?;
+
+        Ok(Some(file))
+    }
+
+    //reads the stored parts in order until the content holds at least line_count lines or the file ends
+    async fn read_file_lines(
+        &self,
+        task_id: &str,
+        file: &ReconFile,
+        line_count: usize,
+    ) -> Result<Vec<u8>, ServiceError> {
+        let mut content = vec![];
+
+        for part in &stored_content_parts(task_id, file)? {
+            content.extend(self.blob_store.read_file(part).await?);
+            if complete_line_count(&content) >= line_count {
+                break;
+            }
+        }
+
+        Ok(content)
+    }
+
+    //an upload id from another task is treated as unknown
+    async fn get_task_upload_session(
+        &self,
+        task_id: &str,
+        upload_id: &str,
+    ) -> Result<UploadSession, ServiceError> {
+        let session = self.upload_session_repo.get_upload_session(upload_id).await?;
+
+        if session.task_id != task_id {
+            return Err(ServiceError::new(
+                AppErrorKind::NotFound,
+                format!("task [{}] has no upload [{}]", task_id, upload_id),
+            ));
+        }
+
+        Ok(session)
+    }
+
+    //a task takes its settings either from a template or from the request, never a mix of both
+    async fn read_task_settings(
+        &self,
+        request: &CreateReconTaskRequest,
+    ) -> Result<ReconTaskSettings, ServiceError> {
+        let template_id = match &request.template_id {
+            Some(template_id) => template_id,
+            None => {
+                let recon_configurations = request.recon_configurations.clone().ok_or_else(|| {
+                    ServiceError::new(
+                        AppErrorKind::BadClientRequest,
+                        String::from("please supply either a template_id or the recon_configurations"),
+                    )
+                })?;
+
+                return Ok(ReconTaskSettings {
+                    recon_configurations,
+                    comparison_pairs: request.comparison_pairs.clone(),
+                    row_key_separator: request
+                        .row_key_separator
+                        .clone()
+                        .unwrap_or_else(default_row_key_separator),
+                    template: None,
+                });
+            }
+        };
+
+        if request.recon_configurations.is_some()
+            || !request.comparison_pairs.is_empty()
+            || request.row_key_separator.is_some()
+        {
+            return Err(ServiceError::new(
+                AppErrorKind::BadClientRequest,
+                String::from(
+                    "please supply either a template_id or the recon_configurations, comparison_pairs and row_key_separator, not both",
+                ),
+            ));
+        }
+
+        let template = match self.recon_template_repo.get_template(template_id).await {
+            Ok(template) => template,
+            Err(e) if matches!(e.kind, AppErrorKind::NotFound) => {
+                return Err(ServiceError::new(
+                    AppErrorKind::BadClientRequest,
+                    format!("template [{}] does not exist", template_id),
+                ))
+            }
+            Err(e) => return Err(e.into()),
+        };
+
+        let template_reference = ReconTemplateReference {
+            template_id: template.id.clone(),
+            template_version: template.version,
+        };
+
+        Ok(ReconTaskSettings {
+            recon_configurations: template.recon_configurations,
+            comparison_pairs: template.comparison_pairs,
+            row_key_separator: template.row_key_separator,
+            template: Some(template_reference),
+        })
+    }
+
+    //saves a task that was read earlier, a task changed by another request since is a conflict
+    async fn save_task_details(&self, recon_task: &ReconTask) -> Result<ReconTask, ServiceError> {
+        return match self.recon_task_details_repo.update_task_details(recon_task).await? {
+            Some(saved_task) => Ok(saved_task),
+            None => Err(RejectionReason::Conflict.error(format!(
+                "task [{}] was changed by another request, please read it again and retry",
+                recon_task.details.id
+            ))),
+        };
+    }
+
+    //takes one of the owner's active task slots for the new task, and gives it back if the task cannot be saved
+    async fn create_active_task(&self, recon_task: &ReconTask) -> Result<String, ServiceError> {
+        let owner = &recon_task.created_by;
+        let task_id = &recon_task.details.id;
+        let is_reserved = self
+            .recon_task_details_repo
+            .reserve_active_task_slot(owner, task_id, self.quotas.max_active_tasks_per_user)
+            .await?;
+
+        if !is_reserved {
+            return Err(RejectionReason::TooManyActiveTasks.error(format!(
+                "user [{}] already has the maximum of {} active tasks",
+                owner, self.quotas.max_active_tasks_per_user
+            )));
+        }
+
+        return match self.recon_task_details_repo.create_task_details(recon_task).await {
+            Ok(task_id) => Ok(task_id),
+            Err(e) => {
+                let _ = self
+                    .recon_task_details_repo
+                    .release_active_task_slot(owner, task_id)
+                    .await;
+                Err(e.into())
+            }
+        };
+    }
+
+    //an admin forcing a status can move a task in or out of the active ones,
+    //bringing a task back counts even if the owner is at their quota
+    async fn sync_active_task_slot(
+        &self,
+        recon_task: &ReconTask,
+        was_active: bool,
+    ) -> Result<(), AppError> {
+        let owner = &recon_task.created_by;
+        let task_id = &recon_task.details.id;
+
+        match (was_active, recon_task.is_active()) {
+            (true, false) => {
+                self.recon_task_details_repo
+                    .release_active_task_slot(owner, task_id)
+                    .await
+            }
+            (false, true) => self
+                .recon_task_details_repo
+                .reserve_active_task_slot(owner, task_id, usize::MAX)
+                .await
+                .map(|_| ()),
+            _ => Ok(()),
+        }
+    }
+
+    //saves a copy of the file metadata under a new id and records it in the history of the task
+    async fn copy_recon_file_to_task(
+        &self,
+        source_task_id: &str,
+        source_file_id: &Option<String>,
+        task_id: &str,
+        action: ReconTaskAction,
+        caller: &AuthenticatedUser,
+    ) -> Result<Option<ReconFile>, ServiceError> {
+        let source_file_id = match source_file_id {
+            Some(source_file_id) => source_file_id,
+            None => return Ok(None),
+        };
+
+        let source_file = self
+            .recon_file_details_repo
+            .get_recon_file_details(source_file_id)
+            .await?;
+
+        let mut copied_file = self
+            .transformer
+            .copy_recon_file_details(&source_file, &caller.user_id);
+
+        //a file attached with its metadata only is stored under the source task's id,
+        //so the copy names those parts rather than being looked for under its own task
+        copied_file.stored_file_parts = stored_content_parts(source_task_id, &source_file)?;
+
+        let copied_file_id = self
+            .recon_file_details_repo
+            .create_recon_file_details(&copied_file)
+            .await?;
+
+        self.recon_task_audit_repo
+            .append_audit_entry(&ReconTaskAuditEntry::new(
+                task_id,
+                action,
+                &caller.user_id,
+                Some(copied_file_id.clone()),
+            ))
+            .await?;
+
+        copied_file.metadata.id = copied_file_id;
+        Ok(Some(copied_file))
+    }
+
+    async fn publish_cancellation(
+        &self,
+        recon_task: &ReconTask,
+        cancellation: &ReconTaskCancellation,
+    ) -> Result<(), ServiceError> {
+        let event = ReconTaskEvent::TaskCancelled {
+            task_id: recon_task.details.id.clone(),
+            cancelled_by: cancellation.cancelled_by.clone(),
+            reason: cancellation.reason.clone(),
+            cancelled_at: cancellation.cancelled_at,
+        };
+
+        //the chunking workers listen on the chunks topics, the task's recon_results_queue_info is not used,
+        //workers report on the one results topic every task shares and nothing listens on the per task one
+        for topic in [
+            &recon_task.details.primary_file_chunks_queue_info.topic_id,
+            &recon_task.details.comparison_file_chunks_queue_info.topic_id,
+        ] {
+            self.event_publisher.publish_task_event(topic, &event).await?;
+        }
+
+        Ok(())
+    }
+
+    fn ensure_file_row_count_within_quota(&self, row_count: u64) -> Result<(), ServiceError> {
+        if row_count <= self.quotas.max_file_row_count {
+            return Ok(());
+        }
+
+        Err(RejectionReason::FileTooLarge.error(format!(
+            "file has {} rows, the maximum is {}",
+            row_count, self.quotas.max_file_row_count
+        )))
+    }
+}
+
+//a file attached with its metadata only is looked up in the blob store under its name within the task
+fn stored_content_parts(task_id: &str, file: &ReconFile) -> Result<Vec<String>, ServiceError> {
+    match file.stored_file_parts.is_empty() {
+        true => Ok(vec![attached_file_blob_name(task_id, &file.metadata.file_name)?]),
+        false => Ok(file.stored_file_parts.clone()),
+    }
+}
+
+//the client names the file, so the name must not lead out of the task's own part of the blob store
+fn attached_file_blob_name(task_id: &str, file_name: &str) -> Result<String, ServiceError> {
+    if file_name.contains('/') || file_name.contains('\\') || file_name.contains("..") {
+        return Err(ServiceError::new(
+            AppErrorKind::BadClientRequest,
+            format!("the file name [{}] can not hold a path, please send the name only", file_name),
+        ));
+    }
+
+    Ok(format!("{}-{}", task_id, file_name))
+}
+
+fn ensure_uploaded_file_is_not_empty(
+    file_name: &str,
+    inspection: &FileInspection,
+) -> Result<(), ServiceError> {
+    if inspection.row_count > 0 {
+        return Ok(());
+    }
+
+    Err(ServiceError::new(
+        AppErrorKind::BadClientRequest,
+        format!("the uploaded file [{}] is empty", file_name),
+    ))
+}
+
+fn ensure_upload_session_is_open(session: &UploadSession) -> Result<(), ServiceError> {
+    if session.status == UploadSessionStatus::Open {
+        return Ok(());
+    }
+
+    Err(ServiceError::new(
+        AppErrorKind::BadClientRequest,
+        format!("upload [{}] has already been completed", session.id),
+    ))
+}
+
+//a tab delimited file is read as tsv, anything else the sniffer finds as csv
+fn uploaded_file_format(inspection: &FileInspection) -> FileFormatDescriptor {
+    let layout = match inspection.column_delimiters.as_slice() {
+        ['\t'] => FileLayout::Tsv,
+        _ => FileLayout::default(),
+    };
+
+    FileFormatDescriptor {
+        layout,
+        has_header_row: inspection.has_header_row,
+        ..FileFormatDescriptor::default()
+    }
+}
+
+fn ensure_can_create_tasks(caller: &AuthenticatedUser) -> Result<(), ServiceError> {
+    if caller.has_role(UserRole::Owner) || caller.has_role(UserRole::Admin) {
+        return Ok(());
+    }
+
+    Err(forbidden(String::from("the caller is not allowed to create tasks")))
+}
+
+//viewers and admins can read every task, owners only the tasks they created
+fn ensure_can_read_task(task: &ReconTask, caller: &AuthenticatedUser) -> Result<(), ServiceError> {
+    if caller.has_role(UserRole::Viewer) || caller.has_role(UserRole::Admin) {
+        return Ok(());
+    }
+
+    ensure_task_is_owned_by(task, caller)
+}
+
+fn ensure_can_change_task(task: &ReconTask, caller: &AuthenticatedUser) -> Result<(), ServiceError> {
+    if caller.has_role(UserRole::Admin) {
+        return Ok(());
+    }
+
+    if !caller.has_role(UserRole::Owner) {
+        return Err(forbidden(format!(
+            "the caller is not allowed to change task [{}]",
+            task.details.id
+        )));
+    }
+
+    ensure_task_is_owned_by(task, caller)
+}
+
+//workers save a chunk's rows under the name derived from the chunk, any other name is never read
+fn ensure_results_file_name_is_derived(results: &ReconChunkResults) -> Result<(), ServiceError> {
+    match &results.results_file_name {
+        Some(results_file_name) if *results_file_name != results.derived_results_file_name() => {
+            Err(ServiceError::new(
+                AppErrorKind::BadClientRequest,
+                format!(
+                    "the results file of chunk [{}] of task [{}] must be named [{}], not [{}]",
+                    results.chunk_sequence_number,
+                    results.task_id,
+                    results.derived_results_file_name(),
+                    results_file_name
+                ),
+            ))
+        }
+        _ => Ok(()),
+    }
+}
+
+fn ensure_task_has_not_started(task: &ReconTask) -> Result<(), ServiceError> {
+    if !task.status.has_started() {
+        return Ok(());
+    }
+
+    Err(ServiceError::new(
+        AppErrorKind::BadClientRequest,
+        format!(
+            "task [{}] has already started reconciling, its files and pairs can no longer change",
+            task.details.id
+        ),
+    ))
+}
+
+fn ensure_task_is_not_cancelled(task: &ReconTask) -> Result<(), ServiceError> {
+    if !task.is_cancelled() {
+        return Ok(());
+    }
+
+    Err(ServiceError::new(
+        AppErrorKind::BadClientRequest,
+        format!("task [{}] has been cancelled and no longer accepts files", task.details.id),
+    ))
+}
+
+//checked as each file is attached so a file missing a named column is rejected before it is saved
+fn ensure_named_pairs_find_columns(
+    task: &ReconTask,
+    column_headers: &[String],
+    column_of: fn(&NamedComparisonPair) -> &ColumnReference,
+    file_description: &str,
+) -> Result<(), ServiceError> {
+    let missing_columns: Vec<String> = task
+        .named_comparison_pairs()
+        .iter()
+        .chain(task.accepted_comparison_pairs.iter())
+        .filter(|pair| {
+            column_of(pair)
+                .resolve(column_headers, pair.ignore_header_case)
+                .is_none()
+        })
+        .map(|pair| format!("[{}]", column_of(pair)))
+        .collect();
+
+    if missing_columns.is_empty() {
+        return Ok(());
+    }
+
+    Err(ServiceError::new(
+        AppErrorKind::BadClientRequest,
+        format!(
+            "the {} file has no columns named {}",
+            file_description,
+            missing_columns.join(", ")
+        ),
+    ))
+}
+
+//once both files are attached every header name is known, so the named pairs become plain comparison pairs
+//they are resolved from scratch each time, so a file replaced by one with reordered columns moves its pairs along
+fn resolve_comparison_pairs(
+    task: &mut ReconTask,
+    primary_file_headers: &[String],
+    comparison_file_headers: &[String],
+) -> Result<(), ServiceError> {
+    let named_pairs = task.named_comparison_pairs();
+    let accepted_pairs = task.accepted_comparison_pairs.clone();
+    let index_only_pairs = task.index_only_comparison_pairs().to_vec();
+
+    //rules and row key positions of header based pairs are tied to the indexes they resolved to last time
+    let is_index_only_pair = |primary_file_column_index: usize, comparison_file_column_index: usize| {
+        index_only_pairs.iter().any(|pair| {
+            pair.primary_file_column_index == primary_file_column_index
+                && pair.comparison_file_column_index == comparison_file_column_index
+        })
+    };
+    task.comparison_pair_rules.retain(|pair_rules| {
+        is_index_only_pair(pair_rules.primary_file_column_index, pair_rules.comparison_file_column_index)
+    });
+    task.row_key_positions.retain(|row_key_position| {
+        is_index_only_pair(
+            row_key_position.primary_file_column_index,
+            row_key_position.comparison_file_column_index,
+        )
+    });
+    task.details.comparison_pairs = index_only_pairs;
+    task.resolved_comparison_pairs.clear();
+    task.pending_comparison_pairs.clear();
+
+    for pair in named_pairs {
+        let resolved_pair = pair
+            .resolve(primary_file_headers, comparison_file_headers)
+            .ok_or_else(|| {
+                ServiceError::new(
+                    AppErrorKind::BadClientRequest,
+                    format!(
+                        "unable to find the columns [{}] and [{}] of a comparison pair",
+                        pair.primary_file_column, pair.comparison_file_column
+                    ),
+                )
+            })?;
+
+        task.comparison_pair_rules.extend(pair.rules_for(&resolved_pair));
+        task.row_key_positions.extend(pair.row_key_position_for(&resolved_pair));
+        task.resolved_comparison_pairs.push(ResolvedComparisonPair {
+            named_pair: pair,
+            primary_file_column_index: resolved_pair.primary_file_column_index,
+            comparison_file_column_index: resolved_pair.comparison_file_column_index,
+        });
+        task.details.comparison_pairs.push(resolved_pair);
+    }
+
+    //accepted pairs come last, their indexes are not listed in resolved_comparison_pairs
+    for pair in accepted_pairs {
+        let resolved_pair = pair
+            .resolve(primary_file_headers, comparison_file_headers)
+            .ok_or_else(|| {
+                ServiceError::new(
+                    AppErrorKind::BadClientRequest,
+                    format!(
+                        "unable to find the columns [{}] and [{}] of an accepted comparison pair",
+                        pair.primary_file_column, pair.comparison_file_column
+                    ),
+                )
+            })?;
+
+        task.row_key_positions.extend(pair.row_key_position_for(&resolved_pair));
+        task.details.comparison_pairs.push(resolved_pair);
+    }
+
+    let errors = resolved_row_identifier_errors(&task.details.comparison_pairs);
+    if !errors.is_empty() {
+        return Err(ServiceError::new(AppErrorKind::BadClientRequest, errors.join(" , ")));
+    }
+    Ok(())
+}
+
+//suggestions need the headers of both files, so they only make sense once both are attached
+fn suggest_pairs_for_attached_files(
+    recon_task_details: &ReconTaskResponseDetails,
+) -> Result<Vec<SuggestedComparisonPair>, ServiceError> {
+    match (
+        &recon_task_details.primary_file_metadata,
+        &recon_task_details.comparison_file_metadata,
+    ) {
+        (Some(primary_file), Some(comparison_file)) => Ok(suggest_comparison_pairs(
+            &primary_file.metadata.column_headers,
+            &comparison_file.metadata.column_headers,
+        )),
+        _ => Err(ServiceError::new(
+            AppErrorKind::BadClientRequest,
+            format!(
+                "both files have to be attached to task [{}] before comparison pairs can be suggested",
+                recon_task_details.task_id
+            ),
+        )),
+    }
+}
+
+fn validate_template_request(request: &ReconTemplateRequest) -> Result<(), ServiceError> {
+    if let Err(e) = request.validate() {
+        return Err(ServiceError::new(
+            AppErrorKind::BadClientRequest,
+            e.to_string().replace("\n", " , "),
+        ));
+    }
+
+    validate_comparison_rules(&request.comparison_pairs)?;
+    validate_row_identifiers(&request.comparison_pairs, &request.row_key_separator)
+}
+
+fn validate_row_identifiers(
+    comparison_pairs: &[NamedComparisonPair],
+    row_key_separator: &str,
+) -> Result<(), ServiceError> {
+    let errors = row_identifier_errors(comparison_pairs, row_key_separator);
+    if errors.is_empty() {
+        return Ok(());
+    }
+
+    Err(ServiceError::new(AppErrorKind::BadClientRequest, errors.join(" , ")))
+}
+
+fn validate_file_format(
+    format: &FileFormatDescriptor,
+    column_delimiters: &[char],
+    column_headers: &[String],
+    row_count: u64,
+) -> Result<(), ServiceError> {
+    let errors = format.validation_errors(column_delimiters, column_headers, row_count);
+    if errors.is_empty() {
+        return Ok(());
+    }
+
+    Err(ServiceError::new(AppErrorKind::BadClientRequest, errors.join(" , ")))
+}
+
+fn validate_column_schema(
+    column_schema: &[ColumnSchema],
+    column_headers: &[String],
+) -> Result<(), ServiceError> {
+    let errors = column_schema_errors(column_schema, column_headers);
+    if errors.is_empty() {
+        return Ok(());
+    }
+
+    Err(ServiceError::new(AppErrorKind::BadClientRequest, errors.join(" , ")))
+}
+
+fn validate_comparison_rules(comparison_pairs: &[NamedComparisonPair]) -> Result<(), ServiceError> {
+    let errors: Vec<String> = comparison_pairs
+        .iter()
+        .filter_map(|pair| pair.rules.as_ref())
+        .flat_map(|rules| rules.validation_errors())
+        .collect();
+
+    if errors.is_empty() {
+        return Ok(());
+    }
+
+    Err(ServiceError::new(AppErrorKind::BadClientRequest, errors.join(" , ")))
+}
+
+//admins can change any template, owners only the templates they created
+fn ensure_can_change_template(
+    template: &ReconTemplate,
+    caller: &AuthenticatedUser,
+) -> Result<(), ServiceError> {
+    if caller.has_role(UserRole::Admin) {
+        return Ok(());
+    }
+
+    if caller.has_role(UserRole::Owner) && template.created_by == caller.user_id {
+        return Ok(());
+    }
+
+    Err(forbidden(format!(
+        "the caller is not allowed to change template [{}]",
+        template.id
+    )))
+}
+
+fn ensure_is_admin(caller: &AuthenticatedUser) -> Result<(), ServiceError> {
+    if caller.has_role(UserRole::Admin) {
+        return Ok(());
+    }
+
+    Err(forbidden(String::from("this operation is only available to admins")))
+}
+
+//tasks created before ownership was recorded have no owner, only viewers and admins can reach them
+fn ensure_task_is_owned_by(task: &ReconTask, caller: &AuthenticatedUser) -> Result<(), ServiceError> {
+    if !task.created_by.is_empty() && task.created_by == caller.user_id {
+        return Ok(());
+    }
+
+    Err(forbidden(format!(
+        "task [{}] does not belong to the caller",
+        task.details.id
+    )))
+}
+
+fn forbidden(message: String) -> ServiceError {
+    RejectionReason::Forbidden.error(message)
 }
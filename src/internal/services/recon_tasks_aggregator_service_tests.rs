@@ -1,109 +1,4082 @@
+use std::sync::{Arc, Mutex};
+
+use chrono::{TimeZone, Utc};
+use futures_util::{stream, StreamExt};
+
 use crate::internal::{
     interfaces::{
+        blob_store::MockBlobStoreInterface,
         recon_files_repository::MockReconFileDetailsRepositoryInterface,
-        recon_tasks_aggregator::ReconTaskAggregationServiceInterface,
+        recon_task_audit_repository::MockReconTaskAuditRepositoryInterface,
+        recon_task_event_publisher::MockReconTaskEventPublisherInterface,
+        recon_templates_repository::MockReconTemplateRepositoryInterface,
+        recon_tasks_aggregator::{ReconTaskAggregationServiceInterface, UploadedContent},
         recon_tasks_repository::MockReconTaskDetailsRepositoryInterface,
         transformer::MockTransformerInterface,
+        upload_sessions_repository::MockUploadSessionRepositoryInterface,
     },
-    models::view_models::requests::CreateReconTaskRequest,
-    shared_reconciler_rust_libraries::models::{
+    models::{
         entities::{
-            app_errors::{AppError, AppErrorKind},
-            file_chunk_queue::FileChunkQueue,
-            recon_tasks_models::{
-                ComparisonPair, ReconciliationConfigs, ReconFileMetaData, ReconFileType,
-                ReconTaskDetails,
+            authenticated_user::{AuthenticatedUser, UserRole},
+            column_schema::{ColumnSchema, ColumnType, ColumnTypeMismatch},
+            comparison_rules::{ComparisonRules, NumericTolerance},
+            file_format::{FileFormatDescriptor, FileLayout},
+            file_verification::{FileVerification, FileVerificationStatus},
+            named_comparison_pair::{ColumnReference, NamedComparisonPair, ResolvedComparisonPair},
+            recon_file::ReconFile,
+            recon_results::{ReconChunkResults, ReconResultCounts, ReconResultsSummary},
+            recon_task::{ReconTask, ReconTaskCancellation, ReconTaskStart, ReconTaskStatus},
+            recon_task_audit::{ReconTaskAction, ReconTaskAuditEntry},
+            recon_task_event::ReconTaskEvent,
+            recon_template::{ReconTemplate, ReconTemplateReference},
+            rejection_reason::RejectionReason,
+            service_error::ServiceError,
+            row_key::RowKeyDefinition,
+            upload_session::{UploadSession, UploadSessionStatus, UploadedFileRole},
+        },
+        view_models::{
+            requests::{
+                AttachComparisonFileRequest, AttachPrimaryFileRequest, CancelReconTaskRequest,
+                CloneReconTaskRequest, CompleteUploadRequest, CreateReconTaskRequest,
+                ForceTaskStatusRequest, ReconTemplateRequest,
             },
+            responses::ReconTaskResponseDetails,
+        },
+    },
+    services::core_logic::file_inspector::content_hash,
+    shared_reconciler_rust_libraries::models::entities::{
+        app_errors::{AppError, AppErrorKind},
+        file_chunk_queue::FileChunkQueue,
+        recon_tasks_models::{
+            ComparisonPair, ReconciliationConfigs, ReconFileMetaData, ReconFileType,
+            ReconTaskDetails,
         },
-        view_models::recon_task_response_details::ReconTaskResponseDetails,
     },
 };
 
-use super::recon_tasks_aggregator_service::ReconTaskAggregationService;
+use super::recon_tasks_aggregator_service::{ReconTaskAggregationService, ReconTaskQuotas};
+
+#[actix_web::test]
+async fn given_valid_create_recon_task_request_calls_correct_dependencies_returns_success() {
+    //setup
+    let (
+        mock_recon_task_details_repo,
+        mock_recon_file_details_repo,
+        mock_recon_task_audit_repo,
+        mock_transformer,
+    ) = setup_dependencies();
+
+    let service = ReconTaskAggregationService {
+        recon_task_details_repo: mock_recon_task_details_repo,
+        recon_file_details_repo: mock_recon_file_details_repo,
+        recon_task_audit_repo: mock_recon_task_audit_repo,
+        recon_template_repo: Box::new(MockReconTemplateRepositoryInterface::new()),
+        transformer: mock_transformer,
+        event_publisher: Box::new(MockReconTaskEventPublisherInterface::new()),
+        upload_session_repo: Box::new(MockUploadSessionRepositoryInterface::new()),
+        blob_store: Box::new(MockBlobStoreInterface::new()),
+        quotas: get_dummy_quotas(),
+        recon_results_topic: String::from("recon-results"),
+    };
+
+    let test_request = get_dummy_create_recon_task();
+
+    let expected = String::from("task-1234");
+
+    //act
+    let result = service.create_recon_task(&test_request, &get_dummy_owner()).await;
+
+    //assert
+    assert!(result.is_ok());
+    assert_eq!(result.ok().unwrap().task_id, expected);
+}
+
+#[actix_web::test]
+async fn given_that_invalid_create_recon_task_request_supplied_returns_error() {
+    //setup
+    let (
+        mock_recon_task_details_repo,
+        mock_recon_file_details_repo,
+        mock_recon_task_audit_repo,
+        mock_transformer,
+    ) = setup_dependencies();
+
+    let service = ReconTaskAggregationService {
+        recon_task_details_repo: mock_recon_task_details_repo,
+        recon_file_details_repo: mock_recon_file_details_repo,
+        recon_task_audit_repo: mock_recon_task_audit_repo,
+        recon_template_repo: Box::new(MockReconTemplateRepositoryInterface::new()),
+        transformer: mock_transformer,
+        event_publisher: Box::new(MockReconTaskEventPublisherInterface::new()),
+        upload_session_repo: Box::new(MockUploadSessionRepositoryInterface::new()),
+        blob_store: Box::new(MockBlobStoreInterface::new()),
+        quotas: get_dummy_quotas(),
+        recon_results_topic: String::from("recon-results"),
+    };
+
+    let mut test_request = get_dummy_create_recon_task();
+    test_request.user_id = String::from("");
+
+    //act
+    let result = service.create_recon_task(&test_request, &get_dummy_owner()).await;
+
+    //assert
+    assert!(result.is_err())
+}
+
+#[actix_web::test]
+async fn given_that_errors_occurs_when_handling_create_recon_task_request_returns_error() {
+    //setup
+    let (_, mock_recon_file_details_repo, mock_recon_task_audit_repo, mock_transformer) =
+        setup_dependencies();
+
+    let mut mock_recon_task_details_repo = Box::new(MockReconTaskDetailsRepositoryInterface::new());
+    mock_recon_task_details_repo
+        .expect_reserve_active_task_slot()
+        .returning(|_, _, _| Ok(true));
+    mock_recon_task_details_repo
+        .expect_create_task_details()
+        .returning(|_y| {
+            Err(AppError::new(
+                AppErrorKind::ConnectionError,
+                "unable to connect".to_string(),
+            ))
+        });
+    //the task was never saved, so its slot is given back
+    mock_recon_task_details_repo
+        .expect_release_active_task_slot()
+        .times(1)
+        .returning(|_, _| Ok(()));
+
+    let service = ReconTaskAggregationService {
+        recon_task_details_repo: mock_recon_task_details_repo,
+        recon_file_details_repo: mock_recon_file_details_repo,
+        recon_task_audit_repo: mock_recon_task_audit_repo,
+        recon_template_repo: Box::new(MockReconTemplateRepositoryInterface::new()),
+        transformer: mock_transformer,
+        event_publisher: Box::new(MockReconTaskEventPublisherInterface::new()),
+        upload_session_repo: Box::new(MockUploadSessionRepositoryInterface::new()),
+        blob_store: Box::new(MockBlobStoreInterface::new()),
+        quotas: get_dummy_quotas(),
+        recon_results_topic: String::from("recon-results"),
+    };
+
+    let test_request = get_dummy_create_recon_task();
+
+    //act
+    let result = service.create_recon_task(&test_request, &get_dummy_owner()).await;
+
+    //assert
+    assert!(result.is_err())
+}
+
+#[actix_web::test]
+async fn given_valid_create_recon_task_request_records_task_created_in_history() {
+    //setup
+    let (mock_recon_task_details_repo, mock_recon_file_details_repo, _, mock_transformer) =
+        setup_dependencies();
+
+    let mut mock_recon_task_audit_repo = Box::new(MockReconTaskAuditRepositoryInterface::new());
+    mock_recon_task_audit_repo
+        .expect_append_audit_entry()
+        .withf(|entry| {
+            entry.action == ReconTaskAction::TaskCreated && entry.performed_by == "test-user-id"
+        })
+        .times(1)
+        .returning(|_| Ok(()));
+
+    let service = ReconTaskAggregationService {
+        recon_task_details_repo: mock_recon_task_details_repo,
+        recon_file_details_repo: mock_recon_file_details_repo,
+        recon_task_audit_repo: mock_recon_task_audit_repo,
+        recon_template_repo: Box::new(MockReconTemplateRepositoryInterface::new()),
+        transformer: mock_transformer,
+        event_publisher: Box::new(MockReconTaskEventPublisherInterface::new()),
+        upload_session_repo: Box::new(MockUploadSessionRepositoryInterface::new()),
+        blob_store: Box::new(MockBlobStoreInterface::new()),
+        quotas: get_dummy_quotas(),
+        recon_results_topic: String::from("recon-results"),
+    };
+
+    let test_request = get_dummy_create_recon_task();
+
+    //act
+    let result = service.create_recon_task(&test_request, &get_dummy_owner()).await;
+
+    //assert
+    assert!(result.is_ok());
+}
+
+#[actix_web::test]
+async fn given_valid_attach_primary_file_request_records_file_attached_in_history() {
+    //setup
+    let (mock_recon_task_details_repo, mock_recon_file_details_repo, _, mock_transformer) =
+        setup_dependencies();
+
+    let mut mock_recon_task_audit_repo = Box::new(MockReconTaskAuditRepositoryInterface::new());
+    mock_recon_task_audit_repo
+        .expect_append_audit_entry()
+        .withf(|entry| {
+            entry.action == ReconTaskAction::PrimaryFileAttached
+                && entry.details == Some(String::from("file-1234"))
+        })
+        .times(1)
+        .returning(|_| Ok(()));
+
+    let service = ReconTaskAggregationService {
+        recon_task_details_repo: mock_recon_task_details_repo,
+        recon_file_details_repo: mock_recon_file_details_repo,
+        recon_task_audit_repo: mock_recon_task_audit_repo,
+        recon_template_repo: Box::new(MockReconTemplateRepositoryInterface::new()),
+        transformer: mock_transformer,
+        event_publisher: Box::new(MockReconTaskEventPublisherInterface::new()),
+        upload_session_repo: Box::new(MockUploadSessionRepositoryInterface::new()),
+        blob_store: Box::new(MockBlobStoreInterface::new()),
+        quotas: get_dummy_quotas(),
+        recon_results_topic: String::from("recon-results"),
+    };
+
+    //act
+    let result = service
+        .attach_primary_file_to_task(&get_dummy_attach_primary_file_request(), &get_dummy_owner())
+        .await;
+
+    //assert
+    assert_eq!(result.unwrap().file_id, String::from("file-1234"));
+}
+
+#[actix_web::test]
+async fn given_existing_task_get_recon_task_history_returns_audit_entries() {
+    //setup
+    let (
+        mock_recon_task_details_repo,
+        mock_recon_file_details_repo,
+        mock_recon_task_audit_repo,
+        mock_transformer,
+    ) = setup_dependencies();
+
+    let service = ReconTaskAggregationService {
+        recon_task_details_repo: mock_recon_task_details_repo,
+        recon_file_details_repo: mock_recon_file_details_repo,
+        recon_task_audit_repo: mock_recon_task_audit_repo,
+        recon_template_repo: Box::new(MockReconTemplateRepositoryInterface::new()),
+        transformer: mock_transformer,
+        event_publisher: Box::new(MockReconTaskEventPublisherInterface::new()),
+        upload_session_repo: Box::new(MockUploadSessionRepositoryInterface::new()),
+        blob_store: Box::new(MockBlobStoreInterface::new()),
+        quotas: get_dummy_quotas(),
+        recon_results_topic: String::from("recon-results"),
+    };
+
+    //act
+    let result = service
+        .get_recon_task_history(&String::from("task-1234"), &get_dummy_owner())
+        .await;
+
+    //assert
+    assert_eq!(result.unwrap().len(), 1);
+}
+
+#[actix_web::test]
+async fn given_unknown_task_get_recon_task_history_returns_not_found() {
+    //setup
+    let (_, mock_recon_file_details_repo, mock_recon_task_audit_repo, mock_transformer) =
+        setup_dependencies();
+
+    let mut mock_recon_task_details_repo = Box::new(MockReconTaskDetailsRepositoryInterface::new());
+    mock_recon_task_details_repo
+        .expect_get_task_details()
+        .returning(|_| Err(AppError::new(AppErrorKind::NotFound, String::from("missing"))));
+
+    let service = ReconTaskAggregationService {
+        recon_task_details_repo: mock_recon_task_details_repo,
+        recon_file_details_repo: mock_recon_file_details_repo,
+        recon_task_audit_repo: mock_recon_task_audit_repo,
+        recon_template_repo: Box::new(MockReconTemplateRepositoryInterface::new()),
+        transformer: mock_transformer,
+        event_publisher: Box::new(MockReconTaskEventPublisherInterface::new()),
+        upload_session_repo: Box::new(MockUploadSessionRepositoryInterface::new()),
+        blob_store: Box::new(MockBlobStoreInterface::new()),
+        quotas: get_dummy_quotas(),
+        recon_results_topic: String::from("recon-results"),
+    };
+
+    //act
+    let result = service
+        .get_recon_task_history(&String::from("task-unknown"), &get_dummy_owner())
+        .await;
+
+    //assert
+    assert!(matches!(result.err().unwrap().kind, AppErrorKind::NotFound));
+}
+
+#[actix_web::test]
+async fn given_task_owned_by_another_user_get_recon_task_returns_forbidden() {
+    //setup
+    let (
+        mock_recon_task_details_repo,
+        mock_recon_file_details_repo,
+        mock_recon_task_audit_repo,
+        mock_transformer,
+    ) = setup_dependencies();
+
+    let service = ReconTaskAggregationService {
+        recon_task_details_repo: mock_recon_task_details_repo,
+        recon_file_details_repo: mock_recon_file_details_repo,
+        recon_task_audit_repo: mock_recon_task_audit_repo,
+        recon_template_repo: Box::new(MockReconTemplateRepositoryInterface::new()),
+        transformer: mock_transformer,
+        event_publisher: Box::new(MockReconTaskEventPublisherInterface::new()),
+        upload_session_repo: Box::new(MockUploadSessionRepositoryInterface::new()),
+        blob_store: Box::new(MockBlobStoreInterface::new()),
+        quotas: get_dummy_quotas(),
+        recon_results_topic: String::from("recon-results"),
+    };
+
+    //act
+    let result = service
+        .get_recon_task(&String::from("task-1234"), &AuthenticatedUser::new("another-user-id"))
+        .await;
+
+    //assert
+    assert_eq!(
+        result.err().unwrap().rejection,
+        Some(RejectionReason::Forbidden)
+    );
+}
+
+#[actix_web::test]
+async fn given_task_owned_by_another_user_attach_primary_file_returns_forbidden_without_saving() {
+    //setup
+    let (mock_recon_task_details_repo, _, mock_recon_task_audit_repo, mock_transformer) =
+        setup_dependencies();
+
+    let mut mock_recon_file_details_repo = Box::new(MockReconFileDetailsRepositoryInterface::new());
+    mock_recon_file_details_repo
+        .expect_create_recon_file_details()
+        .times(0);
+
+    let service = ReconTaskAggregationService {
+        recon_task_details_repo: mock_recon_task_details_repo,
+        recon_file_details_repo: mock_recon_file_details_repo,
+        recon_task_audit_repo: mock_recon_task_audit_repo,
+        recon_template_repo: Box::new(MockReconTemplateRepositoryInterface::new()),
+        transformer: mock_transformer,
+        event_publisher: Box::new(MockReconTaskEventPublisherInterface::new()),
+        upload_session_repo: Box::new(MockUploadSessionRepositoryInterface::new()),
+        blob_store: Box::new(MockBlobStoreInterface::new()),
+        quotas: get_dummy_quotas(),
+        recon_results_topic: String::from("recon-results"),
+    };
+
+    //act
+    let result = service
+        .attach_primary_file_to_task(
+            &get_dummy_attach_primary_file_request(),
+            &AuthenticatedUser::new("another-user-id"),
+        )
+        .await;
+
+    //assert
+    assert_eq!(
+        result.err().unwrap().rejection,
+        Some(RejectionReason::Forbidden)
+    );
+}
+
+#[actix_web::test]
+async fn given_task_without_owner_get_recon_task_history_returns_forbidden() {
+    //setup
+    let (_, mock_recon_file_details_repo, mock_recon_task_audit_repo, mock_transformer) =
+        setup_dependencies();
+
+    let mut mock_recon_task_details_repo = Box::new(MockReconTaskDetailsRepositoryInterface::new());
+    mock_recon_task_details_repo
+        .expect_get_task_details()
+        .returning(|_| {
+            let mut legacy_task = get_dummy_recon_task();
+            legacy_task.created_by = String::from("");
+            Ok(legacy_task)
+        });
+
+    let service = ReconTaskAggregationService {
+        recon_task_details_repo: mock_recon_task_details_repo,
+        recon_file_details_repo: mock_recon_file_details_repo,
+        recon_task_audit_repo: mock_recon_task_audit_repo,
+        recon_template_repo: Box::new(MockReconTemplateRepositoryInterface::new()),
+        transformer: mock_transformer,
+        event_publisher: Box::new(MockReconTaskEventPublisherInterface::new()),
+        upload_session_repo: Box::new(MockUploadSessionRepositoryInterface::new()),
+        blob_store: Box::new(MockBlobStoreInterface::new()),
+        quotas: get_dummy_quotas(),
+        recon_results_topic: String::from("recon-results"),
+    };
+
+    //act
+    let result = service
+        .get_recon_task_history(&String::from("task-1234"), &AuthenticatedUser::new(""))
+        .await;
+
+    //assert
+    assert_eq!(
+        result.err().unwrap().rejection,
+        Some(RejectionReason::Forbidden)
+    );
+}
+
+#[actix_web::test]
+async fn given_viewer_create_recon_task_returns_forbidden() {
+    //setup
+    let (_, mock_recon_file_details_repo, mock_recon_task_audit_repo, mock_transformer) =
+        setup_dependencies();
+
+    let mut mock_recon_task_details_repo = Box::new(MockReconTaskDetailsRepositoryInterface::new());
+    mock_recon_task_details_repo
+        .expect_create_task_details()
+        .times(0);
+
+    let service = ReconTaskAggregationService {
+        recon_task_details_repo: mock_recon_task_details_repo,
+        recon_file_details_repo: mock_recon_file_details_repo,
+        recon_task_audit_repo: mock_recon_task_audit_repo,
+        recon_template_repo: Box::new(MockReconTemplateRepositoryInterface::new()),
+        transformer: mock_transformer,
+        event_publisher: Box::new(MockReconTaskEventPublisherInterface::new()),
+        upload_session_repo: Box::new(MockUploadSessionRepositoryInterface::new()),
+        blob_store: Box::new(MockBlobStoreInterface::new()),
+        quotas: get_dummy_quotas(),
+        recon_results_topic: String::from("recon-results"),
+    };
+
+    //act
+    let result = service
+        .create_recon_task(&get_dummy_create_recon_task(), &get_dummy_user_with_role(UserRole::Viewer))
+        .await;
+
+    //assert
+    assert_eq!(
+        result.err().unwrap().rejection,
+        Some(RejectionReason::Forbidden)
+    );
+}
+
+#[actix_web::test]
+async fn given_viewer_get_recon_task_owned_by_another_user_returns_task() {
+    //setup
+    let (
+        mock_recon_task_details_repo,
+        mock_recon_file_details_repo,
+        mock_recon_task_audit_repo,
+        mock_transformer,
+    ) = setup_dependencies();
+
+    let service = ReconTaskAggregationService {
+        recon_task_details_repo: mock_recon_task_details_repo,
+        recon_file_details_repo: mock_recon_file_details_repo,
+        recon_task_audit_repo: mock_recon_task_audit_repo,
+        recon_template_repo: Box::new(MockReconTemplateRepositoryInterface::new()),
+        transformer: mock_transformer,
+        event_publisher: Box::new(MockReconTaskEventPublisherInterface::new()),
+        upload_session_repo: Box::new(MockUploadSessionRepositoryInterface::new()),
+        blob_store: Box::new(MockBlobStoreInterface::new()),
+        quotas: get_dummy_quotas(),
+        recon_results_topic: String::from("recon-results"),
+    };
+
+    //act
+    let result = service
+        .get_recon_task(&String::from("task-1234"), &get_dummy_user_with_role(UserRole::Viewer))
+        .await;
+
+    //assert
+    assert!(result.is_ok());
+}
+
+#[actix_web::test]
+async fn given_viewer_attach_primary_file_returns_forbidden() {
+    //setup
+    let (mock_recon_task_details_repo, _, mock_recon_task_audit_repo, mock_transformer) =
+        setup_dependencies();
+
+    let mut mock_recon_file_details_repo = Box::new(MockReconFileDetailsRepositoryInterface::new());
+    mock_recon_file_details_repo
+        .expect_get_recon_file_details()
+        .returning(|_| Ok(get_dummy_recon_file()));
+    mock_recon_file_details_repo
+        .expect_create_recon_file_details()
+        .times(0);
+
+    let service = ReconTaskAggregationService {
+        recon_task_details_repo: mock_recon_task_details_repo,
+        recon_file_details_repo: mock_recon_file_details_repo,
+        recon_task_audit_repo: mock_recon_task_audit_repo,
+        recon_template_repo: Box::new(MockReconTemplateRepositoryInterface::new()),
+        transformer: mock_transformer,
+        event_publisher: Box::new(MockReconTaskEventPublisherInterface::new()),
+        upload_session_repo: Box::new(MockUploadSessionRepositoryInterface::new()),
+        blob_store: Box::new(MockBlobStoreInterface::new()),
+        quotas: get_dummy_quotas(),
+        recon_results_topic: String::from("recon-results"),
+    };
+
+    //act
+    let result = service
+        .attach_primary_file_to_task(
+            &get_dummy_attach_primary_file_request(),
+            &get_dummy_user_with_role(UserRole::Viewer),
+        )
+        .await;
+
+    //assert
+    assert_eq!(
+        result.err().unwrap().rejection,
+        Some(RejectionReason::Forbidden)
+    );
+}
+
+#[actix_web::test]
+async fn given_admin_attach_primary_file_to_another_users_task_returns_success() {
+    //setup
+    let (
+        mock_recon_task_details_repo,
+        mock_recon_file_details_repo,
+        mock_recon_task_audit_repo,
+        mock_transformer,
+    ) = setup_dependencies();
+
+    let service = ReconTaskAggregationService {
+        recon_task_details_repo: mock_recon_task_details_repo,
+        recon_file_details_repo: mock_recon_file_details_repo,
+        recon_task_audit_repo: mock_recon_task_audit_repo,
+        recon_template_repo: Box::new(MockReconTemplateRepositoryInterface::new()),
+        transformer: mock_transformer,
+        event_publisher: Box::new(MockReconTaskEventPublisherInterface::new()),
+        upload_session_repo: Box::new(MockUploadSessionRepositoryInterface::new()),
+        blob_store: Box::new(MockBlobStoreInterface::new()),
+        quotas: get_dummy_quotas(),
+        recon_results_topic: String::from("recon-results"),
+    };
+
+    //act
+    let result = service
+        .attach_primary_file_to_task(
+            &get_dummy_attach_primary_file_request(),
+            &get_dummy_user_with_role(UserRole::Admin),
+        )
+        .await;
+
+    //assert
+    assert!(result.is_ok());
+}
+
+#[actix_web::test]
+async fn given_owner_list_all_recon_tasks_returns_forbidden() {
+    //setup
+    let (_, mock_recon_file_details_repo, mock_recon_task_audit_repo, mock_transformer) =
+        setup_dependencies();
+
+    let mut mock_recon_task_details_repo = Box::new(MockReconTaskDetailsRepositoryInterface::new());
+    mock_recon_task_details_repo
+        .expect_reserve_active_task_slot()
+        .times(0);
+
+    let service = ReconTaskAggregationService {
+        recon_task_details_repo: mock_recon_task_details_repo,
+        recon_file_details_repo: mock_recon_file_details_repo,
+        recon_task_audit_repo: mock_recon_task_audit_repo,
+        recon_template_repo: Box::new(MockReconTemplateRepositoryInterface::new()),
+        transformer: mock_transformer,
+        event_publisher: Box::new(MockReconTaskEventPublisherInterface::new()),
+        upload_session_repo: Box::new(MockUploadSessionRepositoryInterface::new()),
+        blob_store: Box::new(MockBlobStoreInterface::new()),
+        quotas: get_dummy_quotas(),
+        recon_results_topic: String::from("recon-results"),
+    };
+
+    //act
+    let result = service.list_all_recon_tasks(&get_dummy_owner()).await;
+
+    //assert
+    assert_eq!(
+        result.err().unwrap().rejection,
+        Some(RejectionReason::Forbidden)
+    );
+}
+
+#[actix_web::test]
+async fn given_admin_list_all_recon_tasks_returns_every_task() {
+    //setup
+    let (_, mock_recon_file_details_repo, mock_recon_task_audit_repo, mock_transformer) =
+        setup_dependencies();
+
+    let mut mock_recon_task_details_repo = Box::new(MockReconTaskDetailsRepositoryInterface::new());
+    mock_recon_task_details_repo
+        .expect_list_task_details()
+        .returning(|| Ok(vec![get_dummy_recon_task(), get_dummy_recon_task()]));
+
+    let service = ReconTaskAggregationService {
+        recon_task_details_repo: mock_recon_task_details_repo,
+        recon_file_details_repo: mock_recon_file_details_repo,
+        recon_task_audit_repo: mock_recon_task_audit_repo,
+        recon_template_repo: Box::new(MockReconTemplateRepositoryInterface::new()),
+        transformer: mock_transformer,
+        event_publisher: Box::new(MockReconTaskEventPublisherInterface::new()),
+        upload_session_repo: Box::new(MockUploadSessionRepositoryInterface::new()),
+        blob_store: Box::new(MockBlobStoreInterface::new()),
+        quotas: get_dummy_quotas(),
+        recon_results_topic: String::from("recon-results"),
+    };
+
+    //act
+    let result = service
+        .list_all_recon_tasks(&get_dummy_user_with_role(UserRole::Admin))
+        .await;
+
+    //assert
+    assert_eq!(result.unwrap().len(), 2);
+}
+
+#[actix_web::test]
+async fn given_admin_force_recon_task_status_saves_status_and_records_it_in_history() {
+    //setup
+    let (_, mock_recon_file_details_repo, _, mock_transformer) = setup_dependencies();
+
+    let mut mock_recon_task_details_repo = Box::new(MockReconTaskDetailsRepositoryInterface::new());
+    mock_recon_task_details_repo
+        .expect_get_task_details()
+        .returning(|_| Ok(get_dummy_recon_task()));
+    mock_recon_task_details_repo
+        .expect_update_task_details()
+        .withf(|task| {
+            task.details.is_done
+                && task.details.has_begun
+                && task.status == ReconTaskStatus::Completed
+        })
+        .times(1)
+        .returning(|task| Ok(Some(task.clone())));
+    mock_recon_task_details_repo
+        .expect_release_active_task_slot()
+        .withf(|user_id, task_id| user_id == "test-user-id" && task_id == "task-1234")
+        .times(1)
+        .returning(|_, _| Ok(()));
+
+    let mut mock_recon_task_audit_repo = Box::new(MockReconTaskAuditRepositoryInterface::new());
+    mock_recon_task_audit_repo
+        .expect_append_audit_entry()
+        .withf(|entry| entry.action == ReconTaskAction::StatusForced && entry.performed_by == "admin-user-id")
+        .times(1)
+        .returning(|_| Ok(()));
+
+    let service = ReconTaskAggregationService {
+        recon_task_details_repo: mock_recon_task_details_repo,
+        recon_file_details_repo: mock_recon_file_details_repo,
+        recon_task_audit_repo: mock_recon_task_audit_repo,
+        recon_template_repo: Box::new(MockReconTemplateRepositoryInterface::new()),
+        transformer: mock_transformer,
+        event_publisher: Box::new(MockReconTaskEventPublisherInterface::new()),
+        upload_session_repo: Box::new(MockUploadSessionRepositoryInterface::new()),
+        blob_store: Box::new(MockBlobStoreInterface::new()),
+        quotas: get_dummy_quotas(),
+        recon_results_topic: String::from("recon-results"),
+    };
+
+    //act
+    let result = service
+        .force_recon_task_status(
+            &String::from("task-1234"),
+            &ForceTaskStatusRequest {
+                has_begun: false,
+                is_done: true,
+            },
+            &get_dummy_user_with_role(UserRole::Admin),
+        )
+        .await;
+
+    //assert
+    assert!(result.is_ok());
+}
+
+#[actix_web::test]
+async fn given_task_changed_after_it_was_read_force_recon_task_status_returns_conflict_and_keeps_the_slot() {
+    //setup
+    let (_, mock_recon_file_details_repo, _, mock_transformer) = setup_dependencies();
+
+    let mut mock_recon_task_details_repo = Box::new(MockReconTaskDetailsRepositoryInterface::new());
+    mock_recon_task_details_repo
+        .expect_get_task_details()
+        .returning(|_| Ok(get_dummy_recon_task()));
+    mock_recon_task_details_repo
+        .expect_update_task_details()
+        .times(1)
+        .returning(|_| Ok(None));
+    mock_recon_task_details_repo.expect_release_active_task_slot().times(0);
+
+    let mut mock_recon_task_audit_repo = Box::new(MockReconTaskAuditRepositoryInterface::new());
+    mock_recon_task_audit_repo.expect_append_audit_entry().times(0);
+
+    let service = ReconTaskAggregationService {
+        recon_task_details_repo: mock_recon_task_details_repo,
+        recon_file_details_repo: mock_recon_file_details_repo,
+        recon_task_audit_repo: mock_recon_task_audit_repo,
+        recon_template_repo: Box::new(MockReconTemplateRepositoryInterface::new()),
+        transformer: mock_transformer,
+        event_publisher: Box::new(MockReconTaskEventPublisherInterface::new()),
+        upload_session_repo: Box::new(MockUploadSessionRepositoryInterface::new()),
+        blob_store: Box::new(MockBlobStoreInterface::new()),
+        quotas: get_dummy_quotas(),
+        recon_results_topic: String::from("recon-results"),
+    };
+
+    //act
+    let result = service
+        .force_recon_task_status(
+            &String::from("task-1234"),
+            &ForceTaskStatusRequest {
+                has_begun: false,
+                is_done: true,
+            },
+            &get_dummy_user_with_role(UserRole::Admin),
+        )
+        .await;
+
+    //assert
+    assert_eq!(result.err().unwrap().rejection, Some(RejectionReason::Conflict));
+}
+
+#[actix_web::test]
+async fn given_admin_forcing_a_task_back_to_reconciling_force_recon_task_status_sets_the_status_with_the_flags() {
+    //setup
+    let (_, mock_recon_file_details_repo, mock_recon_task_audit_repo, mock_transformer) =
+        setup_dependencies();
+
+    let mut mock_recon_task_details_repo = Box::new(MockReconTaskDetailsRepositoryInterface::new());
+    mock_recon_task_details_repo.expect_get_task_details().returning(|_| {
+        let mut task = get_dummy_recon_task();
+        task.details.has_begun = true;
+        task.details.is_done = true;
+        task.status = ReconTaskStatus::Completed;
+        Ok(task)
+    });
+    mock_recon_task_details_repo
+        .expect_update_task_details()
+        .withf(|task| {
+            task.details.has_begun
+                && !task.details.is_done
+                && task.status == ReconTaskStatus::Reconciling
+        })
+        .times(1)
+        .returning(|task| Ok(Some(task.clone())));
+    mock_recon_task_details_repo
+        .expect_reserve_active_task_slot()
+        .returning(|_, _, _| Ok(true));
+
+    let service = ReconTaskAggregationService {
+        recon_task_details_repo: mock_recon_task_details_repo,
+        recon_file_details_repo: mock_recon_file_details_repo,
+        recon_task_audit_repo: mock_recon_task_audit_repo,
+        recon_template_repo: Box::new(MockReconTemplateRepositoryInterface::new()),
+        transformer: mock_transformer,
+        event_publisher: Box::new(MockReconTaskEventPublisherInterface::new()),
+        upload_session_repo: Box::new(MockUploadSessionRepositoryInterface::new()),
+        blob_store: Box::new(MockBlobStoreInterface::new()),
+        quotas: get_dummy_quotas(),
+        recon_results_topic: String::from("recon-results"),
+    };
+
+    //act
+    let result = service
+        .force_recon_task_status(
+            &String::from("task-1234"),
+            &ForceTaskStatusRequest {
+                has_begun: true,
+                is_done: false,
+            },
+            &get_dummy_user_with_role(UserRole::Admin),
+        )
+        .await;
+
+    //assert
+    assert!(result.is_ok());
+}
+
+#[actix_web::test]
+async fn given_cancelled_task_force_recon_task_status_returns_bad_client_request_without_saving() {
+    //setup
+    let (_, mock_recon_file_details_repo, mock_recon_task_audit_repo, _) = setup_dependencies();
+
+    let mut mock_transformer = Box::new(MockTransformerInterface::new());
+    mock_transformer
+        .expect_build_recon_task_details_response()
+        .returning(|_, _, _| {
+            let mut response = get_dummy_recon_task_response_details();
+            response.task_details = get_dummy_cancelled_recon_task();
+            response
+        });
+
+    let mut mock_recon_task_details_repo = Box::new(MockReconTaskDetailsRepositoryInterface::new());
+    mock_recon_task_details_repo
+        .expect_get_task_details()
+        .returning(|_| Ok(get_dummy_cancelled_recon_task()));
+    mock_recon_task_details_repo.expect_update_task_details().times(0);
+
+    let service = ReconTaskAggregationService {
+        recon_task_details_repo: mock_recon_task_details_repo,
+        recon_file_details_repo: mock_recon_file_details_repo,
+        recon_task_audit_repo: mock_recon_task_audit_repo,
+        recon_template_repo: Box::new(MockReconTemplateRepositoryInterface::new()),
+        transformer: mock_transformer,
+        event_publisher: Box::new(MockReconTaskEventPublisherInterface::new()),
+        upload_session_repo: Box::new(MockUploadSessionRepositoryInterface::new()),
+        blob_store: Box::new(MockBlobStoreInterface::new()),
+        quotas: get_dummy_quotas(),
+        recon_results_topic: String::from("recon-results"),
+    };
+
+    //act
+    let result = service
+        .force_recon_task_status(
+            &String::from("task-1234"),
+            &ForceTaskStatusRequest {
+                has_begun: true,
+                is_done: true,
+            },
+            &get_dummy_user_with_role(UserRole::Admin),
+        )
+        .await;
+
+    //assert
+    assert!(matches!(result.err().unwrap().kind, AppErrorKind::BadClientRequest));
+}
+
+#[actix_web::test]
+async fn given_file_name_with_a_path_attach_primary_file_returns_bad_client_request_without_saving() {
+    //setup
+    let (
+        mock_recon_task_details_repo,
+        mock_recon_file_details_repo,
+        mock_recon_task_audit_repo,
+        mock_transformer,
+    ) = setup_dependencies();
+
+    let service = ReconTaskAggregationService {
+        recon_task_details_repo: mock_recon_task_details_repo,
+        recon_file_details_repo: mock_recon_file_details_repo,
+        recon_task_audit_repo: mock_recon_task_audit_repo,
+        recon_template_repo: Box::new(MockReconTemplateRepositoryInterface::new()),
+        transformer: mock_transformer,
+        event_publisher: Box::new(MockReconTaskEventPublisherInterface::new()),
+        upload_session_repo: Box::new(MockUploadSessionRepositoryInterface::new()),
+        blob_store: Box::new(MockBlobStoreInterface::new()),
+        quotas: get_dummy_quotas(),
+        recon_results_topic: String::from("recon-results"),
+    };
+
+    let mut test_request = get_dummy_attach_primary_file_request();
+    test_request.primary_file_name = String::from("../RECON-TASK-5678-payments.csv");
+
+    //act
+    let result = service
+        .attach_primary_file_to_task(&test_request, &get_dummy_owner())
+        .await;
+
+    //assert
+    assert!(matches!(result.err().unwrap().kind, AppErrorKind::BadClientRequest));
+}
+
+#[actix_web::test]
+async fn given_admin_delete_orphaned_recon_files_deletes_only_unreferenced_files() {
+    //setup
+    let (_, _, mock_recon_task_audit_repo, mock_transformer) = setup_dependencies();
+
+    let mut mock_recon_task_details_repo = Box::new(MockReconTaskDetailsRepositoryInterface::new());
+    mock_recon_task_details_repo
+        .expect_list_task_details()
+        .returning(|| Ok(vec![get_dummy_recon_task()]));
+
+    let mut mock_recon_file_details_repo = Box::new(MockReconFileDetailsRepositoryInterface::new());
+    mock_recon_file_details_repo
+        .expect_list_recon_file_ids()
+        .returning(|| {
+            Ok(vec![
+                String::from("src-file-1234"),
+                String::from("cmp-file-1234"),
+                String::from("orphan-file-1234"),
+            ])
+        });
+    mock_recon_file_details_repo
+        .expect_delete_recon_file_details()
+        .withf(|file_id| file_id == "orphan-file-1234")
+        .times(1)
+        .returning(|_| Ok(true));
+
+    let service = ReconTaskAggregationService {
+        recon_task_details_repo: mock_recon_task_details_repo,
+        recon_file_details_repo: mock_recon_file_details_repo,
+        recon_task_audit_repo: mock_recon_task_audit_repo,
+        recon_template_repo: Box::new(MockReconTemplateRepositoryInterface::new()),
+        transformer: mock_transformer,
+        event_publisher: Box::new(MockReconTaskEventPublisherInterface::new()),
+        upload_session_repo: Box::new(MockUploadSessionRepositoryInterface::new()),
+        blob_store: Box::new(MockBlobStoreInterface::new()),
+        quotas: get_dummy_quotas(),
+        recon_results_topic: String::from("recon-results"),
+    };
+
+    //act
+    let result = service
+        .delete_orphaned_recon_files(&get_dummy_user_with_role(UserRole::Admin))
+        .await;
+
+    //assert
+    assert_eq!(
+        result.unwrap().deleted_file_ids,
+        vec![String::from("orphan-file-1234")]
+    );
+}
+
+#[actix_web::test]
+async fn given_user_at_active_tasks_quota_create_recon_task_returns_quota_exceeded() {
+    //setup
+    let (_, mock_recon_file_details_repo, mock_recon_task_audit_repo, mock_transformer) =
+        setup_dependencies();
+
+    let mut mock_recon_task_details_repo = Box::new(MockReconTaskDetailsRepositoryInterface::new());
+    mock_recon_task_details_repo
+        .expect_reserve_active_task_slot()
+        .withf(|_, _, max_active_tasks| *max_active_tasks == 2)
+        .times(1)
+        .returning(|_, _, _| Ok(false));
+    mock_recon_task_details_repo
+        .expect_create_task_details()
+        .times(0);
+
+    let service = ReconTaskAggregationService {
+        recon_task_details_repo: mock_recon_task_details_repo,
+        recon_file_details_repo: mock_recon_file_details_repo,
+        recon_task_audit_repo: mock_recon_task_audit_repo,
+        recon_template_repo: Box::new(MockReconTemplateRepositoryInterface::new()),
+        transformer: mock_transformer,
+        event_publisher: Box::new(MockReconTaskEventPublisherInterface::new()),
+        upload_session_repo: Box::new(MockUploadSessionRepositoryInterface::new()),
+        blob_store: Box::new(MockBlobStoreInterface::new()),
+        quotas: ReconTaskQuotas {
+            max_active_tasks_per_user: 2,
+            max_file_row_count: 1000,
+        },
+        recon_results_topic: String::from("recon-results"),
+    };
+
+    //act
+    let result = service
+        .create_recon_task(&get_dummy_create_recon_task(), &get_dummy_owner())
+        .await;
+
+    //assert
+    assert_eq!(
+        result.err().unwrap().rejection,
+        Some(RejectionReason::TooManyActiveTasks)
+    );
+}
+
+#[actix_web::test]
+async fn given_file_above_row_count_quota_attach_primary_file_returns_quota_exceeded() {
+    //setup
+    let (mock_recon_task_details_repo, _, mock_recon_task_audit_repo, mock_transformer) =
+        setup_dependencies();
+
+    let mut mock_recon_file_details_repo = Box::new(MockReconFileDetailsRepositoryInterface::new());
+    mock_recon_file_details_repo
+        .expect_get_recon_file_details()
+        .returning(|_| Ok(get_dummy_recon_file()));
+    mock_recon_file_details_repo
+        .expect_create_recon_file_details()
+        .times(0);
+
+    let service = ReconTaskAggregationService {
+        recon_task_details_repo: mock_recon_task_details_repo,
+        recon_file_details_repo: mock_recon_file_details_repo,
+        recon_task_audit_repo: mock_recon_task_audit_repo,
+        recon_template_repo: Box::new(MockReconTemplateRepositoryInterface::new()),
+        transformer: mock_transformer,
+        event_publisher: Box::new(MockReconTaskEventPublisherInterface::new()),
+        upload_session_repo: Box::new(MockUploadSessionRepositoryInterface::new()),
+        blob_store: Box::new(MockBlobStoreInterface::new()),
+        quotas: ReconTaskQuotas {
+            max_active_tasks_per_user: 10,
+            max_file_row_count: 999,
+        },
+        recon_results_topic: String::from("recon-results"),
+    };
+
+    //act
+    let result = service
+        .attach_primary_file_to_task(&get_dummy_attach_primary_file_request(), &get_dummy_owner())
+        .await;
+
+    //assert
+    assert_eq!(
+        result.err().unwrap().rejection,
+        Some(RejectionReason::FileTooLarge)
+    );
+}
+
+#[actix_web::test]
+async fn given_owner_cancel_recon_task_saves_cancellation_publishes_event_and_records_it_in_history() {
+    //setup
+    let (_, mock_recon_file_details_repo, _, mock_transformer) = setup_dependencies();
+
+    let mut mock_recon_task_details_repo = Box::new(MockReconTaskDetailsRepositoryInterface::new());
+    mock_recon_task_details_repo
+        .expect_get_task_details()
+        .returning(|_| Ok(get_dummy_recon_task()));
+    mock_recon_task_details_repo
+        .expect_update_task_details()
+        .withf(|task| {
+            let cancellation = task.cancellation.clone().unwrap();
+            task.status == ReconTaskStatus::Cancelled
+                && cancellation.cancelled_by == "test-user-id"
+                && cancellation.reason == "wrong file uploaded"
+        })
+        .times(1)
+        .returning(|task| Ok(Some(task.clone())));
+    mock_recon_task_details_repo
+        .expect_release_active_task_slot()
+        .withf(|user_id, task_id| user_id == "test-user-id" && task_id == "task-1234")
+        .times(1)
+        .returning(|_, _| Ok(()));
+
+    let mut mock_recon_task_audit_repo = Box::new(MockReconTaskAuditRepositoryInterface::new());
+    mock_recon_task_audit_repo
+        .expect_append_audit_entry()
+        .withf(|entry| {
+            entry.action == ReconTaskAction::TaskCancelled
+                && entry.performed_by == "test-user-id"
+                && entry.details == Some(String::from("wrong file uploaded"))
+        })
+        .times(1)
+        .returning(|_| Ok(()));
+
+    let mut mock_event_publisher = Box::new(MockReconTaskEventPublisherInterface::new());
+    mock_event_publisher
+        .expect_publish_task_event()
+        .withf(|topic, event| {
+            (topic == "test-primary-chunks-topic" || topic == "test-comparison-chunks-topic")
+                && matches!(event, ReconTaskEvent::TaskCancelled { task_id, .. } if task_id == "task-1234")
+        })
+        .times(2)
+        .returning(|_, _| Ok(()));
+
+    let service = ReconTaskAggregationService {
+        recon_task_details_repo: mock_recon_task_details_repo,
+        recon_file_details_repo: mock_recon_file_details_repo,
+        recon_task_audit_repo: mock_recon_task_audit_repo,
+        recon_template_repo: Box::new(MockReconTemplateRepositoryInterface::new()),
+        transformer: mock_transformer,
+        event_publisher: mock_event_publisher,
+        upload_session_repo: Box::new(MockUploadSessionRepositoryInterface::new()),
+        blob_store: Box::new(MockBlobStoreInterface::new()),
+        quotas: get_dummy_quotas(),
+        recon_results_topic: String::from("recon-results"),
+    };
+
+    //act
+    let result = service
+        .cancel_recon_task(
+            &String::from("task-1234"),
+            &get_dummy_cancel_recon_task_request(),
+            &get_dummy_owner(),
+        )
+        .await;
+
+    //assert
+    assert!(result.is_ok());
+}
+
+#[actix_web::test]
+async fn given_task_completed_after_it_was_read_cancel_recon_task_returns_conflict_without_releasing_its_slot() {
+    //setup
+    let (_, mock_recon_file_details_repo, _, mock_transformer) = setup_dependencies();
+
+    let mut mock_recon_task_details_repo = Box::new(MockReconTaskDetailsRepositoryInterface::new());
+    mock_recon_task_details_repo
+        .expect_get_task_details()
+        .returning(|_| Ok(get_dummy_reconciling_recon_task()));
+    //the results that completed the task changed its etag, so the cancellation is not saved
+    mock_recon_task_details_repo
+        .expect_update_task_details()
+        .times(1)
+        .returning(|_| Ok(None));
+    mock_recon_task_details_repo.expect_release_active_task_slot().times(0);
+
+    let mut mock_recon_task_audit_repo = Box::new(MockReconTaskAuditRepositoryInterface::new());
+    mock_recon_task_audit_repo.expect_append_audit_entry().times(0);
+
+    let mut mock_event_publisher = Box::new(MockReconTaskEventPublisherInterface::new());
+    mock_event_publisher.expect_publish_task_event().times(0);
+
+    let service = ReconTaskAggregationService {
+        recon_task_details_repo: mock_recon_task_details_repo,
+        recon_file_details_repo: mock_recon_file_details_repo,
+        recon_task_audit_repo: mock_recon_task_audit_repo,
+        recon_template_repo: Box::new(MockReconTemplateRepositoryInterface::new()),
+        transformer: mock_transformer,
+        event_publisher: mock_event_publisher,
+        upload_session_repo: Box::new(MockUploadSessionRepositoryInterface::new()),
+        blob_store: Box::new(MockBlobStoreInterface::new()),
+        quotas: get_dummy_quotas(),
+        recon_results_topic: String::from("recon-results"),
+    };
+
+    //act
+    let result = service
+        .cancel_recon_task(
+            &String::from("task-1234"),
+            &get_dummy_cancel_recon_task_request(),
+            &get_dummy_owner(),
+        )
+        .await;
+
+    //assert
+    assert_eq!(result.err().unwrap().rejection, Some(RejectionReason::Conflict));
+}
+
+#[actix_web::test]
+async fn given_already_cancelled_task_cancel_recon_task_republishes_event_without_saving_again() {
+    //setup
+    let (_, mock_recon_file_details_repo, _, mock_transformer) = setup_dependencies();
+
+    let mut mock_recon_task_details_repo = Box::new(MockReconTaskDetailsRepositoryInterface::new());
+    mock_recon_task_details_repo
+        .expect_get_task_details()
+        .returning(|_| Ok(get_dummy_cancelled_recon_task()));
+    mock_recon_task_details_repo
+        .expect_update_task_details()
+        .times(0);
+
+    let mut mock_recon_task_audit_repo = Box::new(MockReconTaskAuditRepositoryInterface::new());
+    mock_recon_task_audit_repo
+        .expect_append_audit_entry()
+        .times(0);
+
+    //the event carries the original cancellation, not the one from the repeated request
+    let mut mock_event_publisher = Box::new(MockReconTaskEventPublisherInterface::new());
+    mock_event_publisher
+        .expect_publish_task_event()
+        .withf(|_, event| {
+            matches!(event, ReconTaskEvent::TaskCancelled { reason, .. } if reason == "wrong file uploaded")
+        })
+        .times(2)
+        .returning(|_, _| Ok(()));
+
+    let service = ReconTaskAggregationService {
+        recon_task_details_repo: mock_recon_task_details_repo,
+        recon_file_details_repo: mock_recon_file_details_repo,
+        recon_task_audit_repo: mock_recon_task_audit_repo,
+        recon_template_repo: Box::new(MockReconTemplateRepositoryInterface::new()),
+        transformer: mock_transformer,
+        event_publisher: mock_event_publisher,
+        upload_session_repo: Box::new(MockUploadSessionRepositoryInterface::new()),
+        blob_store: Box::new(MockBlobStoreInterface::new()),
+        quotas: get_dummy_quotas(),
+        recon_results_topic: String::from("recon-results"),
+    };
+
+    //act
+    let result = service
+        .cancel_recon_task(
+            &String::from("task-1234"),
+            &CancelReconTaskRequest {
+                reason: String::from("cancelling again"),
+            },
+            &get_dummy_owner(),
+        )
+        .await;
+
+    //assert
+    assert!(result.is_ok());
+}
+
+#[actix_web::test]
+async fn given_done_task_cancel_recon_task_returns_bad_client_request() {
+    //setup
+    let (_, mock_recon_file_details_repo, mock_recon_task_audit_repo, mock_transformer) =
+        setup_dependencies();
+
+    let mut mock_recon_task_details_repo = Box::new(MockReconTaskDetailsRepositoryInterface::new());
+    mock_recon_task_details_repo
+        .expect_get_task_details()
+        .returning(|_| {
+            let mut task = get_dummy_recon_task();
+            task.details.is_done = true;
+            Ok(task)
+        });
+    mock_recon_task_details_repo
+        .expect_update_task_details()
+        .times(0);
+
+    let service = ReconTaskAggregationService {
+        recon_task_details_repo: mock_recon_task_details_repo,
+        recon_file_details_repo: mock_recon_file_details_repo,
+        recon_task_audit_repo: mock_recon_task_audit_repo,
+        recon_template_repo: Box::new(MockReconTemplateRepositoryInterface::new()),
+        transformer: mock_transformer,
+        event_publisher: Box::new(MockReconTaskEventPublisherInterface::new()),
+        upload_session_repo: Box::new(MockUploadSessionRepositoryInterface::new()),
+        blob_store: Box::new(MockBlobStoreInterface::new()),
+        quotas: get_dummy_quotas(),
+        recon_results_topic: String::from("recon-results"),
+    };
+
+    //act
+    let result = service
+        .cancel_recon_task(
+            &String::from("task-1234"),
+            &get_dummy_cancel_recon_task_request(),
+            &get_dummy_owner(),
+        )
+        .await;
+
+    //assert
+    assert!(matches!(result.err().unwrap().kind, AppErrorKind::BadClientRequest));
+}
+
+#[actix_web::test]
+async fn given_viewer_cancel_recon_task_returns_forbidden() {
+    //setup
+    let (
+        mock_recon_task_details_repo,
+        mock_recon_file_details_repo,
+        mock_recon_task_audit_repo,
+        mock_transformer,
+    ) = setup_dependencies();
+
+    let service = ReconTaskAggregationService {
+        recon_task_details_repo: mock_recon_task_details_repo,
+        recon_file_details_repo: mock_recon_file_details_repo,
+        recon_task_audit_repo: mock_recon_task_audit_repo,
+        recon_template_repo: Box::new(MockReconTemplateRepositoryInterface::new()),
+        transformer: mock_transformer,
+        event_publisher: Box::new(MockReconTaskEventPublisherInterface::new()),
+        upload_session_repo: Box::new(MockUploadSessionRepositoryInterface::new()),
+        blob_store: Box::new(MockBlobStoreInterface::new()),
+        quotas: get_dummy_quotas(),
+        recon_results_topic: String::from("recon-results"),
+    };
+
+    //act
+    let result = service
+        .cancel_recon_task(
+            &String::from("task-1234"),
+            &get_dummy_cancel_recon_task_request(),
+            &get_dummy_user_with_role(UserRole::Viewer),
+        )
+        .await;
+
+    //assert
+    assert_eq!(
+        result.err().unwrap().rejection,
+        Some(RejectionReason::Forbidden)
+    );
+}
+
+#[actix_web::test]
+async fn given_cancelled_task_attach_primary_file_returns_bad_client_request_without_saving() {
+    //setup
+    let (mock_recon_task_details_repo, _, mock_recon_task_audit_repo, _) = setup_dependencies();
+
+    let mut mock_recon_file_details_repo = Box::new(MockReconFileDetailsRepositoryInterface::new());
+    mock_recon_file_details_repo
+        .expect_get_recon_file_details()
+        .returning(|_| Ok(get_dummy_recon_file()));
+    mock_recon_file_details_repo
+        .expect_create_recon_file_details()
+        .times(0);
+
+    let mut mock_transformer = Box::new(MockTransformerInterface::new());
+    mock_transformer
+        .expect_build_recon_task_details_response()
+        .returning(|_, _, _| {
+            let mut response = get_dummy_recon_task_response_details();
+            response.task_details = get_dummy_cancelled_recon_task();
+            response
+        });
+
+    let service = ReconTaskAggregationService {
+        recon_task_details_repo: mock_recon_task_details_repo,
+        recon_file_details_repo: mock_recon_file_details_repo,
+        recon_task_audit_repo: mock_recon_task_audit_repo,
+        recon_template_repo: Box::new(MockReconTemplateRepositoryInterface::new()),
+        transformer: mock_transformer,
+        event_publisher: Box::new(MockReconTaskEventPublisherInterface::new()),
+        upload_session_repo: Box::new(MockUploadSessionRepositoryInterface::new()),
+        blob_store: Box::new(MockBlobStoreInterface::new()),
+        quotas: get_dummy_quotas(),
+        recon_results_topic: String::from("recon-results"),
+    };
+
+    //act
+    let result = service
+        .attach_primary_file_to_task(&get_dummy_attach_primary_file_request(), &get_dummy_owner())
+        .await;
+
+    //assert
+    assert!(matches!(result.err().unwrap().kind, AppErrorKind::BadClientRequest));
+}
+
+#[actix_web::test]
+async fn given_clone_recon_task_request_creates_task_with_same_configuration_and_no_files() {
+    //setup
+    let (_, mut mock_recon_file_details_repo, _, _) = setup_dependencies();
+    mock_recon_file_details_repo
+        .expect_create_recon_file_details()
+        .times(0);
+
+    let mut mock_transformer = Box::new(MockTransformerInterface::new());
+    mock_transformer
+        .expect_get_recon_task_details()
+        .withf(|created_by, _, comparison_pairs| {
+            created_by == "test-user-id"
+                && comparison_pairs.len() == 1
+                && comparison_pairs[0].is_row_identifier
+        })
+        .times(1)
+        .returning(|_, _, _| get_dummy_recon_task());
+    mock_transformer
+        .expect_build_recon_task_details_response()
+        .returning(|_, _, _| get_dummy_recon_task_response_details());
+
+    let mut mock_recon_task_details_repo = Box::new(MockReconTaskDetailsRepositoryInterface::new());
+    mock_recon_task_details_repo
+        .expect_get_task_details()
+        .returning(|_| Ok(get_dummy_recon_task()));
+    mock_recon_task_details_repo
+        .expect_reserve_active_task_slot()
+        .returning(|_, _, _| Ok(true));
+    mock_recon_task_details_repo
+        .expect_create_task_details()
+        .times(1)
+        .returning(|_| Ok(String::from("task-5678")));
+    mock_recon_task_details_repo
+        .expect_update_task_details()
+        .times(0);
+
+    let mut mock_recon_task_audit_repo = Box::new(MockReconTaskAuditRepositoryInterface::new());
+    mock_recon_task_audit_repo
+        .expect_append_audit_entry()
+        .withf(|entry| {
+            entry.task_id == "task-5678"
+                && entry.action == ReconTaskAction::TaskCreated
+                && entry.details == Some(String::from("cloned from task [task-1234]"))
+        })
+        .times(1)
+        .returning(|_| Ok(()));
+
+    let service = ReconTaskAggregationService {
+        recon_task_details_repo: mock_recon_task_details_repo,
+        recon_file_details_repo: mock_recon_file_details_repo,
+        recon_task_audit_repo: mock_recon_task_audit_repo,
+        recon_template_repo: Box::new(MockReconTemplateRepositoryInterface::new()),
+        transformer: mock_transformer,
+        event_publisher: Box::new(MockReconTaskEventPublisherInterface::new()),
+        upload_session_repo: Box::new(MockUploadSessionRepositoryInterface::new()),
+        blob_store: Box::new(MockBlobStoreInterface::new()),
+        quotas: get_dummy_quotas(),
+        recon_results_topic: String::from("recon-results"),
+    };
+
+    //act
+    let result = service
+        .clone_recon_task(
+            &String::from("task-1234"),
+            &CloneReconTaskRequest::default(),
+            &get_dummy_owner(),
+        )
+        .await;
+
+    //assert
+    assert!(result.is_ok());
+}
+
+#[actix_web::test]
+async fn given_clone_recon_task_request_with_copy_file_metadata_copies_both_files() {
+    //setup
+    let (_, _, mock_recon_task_audit_repo, mock_transformer) = setup_dependencies();
+
+    let mut mock_recon_file_details_repo = Box::new(MockReconFileDetailsRepositoryInterface::new());
+    mock_recon_file_details_repo
+        .expect_get_recon_file_details()
+        .returning(|_| Ok(get_dummy_recon_file()));
+    //the source files were attached with their metadata only, so the copies name the source task's blob
+    mock_recon_file_details_repo
+        .expect_create_recon_file_details()
+        .withf(|file| file.stored_file_parts == vec![String::from("task-1234-src-file-1234")])
+        .times(2)
+        .returning(|_| Ok(String::from("file-5678")));
+
+    let mut mock_recon_task_details_repo = Box::new(MockReconTaskDetailsRepositoryInterface::new());
+    mock_recon_task_details_repo
+        .expect_get_task_details()
+        .returning(|task_id| {
+            let mut recon_task = get_dummy_recon_task();
+            recon_task.details.id = task_id.to_string();
+            recon_task.etag = Some(format!("etag-{}", task_id));
+            Ok(recon_task)
+        });
+    mock_recon_task_details_repo
+        .expect_reserve_active_task_slot()
+        .returning(|_, _, _| Ok(true));
+    mock_recon_task_details_repo
+        .expect_create_task_details()
+        .returning(|_| Ok(String::from("task-5678")));
+    mock_recon_task_details_repo
+        .expect_update_task_details()
+        .withf(|task| {
+            task.details.id == "task-5678"
+                && task.etag == Some(String::from("etag-task-5678"))
+                && task.details.primary_file_id == Some(String::from("file-5678"))
+                && task.details.comparison_file_id == Some(String::from("file-5678"))
+        })
+        .times(1)
+        .returning(|task| Ok(Some(task.clone())));
+
+    let service = ReconTaskAggregationService {
+        recon_task_details_repo: mock_recon_task_details_repo,
+        recon_file_details_repo: mock_recon_file_details_repo,
+        recon_task_audit_repo: mock_recon_task_audit_repo,
+        recon_template_repo: Box::new(MockReconTemplateRepositoryInterface::new()),
+        transformer: mock_transformer,
+        event_publisher: Box::new(MockReconTaskEventPublisherInterface::new()),
+        upload_session_repo: Box::new(MockUploadSessionRepositoryInterface::new()),
+        blob_store: Box::new(MockBlobStoreInterface::new()),
+        quotas: get_dummy_quotas(),
+        recon_results_topic: String::from("recon-results"),
+    };
+
+    //act
+    let result = service
+        .clone_recon_task(
+            &String::from("task-1234"),
+            &CloneReconTaskRequest {
+                copy_file_metadata: true,
+            },
+            &get_dummy_owner(),
+        )
+        .await;
+
+    //assert
+    assert!(result.is_ok());
+}
+
+#[actix_web::test]
+async fn given_viewer_clone_recon_task_returns_forbidden() {
+    //setup
+    let (
+        mock_recon_task_details_repo,
+        mock_recon_file_details_repo,
+        mock_recon_task_audit_repo,
+        mock_transformer,
+    ) = setup_dependencies();
+
+    let service = ReconTaskAggregationService {
+        recon_task_details_repo: mock_recon_task_details_repo,
+        recon_file_details_repo: mock_recon_file_details_repo,
+        recon_task_audit_repo: mock_recon_task_audit_repo,
+        recon_template_repo: Box::new(MockReconTemplateRepositoryInterface::new()),
+        transformer: mock_transformer,
+        event_publisher: Box::new(MockReconTaskEventPublisherInterface::new()),
+        upload_session_repo: Box::new(MockUploadSessionRepositoryInterface::new()),
+        blob_store: Box::new(MockBlobStoreInterface::new()),
+        quotas: get_dummy_quotas(),
+        recon_results_topic: String::from("recon-results"),
+    };
+
+    //act
+    let result = service
+        .clone_recon_task(
+            &String::from("task-1234"),
+            &CloneReconTaskRequest::default(),
+            &get_dummy_user_with_role(UserRole::Viewer),
+        )
+        .await;
+
+    //assert
+    assert_eq!(
+        result.err().unwrap().rejection,
+        Some(RejectionReason::Forbidden)
+    );
+}
+
+#[actix_web::test]
+async fn given_create_recon_task_request_with_template_id_uses_template_settings_and_records_its_version() {
+    //setup
+    let (_, mock_recon_file_details_repo, mock_recon_task_audit_repo, _) = setup_dependencies();
+
+    let mut mock_recon_template_repo = Box::new(MockReconTemplateRepositoryInterface::new());
+    mock_recon_template_repo
+        .expect_get_template()
+        .returning(|_| Ok(get_dummy_recon_template()));
+
+    let mut mock_transformer = Box::new(MockTransformerInterface::new());
+    mock_transformer
+        .expect_get_recon_task_details()
+        .withf(|_, _, comparison_pairs| *comparison_pairs == get_dummy_recon_template().comparison_pairs)
+        .times(1)
+        .returning(|_, _, _| get_dummy_recon_task());
+    mock_transformer
+        .expect_build_recon_task_details_response()
+        .returning(|_, _, _| get_dummy_recon_task_response_details());
+
+    let mut mock_recon_task_details_repo = Box::new(MockReconTaskDetailsRepositoryInterface::new());
+    mock_recon_task_details_repo
+        .expect_reserve_active_task_slot()
+        .returning(|_, _, _| Ok(true));
+    mock_recon_task_details_repo
+        .expect_create_task_details()
+        .withf(|task| {
+            task.template
+                == Some(ReconTemplateReference {
+                    template_id: String::from("template-1234"),
+                    template_version: 2,
+                })
+        })
+        .times(1)
+        .returning(|_| Ok(String::from("task-1234")));
+    mock_recon_task_details_repo
+        .expect_get_task_details()
+        .returning(|_| Ok(get_dummy_recon_task()));
+
+    let service = ReconTaskAggregationService {
+        recon_task_details_repo: mock_recon_task_details_repo,
+        recon_file_details_repo: mock_recon_file_details_repo,
+        recon_task_audit_repo: mock_recon_task_audit_repo,
+        recon_template_repo: mock_recon_template_repo,
+        transformer: mock_transformer,
+        event_publisher: Box::new(MockReconTaskEventPublisherInterface::new()),
+        upload_session_repo: Box::new(MockUploadSessionRepositoryInterface::new()),
+        blob_store: Box::new(MockBlobStoreInterface::new()),
+        quotas: get_dummy_quotas(),
+        recon_results_topic: String::from("recon-results"),
+    };
+
+    let test_request = CreateReconTaskRequest {
+        user_id: String::from("test-user-id"),
+        template_id: Some(String::from("template-1234")),
+        recon_configurations: None,
+        comparison_pairs: vec![],
+        row_key_separator: None,
+    };
+
+    //act
+    let result = service.create_recon_task(&test_request, &get_dummy_owner()).await;
+
+    //assert
+    assert!(result.is_ok());
+}
+
+#[actix_web::test]
+async fn given_create_recon_task_request_with_invalid_comparison_rules_returns_error() {
+    //setup
+    let (mock_recon_task_details_repo, mock_recon_file_details_repo, mock_recon_task_audit_repo, _) =
+        setup_dependencies();
+
+    let mut mock_transformer = Box::new(MockTransformerInterface::new());
+    mock_transformer.expect_get_recon_task_details().times(0);
+
+    let service = ReconTaskAggregationService {
+        recon_task_details_repo: mock_recon_task_details_repo,
+        recon_file_details_repo: mock_recon_file_details_repo,
+        recon_task_audit_repo: mock_recon_task_audit_repo,
+        recon_template_repo: Box::new(MockReconTemplateRepositoryInterface::new()),
+        transformer: mock_transformer,
+        event_publisher: Box::new(MockReconTaskEventPublisherInterface::new()),
+        upload_session_repo: Box::new(MockUploadSessionRepositoryInterface::new()),
+        blob_store: Box::new(MockBlobStoreInterface::new()),
+        quotas: get_dummy_quotas(),
+        recon_results_topic: String::from("recon-results"),
+    };
+
+    let mut test_request = get_dummy_create_recon_task();
+    test_request.comparison_pairs[0].rules = Some(ComparisonRules {
+        numeric_tolerance: Some(NumericTolerance::Absolute { value: -1.0 }),
+        ..ComparisonRules::default()
+    });
+
+    //act
+    let result = service.create_recon_task(&test_request, &get_dummy_owner()).await;
+
+    //assert
+    assert!(matches!(result.err().unwrap().kind, AppErrorKind::BadClientRequest));
+}
+
+#[actix_web::test]
+async fn given_create_recon_task_request_without_row_identifier_returns_error() {
+    //setup
+    let (mock_recon_task_details_repo, mock_recon_file_details_repo, mock_recon_task_audit_repo, _) =
+        setup_dependencies();
+
+    let mut mock_transformer = Box::new(MockTransformerInterface::new());
+    mock_transformer.expect_get_recon_task_details().times(0);
+
+    let service = ReconTaskAggregationService {
+        recon_task_details_repo: mock_recon_task_details_repo,
+        recon_file_details_repo: mock_recon_file_details_repo,
+        recon_task_audit_repo: mock_recon_task_audit_repo,
+        recon_template_repo: Box::new(MockReconTemplateRepositoryInterface::new()),
+        transformer: mock_transformer,
+        event_publisher: Box::new(MockReconTaskEventPublisherInterface::new()),
+        upload_session_repo: Box::new(MockUploadSessionRepositoryInterface::new()),
+        blob_store: Box::new(MockBlobStoreInterface::new()),
+        quotas: get_dummy_quotas(),
+        recon_results_topic: String::from("recon-results"),
+    };
+
+    let mut test_request = get_dummy_create_recon_task();
+    test_request.comparison_pairs[0].is_row_identifier = false;
+
+    //act
+    let result = service.create_recon_task(&test_request, &get_dummy_owner()).await;
+
+    //assert
+    assert!(matches!(result.err().unwrap().kind, AppErrorKind::BadClientRequest));
+}
+
+#[actix_web::test]
+async fn given_create_recon_task_request_with_template_id_and_configurations_returns_error() {
+    //setup
+    let (
+        mock_recon_task_details_repo,
+        mock_recon_file_details_repo,
+        mock_recon_task_audit_repo,
+        mock_transformer,
+    ) = setup_dependencies();
+
+    let service = ReconTaskAggregationService {
+        recon_task_details_repo: mock_recon_task_details_repo,
+        recon_file_details_repo: mock_recon_file_details_repo,
+        recon_task_audit_repo: mock_recon_task_audit_repo,
+        recon_template_repo: Box::new(MockReconTemplateRepositoryInterface::new()),
+        transformer: mock_transformer,
+        event_publisher: Box::new(MockReconTaskEventPublisherInterface::new()),
+        upload_session_repo: Box::new(MockUploadSessionRepositoryInterface::new()),
+        blob_store: Box::new(MockBlobStoreInterface::new()),
+        quotas: get_dummy_quotas(),
+        recon_results_topic: String::from("recon-results"),
+    };
+
+    let mut test_request = get_dummy_create_recon_task();
+    test_request.template_id = Some(String::from("template-1234"));
+
+    //act
+    let result = service.create_recon_task(&test_request, &get_dummy_owner()).await;
+
+    //assert
+    assert!(matches!(result.err().unwrap().kind, AppErrorKind::BadClientRequest));
+}
+
+#[actix_web::test]
+async fn given_create_recon_task_request_without_template_or_configurations_returns_error() {
+    //setup
+    let (
+        mock_recon_task_details_repo,
+        mock_recon_file_details_repo,
+        mock_recon_task_audit_repo,
+        mock_transformer,
+    ) = setup_dependencies();
+
+    let service = ReconTaskAggregationService {
+        recon_task_details_repo: mock_recon_task_details_repo,
+        recon_file_details_repo: mock_recon_file_details_repo,
+        recon_task_audit_repo: mock_recon_task_audit_repo,
+        recon_template_repo: Box::new(MockReconTemplateRepositoryInterface::new()),
+        transformer: mock_transformer,
+        event_publisher: Box::new(MockReconTaskEventPublisherInterface::new()),
+        upload_session_repo: Box::new(MockUploadSessionRepositoryInterface::new()),
+        blob_store: Box::new(MockBlobStoreInterface::new()),
+        quotas: get_dummy_quotas(),
+        recon_results_topic: String::from("recon-results"),
+    };
+
+    let mut test_request = get_dummy_create_recon_task();
+    test_request.recon_configurations = None;
+
+    //act
+    let result = service.create_recon_task(&test_request, &get_dummy_owner()).await;
+
+    //assert
+    assert!(matches!(result.err().unwrap().kind, AppErrorKind::BadClientRequest));
+}
+
+#[actix_web::test]
+async fn given_owner_update_recon_template_saves_it_as_next_version() {
+    //setup
+    let (
+        mock_recon_task_details_repo,
+        mock_recon_file_details_repo,
+        mock_recon_task_audit_repo,
+        mock_transformer,
+    ) = setup_dependencies();
+
+    let mut mock_recon_template_repo = Box::new(MockReconTemplateRepositoryInterface::new());
+    mock_recon_template_repo
+        .expect_get_template()
+        .returning(|_| Ok(get_dummy_recon_template()));
+    mock_recon_template_repo
+        .expect_save_template()
+        .withf(|template| {
+            template.id == "template-1234" && template.version == 3 && template.name == "renamed"
+        })
+        .times(1)
+        .returning(|template| Ok(template.id.clone()));
+
+    let service = ReconTaskAggregationService {
+        recon_task_details_repo: mock_recon_task_details_repo,
+        recon_file_details_repo: mock_recon_file_details_repo,
+        recon_task_audit_repo: mock_recon_task_audit_repo,
+        recon_template_repo: mock_recon_template_repo,
+        transformer: mock_transformer,
+        event_publisher: Box::new(MockReconTaskEventPublisherInterface::new()),
+        upload_session_repo: Box::new(MockUploadSessionRepositoryInterface::new()),
+        blob_store: Box::new(MockBlobStoreInterface::new()),
+        quotas: get_dummy_quotas(),
+        recon_results_topic: String::from("recon-results"),
+    };
+
+    let mut test_request = get_dummy_recon_template_request();
+    test_request.name = String::from("renamed");
+
+    //act
+    let result = service
+        .update_recon_template(&String::from("template-1234"), &test_request, &get_dummy_owner())
+        .await;
+
+    //assert
+    assert_eq!(result.unwrap().version, 3);
+}
+
+#[actix_web::test]
+async fn given_template_owned_by_another_user_update_recon_template_returns_forbidden() {
+    //setup
+    let (
+        mock_recon_task_details_repo,
+        mock_recon_file_details_repo,
+        mock_recon_task_audit_repo,
+        mock_transformer,
+    ) = setup_dependencies();
+
+    let mut mock_recon_template_repo = Box::new(MockReconTemplateRepositoryInterface::new());
+    mock_recon_template_repo
+        .expect_get_template()
+        .returning(|_| Ok(get_dummy_recon_template()));
+    mock_recon_template_repo
+        .expect_save_template()
+        .times(0);
+
+    let service = ReconTaskAggregationService {
+        recon_task_details_repo: mock_recon_task_details_repo,
+        recon_file_details_repo: mock_recon_file_details_repo,
+        recon_task_audit_repo: mock_recon_task_audit_repo,
+        recon_template_repo: mock_recon_template_repo,
+        transformer: mock_transformer,
+        event_publisher: Box::new(MockReconTaskEventPublisherInterface::new()),
+        upload_session_repo: Box::new(MockUploadSessionRepositoryInterface::new()),
+        blob_store: Box::new(MockBlobStoreInterface::new()),
+        quotas: get_dummy_quotas(),
+        recon_results_topic: String::from("recon-results"),
+    };
+
+    //act
+    let result = service
+        .update_recon_template(
+            &String::from("template-1234"),
+            &get_dummy_recon_template_request(),
+            &AuthenticatedUser::new("another-user-id"),
+        )
+        .await;
+
+    //assert
+    assert_eq!(
+        result.err().unwrap().rejection,
+        Some(RejectionReason::Forbidden)
+    );
+}
+
+#[actix_web::test]
+async fn given_primary_file_without_named_column_attach_primary_file_returns_error_without_saving() {
+    //setup
+    let (mock_recon_task_details_repo, _, mock_recon_task_audit_repo, _) = setup_dependencies();
+
+    let mut mock_recon_file_details_repo = Box::new(MockReconFileDetailsRepositoryInterface::new());
+    mock_recon_file_details_repo
+        .expect_get_recon_file_details()
+        .returning(|_| Ok(get_dummy_recon_file()));
+    mock_recon_file_details_repo
+        .expect_create_recon_file_details()
+        .times(0);
+
+    let mut mock_transformer = Box::new(MockTransformerInterface::new());
+    mock_transformer
+        .expect_build_recon_task_details_response()
+        .returning(|_, _, _| {
+            let mut response = get_dummy_recon_task_response_details();
+            response.task_details.pending_comparison_pairs =
+                get_dummy_recon_template().comparison_pairs;
+            response
+        });
+
+    let service = ReconTaskAggregationService {
+        recon_task_details_repo: mock_recon_task_details_repo,
+        recon_file_details_repo: mock_recon_file_details_repo,
+        recon_task_audit_repo: mock_recon_task_audit_repo,
+        recon_template_repo: Box::new(MockReconTemplateRepositoryInterface::new()),
+        transformer: mock_transformer,
+        event_publisher: Box::new(MockReconTaskEventPublisherInterface::new()),
+        upload_session_repo: Box::new(MockUploadSessionRepositoryInterface::new()),
+        blob_store: Box::new(MockBlobStoreInterface::new()),
+        quotas: get_dummy_quotas(),
+        recon_results_topic: String::from("recon-results"),
+    };
+
+    //act
+    let result = service
+        .attach_primary_file_to_task(&get_dummy_attach_primary_file_request(), &get_dummy_owner())
+        .await;
+
+    //assert
+    assert!(matches!(result.err().unwrap().kind, AppErrorKind::BadClientRequest));
+}
+
+#[actix_web::test]
+async fn given_primary_file_already_attached_attach_comparison_file_resolves_named_columns() {
+    //setup
+    let (_, mock_recon_file_details_repo, mock_recon_task_audit_repo, _) = setup_dependencies();
+
+    let mut mock_transformer = Box::new(MockTransformerInterface::new());
+    mock_transformer
+        .expect_build_recon_task_details_response()
+        .returning(|_, _, _| {
+            let mut response = get_dummy_recon_task_response_details();
+            response.task_details.pending_comparison_pairs = vec![NamedComparisonPair {
+                primary_file_column: ColumnReference::Header(String::from("header2")),
+                comparison_file_column: ColumnReference::Header(String::from("total")),
+                is_row_identifier: false,
+                ignore_header_case: false,
+                row_key_position: None,
+                rules: None,
+            }];
+            response
+        });
+    mock_transformer
+        .expect_get_comparison_file_details()
+        .returning(|_, _| get_dummy_recon_file());
+
+    let mut mock_recon_task_details_repo = Box::new(MockReconTaskDetailsRepositoryInterface::new());
+    mock_recon_task_details_repo
+        .expect_get_task_details()
+        .returning(|_| Ok(get_dummy_recon_task()));
+    mock_recon_task_details_repo
+        .expect_update_task_details()
+        .withf(|task| {
+            let last_pair = task.details.comparison_pairs.last().unwrap();
+            task.pending_comparison_pairs.is_empty()
+                && last_pair.primary_file_column_index == 1
+                && last_pair.comparison_file_column_index == 2
+        })
+        .times(1)
+        .returning(|task| Ok(Some(task.clone())));
+
+    let service = ReconTaskAggregationService {
+        recon_task_details_repo: mock_recon_task_details_repo,
+        recon_file_details_repo: mock_recon_file_details_repo,
+        recon_task_audit_repo: mock_recon_task_audit_repo,
+        recon_template_repo: Box::new(MockReconTemplateRepositoryInterface::new()),
+        transformer: mock_transformer,
+        event_publisher: Box::new(MockReconTaskEventPublisherInterface::new()),
+        upload_session_repo: Box::new(MockUploadSessionRepositoryInterface::new()),
+        blob_store: Box::new(MockBlobStoreInterface::new()),
+        quotas: get_dummy_quotas(),
+        recon_results_topic: String::from("recon-results"),
+    };
+
+    let test_request = AttachComparisonFileRequest {
+        task_id: String::from("task-1234"),
+        comparison_file_name: String::from("cmp-file-1234"),
+        comparison_file_hash: String::from("cmp-file-1234"),
+        comparison_file_headers: vec![
+            String::from("id"),
+            String::from("date"),
+            String::from("total"),
+        ],
+        comparison_file_row_count: 1000,
+        comparison_file_delimiters: vec![','],
+        comparison_file_format: FileFormatDescriptor::default(),
+        comparison_file_column_schema: vec![],
+    };
+
+    //act
+    let result = service
+        .attach_comparison_file_to_task(&test_request, &get_dummy_owner())
+        .await;
+
+    //assert
+    assert!(result.is_ok());
+}
+
+#[actix_web::test]
+async fn given_resolved_named_pair_attach_comparison_file_with_reordered_columns_resolves_it_again() {
+    //setup
+    let (_, mock_recon_file_details_repo, mock_recon_task_audit_repo, _) = setup_dependencies();
+
+    let mut mock_transformer = Box::new(MockTransformerInterface::new());
+    mock_transformer
+        .expect_build_recon_task_details_response()
+        .returning(|_, _, _| {
+            let mut response = get_dummy_recon_task_response_details();
+            let named_pair = NamedComparisonPair {
+                primary_file_column: ColumnReference::Header(String::from("header2")),
+                comparison_file_column: ColumnReference::Header(String::from("total")),
+                is_row_identifier: false,
+                ignore_header_case: false,
+                row_key_position: None,
+                rules: None,
+            };
+            let resolved_pair = named_pair.resolve(
+                &[String::from("header1"), String::from("header2")],
+                &[String::from("id"), String::from("date"), String::from("total")],
+            );
+            response.task_details.details.comparison_pairs.extend(resolved_pair);
+            response.task_details.resolved_comparison_pairs = vec![ResolvedComparisonPair {
+                named_pair,
+                primary_file_column_index: 1,
+                comparison_file_column_index: 2,
+            }];
+            response
+        });
+    mock_transformer
+        .expect_get_comparison_file_details()
+        .returning(|_, _| get_dummy_recon_file());
+
+    let mut mock_recon_task_details_repo = Box::new(MockReconTaskDetailsRepositoryInterface::new());
+    mock_recon_task_details_repo
+        .expect_get_task_details()
+        .returning(|_| Ok(get_dummy_recon_task()));
+    mock_recon_task_details_repo
+        .expect_update_task_details()
+        .withf(|task| {
+            let index_only_pair = &task.details.comparison_pairs[0];
+            let resolved_pair = &task.details.comparison_pairs[1];
+            task.details.comparison_pairs.len() == 2
+                && task.resolved_comparison_pairs.len() == 1
+                && index_only_pair.comparison_file_column_index == 0
+                && resolved_pair.primary_file_column_index == 1
+                && resolved_pair.comparison_file_column_index == 0
+                && task.resolved_comparison_pairs[0].comparison_file_column_index == 0
+        })
+        .times(1)
+        .returning(|task| Ok(Some(task.clone())));
+
+    let service = ReconTaskAggregationService {
+        recon_task_details_repo: mock_recon_task_details_repo,
+        recon_file_details_repo: mock_recon_file_details_repo,
+        recon_task_audit_repo: mock_recon_task_audit_repo,
+        recon_template_repo: Box::new(MockReconTemplateRepositoryInterface::new()),
+        transformer: mock_transformer,
+        event_publisher: Box::new(MockReconTaskEventPublisherInterface::new()),
+        upload_session_repo: Box::new(MockUploadSessionRepositoryInterface::new()),
+        blob_store: Box::new(MockBlobStoreInterface::new()),
+        quotas: get_dummy_quotas(),
+        recon_results_topic: String::from("recon-results"),
+    };
+
+    //the replacement file moved the total column to the front
+    let test_request = AttachComparisonFileRequest {
+        task_id: String::from("task-1234"),
+        comparison_file_name: String::from("cmp-file-1234"),
+        comparison_file_hash: String::from("cmp-file-1234"),
+        comparison_file_headers: vec![
+            String::from("total"),
+            String::from("id"),
+            String::from("date"),
+        ],
+        comparison_file_row_count: 1000,
+        comparison_file_delimiters: vec![','],
+        comparison_file_format: FileFormatDescriptor::default(),
+        comparison_file_column_schema: vec![],
+    };
+
+    //act
+    let result = service
+        .attach_comparison_file_to_task(&test_request, &get_dummy_owner())
+        .await;
+
+    //assert
+    assert!(result.is_ok());
+}
+
+#[actix_web::test]
+async fn given_pair_ignoring_header_case_attach_comparison_file_stores_resolved_indexes() {
+    //setup
+    let (_, mock_recon_file_details_repo, mock_recon_task_audit_repo, _) = setup_dependencies();
+
+    let mut mock_transformer = Box::new(MockTransformerInterface::new());
+    mock_transformer
+        .expect_build_recon_task_details_response()
+        .returning(|_, _, _| {
+            let mut response = get_dummy_recon_task_response_details();
+            response.task_details.pending_comparison_pairs = vec![NamedComparisonPair {
+                primary_file_column: ColumnReference::Header(String::from("HEADER2")),
+                comparison_file_column: ColumnReference::Header(String::from("Total")),
+                is_row_identifier: false,
+                ignore_header_case: true,
+                row_key_position: None,
+                rules: None,
+            }];
+            response
+        });
+    mock_transformer
+        .expect_get_comparison_file_details()
+        .returning(|_, _| get_dummy_recon_file());
+
+    let mut mock_recon_task_details_repo = Box::new(MockReconTaskDetailsRepositoryInterface::new());
+    mock_recon_task_details_repo
+        .expect_get_task_details()
+        .returning(|_| Ok(get_dummy_recon_task()));
+    mock_recon_task_details_repo
+        .expect_update_task_details()
+        .withf(|task| {
+            let resolved_pair = task.resolved_comparison_pairs.last().unwrap();
+            task.resolved_comparison_pairs.len() == 1
+                && resolved_pair.named_pair.primary_file_column
+                    == ColumnReference::Header(String::from("HEADER2"))
+                && resolved_pair.primary_file_column_index == 1
+                && resolved_pair.comparison_file_column_index == 2
+        })
+        .times(1)
+        .returning(|task| Ok(Some(task.clone())));
+
+    let service = ReconTaskAggregationService {
+        recon_task_details_repo: mock_recon_task_details_repo,
+        recon_file_details_repo: mock_recon_file_details_repo,
+        recon_task_audit_repo: mock_recon_task_audit_repo,
+        recon_template_repo: Box::new(MockReconTemplateRepositoryInterface::new()),
+        transformer: mock_transformer,
+        event_publisher: Box::new(MockReconTaskEventPublisherInterface::new()),
+        upload_session_repo: Box::new(MockUploadSessionRepositoryInterface::new()),
+        blob_store: Box::new(MockBlobStoreInterface::new()),
+        quotas: get_dummy_quotas(),
+        recon_results_topic: String::from("recon-results"),
+    };
+
+    let test_request = AttachComparisonFileRequest {
+        task_id: String::from("task-1234"),
+        comparison_file_name: String::from("cmp-file-1234"),
+        comparison_file_hash: String::from("cmp-file-1234"),
+        comparison_file_headers: vec![
+            String::from("id"),
+            String::from("date"),
+            String::from("total"),
+        ],
+        comparison_file_row_count: 1000,
+        comparison_file_delimiters: vec![','],
+        comparison_file_format: FileFormatDescriptor::default(),
+        comparison_file_column_schema: vec![],
+    };
+
+    //act
+    let result = service
+        .attach_comparison_file_to_task(&test_request, &get_dummy_owner())
+        .await;
+
+    //assert
+    assert!(result.is_ok());
+}
+
+#[actix_web::test]
+async fn given_both_files_attached_get_suggested_comparison_pairs_matches_their_headers() {
+    //setup
+    let (
+        mock_recon_task_details_repo,
+        mock_recon_file_details_repo,
+        mock_recon_task_audit_repo,
+        mock_transformer,
+    ) = setup_dependencies();
+
+    let service = ReconTaskAggregationService {
+        recon_task_details_repo: mock_recon_task_details_repo,
+        recon_file_details_repo: mock_recon_file_details_repo,
+        recon_task_audit_repo: mock_recon_task_audit_repo,
+        recon_template_repo: Box::new(MockReconTemplateRepositoryInterface::new()),
+        transformer: mock_transformer,
+        event_publisher: Box::new(MockReconTaskEventPublisherInterface::new()),
+        upload_session_repo: Box::new(MockUploadSessionRepositoryInterface::new()),
+        blob_store: Box::new(MockBlobStoreInterface::new()),
+        quotas: get_dummy_quotas(),
+        recon_results_topic: String::from("recon-results"),
+    };
+
+    //act
+    let result = service
+        .get_suggested_comparison_pairs(&String::from("task-1234"), &get_dummy_owner())
+        .await;
+
+    //assert
+    let suggested_pairs = result.unwrap().suggested_pairs;
+    assert_eq!(suggested_pairs.len(), 2);
+    assert_eq!(suggested_pairs[1].primary_file_column_header, "header2");
+    assert_eq!(suggested_pairs[1].comparison_file_column_index, 1);
+    assert_eq!(suggested_pairs[1].similarity_score, 1.0);
+}
+
+#[actix_web::test]
+async fn given_comparison_file_not_attached_get_suggested_comparison_pairs_returns_bad_client_request() {
+    //setup
+    let (mock_recon_task_details_repo, mock_recon_file_details_repo, mock_recon_task_audit_repo, _) =
+        setup_dependencies();
+
+    let mut mock_transformer = Box::new(MockTransformerInterface::new());
+    mock_transformer
+        .expect_build_recon_task_details_response()
+        .returning(|_, _, _| {
+            let mut response = get_dummy_recon_task_response_details();
+            response.comparison_file_metadata = None;
+            response
+        });
+
+    let service = ReconTaskAggregationService {
+        recon_task_details_repo: mock_recon_task_details_repo,
+        recon_file_details_repo: mock_recon_file_details_repo,
+        recon_task_audit_repo: mock_recon_task_audit_repo,
+        recon_template_repo: Box::new(MockReconTemplateRepositoryInterface::new()),
+        transformer: mock_transformer,
+        event_publisher: Box::new(MockReconTaskEventPublisherInterface::new()),
+        upload_session_repo: Box::new(MockUploadSessionRepositoryInterface::new()),
+        blob_store: Box::new(MockBlobStoreInterface::new()),
+        quotas: get_dummy_quotas(),
+        recon_results_topic: String::from("recon-results"),
+    };
+
+    //act
+    let result = service
+        .get_suggested_comparison_pairs(&String::from("task-1234"), &get_dummy_owner())
+        .await;
+
+    //assert
+    assert!(matches!(result.err().unwrap().kind, AppErrorKind::BadClientRequest));
+}
+
+#[actix_web::test]
+async fn given_existing_pair_accept_suggested_comparison_pairs_only_adds_pairs_for_unused_columns() {
+    //setup
+    let (_, mock_recon_file_details_repo, _, _) = setup_dependencies();
+
+    let mut mock_transformer = Box::new(MockTransformerInterface::new());
+    mock_transformer
+        .expect_build_recon_task_details_response()
+        .returning(|_, _, _| get_dummy_recon_task_response_details());
+
+    let mut mock_recon_task_details_repo = Box::new(MockReconTaskDetailsRepositoryInterface::new());
+    mock_recon_task_details_repo
+        .expect_get_task_details()
+        .returning(|_| Ok(get_dummy_recon_task()));
+    mock_recon_task_details_repo
+        .expect_update_task_details()
+        .withf(|task| {
+            let accepted_pair = task.details.comparison_pairs.last().unwrap();
+            let definitions = task.comparison_pair_definitions();
+            task.details.comparison_pairs.len() == 2
+                && accepted_pair.primary_file_column_index == 1
+                && accepted_pair.comparison_file_column_index == 1
+                && task.resolved_comparison_pairs.is_empty()
+                && task.accepted_comparison_pairs.len() == 1
+                && task.accepted_comparison_pairs[0].primary_file_column
+                    == ColumnReference::Header(String::from("header2"))
+                && definitions.len() == 2
+                && definitions[0].primary_file_column == ColumnReference::Index(0)
+        })
+        .times(1)
+        .returning(|task| Ok(Some(task.clone())));
+
+    let mut mock_recon_task_audit_repo = Box::new(MockReconTaskAuditRepositoryInterface::new());
+    mock_recon_task_audit_repo
+        .expect_append_audit_entry()
+        .withf(|entry| entry.action == ReconTaskAction::SuggestedPairsAccepted)
+        .times(1)
+        .returning(|_| Ok(()));
+
+    let service = ReconTaskAggregationService {
+        recon_task_details_repo: mock_recon_task_details_repo,
+        recon_file_details_repo: mock_recon_file_details_repo,
+        recon_task_audit_repo: mock_recon_task_audit_repo,
+        recon_template_repo: Box::new(MockReconTemplateRepositoryInterface::new()),
+        transformer: mock_transformer,
+        event_publisher: Box::new(MockReconTaskEventPublisherInterface::new()),
+        upload_session_repo: Box::new(MockUploadSessionRepositoryInterface::new()),
+        blob_store: Box::new(MockBlobStoreInterface::new()),
+        quotas: get_dummy_quotas(),
+        recon_results_topic: String::from("recon-results"),
+    };
+
+    //act
+    let result = service
+        .accept_suggested_comparison_pairs(&String::from("task-1234"), &get_dummy_owner())
+        .await;
+
+    //assert
+    assert!(result.is_ok());
+}
+
+#[actix_web::test]
+async fn given_invalid_file_format_attach_primary_file_returns_bad_client_request_without_saving() {
+    //setup
+    let (mock_recon_task_details_repo, _, mock_recon_task_audit_repo, mock_transformer) =
+        setup_dependencies();
+
+    let mut mock_recon_file_details_repo = Box::new(MockReconFileDetailsRepositoryInterface::new());
+    mock_recon_file_details_repo
+        .expect_get_recon_file_details()
+        .returning(|_| Ok(get_dummy_recon_file()));
+    mock_recon_file_details_repo
+        .expect_create_recon_file_details()
+        .times(0);
+
+    let service = ReconTaskAggregationService {
+        recon_task_details_repo: mock_recon_task_details_repo,
+        recon_file_details_repo: mock_recon_file_details_repo,
+        recon_task_audit_repo: mock_recon_task_audit_repo,
+        recon_template_repo: Box::new(MockReconTemplateRepositoryInterface::new()),
+        transformer: mock_transformer,
+        event_publisher: Box::new(MockReconTaskEventPublisherInterface::new()),
+        upload_session_repo: Box::new(MockUploadSessionRepositoryInterface::new()),
+        blob_store: Box::new(MockBlobStoreInterface::new()),
+        quotas: get_dummy_quotas(),
+        recon_results_topic: String::from("recon-results"),
+    };
+
+    let mut test_request = get_dummy_attach_primary_file_request();
+    test_request.primary_file_format = FileFormatDescriptor {
+        layout: FileLayout::FixedWidth {
+            column_offsets: vec![0],
+        },
+        ..FileFormatDescriptor::default()
+    };
+
+    //act
+    let result = service
+        .attach_primary_file_to_task(&test_request, &get_dummy_owner())
+        .await;
+
+    //assert
+    assert!(matches!(result.err().unwrap().kind, AppErrorKind::BadClientRequest));
+}
+
+#[actix_web::test]
+async fn given_primary_file_with_schema_attach_comparison_file_records_column_type_mismatches() {
+    //setup
+    let (_, mock_recon_file_details_repo, mock_recon_task_audit_repo, _) = setup_dependencies();
+
+    let mut mock_transformer = Box::new(MockTransformerInterface::new());
+    mock_transformer
+        .expect_build_recon_task_details_response()
+        .returning(|_, _, _| {
+            let mut response = get_dummy_recon_task_response_details();
+            response.primary_file_metadata.as_mut().unwrap().column_schema = vec![
+                ColumnSchema {
+                    column_type: ColumnType::Integer,
+                    nullable: false,
+                    format: None,
+                },
+                ColumnSchema {
+                    column_type: ColumnType::Decimal,
+                    nullable: false,
+                    format: None,
+                },
+            ];
+            response
+        });
+    mock_transformer
+        .expect_get_comparison_file_details()
+        .returning(|_, _| get_dummy_recon_file());
+
+    let mut mock_recon_task_details_repo = Box::new(MockReconTaskDetailsRepositoryInterface::new());
+    mock_recon_task_details_repo
+        .expect_get_task_details()
+        .returning(|_| Ok(get_dummy_recon_task()));
+    mock_recon_task_details_repo
+        .expect_update_task_details()
+        .withf(|task| {
+            task.column_type_mismatches
+                == vec![ColumnTypeMismatch {
+                    primary_file_column_index: 0,
+                    primary_file_column_type: ColumnType::Integer,
+                    comparison_file_column_index: 0,
+                    comparison_file_column_type: ColumnType::String,
+                }]
+        })
+        .times(1)
+        .returning(|task| Ok(Some(task.clone())));
+
+    let service = ReconTaskAggregationService {
+        recon_task_details_repo: mock_recon_task_details_repo,
+        recon_file_details_repo: mock_recon_file_details_repo,
+        recon_task_audit_repo: mock_recon_task_audit_repo,
+        recon_template_repo: Box::new(MockReconTemplateRepositoryInterface::new()),
+        transformer: mock_transformer,
+        event_publisher: Box::new(MockReconTaskEventPublisherInterface::new()),
+        upload_session_repo: Box::new(MockUploadSessionRepositoryInterface::new()),
+        blob_store: Box::new(MockBlobStoreInterface::new()),
+        quotas: get_dummy_quotas(),
+        recon_results_topic: String::from("recon-results"),
+    };
+
+    let test_request = AttachComparisonFileRequest {
+        task_id: String::from("task-1234"),
+        comparison_file_name: String::from("cmp-file-1234"),
+        comparison_file_hash: String::from("cmp-file-1234"),
+        comparison_file_headers: vec![String::from("reference"), String::from("total")],
+        comparison_file_row_count: 1000,
+        comparison_file_delimiters: vec![','],
+        comparison_file_format: FileFormatDescriptor::default(),
+        comparison_file_column_schema: vec![
+            ColumnSchema {
+                column_type: ColumnType::String,
+                nullable: false,
+                format: None,
+            },
+            ColumnSchema {
+                column_type: ColumnType::Decimal,
+                nullable: true,
+                format: None,
+            },
+        ],
+    };
+
+    //act
+    let result = service
+        .attach_comparison_file_to_task(&test_request, &get_dummy_owner())
+        .await;
+
+    //assert
+    assert!(result.is_ok());
+}
+
+#[actix_web::test]
+async fn given_uploaded_csv_upload_primary_file_saves_it_and_attaches_the_metadata_read_from_it() {
+    //setup
+    let (mock_recon_task_details_repo, _, mock_recon_task_audit_repo, _) = setup_dependencies();
+
+    let mut mock_blob_store = Box::new(MockBlobStoreInterface::new());
+    mock_blob_store
+        .expect_save_file()
+        .withf(|file_name, content| {
+            file_name.starts_with("task-1234-") && content == b"id,amount\n1,10\n2,20\n"
+        })
+        .times(1)
+        .returning(|_, _| Ok(()));
+
+    let mut mock_transformer = Box::new(MockTransformerInterface::new());
+    mock_transformer
+        .expect_build_recon_task_details_response()
+        .returning(|_, _, _| get_dummy_recon_task_response_details());
+    mock_transformer
+        .expect_get_primary_file_details()
+        .withf(|request, _| {
+            request.primary_file_name == "payments.csv"
+                && request.primary_file_hash.len() == 64
+                && request.primary_file_row_count == 3
+                && request.primary_file_headers == vec![String::from("id"), String::from("amount")]
+                && request.primary_file_delimiters == vec![',']
+                && request.primary_file_format.has_header_row
+        })
+        .times(1)
+        .returning(|_, _| get_dummy_recon_file());
+
+    let mut mock_recon_file_details_repo = Box::new(MockReconFileDetailsRepositoryInterface::new());
+    mock_recon_file_details_repo
+        .expect_get_recon_file_details()
+        .returning(|_| Ok(get_dummy_recon_file()));
+    mock_recon_file_details_repo
+        .expect_create_recon_file_details()
+        .withf(|file| {
+            file.stored_file_parts.len() == 1
+                && file.stored_file_parts[0].starts_with("task-1234-")
+                && file.verification.is_verified()
+        })
+        .times(1)
+        .returning(|_| Ok(String::from("file-1234")));
+
+    let service = ReconTaskAggregationService {
+        recon_task_details_repo: mock_recon_task_details_repo,
+        recon_file_details_repo: mock_recon_file_details_repo,
+        recon_task_audit_repo: mock_recon_task_audit_repo,
+        recon_template_repo: Box::new(MockReconTemplateRepositoryInterface::new()),
+        transformer: mock_transformer,
+        event_publisher: Box::new(MockReconTaskEventPublisherInterface::new()),
+        upload_session_repo: Box::new(MockUploadSessionRepositoryInterface::new()),
+        blob_store: mock_blob_store,
+        quotas: get_dummy_quotas(),
+        recon_results_topic: String::from("recon-results"),
+    };
+
+    //act
+    let result = service
+        .upload_primary_file_to_task(
+            &String::from("task-1234"),
+            &String::from("payments.csv"),
+            uploaded_content(vec![b"id,amount\n1,10\n2,20\n"]),
+            &get_dummy_owner(),
+        )
+        .await;
+
+    //assert
+    assert_eq!(result.unwrap().file_id, "file-1234");
+}
+
+#[actix_web::test]
+async fn given_upload_larger_than_a_part_upload_primary_file_saves_it_in_parts_named_by_content() {
+    //setup
+    let (mock_recon_task_details_repo, _, mock_recon_task_audit_repo, _) = setup_dependencies();
+
+    let first_piece = "a,b\n".repeat(1024 * 1024).into_bytes();
+    let first_part_name = format!("task-1234-{}", content_hash(&first_piece));
+    let last_part_name = format!("task-1234-{}", content_hash(b"1,2\n"));
+
+    let mut mock_blob_store = Box::new(MockBlobStoreInterface::new());
+    let expected_first_part_name = first_part_name.clone();
+    mock_blob_store
+        .expect_save_file()
+        .withf(move |file_name, content| {
+            file_name == expected_first_part_name && content.len() == 4 * 1024 * 1024
+        })
+        .times(1)
+        .returning(|_, _| Ok(()));
+    mock_blob_store
+        .expect_save_file()
+        .withf(|_, content| content == b"1,2\n")
+        .times(1)
+        .returning(|_, _| Ok(()));
+
+    let mut mock_transformer = Box::new(MockTransformerInterface::new());
+    mock_transformer
+        .expect_build_recon_task_details_response()
+        .returning(|_, _, _| get_dummy_recon_task_response_details());
+    mock_transformer
+        .expect_get_primary_file_details()
+        .withf(|request, _| request.primary_file_row_count == 1024 * 1024 + 1)
+        .times(1)
+        .returning(|_, _| get_dummy_recon_file());
+
+    let mut mock_recon_file_details_repo = Box::new(MockReconFileDetailsRepositoryInterface::new());
+    mock_recon_file_details_repo
+        .expect_get_recon_file_details()
+        .returning(|_| Ok(get_dummy_recon_file()));
+    mock_recon_file_details_repo
+        .expect_create_recon_file_details()
+        .withf(move |file| file.stored_file_parts == vec![first_part_name.clone(), last_part_name.clone()])
+        .times(1)
+        .returning(|_| Ok(String::from("file-1234")));
+
+    let service = ReconTaskAggregationService {
+        recon_task_details_repo: mock_recon_task_details_repo,
+        recon_file_details_repo: mock_recon_file_details_repo,
+        recon_task_audit_repo: mock_recon_task_audit_repo,
+        recon_template_repo: Box::new(MockReconTemplateRepositoryInterface::new()),
+        transformer: mock_transformer,
+        event_publisher: Box::new(MockReconTaskEventPublisherInterface::new()),
+        upload_session_repo: Box::new(MockUploadSessionRepositoryInterface::new()),
+        blob_store: mock_blob_store,
+        quotas: ReconTaskQuotas {
+            max_active_tasks_per_user: 10,
+            max_file_row_count: 2 * 1024 * 1024,
+        },
+        recon_results_topic: String::from("recon-results"),
+    };
+
+    //act
+    let result = service
+        .upload_primary_file_to_task(
+            &String::from("task-1234"),
+            &String::from("payments.csv"),
+            uploaded_content(vec![&first_piece, b"1,2\n"]),
+            &get_dummy_owner(),
+        )
+        .await;
+
+    //assert
+    assert_eq!(result.unwrap().file_id, "file-1234");
+}
+
+#[actix_web::test]
+async fn given_empty_upload_upload_primary_file_returns_bad_client_request_without_saving() {
+    //setup
+    let (
+        mock_recon_task_details_repo,
+        mock_recon_file_details_repo,
+        mock_recon_task_audit_repo,
+        mock_transformer,
+    ) = setup_dependencies();
+
+    let mut mock_blob_store = Box::new(MockBlobStoreInterface::new());
+    mock_blob_store.expect_save_file().times(0);
+
+    let service = ReconTaskAggregationService {
+        recon_task_details_repo: mock_recon_task_details_repo,
+        recon_file_details_repo: mock_recon_file_details_repo,
+        recon_task_audit_repo: mock_recon_task_audit_repo,
+        recon_template_repo: Box::new(MockReconTemplateRepositoryInterface::new()),
+        transformer: mock_transformer,
+        event_publisher: Box::new(MockReconTaskEventPublisherInterface::new()),
+        upload_session_repo: Box::new(MockUploadSessionRepositoryInterface::new()),
+        blob_store: mock_blob_store,
+        quotas: get_dummy_quotas(),
+        recon_results_topic: String::from("recon-results"),
+    };
+
+    //act
+    let result = service
+        .upload_primary_file_to_task(
+            &String::from("task-1234"),
+            &String::from("payments.csv"),
+            uploaded_content(vec![b"\r\n\n"]),
+            &get_dummy_owner(),
+        )
+        .await;
+
+    //assert
+    assert!(matches!(result.err().unwrap().kind, AppErrorKind::BadClientRequest));
+}
+
+#[actix_web::test]
+async fn given_cancelled_task_upload_comparison_file_returns_bad_client_request_without_saving() {
+    //setup
+    let (mock_recon_task_details_repo, mock_recon_file_details_repo, mock_recon_task_audit_repo, _) =
+        setup_dependencies();
+
+    let mut mock_transformer = Box::new(MockTransformerInterface::new());
+    mock_transformer
+        .expect_build_recon_task_details_response()
+        .returning(|_, _, _| {
+            let mut response = get_dummy_recon_task_response_details();
+            response.task_details = get_dummy_cancelled_recon_task();
+            response
+        });
+
+    let mut mock_blob_store = Box::new(MockBlobStoreInterface::new());
+    mock_blob_store.expect_save_file().times(0);
+
+    let service = ReconTaskAggregationService {
+        recon_task_details_repo: mock_recon_task_details_repo,
+        recon_file_details_repo: mock_recon_file_details_repo,
+        recon_task_audit_repo: mock_recon_task_audit_repo,
+        recon_template_repo: Box::new(MockReconTemplateRepositoryInterface::new()),
+        transformer: mock_transformer,
+        event_publisher: Box::new(MockReconTaskEventPublisherInterface::new()),
+        upload_session_repo: Box::new(MockUploadSessionRepositoryInterface::new()),
+        blob_store: mock_blob_store,
+        quotas: get_dummy_quotas(),
+        recon_results_topic: String::from("recon-results"),
+    };
+
+    //act
+    let result = service
+        .upload_comparison_file_to_task(
+            &String::from("task-1234"),
+            &String::from("ledger.csv"),
+            uploaded_content(vec![b"reference,total\nA1,10\n"]),
+            &get_dummy_owner(),
+        )
+        .await;
+
+    //assert
+    assert!(matches!(result.err().unwrap().kind, AppErrorKind::BadClientRequest));
+}
+
+#[actix_web::test]
+async fn given_chunk_of_wrong_size_upload_file_chunk_returns_bad_client_request_without_saving() {
+    //setup
+    let (
+        mock_recon_task_details_repo,
+        mock_recon_file_details_repo,
+        mock_recon_task_audit_repo,
+        mock_transformer,
+    ) = setup_dependencies();
+
+    let mut mock_upload_session_repo = Box::new(MockUploadSessionRepositoryInterface::new());
+    mock_upload_session_repo
+        .expect_get_upload_session()
+        .returning(|_| Ok(get_dummy_upload_session()));
+    mock_upload_session_repo.expect_record_upload_chunk().times(0);
+
+    let mut mock_blob_store = Box::new(MockBlobStoreInterface::new());
+    mock_blob_store.expect_save_file().times(0);
+
+    let service = ReconTaskAggregationService {
+        recon_task_details_repo: mock_recon_task_details_repo,
+        recon_file_details_repo: mock_recon_file_details_repo,
+        recon_task_audit_repo: mock_recon_task_audit_repo,
+        recon_template_repo: Box::new(MockReconTemplateRepositoryInterface::new()),
+        transformer: mock_transformer,
+        event_publisher: Box::new(MockReconTaskEventPublisherInterface::new()),
+        upload_session_repo: mock_upload_session_repo,
+        blob_store: mock_blob_store,
+        quotas: get_dummy_quotas(),
+        recon_results_topic: String::from("recon-results"),
+    };
+
+    //act
+    let result = service
+        .upload_file_chunk(
+            &String::from("task-1234"),
+            &String::from("upload-1234"),
+            2,
+            b"2,20\n and more",
+            &get_dummy_owner(),
+        )
+        .await;
+
+    //assert
+    assert!(matches!(result.err().unwrap().kind, AppErrorKind::BadClientRequest));
+}
+
+#[actix_web::test]
+async fn given_upload_of_another_task_upload_file_chunk_returns_not_found() {
+    //setup
+    let (
+        mock_recon_task_details_repo,
+        mock_recon_file_details_repo,
+        mock_recon_task_audit_repo,
+        mock_transformer,
+    ) = setup_dependencies();
+
+    let mut mock_upload_session_repo = Box::new(MockUploadSessionRepositoryInterface::new());
+    mock_upload_session_repo
+        .expect_get_upload_session()
+        .returning(|_| Ok(get_dummy_upload_session()));
+
+    let service = ReconTaskAggregationService {
+        recon_task_details_repo: mock_recon_task_details_repo,
+        recon_file_details_repo: mock_recon_file_details_repo,
+        recon_task_audit_repo: mock_recon_task_audit_repo,
+        recon_template_repo: Box::new(MockReconTemplateRepositoryInterface::new()),
+        transformer: mock_transformer,
+        event_publisher: Box::new(MockReconTaskEventPublisherInterface::new()),
+        upload_session_repo: mock_upload_session_repo,
+        blob_store: Box::new(MockBlobStoreInterface::new()),
+        quotas: get_dummy_quotas(),
+        recon_results_topic: String::from("recon-results"),
+    };
+
+    //act
+    let result = service
+        .upload_file_chunk(
+            &String::from("another-task"),
+            &String::from("upload-1234"),
+            0,
+            b"id,amoun",
+            &get_dummy_owner(),
+        )
+        .await;
+
+    //assert
+    assert!(matches!(result.err().unwrap().kind, AppErrorKind::NotFound));
+}
+
+#[actix_web::test]
+async fn given_valid_chunk_upload_file_chunk_saves_chunk_and_records_it() {
+    //setup
+    let (
+        mock_recon_task_details_repo,
+        mock_recon_file_details_repo,
+        mock_recon_task_audit_repo,
+        mock_transformer,
+    ) = setup_dependencies();
+
+    let mut mock_upload_session_repo = Box::new(MockUploadSessionRepositoryInterface::new());
+    mock_upload_session_repo
+        .expect_get_upload_session()
+        .returning(|_| Ok(get_dummy_upload_session()));
+    mock_upload_session_repo
+        .expect_record_upload_chunk()
+        .withf(|upload_id, chunk_number| upload_id == "upload-1234" && *chunk_number == 2)
+        .times(1)
+        .returning(|_, chunk_number| {
+            let mut session = get_dummy_upload_session();
+            session.record_chunk(chunk_number);
+            Ok(session)
+        });
+
+    let mut mock_blob_store = Box::new(MockBlobStoreInterface::new());
+    mock_blob_store
+        .expect_save_file()
+        .withf(|file_name, content| file_name == "upload-1234-chunk-2" && content == b",20\n")
+        .times(1)
+        .returning(|_, _| Ok(()));
+
+    let service = ReconTaskAggregationService {
+        recon_task_details_repo: mock_recon_task_details_repo,
+        recon_file_details_repo: mock_recon_file_details_repo,
+        recon_task_audit_repo: mock_recon_task_audit_repo,
+        recon_template_repo: Box::new(MockReconTemplateRepositoryInterface::new()),
+        transformer: mock_transformer,
+        event_publisher: Box::new(MockReconTaskEventPublisherInterface::new()),
+        upload_session_repo: mock_upload_session_repo,
+        blob_store: mock_blob_store,
+        quotas: get_dummy_quotas(),
+        recon_results_topic: String::from("recon-results"),
+    };
+
+    //act
+    let result = service
+        .upload_file_chunk(
+            &String::from("task-1234"),
+            &String::from("upload-1234"),
+            2,
+            b",20\n",
+            &get_dummy_owner(),
+        )
+        .await;
+
+    //assert
+    let response = result.unwrap();
+    assert_eq!(response.missing_chunks, vec![0, 1]);
+}
+
+#[actix_web::test]
+async fn given_missing_chunk_complete_upload_session_returns_bad_client_request() {
+    //setup
+    let (
+        mock_recon_task_details_repo,
+        mock_recon_file_details_repo,
+        mock_recon_task_audit_repo,
+        mock_transformer,
+    ) = setup_dependencies();
+
+    let mut mock_upload_session_repo = Box::new(MockUploadSessionRepositoryInterface::new());
+    mock_upload_session_repo.expect_get_upload_session().returning(|_| {
+        let mut session = get_dummy_upload_session();
+        session.received_chunks = vec![0, 2];
+        Ok(session)
+    });
+    mock_upload_session_repo.expect_complete_upload_session().times(0);
+
+    let mut mock_blob_store = Box::new(MockBlobStoreInterface::new());
+    mock_blob_store.expect_read_file().times(0);
+
+    let service = ReconTaskAggregationService {
+        recon_task_details_repo: mock_recon_task_details_repo,
+        recon_file_details_repo: mock_recon_file_details_repo,
+        recon_task_audit_repo: mock_recon_task_audit_repo,
+        recon_template_repo: Box::new(MockReconTemplateRepositoryInterface::new()),
+        transformer: mock_transformer,
+        event_publisher: Box::new(MockReconTaskEventPublisherInterface::new()),
+        upload_session_repo: mock_upload_session_repo,
+        blob_store: mock_blob_store,
+        quotas: get_dummy_quotas(),
+        recon_results_topic: String::from("recon-results"),
+    };
+
+    //act
+    let result = service
+        .complete_upload_session(
+            &String::from("task-1234"),
+            &String::from("upload-1234"),
+            &CompleteUploadRequest {
+                file_hash: String::from(DUMMY_UPLOAD_FILE_HASH),
+            },
+            &get_dummy_owner(),
+        )
+        .await;
+
+    //assert
+    assert!(matches!(result.err().unwrap().kind, AppErrorKind::BadClientRequest));
+}
+
+#[actix_web::test]
+async fn given_chunks_not_matching_file_hash_complete_upload_session_returns_bad_client_request() {
+    //setup
+    let (
+        mock_recon_task_details_repo,
+        mock_recon_file_details_repo,
+        mock_recon_task_audit_repo,
+        mock_transformer,
+    ) = setup_dependencies();
+
+    let mut mock_upload_session_repo = Box::new(MockUploadSessionRepositoryInterface::new());
+    mock_upload_session_repo.expect_get_upload_session().returning(|_| {
+        let mut session = get_dummy_upload_session();
+        session.received_chunks = vec![0, 1, 2];
+        Ok(session)
+    });
+    mock_upload_session_repo.expect_complete_upload_session().times(0);
+
+    let mut mock_blob_store = Box::new(MockBlobStoreInterface::new());
+    mock_blob_store
+        .expect_read_file()
+        .returning(|file_name| Ok(get_dummy_upload_chunk(file_name)));
+
+    let service = ReconTaskAggregationService {
+        recon_task_details_repo: mock_recon_task_details_repo,
+        recon_file_details_repo: mock_recon_file_details_repo,
+        recon_task_audit_repo: mock_recon_task_audit_repo,
+        recon_template_repo: Box::new(MockReconTemplateRepositoryInterface::new()),
+        transformer: mock_transformer,
+        event_publisher: Box::new(MockReconTaskEventPublisherInterface::new()),
+        upload_session_repo: mock_upload_session_repo,
+        blob_store: mock_blob_store,
+        quotas: get_dummy_quotas(),
+        recon_results_topic: String::from("recon-results"),
+    };
+
+    //act
+    let result = service
+        .complete_upload_session(
+            &String::from("task-1234"),
+            &String::from("upload-1234"),
+            &CompleteUploadRequest {
+                file_hash: "0".repeat(64),
+            },
+            &get_dummy_owner(),
+        )
+        .await;
+
+    //assert
+    assert!(matches!(result.err().unwrap().kind, AppErrorKind::BadClientRequest));
+}
+
+#[actix_web::test]
+async fn given_all_chunks_complete_upload_session_attaches_file_made_of_the_chunks() {
+    //setup
+    let (mock_recon_task_details_repo, _, mock_recon_task_audit_repo, mock_transformer) =
+        setup_dependencies();
+
+    let mut mock_recon_file_details_repo = Box::new(MockReconFileDetailsRepositoryInterface::new());
+    mock_recon_file_details_repo
+        .expect_get_recon_file_details()
+        .returning(|_| Ok(get_dummy_recon_file()));
+    mock_recon_file_details_repo
+        .expect_create_recon_file_details()
+        .withf(|file| {
+            file.stored_file_parts
+                == vec![
+                    String::from("upload-1234-chunk-0"),
+                    String::from("upload-1234-chunk-1"),
+                    String::from("upload-1234-chunk-2"),
+                ]
+        })
+        .times(1)
+        .returning(|_| Ok(String::from("file-1234")));
+
+    let mut mock_upload_session_repo = Box::new(MockUploadSessionRepositoryInterface::new());
+    mock_upload_session_repo.expect_get_upload_session().returning(|_| {
+        let mut session = get_dummy_upload_session();
+        session.received_chunks = vec![0, 1, 2];
+        Ok(session)
+    });
+    mock_upload_session_repo
+        .expect_complete_upload_session()
+        .withf(|upload_id, file_id| upload_id == "upload-1234" && file_id == "file-1234")
+        .times(1)
+        .returning(|_, file_id| {
+            let mut session = get_dummy_upload_session();
+            session.status = UploadSessionStatus::Completed;
+            session.file_id = Some(file_id.to_string());
+            Ok(session)
+        });
+
+    let mut mock_blob_store = Box::new(MockBlobStoreInterface::new());
+    mock_blob_store
+        .expect_read_file()
+        .times(3)
+        .returning(|file_name| Ok(get_dummy_upload_chunk(file_name)));
+
+    let service = ReconTaskAggregationService {
+        recon_task_details_repo: mock_recon_task_details_repo,
+        recon_file_details_repo: mock_recon_file_details_repo,
+        recon_task_audit_repo: mock_recon_task_audit_repo,
+        recon_template_repo: Box::new(MockReconTemplateRepositoryInterface::new()),
+        transformer: mock_transformer,
+        event_publisher: Box::new(MockReconTaskEventPublisherInterface::new()),
+        upload_session_repo: mock_upload_session_repo,
+        blob_store: mock_blob_store,
+        quotas: get_dummy_quotas(),
+        recon_results_topic: String::from("recon-results"),
+    };
+
+    //act
+    let result = service
+        .complete_upload_session(
+            &String::from("task-1234"),
+            &String::from("upload-1234"),
+            &CompleteUploadRequest {
+                file_hash: DUMMY_UPLOAD_FILE_HASH.to_uppercase(),
+            },
+            &get_dummy_owner(),
+        )
+        .await;
+
+    //assert
+    assert_eq!(result.unwrap().file_id, "file-1234");
+}
+
+#[actix_web::test]
+async fn given_upload_completed_by_a_racing_request_complete_upload_session_returns_the_file_it_was_completed_as() {
+    //setup
+    let (mock_recon_task_details_repo, _, mock_recon_task_audit_repo, mock_transformer) =
+        setup_dependencies();
+
+    let mut mock_recon_file_details_repo = Box::new(MockReconFileDetailsRepositoryInterface::new());
+    mock_recon_file_details_repo
+        .expect_get_recon_file_details()
+        .returning(|_| Ok(get_dummy_recon_file()));
+    mock_recon_file_details_repo
+        .expect_create_recon_file_details()
+        .withf(|file| {
+            file.stored_file_parts
+                == vec![
+                    String::from("upload-1234-chunk-0"),
+                    String::from("upload-1234-chunk-1"),
+                    String::from("upload-1234-chunk-2"),
+                ]
+        })
+        .times(1)
+        .returning(|_| Ok(String::from("file-1234")));
+
+    let mut mock_upload_session_repo = Box::new(MockUploadSessionRepositoryInterface::new());
+    mock_upload_session_repo.expect_get_upload_session().returning(|_| {
+        let mut session = get_dummy_upload_session();
+        session.received_chunks = vec![0, 1, 2];
+        Ok(session)
+    });
+    mock_upload_session_repo
+        .expect_complete_upload_session()
+        .withf(|upload_id, file_id| upload_id == "upload-1234" && file_id == "file-1234")
+        .times(1)
+        .returning(|_, _| {
+            //the other request attached its file first
+            let mut session = get_dummy_upload_session();
+            session.status = UploadSessionStatus::Completed;
+            session.file_id = Some(String::from("file-5678"));
+            Ok(session)
+        });
+
+    let mut mock_blob_store = Box::new(MockBlobStoreInterface::new());
+    mock_blob_store
+        .expect_read_file()
+        .times(3)
+        .returning(|file_name| Ok(get_dummy_upload_chunk(file_name)));
+
+    let service = ReconTaskAggregationService {
+        recon_task_details_repo: mock_recon_task_details_repo,
+        recon_file_details_repo: mock_recon_file_details_repo,
+        recon_task_audit_repo: mock_recon_task_audit_repo,
+        recon_template_repo: Box::new(MockReconTemplateRepositoryInterface::new()),
+        transformer: mock_transformer,
+        event_publisher: Box::new(MockReconTaskEventPublisherInterface::new()),
+        upload_session_repo: mock_upload_session_repo,
+        blob_store: mock_blob_store,
+        quotas: get_dummy_quotas(),
+        recon_results_topic: String::from("recon-results"),
+    };
+
+    //act
+    let result = service
+        .complete_upload_session(
+            &String::from("task-1234"),
+            &String::from("upload-1234"),
+            &CompleteUploadRequest {
+                file_hash: DUMMY_UPLOAD_FILE_HASH.to_uppercase(),
+            },
+            &get_dummy_owner(),
+        )
+        .await;
+
+    //assert
+    assert_eq!(result.unwrap().file_id, "file-5678");
+}
+
+#[actix_web::test]
+async fn given_stored_files_matching_their_metadata_verify_task_files_verifies_them_and_readies_the_task() {
+    //setup
+    let (_, _, mock_recon_task_audit_repo, _) = setup_dependencies();
+
+    let mut mock_transformer = Box::new(MockTransformerInterface::new());
+    mock_transformer
+        .expect_build_recon_task_details_response()
+        .returning(|_, _, _| get_dummy_response_with_stored_files());
+
+    let mut mock_recon_task_details_repo = Box::new(MockReconTaskDetailsRepositoryInterface::new());
+    mock_recon_task_details_repo
+        .expect_get_task_details()
+        .returning(|_| Ok(get_dummy_recon_task()));
+    mock_recon_task_details_repo
+        .expect_update_task_details()
+        .withf(|task| task.status == ReconTaskStatus::Ready)
+        .times(1)
+        .returning(|task| Ok(Some(task.clone())));
+
+    let mut mock_recon_file_details_repo = Box::new(MockReconFileDetailsRepositoryInterface::new());
+    mock_recon_file_details_repo
+        .expect_get_recon_file_details()
+        .returning(|_| Ok(get_dummy_recon_file()));
+    mock_recon_file_details_repo
+        .expect_update_recon_file_details()
+        .withf(|file| {
+            file.verification.status == FileVerificationStatus::Verified
+                && file.verification.actual_row_count == Some(3)
+        })
+        .times(2)
+        .returning(|file| Ok(file.clone()));
+
+    let mut mock_blob_store = Box::new(MockBlobStoreInterface::new());
+    mock_blob_store
+        .expect_read_file_if_exists()
+        .times(6)
+        .returning(|file_name| Ok(Some(get_dummy_upload_chunk(file_name))));
+
+    let service = ReconTaskAggregationService {
+        recon_task_details_repo: mock_recon_task_details_repo,
+        recon_file_details_repo: mock_recon_file_details_repo,
+        recon_task_audit_repo: mock_recon_task_audit_repo,
+        recon_template_repo: Box::new(MockReconTemplateRepositoryInterface::new()),
+        transformer: mock_transformer,
+        event_publisher: Box::new(MockReconTaskEventPublisherInterface::new()),
+        upload_session_repo: Box::new(MockUploadSessionRepositoryInterface::new()),
+        blob_store: mock_blob_store,
+        quotas: get_dummy_quotas(),
+        recon_results_topic: String::from("recon-results"),
+    };
+
+    //act
+    let result = service
+        .verify_task_files(&String::from("task-1234"), &get_dummy_owner())
+        .await;
+
+    //assert
+    assert!(result.is_ok());
+}
+
+#[actix_web::test]
+async fn given_stored_file_with_other_row_count_verify_task_files_records_mismatch_and_keeps_task_awaiting_files() {
+    //setup
+    let (_, _, mock_recon_task_audit_repo, _) = setup_dependencies();
+
+    let mut mock_transformer = Box::new(MockTransformerInterface::new());
+    mock_transformer
+        .expect_build_recon_task_details_response()
+        .returning(|_, _, _| {
+            let mut response = get_dummy_response_with_stored_files();
+            if let Some(file) = response.primary_file_metadata.as_mut() {
+                file.metadata.row_count = 4;
+            }
+            response
+        });
+
+    let mut mock_recon_task_details_repo = Box::new(MockReconTaskDetailsRepositoryInterface::new());
+    mock_recon_task_details_repo
+        .expect_get_task_details()
+        .returning(|_| Ok(get_dummy_recon_task()));
+    mock_recon_task_details_repo.expect_update_task_details().times(0);
+
+    let mut mock_recon_file_details_repo = Box::new(MockReconFileDetailsRepositoryInterface::new());
+    mock_recon_file_details_repo
+        .expect_get_recon_file_details()
+        .returning(|_| Ok(get_dummy_recon_file()));
+    mock_recon_file_details_repo
+        .expect_update_recon_file_details()
+        .withf(|file| file.metadata.id == "src-file-1234")
+        .times(1)
+        .returning(|file| {
+            assert_eq!(file.verification.status, FileVerificationStatus::Mismatch);
+            assert_eq!(file.verification.actual_row_count, Some(3));
+            Ok(file.clone())
+        });
+    mock_recon_file_details_repo
+        .expect_update_recon_file_details()
+        .withf(|file| file.metadata.id == "cmp-file-1234")
+        .times(1)
+        .returning(|file| Ok(file.clone()));
+
+    let mut mock_blob_store = Box::new(MockBlobStoreInterface::new());
+    mock_blob_store
+        .expect_read_file_if_exists()
+        .returning(|file_name| Ok(Some(get_dummy_upload_chunk(file_name))));
+
+    let service = ReconTaskAggregationService {
+        recon_task_details_repo: mock_recon_task_details_repo,
+        recon_file_details_repo: mock_recon_file_details_repo,
+        recon_task_audit_repo: mock_recon_task_audit_repo,
+        recon_template_repo: Box::new(MockReconTemplateRepositoryInterface::new()),
+        transformer: mock_transformer,
+        event_publisher: Box::new(MockReconTaskEventPublisherInterface::new()),
+        upload_session_repo: Box::new(MockUploadSessionRepositoryInterface::new()),
+        blob_store: mock_blob_store,
+        quotas: get_dummy_quotas(),
+        recon_results_topic: String::from("recon-results"),
+    };
+
+    //act
+    let result = service
+        .verify_task_files(&String::from("task-1234"), &get_dummy_owner())
+        .await;
+
+    //assert
+    assert!(result.is_ok());
+}
+
+#[actix_web::test]
+async fn given_file_content_not_in_blob_store_verify_task_files_leaves_files_pending() {
+    //setup
+    let (
+        mock_recon_task_details_repo,
+        _,
+        mock_recon_task_audit_repo,
+        mock_transformer,
+    ) = setup_dependencies();
+
+    let mut mock_recon_file_details_repo = Box::new(MockReconFileDetailsRepositoryInterface::new());
+    mock_recon_file_details_repo
+        .expect_get_recon_file_details()
+        .returning(|_| Ok(get_dummy_recon_file()));
+    mock_recon_file_details_repo
+        .expect_update_recon_file_details()
+        .times(0);
+
+    let mut mock_blob_store = Box::new(MockBlobStoreInterface::new());
+    //files attached with their metadata only are looked for under their name within the task
+    mock_blob_store
+        .expect_read_file_if_exists()
+        .withf(|file_name| {
+            file_name == "task-1234-src-file-1234" || file_name == "task-1234-cmp-file-1234"
+        })
+        .times(2)
+        .returning(|_| Ok(None));
+
+    let service = ReconTaskAggregationService {
+        recon_task_details_repo: mock_recon_task_details_repo,
+        recon_file_details_repo: mock_recon_file_details_repo,
+        recon_task_audit_repo: mock_recon_task_audit_repo,
+        recon_template_repo: Box::new(MockReconTemplateRepositoryInterface::new()),
+        transformer: mock_transformer,
+        event_publisher: Box::new(MockReconTaskEventPublisherInterface::new()),
+        upload_session_repo: Box::new(MockUploadSessionRepositoryInterface::new()),
+        blob_store: mock_blob_store,
+        quotas: get_dummy_quotas(),
+        recon_results_topic: String::from("recon-results"),
+    };
+
+    //act
+    let result = service
+        .verify_task_files(&String::from("task-1234"), &get_dummy_owner())
+        .await;
+
+    //assert
+    assert!(result.is_ok());
+}
+
+#[actix_web::test]
+async fn given_unverified_files_start_recon_task_returns_bad_client_request_without_publishing() {
+    //setup
+    let (mock_recon_task_details_repo, mock_recon_file_details_repo, mock_recon_task_audit_repo, _) =
+        setup_dependencies();
+
+    let mut mock_transformer = Box::new(MockTransformerInterface::new());
+    mock_transformer
+        .expect_build_recon_task_details_response()
+        .returning(|_, _, _| get_dummy_response_with_stored_files());
+
+    let mut mock_event_publisher = Box::new(MockReconTaskEventPublisherInterface::new());
+    mock_event_publisher.expect_publish_task_event().times(0);
+
+    let service = ReconTaskAggregationService {
+        recon_task_details_repo: mock_recon_task_details_repo,
+        recon_file_details_repo: mock_recon_file_details_repo,
+        recon_task_audit_repo: mock_recon_task_audit_repo,
+        recon_template_repo: Box::new(MockReconTemplateRepositoryInterface::new()),
+        transformer: mock_transformer,
+        event_publisher: mock_event_publisher,
+        upload_session_repo: Box::new(MockUploadSessionRepositoryInterface::new()),
+        blob_store: Box::new(MockBlobStoreInterface::new()),
+        quotas: get_dummy_quotas(),
+        recon_results_topic: String::from("recon-results"),
+    };
+
+    //act
+    let result = service
+        .start_recon_task(&String::from("task-1234"), &get_dummy_owner())
+        .await;
+
+    //assert
+    assert!(matches!(result.err().unwrap().kind, AppErrorKind::BadClientRequest));
+}
+
+#[actix_web::test]
+async fn given_verified_files_start_recon_task_publishes_each_file_on_its_chunks_topic_and_marks_task_reconciling() {
+    //setup
+    let (_, mock_recon_file_details_repo, _, _) = setup_dependencies();
+
+    let mut mock_transformer = Box::new(MockTransformerInterface::new());
+    mock_transformer
+        .expect_build_recon_task_details_response()
+        .returning(|_, _, _| get_dummy_response_with_verified_files());
+
+    let mut mock_recon_task_details_repo = Box::new(MockReconTaskDetailsRepositoryInterface::new());
+    mock_recon_task_details_repo
+        .expect_get_task_details()
+        .returning(|_| Ok(get_dummy_recon_task()));
+    mock_recon_task_details_repo
+        .expect_update_task_details()
+        .withf(|task| {
+            task.status == ReconTaskStatus::Starting
+                && task.reconciliation_start.as_ref().unwrap().started_by == "test-user-id"
+        })
+        .times(1)
+        .returning(|task| Ok(Some(task.clone())));
+    mock_recon_task_details_repo
+        .expect_mark_task_reconciling()
+        .withf(|task_id| task_id == "task-1234")
+        .times(1)
+        .returning(|_| Ok(get_dummy_recon_task()));
+
+    let mut mock_recon_task_audit_repo = Box::new(MockReconTaskAuditRepositoryInterface::new());
+    mock_recon_task_audit_repo
+        .expect_append_audit_entry()
+        .withf(|entry| entry.action == ReconTaskAction::ReconciliationStarted)
+        .times(1)
+        .returning(|_| Ok(()));
+
+    let mut mock_event_publisher = Box::new(MockReconTaskEventPublisherInterface::new());
+    mock_event_publisher
+        .expect_publish_task_event()
+        .withf(|topic, event| {
+            topic == "test-topic"
+                && matches!(
+                    event,
+                    ReconTaskEvent::ReconciliationStarted { file, comparison_pairs, idempotency_key, .. }
+                        if ((file.file_id == "src-file-1234" && idempotency_key.ends_with("-primary"))
+                            || (file.file_id == "cmp-file-1234" && idempotency_key.ends_with("-comparison")))
+                            && idempotency_key.starts_with("task-1234-start-")
+                            && file.stored_file_parts.len() == 3
+                            && comparison_pairs.len() == 1
+                )
+        })
+        .times(2)
+        .returning(|_, _| Ok(()));
+
+    let service = ReconTaskAggregationService {
+        recon_task_details_repo: mock_recon_task_details_repo,
+        recon_file_details_repo: mock_recon_file_details_repo,
+        recon_task_audit_repo: mock_recon_task_audit_repo,
+        recon_template_repo: Box::new(MockReconTemplateRepositoryInterface::new()),
+        transformer: mock_transformer,
+        event_publisher: mock_event_publisher,
+        upload_session_repo: Box::new(MockUploadSessionRepositoryInterface::new()),
+        blob_store: Box::new(MockBlobStoreInterface::new()),
+        quotas: get_dummy_quotas(),
+        recon_results_topic: String::from("recon-results"),
+    };
+
+    //act
+    let result = service
+        .start_recon_task(&String::from("task-1234"), &get_dummy_owner())
+        .await;
+
+    //assert
+    assert!(result.is_ok());
+}
+
+#[actix_web::test]
+async fn given_verified_files_attached_with_their_metadata_only_start_recon_task_sends_where_they_are_kept_within_the_task() {
+    //setup
+    let (_, mock_recon_file_details_repo, _, _) = setup_dependencies();
+
+    let mut mock_transformer = Box::new(MockTransformerInterface::new());
+    mock_transformer
+        .expect_build_recon_task_details_response()
+        .returning(|_, _, _| {
+            let mut response = get_dummy_response_with_verified_files();
+            let files = [
+                response.primary_file_metadata.as_mut(),
+                response.comparison_file_metadata.as_mut(),
+            ];
+            for file in files.into_iter().flatten() {
+                file.stored_file_parts = vec![];
+            }
+            response
+        });
+
+    let mut mock_recon_task_details_repo = Box::new(MockReconTaskDetailsRepositoryInterface::new());
+    mock_recon_task_details_repo
+        .expect_get_task_details()
+        .returning(|_| Ok(get_dummy_recon_task()));
+    mock_recon_task_details_repo
+        .expect_update_task_details()
+        .withf(|task| {
+            task.status == ReconTaskStatus::Starting
+                && task.reconciliation_start.as_ref().unwrap().started_by == "test-user-id"
+        })
+        .times(1)
+        .returning(|task| Ok(Some(task.clone())));
+    mock_recon_task_details_repo
+        .expect_mark_task_reconciling()
+        .withf(|task_id| task_id == "task-1234")
+        .times(1)
+        .returning(|_| Ok(get_dummy_recon_task()));
+
+    let mut mock_recon_task_audit_repo = Box::new(MockReconTaskAuditRepositoryInterface::new());
+    mock_recon_task_audit_repo
+        .expect_append_audit_entry()
+        .withf(|entry| entry.action == ReconTaskAction::ReconciliationStarted)
+        .times(1)
+        .returning(|_| Ok(()));
+
+    let mut mock_event_publisher = Box::new(MockReconTaskEventPublisherInterface::new());
+    mock_event_publisher
+        .expect_publish_task_event()
+        .withf(|topic, event| {
+            topic == "test-topic"
+                && matches!(
+                    event,
+                    ReconTaskEvent::ReconciliationStarted { file, comparison_pairs, .. }
+                        if file.stored_file_parts == vec![format!("task-1234-{}", file.file_name)]
+                            && comparison_pairs.len() == 1
+                )
+        })
+        .times(2)
+        .returning(|_, _| Ok(()));
+
+    let service = ReconTaskAggregationService {
+        recon_task_details_repo: mock_recon_task_details_repo,
+        recon_file_details_repo: mock_recon_file_details_repo,
+        recon_task_audit_repo: mock_recon_task_audit_repo,
+        recon_template_repo: Box::new(MockReconTemplateRepositoryInterface::new()),
+        transformer: mock_transformer,
+        event_publisher: mock_event_publisher,
+        upload_session_repo: Box::new(MockUploadSessionRepositoryInterface::new()),
+        blob_store: Box::new(MockBlobStoreInterface::new()),
+        quotas: get_dummy_quotas(),
+        recon_results_topic: String::from("recon-results"),
+    };
+
+    //act
+    let result = service
+        .start_recon_task(&String::from("task-1234"), &get_dummy_owner())
+        .await;
+
+    //assert
+    assert!(result.is_ok());
+}
+
+#[actix_web::test]
+async fn given_task_left_starting_start_recon_task_sends_the_same_commands_again_and_marks_task_reconciling() {
+    //setup
+    let (_, mock_recon_file_details_repo, _, _) = setup_dependencies();
+
+    let mut mock_transformer = Box::new(MockTransformerInterface::new());
+    mock_transformer
+        .expect_build_recon_task_details_response()
+        .returning(|_, _, _| {
+            let mut response = get_dummy_response_with_verified_files();
+            response.task_details = get_dummy_starting_recon_task();
+            response
+        });
+
+    let mut mock_recon_task_details_repo = Box::new(MockReconTaskDetailsRepositoryInterface::new());
+    mock_recon_task_details_repo
+        .expect_get_task_details()
+        .returning(|_| Ok(get_dummy_starting_recon_task()));
+    mock_recon_task_details_repo
+        .expect_update_task_details()
+        .times(0);
+    mock_recon_task_details_repo
+        .expect_mark_task_reconciling()
+        .withf(|task_id| task_id == "task-1234")
+        .times(1)
+        .returning(|_| Ok(get_dummy_starting_recon_task()));
+
+    let mut mock_recon_task_audit_repo = Box::new(MockReconTaskAuditRepositoryInterface::new());
+    mock_recon_task_audit_repo
+        .expect_append_audit_entry()
+        .withf(|entry| entry.action == ReconTaskAction::ReconciliationStarted)
+        .times(1)
+        .returning(|_| Ok(()));
+
+    let mut mock_event_publisher = Box::new(MockReconTaskEventPublisherInterface::new());
+    mock_event_publisher
+        .expect_publish_task_event()
+        .withf(|_, event| {
+            matches!(
+                event,
+                ReconTaskEvent::ReconciliationStarted { idempotency_key, started_by, .. }
+                    if (idempotency_key == "task-1234-start-1000-primary"
+                        || idempotency_key == "task-1234-start-1000-comparison")
+                        && started_by == "first-user-id"
+            )
+        })
+        .times(2)
+        .returning(|_, _| Ok(()));
+
+    let service = ReconTaskAggregationService {
+        recon_task_details_repo: mock_recon_task_details_repo,
+        recon_file_details_repo: mock_recon_file_details_repo,
+        recon_task_audit_repo: mock_recon_task_audit_repo,
+        recon_template_repo: Box::new(MockReconTemplateRepositoryInterface::new()),
+        transformer: mock_transformer,
+        event_publisher: mock_event_publisher,
+        upload_session_repo: Box::new(MockUploadSessionRepositoryInterface::new()),
+        blob_store: Box::new(MockBlobStoreInterface::new()),
+        quotas: get_dummy_quotas(),
+        recon_results_topic: String::from("recon-results"),
+    };
+
+    //act
+    let result = service
+        .start_recon_task(&String::from("task-1234"), &get_dummy_owner())
+        .await;
+
+    //assert
+    assert!(result.is_ok());
+}
+
+#[actix_web::test]
+async fn given_results_recorded_while_task_is_starting_start_recon_task_keeps_them_when_marking_task_reconciling() {
+    //setup
+    let (_, mock_recon_file_details_repo, _, _) = setup_dependencies();
+    let stored_task = Arc::new(Mutex::new(get_dummy_recon_task()));
+
+    let mut mock_transformer = Box::new(MockTransformerInterface::new());
+    mock_transformer
+        .expect_build_recon_task_details_response()
+        .returning(|_, _, _| get_dummy_response_with_verified_files());
+
+    let mut mock_recon_task_details_repo = Box::new(MockReconTaskDetailsRepositoryInterface::new());
+    let read_task = stored_task.clone();
+    mock_recon_task_details_repo
+        .expect_get_task_details()
+        .returning(move |_| Ok(read_task.lock().unwrap().clone()));
+    let saved_task = stored_task.clone();
+    mock_recon_task_details_repo
+        .expect_update_task_details()
+        .withf(|task| task.status == ReconTaskStatus::Starting)
+        .times(1)
+        .returning(move |task| {
+            *saved_task.lock().unwrap() = task.clone();
+            Ok(Some(task.clone()))
+        });
+    let marked_task = stored_task.clone();
+    mock_recon_task_details_repo
+        .expect_mark_task_reconciling()
+        .times(1)
+        .returning(move |_| {
+            let mut task = marked_task.lock().unwrap();
+            task.mark_reconciling(Utc::now());
+            Ok(task.clone())
+        });
+
+    let mut mock_recon_task_audit_repo = Box::new(MockReconTaskAuditRepositoryInterface::new());
+    mock_recon_task_audit_repo
+        .expect_append_audit_entry()
+        .returning(|_| Ok(()));
+
+    //a worker reports each file's results as soon as its command is published
+    let recorded_task = stored_task.clone();
+    let mut mock_event_publisher = Box::new(MockReconTaskEventPublisherInterface::new());
+    mock_event_publisher
+        .expect_publish_task_event()
+        .times(2)
+        .returning(move |_, event| {
+            let file_role = match event {
+                ReconTaskEvent::ReconciliationStarted { idempotency_key, .. }
+                    if idempotency_key.ends_with("-primary") => UploadedFileRole::Primary,
+                _ => UploadedFileRole::Comparison,
+            };
+            recorded_task
+                .lock()
+                .unwrap()
+                .record_chunk_results(&get_dummy_recon_chunk_results(file_role), Utc::now())
+                .unwrap();
+            Ok(())
+        });
+
+    let service = ReconTaskAggregationService {
+        recon_task_details_repo: mock_recon_task_details_repo,
+        recon_file_details_repo: mock_recon_file_details_repo,
+        recon_task_audit_repo: mock_recon_task_audit_repo,
+        recon_template_repo: Box::new(MockReconTemplateRepositoryInterface::new()),
+        transformer: mock_transformer,
+        event_publisher: mock_event_publisher,
+        upload_session_repo: Box::new(MockUploadSessionRepositoryInterface::new()),
+        blob_store: Box::new(MockBlobStoreInterface::new()),
+        quotas: get_dummy_quotas(),
+        recon_results_topic: String::from("recon-results"),
+    };
+
+    //act
+    let result = service
+        .start_recon_task(&String::from("task-1234"), &get_dummy_owner())
+        .await;
+
+    //assert
+    let stored_task = stored_task.lock().unwrap();
+    assert!(result.is_ok());
+    assert_eq!(stored_task.status, ReconTaskStatus::Completed);
+    assert!(stored_task.details.is_done);
+    assert_eq!(stored_task.results_summary.counts.matched, 20);
+}
+
+#[actix_web::test]
+async fn given_reconciling_task_attach_primary_file_returns_bad_client_request_without_saving() {
+    //setup
+    let (mock_recon_task_details_repo, _, mock_recon_task_audit_repo, _) = setup_dependencies();
+
+    let mut mock_recon_file_details_repo = Box::new(MockReconFileDetailsRepositoryInterface::new());
+    mock_recon_file_details_repo
+        .expect_get_recon_file_details()
+        .returning(|_| Ok(get_dummy_recon_file()));
+    mock_recon_file_details_repo
+        .expect_create_recon_file_details()
+        .times(0);
+
+    let mut mock_transformer = Box::new(MockTransformerInterface::new());
+    mock_transformer
+        .expect_build_recon_task_details_response()
+        .returning(|_, _, _| {
+            let mut response = get_dummy_response_with_verified_files();
+            response.task_details.status = ReconTaskStatus::Reconciling;
+            response
+        });
+
+    let service = ReconTaskAggregationService {
+        recon_task_details_repo: mock_recon_task_details_repo,
+        recon_file_details_repo: mock_recon_file_details_repo,
+        recon_task_audit_repo: mock_recon_task_audit_repo,
+        recon_template_repo: Box::new(MockReconTemplateRepositoryInterface::new()),
+        transformer: mock_transformer,
+        event_publisher: Box::new(MockReconTaskEventPublisherInterface::new()),
+        upload_session_repo: Box::new(MockUploadSessionRepositoryInterface::new()),
+        blob_store: Box::new(MockBlobStoreInterface::new()),
+        quotas: get_dummy_quotas(),
+        recon_results_topic: String::from("recon-results"),
+    };
+
+    //act
+    let result = service
+        .attach_primary_file_to_task(&get_dummy_attach_primary_file_request(), &get_dummy_owner())
+        .await;
+
+    //assert
+    assert!(matches!(result.err().unwrap().kind, AppErrorKind::BadClientRequest));
+}
 
 #[actix_web::test]
-async fn given_valid_create_recon_task_request_calls_correct_dependencies_returns_success() {
+async fn given_last_missing_chunk_record_recon_results_completes_the_task() {
     //setup
-    let (mock_recon_task_details_repo, mock_recon_file_details_repo, mock_transformer) =
-        setup_dependencies();
+    let (_, mock_recon_file_details_repo, _, mock_transformer) = setup_dependencies();
+
+    let mut mock_recon_task_details_repo = Box::new(MockReconTaskDetailsRepositoryInterface::new());
+    mock_recon_task_details_repo
+        .expect_record_chunk_results()
+        .withf(|results| results.task_id == "task-1234" && results.file_role == UploadedFileRole::Comparison)
+        .times(1)
+        .returning(|_| Ok(get_dummy_completed_recon_task()));
+    mock_recon_task_details_repo.expect_update_task_details().times(0);
+    mock_recon_task_details_repo
+        .expect_release_active_task_slot()
+        .withf(|user_id, task_id| user_id == "test-user-id" && task_id == "task-1234")
+        .times(1)
+        .returning(|_, _| Ok(()));
+
+    let mut mock_recon_task_audit_repo = Box::new(MockReconTaskAuditRepositoryInterface::new());
+    mock_recon_task_audit_repo
+        .expect_append_audit_entry()
+        .withf(|entry| {
+            entry.action == ReconTaskAction::ReconciliationCompleted
+                && entry.performed_at == Utc.timestamp_millis_opt(5000).unwrap()
+        })
+        .times(1)
+        .returning(|_| Ok(()));
 
-    let mut service = ReconTaskAggregationService {
+    let service = ReconTaskAggregationService {
         recon_task_details_repo: mock_recon_task_details_repo,
         recon_file_details_repo: mock_recon_file_details_repo,
+        recon_task_audit_repo: mock_recon_task_audit_repo,
+        recon_template_repo: Box::new(MockReconTemplateRepositoryInterface::new()),
         transformer: mock_transformer,
+        event_publisher: Box::new(MockReconTaskEventPublisherInterface::new()),
+        upload_session_repo: Box::new(MockUploadSessionRepositoryInterface::new()),
+        blob_store: Box::new(MockBlobStoreInterface::new()),
+        quotas: get_dummy_quotas(),
+        recon_results_topic: String::from("recon-results"),
     };
 
-    let test_request = get_dummy_create_recon_task();
+    //act
+    let result = service
+        .record_recon_results(&get_dummy_recon_chunk_results(UploadedFileRole::Comparison))
+        .await;
 
-    let expected = String::from("task-1234");
+    //assert
+    assert!(result.is_ok());
+}
+
+#[actix_web::test]
+async fn given_results_delivered_again_after_the_task_completed_record_recon_results_releases_the_slot_and_audits_the_same_completion() {
+    //setup
+    let (_, mock_recon_file_details_repo, _, mock_transformer) = setup_dependencies();
+
+    //the first delivery completed the task, but its response was lost
+    let mut mock_recon_task_details_repo = Box::new(MockReconTaskDetailsRepositoryInterface::new());
+    mock_recon_task_details_repo
+        .expect_record_chunk_results()
+        .times(2)
+        .returning(|_| Ok(get_dummy_completed_recon_task()));
+    mock_recon_task_details_repo
+        .expect_release_active_task_slot()
+        .withf(|user_id, task_id| user_id == "test-user-id" && task_id == "task-1234")
+        .times(2)
+        .returning(|_, _| Ok(()));
+
+    let audited_entries = Arc::new(Mutex::new(vec![]));
+    let appended_entries = audited_entries.clone();
+    let mut mock_recon_task_audit_repo = Box::new(MockReconTaskAuditRepositoryInterface::new());
+    mock_recon_task_audit_repo
+        .expect_append_audit_entry()
+        .times(2)
+        .returning(move |entry| {
+            appended_entries.lock().unwrap().push(entry.clone());
+            Ok(())
+        });
+
+    let service = ReconTaskAggregationService {
+        recon_task_details_repo: mock_recon_task_details_repo,
+        recon_file_details_repo: mock_recon_file_details_repo,
+        recon_task_audit_repo: mock_recon_task_audit_repo,
+        recon_template_repo: Box::new(MockReconTemplateRepositoryInterface::new()),
+        transformer: mock_transformer,
+        event_publisher: Box::new(MockReconTaskEventPublisherInterface::new()),
+        upload_session_repo: Box::new(MockUploadSessionRepositoryInterface::new()),
+        blob_store: Box::new(MockBlobStoreInterface::new()),
+        quotas: get_dummy_quotas(),
+        recon_results_topic: String::from("recon-results"),
+    };
+    let results = get_dummy_recon_chunk_results(UploadedFileRole::Comparison);
 
     //act
-    let result = service.create_recon_task(&test_request).await;
+    let first_delivery = service.record_recon_results(&results).await;
+    let second_delivery = service.record_recon_results(&results).await;
 
     //assert
-    assert!(result.is_ok());
-    assert_eq!(result.ok().unwrap().task_id, expected);
+    let audited_entries = audited_entries.lock().unwrap();
+    assert!(first_delivery.is_ok() && second_delivery.is_ok());
+    assert_eq!(audited_entries[0], audited_entries[1]);
 }
 
 #[actix_web::test]
-async fn given_that_invalid_create_recon_task_request_supplied_returns_error() {
+async fn given_results_file_not_named_after_the_chunk_record_recon_results_returns_bad_client_request_without_recording() {
     //setup
-    let (mock_recon_task_details_repo, mock_recon_file_details_repo, mock_transformer) =
+    let (_, mock_recon_file_details_repo, mock_recon_task_audit_repo, mock_transformer) =
         setup_dependencies();
 
-    let mut service = ReconTaskAggregationService {
+    let mut mock_recon_task_details_repo = Box::new(MockReconTaskDetailsRepositoryInterface::new());
+    mock_recon_task_details_repo.expect_record_chunk_results().times(0);
+
+    let service = ReconTaskAggregationService {
         recon_task_details_repo: mock_recon_task_details_repo,
         recon_file_details_repo: mock_recon_file_details_repo,
+        recon_task_audit_repo: mock_recon_task_audit_repo,
+        recon_template_repo: Box::new(MockReconTemplateRepositoryInterface::new()),
         transformer: mock_transformer,
+        event_publisher: Box::new(MockReconTaskEventPublisherInterface::new()),
+        upload_session_repo: Box::new(MockUploadSessionRepositoryInterface::new()),
+        blob_store: Box::new(MockBlobStoreInterface::new()),
+        quotas: get_dummy_quotas(),
+        recon_results_topic: String::from("recon-results"),
     };
 
-    let mut test_request = get_dummy_create_recon_task();
-    test_request.user_id = String::from("");
+    let mut results = get_dummy_recon_chunk_results(UploadedFileRole::Primary);
+    results.results_file_name = Some(String::from("other-task-src-file-1234"));
 
     //act
-    let result = service.create_recon_task(&test_request).await;
+    let result = service.record_recon_results(&results).await;
 
     //assert
-    assert!(result.is_err())
+    assert!(matches!(result.err().unwrap().kind, AppErrorKind::BadClientRequest));
 }
 
 #[actix_web::test]
-async fn given_that_errors_occurs_when_handling_create_recon_task_request_returns_error() {
+async fn given_results_that_do_not_complete_the_task_record_recon_results_neither_releases_the_slot_nor_audits() {
     //setup
-    let (_, mock_recon_file_details_repo, mock_transformer) = setup_dependencies();
+    let (_, mock_recon_file_details_repo, _, mock_transformer) = setup_dependencies();
 
     let mut mock_recon_task_details_repo = Box::new(MockReconTaskDetailsRepositoryInterface::new());
     mock_recon_task_details_repo
-        .expect_create_task_details()
-        .returning(|_y| {
-            Err(AppError::new(
-                AppErrorKind::ConnectionError,
-                "unable to connect".to_string(),
-            ))
+        .expect_record_chunk_results()
+        .times(1)
+        .returning(|_| Ok(get_dummy_reconciling_recon_task()));
+    mock_recon_task_details_repo.expect_update_task_details().times(0);
+    mock_recon_task_details_repo.expect_release_active_task_slot().times(0);
+
+    let mut mock_recon_task_audit_repo = Box::new(MockReconTaskAuditRepositoryInterface::new());
+    mock_recon_task_audit_repo.expect_append_audit_entry().times(0);
+
+    let service = ReconTaskAggregationService {
+        recon_task_details_repo: mock_recon_task_details_repo,
+        recon_file_details_repo: mock_recon_file_details_repo,
+        recon_task_audit_repo: mock_recon_task_audit_repo,
+        recon_template_repo: Box::new(MockReconTemplateRepositoryInterface::new()),
+        transformer: mock_transformer,
+        event_publisher: Box::new(MockReconTaskEventPublisherInterface::new()),
+        upload_session_repo: Box::new(MockUploadSessionRepositoryInterface::new()),
+        blob_store: Box::new(MockBlobStoreInterface::new()),
+        quotas: get_dummy_quotas(),
+        recon_results_topic: String::from("recon-results"),
+    };
+
+    //act
+    let result = service
+        .record_recon_results(&get_dummy_recon_chunk_results(UploadedFileRole::Primary))
+        .await;
+
+    //assert
+    assert!(result.is_ok());
+}
+
+#[actix_web::test]
+async fn given_task_still_reconciling_get_recon_results_export_returns_bad_client_request() {
+    //setup
+    let (mock_recon_task_details_repo, mock_recon_file_details_repo, mock_recon_task_audit_repo, _) =
+        setup_dependencies();
+
+    let mut mock_transformer = Box::new(MockTransformerInterface::new());
+    mock_transformer
+        .expect_build_recon_task_details_response()
+        .returning(|_, _, _| {
+            let mut response = get_dummy_recon_task_response_details();
+            response.task_details = get_dummy_reconciling_recon_task();
+            response
         });
 
-    let mut service = ReconTaskAggregationService {
+    let service = ReconTaskAggregationService {
         recon_task_details_repo: mock_recon_task_details_repo,
         recon_file_details_repo: mock_recon_file_details_repo,
+        recon_task_audit_repo: mock_recon_task_audit_repo,
+        recon_template_repo: Box::new(MockReconTemplateRepositoryInterface::new()),
         transformer: mock_transformer,
+        event_publisher: Box::new(MockReconTaskEventPublisherInterface::new()),
+        upload_session_repo: Box::new(MockUploadSessionRepositoryInterface::new()),
+        blob_store: Box::new(MockBlobStoreInterface::new()),
+        quotas: get_dummy_quotas(),
+        recon_results_topic: String::from("recon-results"),
     };
 
-    let test_request = get_dummy_create_recon_task();
+    //act
+    let result = service
+        .get_recon_results_export(&String::from("task-1234"), &get_dummy_owner())
+        .await;
+
+    //assert
+    assert!(matches!(result.err().unwrap().kind, AppErrorKind::BadClientRequest));
+}
+
+#[actix_web::test]
+async fn given_completed_task_get_recon_results_export_returns_file_headers_and_results_files() {
+    //setup
+    let (mock_recon_task_details_repo, mock_recon_file_details_repo, mock_recon_task_audit_repo, _) =
+        setup_dependencies();
+
+    let mut mock_transformer = Box::new(MockTransformerInterface::new());
+    mock_transformer
+        .expect_build_recon_task_details_response()
+        .returning(|_, _, _| {
+            let mut response = get_dummy_recon_task_response_details();
+            response.task_details.status = ReconTaskStatus::Completed;
+            for file_role in [UploadedFileRole::Comparison, UploadedFileRole::Primary] {
+                let mut results = get_dummy_recon_chunk_results(file_role);
+                results.results_file_name = Some(results.derived_results_file_name());
+                response.task_details.results_summary.record(&results).unwrap();
+            }
+            response
+        });
+
+    let service = ReconTaskAggregationService {
+        recon_task_details_repo: mock_recon_task_details_repo,
+        recon_file_details_repo: mock_recon_file_details_repo,
+        recon_task_audit_repo: mock_recon_task_audit_repo,
+        recon_template_repo: Box::new(MockReconTemplateRepositoryInterface::new()),
+        transformer: mock_transformer,
+        event_publisher: Box::new(MockReconTaskEventPublisherInterface::new()),
+        upload_session_repo: Box::new(MockUploadSessionRepositoryInterface::new()),
+        blob_store: Box::new(MockBlobStoreInterface::new()),
+        quotas: get_dummy_quotas(),
+        recon_results_topic: String::from("recon-results"),
+    };
 
     //act
-    let result = service.create_recon_task(&test_request).await;
+    let result = service
+        .get_recon_results_export(&String::from("task-1234"), &get_dummy_owner())
+        .await;
 
     //assert
-    assert!(result.is_err())
+    let export = result.unwrap();
+    assert_eq!(export.primary_file_headers, vec!["header1", "header2"]);
+    assert_eq!(
+        export.results_file_names,
+        vec!["task-1234-primary-results-1", "task-1234-comparison-results-1"]
+    );
+}
+
+#[actix_web::test]
+async fn given_primary_file_preview_recon_file_reads_only_the_parts_it_needs_and_highlights_compared_columns() {
+    //setup
+    let (mock_recon_task_details_repo, mock_recon_file_details_repo, mock_recon_task_audit_repo, _) =
+        setup_dependencies();
+
+    let mut mock_transformer = Box::new(MockTransformerInterface::new());
+    mock_transformer
+        .expect_build_recon_task_details_response()
+        .returning(|_, _, _| get_dummy_response_with_stored_files());
+
+    //the first two chunks hold the header row and the first data row
+    let mut mock_blob_store = Box::new(MockBlobStoreInterface::new());
+    mock_blob_store
+        .expect_read_file()
+        .times(2)
+        .returning(|file_name| Ok(get_dummy_upload_chunk(file_name)));
+
+    let service = ReconTaskAggregationService {
+        recon_task_details_repo: mock_recon_task_details_repo,
+        recon_file_details_repo: mock_recon_file_details_repo,
+        recon_task_audit_repo: mock_recon_task_audit_repo,
+        recon_template_repo: Box::new(MockReconTemplateRepositoryInterface::new()),
+        transformer: mock_transformer,
+        event_publisher: Box::new(MockReconTaskEventPublisherInterface::new()),
+        upload_session_repo: Box::new(MockUploadSessionRepositoryInterface::new()),
+        blob_store: mock_blob_store,
+        quotas: get_dummy_quotas(),
+        recon_results_topic: String::from("recon-results"),
+    };
+
+    //act
+    let result = service
+        .preview_recon_file(
+            &String::from("task-1234"),
+            &String::from("src-file-1234"),
+            Some(1),
+            &get_dummy_owner(),
+        )
+        .await;
+
+    //assert
+    let preview = result.unwrap();
+    assert_eq!(preview.rows.len(), 1);
+    assert_eq!(preview.rows[0]["header1"], "1");
+    assert_eq!(preview.rows[0]["header2"], "10");
+    assert!(preview.columns[0].is_compared && preview.columns[0].is_row_identifier);
+    assert!(!preview.columns[1].is_compared);
+}
+
+#[actix_web::test]
+async fn given_file_of_another_task_preview_recon_file_returns_not_found() {
+    //setup
+    let (
+        mock_recon_task_details_repo,
+        mock_recon_file_details_repo,
+        mock_recon_task_audit_repo,
+        mock_transformer,
+    ) = setup_dependencies();
+
+    let mut mock_blob_store = Box::new(MockBlobStoreInterface::new());
+    mock_blob_store.expect_read_file().times(0);
+
+    let service = ReconTaskAggregationService {
+        recon_task_details_repo: mock_recon_task_details_repo,
+        recon_file_details_repo: mock_recon_file_details_repo,
+        recon_task_audit_repo: mock_recon_task_audit_repo,
+        recon_template_repo: Box::new(MockReconTemplateRepositoryInterface::new()),
+        transformer: mock_transformer,
+        event_publisher: Box::new(MockReconTaskEventPublisherInterface::new()),
+        upload_session_repo: Box::new(MockUploadSessionRepositoryInterface::new()),
+        blob_store: mock_blob_store,
+        quotas: get_dummy_quotas(),
+        recon_results_topic: String::from("recon-results"),
+    };
+
+    //act
+    let result = service
+        .preview_recon_file(
+            &String::from("task-1234"),
+            &String::from("another-file"),
+            None,
+            &get_dummy_owner(),
+        )
+        .await;
+
+    //assert
+    assert!(matches!(result.err().unwrap().kind, AppErrorKind::NotFound));
+}
+
+#[actix_web::test]
+async fn given_file_attached_with_its_metadata_only_preview_recon_file_reads_it_from_within_the_task() {
+    //setup
+    let (
+        mock_recon_task_details_repo,
+        mock_recon_file_details_repo,
+        mock_recon_task_audit_repo,
+        mock_transformer,
+    ) = setup_dependencies();
+
+    let mut mock_blob_store = Box::new(MockBlobStoreInterface::new());
+    mock_blob_store
+        .expect_read_file()
+        .withf(|file_name| file_name == "task-1234-src-file-1234")
+        .times(1)
+        .returning(|_| Ok(b"header1,header2\n1,10\n".to_vec()));
+
+    let service = ReconTaskAggregationService {
+        recon_task_details_repo: mock_recon_task_details_repo,
+        recon_file_details_repo: mock_recon_file_details_repo,
+        recon_task_audit_repo: mock_recon_task_audit_repo,
+        recon_template_repo: Box::new(MockReconTemplateRepositoryInterface::new()),
+        transformer: mock_transformer,
+        event_publisher: Box::new(MockReconTaskEventPublisherInterface::new()),
+        upload_session_repo: Box::new(MockUploadSessionRepositoryInterface::new()),
+        blob_store: mock_blob_store,
+        quotas: get_dummy_quotas(),
+        recon_results_topic: String::from("recon-results"),
+    };
+
+    //act
+    let result = service
+        .preview_recon_file(
+            &String::from("task-1234"),
+            &String::from("src-file-1234"),
+            Some(1),
+            &get_dummy_owner(),
+        )
+        .await;
+
+    //assert
+    assert!(result.is_ok());
+}
+
+#[actix_web::test]
+async fn given_metadata_only_file_named_with_a_path_preview_recon_file_returns_bad_client_request_without_reading() {
+    //setup
+    let (mock_recon_task_details_repo, mock_recon_file_details_repo, mock_recon_task_audit_repo, _) =
+        setup_dependencies();
+
+    //saved before file names were checked on attach
+    let mut mock_transformer = Box::new(MockTransformerInterface::new());
+    mock_transformer
+        .expect_build_recon_task_details_response()
+        .returning(|_, _, _| {
+            let mut response = get_dummy_recon_task_response_details();
+            if let Some(file) = response.primary_file_metadata.as_mut() {
+                file.metadata.file_name = String::from("../RECON-TASK-5678-payments.csv");
+            }
+            response
+        });
+
+    let mut mock_blob_store = Box::new(MockBlobStoreInterface::new());
+    mock_blob_store.expect_read_file().times(0);
+
+    let service = ReconTaskAggregationService {
+        recon_task_details_repo: mock_recon_task_details_repo,
+        recon_file_details_repo: mock_recon_file_details_repo,
+        recon_task_audit_repo: mock_recon_task_audit_repo,
+        recon_template_repo: Box::new(MockReconTemplateRepositoryInterface::new()),
+        transformer: mock_transformer,
+        event_publisher: Box::new(MockReconTaskEventPublisherInterface::new()),
+        upload_session_repo: Box::new(MockUploadSessionRepositoryInterface::new()),
+        blob_store: mock_blob_store,
+        quotas: get_dummy_quotas(),
+        recon_results_topic: String::from("recon-results"),
+    };
+
+    //act
+    let result = service
+        .preview_recon_file(
+            &String::from("task-1234"),
+            &String::from("src-file-1234"),
+            None,
+            &get_dummy_owner(),
+        )
+        .await;
+
+    //assert
+    assert!(matches!(result.err().unwrap().kind, AppErrorKind::BadClientRequest));
+}
+
+#[actix_web::test]
+async fn given_too_many_rows_preview_recon_file_returns_bad_client_request() {
+    //setup
+    let (
+        mock_recon_task_details_repo,
+        mock_recon_file_details_repo,
+        mock_recon_task_audit_repo,
+        mock_transformer,
+    ) = setup_dependencies();
+
+    let mock_blob_store = Box::new(MockBlobStoreInterface::new());
+
+    let service = ReconTaskAggregationService {
+        recon_task_details_repo: mock_recon_task_details_repo,
+        recon_file_details_repo: mock_recon_file_details_repo,
+        recon_task_audit_repo: mock_recon_task_audit_repo,
+        recon_template_repo: Box::new(MockReconTemplateRepositoryInterface::new()),
+        transformer: mock_transformer,
+        event_publisher: Box::new(MockReconTaskEventPublisherInterface::new()),
+        upload_session_repo: Box::new(MockUploadSessionRepositoryInterface::new()),
+        blob_store: mock_blob_store,
+        quotas: get_dummy_quotas(),
+        recon_results_topic: String::from("recon-results"),
+    };
+
+    //act
+    let result = service
+        .preview_recon_file(
+            &String::from("task-1234"),
+            &String::from("src-file-1234"),
+            Some(1000),
+            &get_dummy_owner(),
+        )
+        .await;
+
+    //assert
+    assert!(matches!(result.err().unwrap().kind, AppErrorKind::BadClientRequest));
+}
+
+fn get_dummy_quotas() -> ReconTaskQuotas {
+    ReconTaskQuotas {
+        max_active_tasks_per_user: 10,
+        max_file_row_count: 1_000_000,
+    }
+}
+
+//an upload as the handler streams it, one piece per slice
+fn uploaded_content(pieces: Vec<&[u8]>) -> UploadedContent {
+    let pieces: Vec<Result<Vec<u8>, ServiceError>> = pieces.into_iter().map(|piece| Ok(piece.to_vec())).collect();
+    stream::iter(pieces).boxed()
+}
+
+fn get_dummy_user_with_role(role: UserRole) -> AuthenticatedUser {
+    let user_id = match role {
+        UserRole::Admin => "admin-user-id",
+        _ => "another-user-id",
+    };
+
+    AuthenticatedUser::with_roles(user_id, vec![role])
+}
+
+fn get_dummy_owner() -> AuthenticatedUser {
+    AuthenticatedUser::new("test-user-id")
 }
 
 fn setup_dependencies() -> (
     Box<MockReconTaskDetailsRepositoryInterface>,
     Box<MockReconFileDetailsRepositoryInterface>,
+    Box<MockReconTaskAuditRepositoryInterface>,
     Box<MockTransformerInterface>,
 ) {
     let mut mock_recon_file_details_repo = Box::new(MockReconFileDetailsRepositoryInterface::new());
     let mut mock_recon_task_details_repo = Box::new(MockReconTaskDetailsRepositoryInterface::new());
+    let mut mock_recon_task_audit_repo = Box::new(MockReconTaskAuditRepositoryInterface::new());
     let mut mock_transformer = Box::new(MockTransformerInterface::new());
 
     mock_recon_task_details_repo
@@ -112,7 +4085,30 @@ fn setup_dependencies() -> (
 
     mock_recon_task_details_repo
         .expect_get_task_details()
-        .returning(|_y| Ok(get_dummy_recon_task_details()));
+        .returning(|_y| Ok(get_dummy_recon_task()));
+
+    mock_recon_task_details_repo
+        .expect_update_task_details()
+        .returning(|task| Ok(Some(task.clone())));
+
+    mock_recon_task_details_repo
+        .expect_reserve_active_task_slot()
+        .returning(|_, _, _| Ok(true));
+
+    mock_recon_task_audit_repo
+        .expect_append_audit_entry()
+        .returning(|_| Ok(()));
+
+    mock_recon_task_audit_repo
+        .expect_get_audit_entries()
+        .returning(|task_id| {
+            Ok(vec![ReconTaskAuditEntry::new(
+                task_id,
+                ReconTaskAction::TaskCreated,
+                &String::from("test-user-id"),
+                None,
+            )])
+        });
 
     mock_recon_file_details_repo
         .expect_create_recon_file_details()
@@ -120,7 +4116,7 @@ fn setup_dependencies() -> (
 
     mock_recon_file_details_repo
         .expect_get_recon_file_details()
-        .returning(|_y| Ok(get_dummy_recon_file_metadata()));
+        .returning(|_y| Ok(get_dummy_recon_file()));
 
     mock_transformer
         .expect_build_recon_task_details_response()
@@ -128,37 +4124,179 @@ fn setup_dependencies() -> (
 
     mock_transformer
         .expect_get_comparison_file_details()
-        .returning(|_| get_dummy_recon_file_metadata());
+        .returning(|_, _| get_dummy_recon_file());
 
     mock_transformer
         .expect_get_primary_file_details()
-        .returning(|_| get_dummy_recon_file_metadata());
+        .returning(|_, _| get_dummy_recon_file());
 
     mock_transformer
         .expect_get_recon_task_details()
-        .returning(|_| get_dummy_recon_task_details());
+        .returning(|_, _, _| get_dummy_recon_task());
+
+    mock_transformer
+        .expect_copy_recon_file_details()
+        .returning(|_, _| get_dummy_recon_file());
 
-    return (
+    (
         mock_recon_task_details_repo,
         mock_recon_file_details_repo,
+        mock_recon_task_audit_repo,
         mock_transformer,
-    );
+    )
 }
 
 fn get_dummy_create_recon_task() -> CreateReconTaskRequest {
     CreateReconTaskRequest {
         user_id: String::from("test-user-id"),
-        recon_configurations: ReconciliationConfigs {
-            should_check_for_duplicate_records_in_comparison_file: false,
-            should_reconciliation_be_case_sensitive: true,
-            should_ignore_white_space: true,
-            should_do_reverse_reconciliation: false,
+        template_id: None,
+        recon_configurations: Some(default_recon_configs()),
+        comparison_pairs: vec![NamedComparisonPair {
+            primary_file_column: ColumnReference::Index(0),
+            comparison_file_column: ColumnReference::Index(0),
+            is_row_identifier: true,
+            ignore_header_case: false,
+            row_key_position: None,
+            rules: None,
+        }],
+        row_key_separator: None,
+    }
+}
+
+fn get_dummy_attach_primary_file_request() -> AttachPrimaryFileRequest {
+    AttachPrimaryFileRequest {
+        task_id: String::from("task-1234"),
+        primary_file_name: String::from("src-file-1234"),
+        primary_file_hash: String::from("src-file-1234"),
+        primary_file_row_count: 1000,
+        primary_file_headers: vec![String::from("header1"), String::from("header2")],
+        primary_file_delimiters: vec![','],
+        primary_file_format: FileFormatDescriptor::default(),
+        primary_file_column_schema: vec![],
+    }
+}
+
+fn get_dummy_recon_file() -> ReconFile {
+    ReconFile {
+        format: FileFormatDescriptor::default(),
+        column_schema: vec![],
+        stored_file_parts: vec![],
+        verification: FileVerification::default(),
+        metadata: get_dummy_recon_file_metadata(),
+        created_by: String::from("test-user-id"),
+        created_at: Utc::now(),
+        updated_at: Utc::now(),
+    }
+}
+
+fn get_dummy_recon_task() -> ReconTask {
+    ReconTask {
+        details: get_dummy_recon_task_details(),
+        created_by: String::from("test-user-id"),
+        created_at: Utc::now(),
+        updated_at: Utc::now(),
+        cancellation: None,
+        reconciliation_start: None,
+        template: None,
+        pending_comparison_pairs: vec![],
+        resolved_comparison_pairs: vec![],
+        accepted_comparison_pairs: vec![],
+        comparison_pair_rules: vec![],
+        row_key_positions: vec![],
+        row_key_separator: String::from("|"),
+        column_type_mismatches: vec![],
+        status: ReconTaskStatus::AwaitingFiles,
+        results_summary: ReconResultsSummary::default(),
+        etag: None,
+    }
+}
+
+fn get_dummy_reconciling_recon_task() -> ReconTask {
+    let mut task = get_dummy_recon_task();
+    task.status = ReconTaskStatus::Reconciling;
+    task
+}
+
+fn get_dummy_starting_recon_task() -> ReconTask {
+    let mut task = get_dummy_recon_task();
+    task.status = ReconTaskStatus::Starting;
+    task.reconciliation_start = Some(ReconTaskStart::new(
+        &String::from("task-1234"),
+        &String::from("first-user-id"),
+        Utc.timestamp_millis_opt(1000).unwrap(),
+    ));
+    task
+}
+
+fn get_dummy_completed_recon_task() -> ReconTask {
+    let mut task = get_dummy_reconciling_recon_task();
+    task.status = ReconTaskStatus::Completed;
+    task.details.is_done = true;
+    task.results_summary.completed_at = Some(Utc.timestamp_millis_opt(5000).unwrap());
+    task
+}
+
+fn get_dummy_recon_chunk_results(file_role: UploadedFileRole) -> ReconChunkResults {
+    ReconChunkResults {
+        task_id: String::from("task-1234"),
+        file_role,
+        chunk_sequence_number: 1,
+        total_chunks: 1,
+        counts: ReconResultCounts {
+            matched: 10,
+            unmatched_in_primary: 0,
+            unmatched_in_comparison: 0,
+            duplicates: 0,
+            field_mismatches: 0,
         },
-        comparison_pairs: vec![ComparisonPair {
-            primary_file_column_index: 0,
-            comparison_file_column_index: 0,
+        results_file_name: None,
+    }
+}
+
+fn get_dummy_cancelled_recon_task() -> ReconTask {
+    let mut task = get_dummy_recon_task();
+    task.status = ReconTaskStatus::Cancelled;
+    task.cancellation = Some(ReconTaskCancellation {
+        cancelled_by: String::from("test-user-id"),
+        cancelled_at: Utc::now(),
+        reason: String::from("wrong file uploaded"),
+    });
+    task
+}
+
+fn get_dummy_cancel_recon_task_request() -> CancelReconTaskRequest {
+    CancelReconTaskRequest {
+        reason: String::from("wrong file uploaded"),
+    }
+}
+
+fn get_dummy_recon_template() -> ReconTemplate {
+    ReconTemplate {
+        id: String::from("template-1234"),
+        name: String::from("daily bank recon"),
+        version: 2,
+        recon_configurations: default_recon_configs(),
+        comparison_pairs: vec![NamedComparisonPair {
+            primary_file_column: ColumnReference::Header(String::from("reference")),
+            comparison_file_column: ColumnReference::Header(String::from("bank_reference")),
             is_row_identifier: true,
+            ignore_header_case: false,
+            row_key_position: None,
+            rules: None,
         }],
+        row_key_separator: String::from("|"),
+        created_by: String::from("test-user-id"),
+        created_at: Utc::now(),
+        updated_at: Utc::now(),
+    }
+}
+
+fn get_dummy_recon_template_request() -> ReconTemplateRequest {
+    ReconTemplateRequest {
+        name: String::from("daily bank recon"),
+        recon_configurations: default_recon_configs(),
+        comparison_pairs: get_dummy_recon_template().comparison_pairs,
+        row_key_separator: String::from("|"),
     }
 }
 
@@ -197,11 +4335,11 @@ fn get_dummy_recon_task_details() -> ReconTaskDetails {
             last_acknowledged_id: None,
         },
         primary_file_chunks_queue_info: FileChunkQueue {
-            topic_id: String::from("test-topic"),
+            topic_id: String::from("test-primary-chunks-topic"),
             last_acknowledged_id: None,
         },
         comparison_file_chunks_queue_info: FileChunkQueue {
-            topic_id: String::from("test-topic"),
+            topic_id: String::from("test-comparison-chunks-topic"),
             last_acknowledged_id: None,
         },
     }
@@ -210,7 +4348,29 @@ fn get_dummy_recon_task_details() -> ReconTaskDetails {
 fn get_dummy_recon_task_response_details() -> ReconTaskResponseDetails {
     ReconTaskResponseDetails {
         task_id: String::from("task-1234"),
-        task_details: ReconTaskDetails {
+        row_key: RowKeyDefinition {
+            key_separator: String::from("|"),
+            identifier_columns: vec![],
+            is_complete: true,
+        },
+        task_details: ReconTask {
+            created_by: String::from("test-user-id"),
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            cancellation: None,
+            reconciliation_start: None,
+            template: None,
+            pending_comparison_pairs: vec![],
+            resolved_comparison_pairs: vec![],
+            accepted_comparison_pairs: vec![],
+            comparison_pair_rules: vec![],
+            row_key_positions: vec![],
+            row_key_separator: String::from("|"),
+            column_type_mismatches: vec![],
+            status: ReconTaskStatus::AwaitingFiles,
+            results_summary: ReconResultsSummary::default(),
+            etag: None,
+            details: ReconTaskDetails {
             id: String::from("task-1234"),
             primary_file_id: Some(String::from("src-file-1234")),
             comparison_file_id: Some(String::from("cmp-file-1234")),
@@ -230,8 +4390,17 @@ fn get_dummy_recon_task_response_details() -> ReconTaskResponseDetails {
                 topic_id: String::from("test-topic"),
                 last_acknowledged_id: None,
             },
+            },
         },
-        primary_file_metadata: Some(ReconFileMetaData {
+        primary_file_metadata: Some(ReconFile {
+            format: FileFormatDescriptor::default(),
+            column_schema: vec![],
+            stored_file_parts: vec![],
+            verification: FileVerification::default(),
+            created_by: String::from("test-user-id"),
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            metadata: ReconFileMetaData {
             id: String::from("src-file-1234"),
             file_name: String::from("src-file-1234"),
             row_count: 1000,
@@ -239,8 +4408,17 @@ fn get_dummy_recon_task_response_details() -> ReconTaskResponseDetails {
             recon_file_type: ReconFileType::PrimaryFile,
             column_headers: vec![String::from("header1"), String::from("header2")],
             file_hash: String::from("src-file-1234"),
+            },
         }),
-        comparison_file_metadata: Some(ReconFileMetaData {
+        comparison_file_metadata: Some(ReconFile {
+            format: FileFormatDescriptor::default(),
+            column_schema: vec![],
+            stored_file_parts: vec![],
+            verification: FileVerification::default(),
+            created_by: String::from("test-user-id"),
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            metadata: ReconFileMetaData {
             id: String::from("cmp-file-1234"),
             file_name: String::from("cmp-file-1234"),
             row_count: 1000,
@@ -248,6 +4426,7 @@ fn get_dummy_recon_task_response_details() -> ReconTaskResponseDetails {
             recon_file_type: ReconFileType::ComparisonFile,
             column_headers: vec![String::from("header1"), String::from("header2")],
             file_hash: String::from("cmp-file-1234"),
+            },
         }),
     }
 }
@@ -268,3 +4447,62 @@ fn new_same_column_index_comparison_pair(column_index: usize) -> ComparisonPair
         is_row_identifier: true,
     }
 }
+
+//the sha-256 of id,amount\n1,10\n2,20\n, the content of the dummy upload
+const DUMMY_UPLOAD_FILE_HASH: &str = "31e26370225c0855b0966f4eb7b0820feaa07d086f131723c6362801ec880413";
+
+//20 bytes in chunks of 8, so the last chunk is 4 bytes long
+fn get_dummy_upload_session() -> UploadSession {
+    UploadSession {
+        id: String::from("upload-1234"),
+        task_id: String::from("task-1234"),
+        file_role: UploadedFileRole::Primary,
+        file_name: String::from("payments.csv"),
+        total_size_bytes: 20,
+        chunk_size_bytes: 8,
+        received_chunks: vec![],
+        status: UploadSessionStatus::Open,
+        file_id: None,
+        created_by: String::from("test-user-id"),
+        created_at: Utc::now(),
+        updated_at: Utc::now(),
+    }
+}
+
+fn get_dummy_upload_chunk(file_name: &str) -> Vec<u8> {
+    let content = b"id,amount\n1,10\n2,20\n";
+    let chunk = match file_name {
+        "upload-1234-chunk-0" => &content[0..8],
+        "upload-1234-chunk-1" => &content[8..16],
+        _ => &content[16..20],
+    };
+    chunk.to_vec()
+}
+
+//both files stored as the three chunks of the dummy upload, with metadata matching that content
+fn get_dummy_response_with_stored_files() -> ReconTaskResponseDetails {
+    let mut response = get_dummy_recon_task_response_details();
+    let stored_files = [
+        response.primary_file_metadata.as_mut(),
+        response.comparison_file_metadata.as_mut(),
+    ];
+    for file in stored_files.into_iter().flatten() {
+        file.stored_file_parts = get_dummy_upload_session().chunk_file_names();
+        file.metadata.file_hash = String::from(DUMMY_UPLOAD_FILE_HASH);
+        file.metadata.row_count = 3;
+    }
+    response
+}
+
+fn get_dummy_response_with_verified_files() -> ReconTaskResponseDetails {
+    let mut response = get_dummy_response_with_stored_files();
+    let stored_files = [
+        response.primary_file_metadata.as_mut(),
+        response.comparison_file_metadata.as_mut(),
+    ];
+    for file in stored_files.into_iter().flatten() {
+        file.verification = FileVerification::verified(&String::from(DUMMY_UPLOAD_FILE_HASH), 3);
+    }
+    response.task_details.status = ReconTaskStatus::Ready;
+    response
+}
@@ -0,0 +1,235 @@
+use actix_web::{dev::ServiceRequest, error::ErrorInternalServerError, web::Data, Error, HttpMessage};
+use actix_web_httpauth::extractors::{
+    bearer::{self, BearerAuth},
+    AuthenticationError,
+};
+use jsonwebtoken::{
+    decode, decode_header,
+    jwk::{AlgorithmParameters, Jwk, JwkSet},
+    Algorithm, DecodingKey, Validation,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::internal::{
+    models::entities::authenticated_user::{AuthenticatedUser, UserRole},
+    shared_reconciler_rust_libraries::models::entities::app_errors::{AppError, AppErrorKind},
+    web_api::utils::AppSettings,
+};
+
+const DEFAULT_JWT_ALGORITHM: Algorithm = Algorithm::HS256;
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Claims {
+    pub sub: String,
+
+    pub exp: usize,
+
+    //absent means the token predates roles, unknown role names are ignored
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub roles: Option<Vec<String>>,
+}
+
+enum TokenKeys {
+    Static {
+        key: DecodingKey,
+        algorithm: Algorithm,
+    },
+    Jwks(JwkSet),
+}
+
+pub struct TokenValidator {
+    keys: TokenKeys,
+    issuer: Option<String>,
+    audience: Option<String>,
+}
+
+impl TokenValidator {
+    pub fn from_static_key(key: DecodingKey, algorithm: Algorithm) -> Self {
+        TokenValidator {
+            keys: TokenKeys::Static { key, algorithm },
+            issuer: None,
+            audience: None,
+        }
+    }
+
+    pub fn from_jwks(jwks: JwkSet) -> Self {
+        TokenValidator {
+            keys: TokenKeys::Jwks(jwks),
+            issuer: None,
+            audience: None,
+        }
+    }
+
+    pub fn with_issuer(mut self, issuer: Option<String>) -> Self {
+        self.issuer = issuer;
+        self
+    }
+
+    pub fn with_audience(mut self, audience: Option<String>) -> Self {
+        self.audience = audience;
+        self
+    }
+
+    pub fn validate(&self, token: &str) -> Result<AuthenticatedUser, AppError> {
+        let header = decode_header(token).map_err(|e| invalid_token(e.to_string()))?;
+
+        let (key, algorithm) = match &self.keys {
+            TokenKeys::Static { key, algorithm } => (key.clone(), *algorithm),
+            TokenKeys::Jwks(jwks) => {
+                let kid = header
+                    .kid
+                    .ok_or_else(|| invalid_token(String::from("token has no kid")))?;
+
+                let jwk = jwks
+                    .find(&kid)
+                    .ok_or_else(|| invalid_token(format!("no signing key with kid [{}]", kid)))?;
+
+                let key = jwk_decoding_key(jwk)?;
+                (key, jwk_algorithm(jwk))
+            }
+        };
+
+        //the algorithm always comes from our configuration, never from the token header
+        let mut validation = Validation::new(algorithm);
+
+        if let Some(issuer) = &self.issuer {
+            validation.set_issuer(&[issuer]);
+        }
+
+        if let Some(audience) = &self.audience {
+            validation.set_audience(&[audience]);
+        }
+
+        let token_data =
+            decode::<Claims>(token, &key, &validation).map_err(|e| invalid_token(e.to_string()))?;
+
+        if token_data.claims.sub.is_empty() {
+            return Err(invalid_token(String::from("token has no subject")));
+        }
+
+        Ok(match token_data.claims.roles {
+            Some(roles) => AuthenticatedUser::with_roles(
+                &token_data.claims.sub,
+                roles.iter().filter_map(|role| UserRole::parse(role)).collect(),
+            ),
+            None => AuthenticatedUser::new(&token_data.claims.sub),
+        })
+    }
+}
+
+//a JWKS file takes precedence over a static key when both are configured
+pub fn read_token_validator(app_settings: &AppSettings) -> Result<TokenValidator, AppError> {
+    let validator = if let Some(jwks_file_path) = &app_settings.jwt_jwks_file_path {
+        let jwks_json = std::fs::read_to_string(jwks_file_path).map_err(|e| {
+            auth_config_error(format!("unable to read JWKS file [{}]: {}", jwks_file_path, e))
+        })?;
+
+        let jwks: JwkSet = serde_json::from_str(&jwks_json).map_err(|e| {
+            auth_config_error(format!("unable to parse JWKS file [{}]: {}", jwks_file_path, e))
+        })?;
+
+        TokenValidator::from_jwks(jwks)
+    } else if let Some(static_key) = &app_settings.jwt_static_key {
+        let algorithm = app_settings.jwt_algorithm.unwrap_or(DEFAULT_JWT_ALGORITHM);
+        TokenValidator::from_static_key(static_decoding_key(static_key, algorithm)?, algorithm)
+    } else {
+        return Err(auth_config_error(String::from(
+            "please supply either JWT_JWKS_FILE or JWT_STATIC_KEY",
+        )));
+    };
+
+    Ok(validator
+        .with_issuer(app_settings.jwt_issuer.clone())
+        .with_audience(app_settings.jwt_audience.clone()))
+}
+
+//hmac algorithms take the key as a shared secret, everything else as a PEM encoded public key
+fn static_decoding_key(key: &str, algorithm: Algorithm) -> Result<DecodingKey, AppError> {
+    let decoding_key = match algorithm {
+        Algorithm::HS256 | Algorithm::HS384 | Algorithm::HS512 => {
+            Ok(DecodingKey::from_secret(key.as_bytes()))
+        }
+        Algorithm::ES256 | Algorithm::ES384 => DecodingKey::from_ec_pem(key.as_bytes()),
+        Algorithm::EdDSA => DecodingKey::from_ed_pem(key.as_bytes()),
+        _ => DecodingKey::from_rsa_pem(key.as_bytes()),
+    };
+
+    decoding_key.map_err(|e| auth_config_error(format!("invalid JWT_STATIC_KEY: {}", e)))
+}
+
+//jsonwebtoken reads octet keys as padded standard base64,
+//but JWKS files hold them as unpadded base64url (RFC 7518), so those are converted first
+fn jwk_decoding_key(jwk: &Jwk) -> Result<DecodingKey, AppError> {
+    let decoding_key = match &jwk.algorithm {
+        AlgorithmParameters::OctetKey(params) => {
+            let mut secret = params.value.replace('-', "+").replace('_', "/");
+            while secret.len() % 4 != 0 {
+                secret.push('=');
+            }
+            DecodingKey::from_base64_secret(&secret)
+        }
+        _ => DecodingKey::from_jwk(jwk),
+    };
+
+    decoding_key.map_err(|e| invalid_token(e.to_string()))
+}
+
+fn jwk_algorithm(jwk: &Jwk) -> Algorithm {
+    if let Some(algorithm) = jwk.common.algorithm {
+        return algorithm;
+    }
+
+    match jwk.algorithm {
+        AlgorithmParameters::EllipticCurve(_) => Algorithm::ES256,
+        AlgorithmParameters::RSA(_) => Algorithm::RS256,
+        AlgorithmParameters::OctetKey(_) => Algorithm::HS256,
+        AlgorithmParameters::OctetKeyPair(_) => Algorithm::EdDSA,
+    }
+}
+
+//bearer validator for actix_web_httpauth, stores the caller in the request extensions
+pub async fn validate_bearer_token(
+    req: ServiceRequest,
+    credentials: BearerAuth,
+) -> Result<ServiceRequest, (Error, ServiceRequest)> {
+    let validator = match req.app_data::<Data<TokenValidator>>() {
+        Some(validator) => validator.clone(),
+        None => {
+            return Err((
+                ErrorInternalServerError("token validator has not been configured"),
+                req,
+            ))
+        }
+    };
+
+    match validator.validate(credentials.token()) {
+        Ok(user) => {
+            req.extensions_mut().insert(user);
+            Ok(req)
+        }
+        Err(_) => {
+            let config = req
+                .app_data::<bearer::Config>()
+                .cloned()
+                .unwrap_or_default();
+
+            Err((
+                AuthenticationError::from(config)
+                    .with_error(bearer::Error::InvalidToken)
+                    .into(),
+                req,
+            ))
+        }
+    }
+}
+
+fn invalid_token(reason: String) -> AppError {
+    AppError::new(
+        AppErrorKind::BadClientRequest,
+        format!("invalid bearer token: {}", reason),
+    )
+}
+
+fn auth_config_error(reason: String) -> AppError {
+    AppError::new(AppErrorKind::InternalError, reason)
+}
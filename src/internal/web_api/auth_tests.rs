@@ -0,0 +1,136 @@
+use chrono::{Duration, Utc};
+use jsonwebtoken::{encode, jwk::JwkSet, Algorithm, EncodingKey, Header};
+use serde_json::json;
+
+use crate::internal::{
+    models::entities::authenticated_user::UserRole,
+    web_api::{
+        auth::{Claims, TokenValidator},
+        test_tokens::{
+            sign_test_token, test_claims_for, test_claims_with_roles, test_token_validator,
+            TEST_JWT_SECRET,
+        },
+    },
+};
+
+#[test]
+fn given_token_signed_with_static_key_validate_returns_subject_as_user() {
+    //setup
+    let validator = test_token_validator();
+    let token = sign_test_token(&test_claims_for("user-1234"), TEST_JWT_SECRET);
+
+    //act
+    let result = validator.validate(&token);
+
+    //assert
+    assert_eq!(result.unwrap().user_id, "user-1234");
+}
+
+#[test]
+fn given_token_without_roles_claim_validate_treats_user_as_owner() {
+    //setup
+    let validator = test_token_validator();
+    let token = sign_test_token(&test_claims_for("user-1234"), TEST_JWT_SECRET);
+
+    //act
+    let result = validator.validate(&token);
+
+    //assert
+    assert_eq!(result.unwrap().roles, vec![UserRole::Owner]);
+}
+
+#[test]
+fn given_token_with_roles_claim_validate_keeps_known_roles_only() {
+    //setup
+    let validator = test_token_validator();
+    let claims = test_claims_with_roles("user-1234", &["admin", "Viewer", "superuser"]);
+    let token = sign_test_token(&claims, TEST_JWT_SECRET);
+
+    //act
+    let result = validator.validate(&token);
+
+    //assert
+    assert_eq!(result.unwrap().roles, vec![UserRole::Admin, UserRole::Viewer]);
+}
+
+#[test]
+fn given_token_signed_with_another_key_validate_returns_error() {
+    //setup
+    let validator = test_token_validator();
+    let token = sign_test_token(&test_claims_for("user-1234"), "some-other-secret");
+
+    //act
+    let result = validator.validate(&token);
+
+    //assert
+    assert!(result.is_err());
+}
+
+#[test]
+fn given_expired_token_validate_returns_error() {
+    //setup
+    let validator = test_token_validator();
+    let claims = Claims {
+        sub: String::from("user-1234"),
+        exp: (Utc::now() - Duration::hours(1)).timestamp() as usize,
+        roles: None,
+    };
+    let token = sign_test_token(&claims, TEST_JWT_SECRET);
+
+    //act
+    let result = validator.validate(&token);
+
+    //assert
+    assert!(result.is_err());
+}
+
+#[test]
+fn given_token_with_known_kid_validate_uses_matching_jwks_key() {
+    //setup
+    let validator = TokenValidator::from_jwks(get_dummy_jwks());
+    let token = sign_token_with_kid("key-1", "jwks-secret");
+
+    //act
+    let result = validator.validate(&token);
+
+    //assert
+    assert_eq!(result.unwrap().user_id, "user-1234");
+}
+
+#[test]
+fn given_token_with_unknown_kid_validate_returns_error() {
+    //setup
+    let validator = TokenValidator::from_jwks(get_dummy_jwks());
+    let token = sign_token_with_kid("key-2", "jwks-secret");
+
+    //act
+    let result = validator.validate(&token);
+
+    //assert
+    assert!(result.is_err());
+}
+
+fn sign_token_with_kid(kid: &str, secret: &str) -> String {
+    let mut header = Header::new(Algorithm::HS256);
+    header.kid = Some(String::from(kid));
+
+    encode(
+        &header,
+        &test_claims_for("user-1234"),
+        &EncodingKey::from_secret(secret.as_bytes()),
+    )
+    .unwrap()
+}
+
+//the same shape as a JWKS file, with "jwks-secret" as an unpadded base64url octet key
+fn get_dummy_jwks() -> JwkSet {
+    serde_json::from_value(json!({
+        "keys": [{
+            "kty": "oct",
+            "kid": "key-1",
+            "alg": "HS256",
+            "k": "andrcy1zZWNyZXQ"
+        }]
+    }))
+    .unwrap()
+}
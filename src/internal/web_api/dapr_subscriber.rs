@@ -0,0 +1,41 @@
+use actix_web::HttpRequest;
+
+use crate::internal::web_api::utils::AppSettings;
+
+//the sidecar sends its APP_API_TOKEN in this header on every call to the app
+pub const DAPR_API_TOKEN_HEADER: &str = "dapr-api-token";
+
+pub const RECON_RESULTS_ROUTE: &str = "/dapr/recon-results";
+
+//the pubsub routes are called by the sidecar, which has no bearer token to send
+pub struct DaprSubscriberSettings {
+    pub pubsub_name: String,
+
+    pub results_topic: String,
+
+    //without one every call is turned away, results are only taken from a sidecar that sends it
+    pub app_api_token: Option<String>,
+}
+
+impl DaprSubscriberSettings {
+    pub fn new(app_settings: &AppSettings) -> Self {
+        DaprSubscriberSettings {
+            pubsub_name: app_settings.dapr_pubsub_name.clone(),
+            results_topic: app_settings.dapr_recon_results_topic.clone(),
+            app_api_token: app_settings.dapr_app_api_token.clone(),
+        }
+    }
+
+    pub fn accepts(&self, request: &HttpRequest) -> bool {
+        let expected_token = match &self.app_api_token {
+            Some(token) => token,
+            None => return false,
+        };
+
+        request
+            .headers()
+            .get(DAPR_API_TOKEN_HEADER)
+            .and_then(|token| token.to_str().ok())
+            .is_some_and(|token| token == expected_token)
+    }
+}
@@ -0,0 +1,53 @@
+use actix_web::test::TestRequest;
+
+use crate::internal::web_api::dapr_subscriber::{DaprSubscriberSettings, DAPR_API_TOKEN_HEADER};
+
+#[test]
+fn given_no_app_api_token_accepts_returns_false_for_any_request() {
+    //setup
+    let settings = get_dummy_settings(None);
+
+    //act
+    let no_token = settings.accepts(&TestRequest::default().to_http_request());
+    let any_token = settings.accepts(
+        &TestRequest::default()
+            .insert_header((DAPR_API_TOKEN_HEADER, "sidecar-token"))
+            .to_http_request(),
+    );
+
+    //assert
+    assert!(!no_token);
+    assert!(!any_token);
+}
+
+#[test]
+fn given_app_api_token_accepts_returns_true_only_for_the_same_token() {
+    //setup
+    let settings = get_dummy_settings(Some(String::from("sidecar-token")));
+
+    //act
+    let same_token = settings.accepts(
+        &TestRequest::default()
+            .insert_header((DAPR_API_TOKEN_HEADER, "sidecar-token"))
+            .to_http_request(),
+    );
+    let other_token = settings.accepts(
+        &TestRequest::default()
+            .insert_header((DAPR_API_TOKEN_HEADER, "other-token"))
+            .to_http_request(),
+    );
+    let no_token = settings.accepts(&TestRequest::default().to_http_request());
+
+    //assert
+    assert!(same_token);
+    assert!(!other_token);
+    assert!(!no_token);
+}
+
+fn get_dummy_settings(app_api_token: Option<String>) -> DaprSubscriberSettings {
+    DaprSubscriberSettings {
+        pubsub_name: String::from("pubsub"),
+        results_topic: String::from("recon-results"),
+        app_api_token,
+    }
+}
@@ -1,69 +1,452 @@
-use std::sync::Mutex;
-
 use actix_web::{
-    get, HttpResponse,
-    post,
-    web::{self, Path},
+    delete, get, HttpRequest, HttpResponse,
+    post, put,
+    web::{self, Path, Query, ReqData},
 };
+use actix_multipart::Multipart;
 use actix_web::web::Data;
+use futures_util::{stream, Stream, StreamExt};
+
+use crate::internal::{interfaces::recon_tasks_aggregator::ReconTaskAggregationServiceInterface, models::{
+    entities::{authenticated_user::AuthenticatedUser, service_error::ServiceError},
+    view_models::requests::{
+        AttachComparisonFileRequest, AttachPrimaryFileRequest, CancelReconTaskRequest,
+        CloneReconTaskRequest, CompleteUploadRequest, CreateReconTaskRequest,
+        CreateUploadSessionRequest, FilePreviewPath, FilePreviewQuery, ForceTaskStatusRequest,
+        GetTaskDetailsRequest, GetTemplateDetailsRequest, GetTemplateVersionQuery,
+        ReconResultsCloudEvent, ReconResultsQuery, ReconTemplateRequest, UploadChunkPath,
+        UploadFilePath, UploadSessionPath,
+    },
+    view_models::responses::{DaprEventResponse, DaprEventStatus, DaprSubscription},
+}, services::core_logic::results_export::ResultsExportWriter,
+    shared_reconciler_rust_libraries::models::entities::app_errors::AppErrorKind};
+use crate::internal::web_api::dapr_subscriber::{DaprSubscriberSettings, RECON_RESULTS_ROUTE};
+use crate::internal::web_api::multipart_upload::{stream_uploaded_file, UploadFormConfig};
+use crate::internal::web_api::utils::ok_or_error;
 
-use crate::internal::{interfaces::recon_tasks_aggregator::ReconTaskAggregationServiceInterface, models::view_models::requests::{
-    AttachComparisonFileRequest, AttachPrimaryFileRequest, CreateReconTaskRequest,
-    GetTaskDetailsRequest,
-}};
-use crate::internal::shared_reconciler_rust_libraries::web_api::utils::{internal_server_error, ok_or_error};
-use crate::internal::web_api::utils::setup_service;
+pub(crate) type ReconTaskService = Data<Box<dyn ReconTaskAggregationServiceInterface>>;
 
 #[get("/recon-tasks/{task_id}")]
 pub(crate) async fn get_task_details(
     get_task_details_request: Path<GetTaskDetailsRequest>,
-    service: Data<Mutex<Box<dyn ReconTaskAggregationServiceInterface>>>,
+    caller: ReqData<AuthenticatedUser>,
+    service: ReconTaskService,
+) -> HttpResponse {
+    let task_id = &get_task_details_request.task_id;
+    let response = service.get_recon_task(task_id, &caller).await;
+    ok_or_error(response)
+}
+
+#[get("/recon-tasks/{task_id}/history")]
+pub(crate) async fn get_task_history(
+    get_task_details_request: Path<GetTaskDetailsRequest>,
+    caller: ReqData<AuthenticatedUser>,
+    service: ReconTaskService,
 ) -> HttpResponse {
     let task_id = &get_task_details_request.task_id;
-    let response = service.lock().unwrap().get_recon_task(task_id).await;
-    return ok_or_error(response);
+    let response = service.get_recon_task_history(task_id, &caller).await;
+    ok_or_error(response)
 }
 
 #[post("/recon-tasks")]
 pub(crate) async fn create_task_details(
     task_details: web::Json<CreateReconTaskRequest>,
+    caller: ReqData<AuthenticatedUser>,
+    service: ReconTaskService,
 ) -> HttpResponse {
-    let mut service = match setup_service().await {
-        Ok(s) => s,
-        Err(err) => return internal_server_error(err),
-    };
+    //the owner of a task is whoever the token says it is, not what the body claims
+    let mut request = task_details.into_inner();
+    request.user_id = caller.user_id.clone();
 
-    let response = service.create_recon_task(&task_details.0).await;
-    return ok_or_error(response);
+    let response = service.create_recon_task(&request, &caller).await;
+    ok_or_error(response)
 }
 
 
 #[post("/recon-tasks/attach-primary-file")]
 pub(crate) async fn attach_primary_file_to_task(
     task_details: web::Json<AttachPrimaryFileRequest>,
+    caller: ReqData<AuthenticatedUser>,
+    service: ReconTaskService,
 ) -> HttpResponse {
-    let mut service = match setup_service().await {
-        Ok(s) => s,
-        Err(err) => return internal_server_error(err),
-    };
-
-    let response = service.attach_primary_file_to_task(&task_details.0).await;
-    return ok_or_error(response);
+    let response = service.attach_primary_file_to_task(&task_details.0, &caller).await;
+    ok_or_error(response)
 }
 
 #[post("/recon-tasks/attach-comparison-file")]
 pub(crate) async fn attach_comparison_file_to_task(
     task_details: web::Json<AttachComparisonFileRequest>,
+    caller: ReqData<AuthenticatedUser>,
+    service: ReconTaskService,
 ) -> HttpResponse {
-    let mut service = match setup_service().await {
-        Ok(s) => s,
-        Err(err) => return internal_server_error(err),
-    };
+    let response = service.attach_comparison_file_to_task(&task_details.0, &caller).await;
+    ok_or_error(response)
+}
+
+//the hash, row count, headers and delimiter are read from the uploaded content rather than sent by the client
+#[post("/recon-tasks/{task_id}/files/primary/upload")]
+pub(crate) async fn upload_primary_file(
+    get_task_details_request: Path<GetTaskDetailsRequest>,
+    request: HttpRequest,
+    payload: Multipart,
+    upload_config: Data<UploadFormConfig>,
+    caller: ReqData<AuthenticatedUser>,
+    service: ReconTaskService,
+) -> HttpResponse {
+    let task_id = &get_task_details_request.task_id;
+    let response = stream_uploaded_file(&request, payload, &upload_config, |file_name, content| async move {
+        service
+            .upload_primary_file_to_task(task_id, &file_name, content, &caller)
+            .await
+    })
+    .await;
+    ok_or_error(response)
+}
+
+#[post("/recon-tasks/{task_id}/files/comparison/upload")]
+pub(crate) async fn upload_comparison_file(
+    get_task_details_request: Path<GetTaskDetailsRequest>,
+    request: HttpRequest,
+    payload: Multipart,
+    upload_config: Data<UploadFormConfig>,
+    caller: ReqData<AuthenticatedUser>,
+    service: ReconTaskService,
+) -> HttpResponse {
+    let task_id = &get_task_details_request.task_id;
+    let response = stream_uploaded_file(&request, payload, &upload_config, |file_name, content| async move {
+        service
+            .upload_comparison_file_to_task(task_id, &file_name, content, &caller)
+            .await
+    })
+    .await;
+    ok_or_error(response)
+}
 
+//files attached with their metadata only are read from the blob store under <task_id>-<file_name>
+#[post("/recon-tasks/{task_id}/files/verify")]
+pub(crate) async fn verify_task_files(
+    get_task_details_request: Path<GetTaskDetailsRequest>,
+    caller: ReqData<AuthenticatedUser>,
+    service: ReconTaskService,
+) -> HttpResponse {
+    let task_id = &get_task_details_request.task_id;
+    let response = service.verify_task_files(task_id, &caller).await;
+    ok_or_error(response)
+}
+
+#[get("/recon-tasks/{task_id}/files/{file_id}/preview")]
+pub(crate) async fn preview_file(
+    file_preview_path: Path<FilePreviewPath>,
+    preview_query: Query<FilePreviewQuery>,
+    caller: ReqData<AuthenticatedUser>,
+    service: ReconTaskService,
+) -> HttpResponse {
     let response = service
-        .attach_comparison_file_to_task(&task_details.0)
+        .preview_recon_file(
+            &file_preview_path.task_id,
+            &file_preview_path.file_id,
+            preview_query.rows,
+            &caller,
+        )
         .await;
-    return ok_or_error(response);
+    ok_or_error(response)
+}
+
+//files too large for a single request are sent in chunks, file_role is primary or comparison
+#[post("/recon-tasks/{task_id}/files/{file_role}/uploads")]
+pub(crate) async fn create_upload_session(
+    upload_file_path: Path<UploadFilePath>,
+    request: web::Json<CreateUploadSessionRequest>,
+    caller: ReqData<AuthenticatedUser>,
+    service: ReconTaskService,
+) -> HttpResponse {
+    let response = service
+        .create_upload_session(
+            &upload_file_path.task_id,
+            upload_file_path.file_role,
+            &request.0,
+            &caller,
+        )
+        .await;
+    ok_or_error(response)
+}
+
+//the chunk is the raw request body
+#[put("/recon-tasks/{task_id}/uploads/{upload_id}/chunks/{chunk_number}")]
+pub(crate) async fn upload_file_chunk(
+    upload_chunk_path: Path<UploadChunkPath>,
+    chunk: web::Bytes,
+    caller: ReqData<AuthenticatedUser>,
+    service: ReconTaskService,
+) -> HttpResponse {
+    let response = service
+        .upload_file_chunk(
+            &upload_chunk_path.task_id,
+            &upload_chunk_path.upload_id,
+            upload_chunk_path.chunk_number,
+            &chunk,
+            &caller,
+        )
+        .await;
+    ok_or_error(response)
+}
+
+#[get("/recon-tasks/{task_id}/uploads/{upload_id}")]
+pub(crate) async fn get_upload_session(
+    upload_session_path: Path<UploadSessionPath>,
+    caller: ReqData<AuthenticatedUser>,
+    service: ReconTaskService,
+) -> HttpResponse {
+    let response = service
+        .get_upload_session(&upload_session_path.task_id, &upload_session_path.upload_id, &caller)
+        .await;
+    ok_or_error(response)
+}
+
+#[post("/recon-tasks/{task_id}/uploads/{upload_id}/complete")]
+pub(crate) async fn complete_upload_session(
+    upload_session_path: Path<UploadSessionPath>,
+    request: web::Json<CompleteUploadRequest>,
+    caller: ReqData<AuthenticatedUser>,
+    service: ReconTaskService,
+) -> HttpResponse {
+    let response = service
+        .complete_upload_session(
+            &upload_session_path.task_id,
+            &upload_session_path.upload_id,
+            &request.0,
+            &caller,
+        )
+        .await;
+    ok_or_error(response)
+}
+
+//streamed one results file at a time, so memory use does not grow with the number of results
+#[get("/recon-tasks/{task_id}/results")]
+pub(crate) async fn export_results(
+    get_task_details_request: Path<GetTaskDetailsRequest>,
+    results_query: Query<ReconResultsQuery>,
+    caller: ReqData<AuthenticatedUser>,
+    service: ReconTaskService,
+) -> HttpResponse {
+    let task_id = &get_task_details_request.task_id;
+    let export = match service.get_recon_results_export(task_id, &caller).await {
+        Ok(export) => export,
+        Err(e) => return ok_or_error::<()>(Err(e)),
+    };
+
+    let writer = ResultsExportWriter::new(results_query.format, results_query.status, &export);
+    HttpResponse::Ok()
+        .content_type(writer.content_type())
+        .streaming(stream_results(service.clone(), export.results_file_names, writer))
+}
+
+//the service is only locked while a results file is read, other requests go through in between
+fn stream_results(
+    service: ReconTaskService,
+    results_file_names: Vec<String>,
+    writer: ResultsExportWriter,
+) -> impl Stream<Item = Result<web::Bytes, std::io::Error>> {
+    let header = stream::iter(writer.header().map(|header| Ok(web::Bytes::from(header))));
+
+    let rows = stream::iter(results_file_names).then(move |results_file_name| {
+        let service = service.clone();
+        let writer = writer.clone();
+        async move {
+            let results_file = service.read_recon_results_file(&results_file_name).await;
+            results_file
+                .and_then(|results_file| writer.write_rows(&results_file).map_err(ServiceError::from))
+                .map(web::Bytes::from)
+                .map_err(|e| std::io::Error::other(e.error.message))
+        }
+    });
+
+    header.chain(rows)
 }
 
+//both files must be attached and verified, the start command goes out on each file's chunks topic
+#[post("/recon-tasks/{task_id}/start")]
+pub(crate) async fn start_task(
+    get_task_details_request: Path<GetTaskDetailsRequest>,
+    caller: ReqData<AuthenticatedUser>,
+    service: ReconTaskService,
+) -> HttpResponse {
+    let task_id = &get_task_details_request.task_id;
+    let response = service.start_recon_task(task_id, &caller).await;
+    ok_or_error(response)
+}
+
+#[post("/recon-tasks/{task_id}/cancel")]
+pub(crate) async fn cancel_task(
+    get_task_details_request: Path<GetTaskDetailsRequest>,
+    cancellation: web::Json<CancelReconTaskRequest>,
+    caller: ReqData<AuthenticatedUser>,
+    service: ReconTaskService,
+) -> HttpResponse {
+    let task_id = &get_task_details_request.task_id;
+    let response = service.cancel_recon_task(task_id, &cancellation.0, &caller).await;
+    ok_or_error(response)
+}
+
+//the body is optional, without one only the configuration is cloned
+#[post("/recon-tasks/{task_id}/clone")]
+pub(crate) async fn clone_task(
+    get_task_details_request: Path<GetTaskDetailsRequest>,
+    clone_request: Option<web::Json<CloneReconTaskRequest>>,
+    caller: ReqData<AuthenticatedUser>,
+    service: ReconTaskService,
+) -> HttpResponse {
+    let task_id = &get_task_details_request.task_id;
+    let request = clone_request
+        .map(|clone_request| clone_request.into_inner())
+        .unwrap_or_default();
+
+    let response = service.clone_recon_task(task_id, &request, &caller).await;
+    ok_or_error(response)
+}
 
+#[get("/recon-tasks/{task_id}/suggested-pairs")]
+pub(crate) async fn get_suggested_pairs(
+    get_task_details_request: Path<GetTaskDetailsRequest>,
+    caller: ReqData<AuthenticatedUser>,
+    service: ReconTaskService,
+) -> HttpResponse {
+    let task_id = &get_task_details_request.task_id;
+    let response = service.get_suggested_comparison_pairs(task_id, &caller).await;
+    ok_or_error(response)
+}
+
+#[post("/recon-tasks/{task_id}/suggested-pairs/accept")]
+pub(crate) async fn accept_suggested_pairs(
+    get_task_details_request: Path<GetTaskDetailsRequest>,
+    caller: ReqData<AuthenticatedUser>,
+    service: ReconTaskService,
+) -> HttpResponse {
+    let task_id = &get_task_details_request.task_id;
+    let response = service.accept_suggested_comparison_pairs(task_id, &caller).await;
+    ok_or_error(response)
+}
+
+#[get("/admin/recon-tasks")]
+pub(crate) async fn list_all_tasks(
+    caller: ReqData<AuthenticatedUser>,
+    service: ReconTaskService,
+) -> HttpResponse {
+    let response = service.list_all_recon_tasks(&caller).await;
+    ok_or_error(response)
+}
+
+#[post("/admin/recon-tasks/{task_id}/status")]
+pub(crate) async fn force_task_status(
+    get_task_details_request: Path<GetTaskDetailsRequest>,
+    status: web::Json<ForceTaskStatusRequest>,
+    caller: ReqData<AuthenticatedUser>,
+    service: ReconTaskService,
+) -> HttpResponse {
+    let task_id = &get_task_details_request.task_id;
+    let response = service.force_recon_task_status(task_id, &status.0, &caller).await;
+    ok_or_error(response)
+}
+
+#[delete("/admin/recon-files/orphaned")]
+pub(crate) async fn delete_orphaned_files(
+    caller: ReqData<AuthenticatedUser>,
+    service: ReconTaskService,
+) -> HttpResponse {
+    let response = service.delete_orphaned_recon_files(&caller).await;
+    ok_or_error(response)
+}
+
+#[post("/recon-templates")]
+pub(crate) async fn create_template(
+    template: web::Json<ReconTemplateRequest>,
+    caller: ReqData<AuthenticatedUser>,
+    service: ReconTaskService,
+) -> HttpResponse {
+    let response = service.create_recon_template(&template.0, &caller).await;
+    ok_or_error(response)
+}
+
+#[get("/recon-templates")]
+pub(crate) async fn list_templates(
+    caller: ReqData<AuthenticatedUser>,
+    service: ReconTaskService,
+) -> HttpResponse {
+    let response = service.list_recon_templates(&caller).await;
+    ok_or_error(response)
+}
+
+#[get("/recon-templates/{template_id}")]
+pub(crate) async fn get_template(
+    get_template_request: Path<GetTemplateDetailsRequest>,
+    version_query: Query<GetTemplateVersionQuery>,
+    caller: ReqData<AuthenticatedUser>,
+    service: ReconTaskService,
+) -> HttpResponse {
+    let template_id = &get_template_request.template_id;
+    let response = service.get_recon_template(template_id, version_query.version, &caller).await;
+    ok_or_error(response)
+}
+
+#[put("/recon-templates/{template_id}")]
+pub(crate) async fn update_template(
+    get_template_request: Path<GetTemplateDetailsRequest>,
+    template: web::Json<ReconTemplateRequest>,
+    caller: ReqData<AuthenticatedUser>,
+    service: ReconTaskService,
+) -> HttpResponse {
+    let template_id = &get_template_request.template_id;
+    let response = service.update_recon_template(template_id, &template.0, &caller).await;
+    ok_or_error(response)
+}
+
+#[delete("/recon-templates/{template_id}")]
+pub(crate) async fn delete_template(
+    get_template_request: Path<GetTemplateDetailsRequest>,
+    caller: ReqData<AuthenticatedUser>,
+    service: ReconTaskService,
+) -> HttpResponse {
+    let template_id = &get_template_request.template_id;
+    let response = service.delete_recon_template(template_id, &caller).await;
+    ok_or_error(response)
+}
+
+//read by the sidecar, the one results topic all workers report on is routed to receive_recon_results
+#[get("/dapr/subscribe")]
+pub(crate) async fn list_dapr_subscriptions(
+    request: HttpRequest,
+    settings: Data<DaprSubscriberSettings>,
+) -> HttpResponse {
+    if !settings.accepts(&request) {
+        return HttpResponse::Unauthorized().finish();
+    }
+
+    HttpResponse::Ok().json(vec![DaprSubscription {
+        pubsubname: settings.pubsub_name.clone(),
+        topic: settings.results_topic.clone(),
+        route: String::from(RECON_RESULTS_ROUTE),
+    }])
+}
+
+//routed on the task_id the results carry, results for unknown tasks or that can never be recorded are dropped,
+//anything else is redelivered
+#[post("/dapr/recon-results")]
+pub(crate) async fn receive_recon_results(
+    request: HttpRequest,
+    event: web::Json<ReconResultsCloudEvent>,
+    settings: Data<DaprSubscriberSettings>,
+    service: ReconTaskService,
+) -> HttpResponse {
+    if !settings.accepts(&request) {
+        return HttpResponse::Unauthorized().finish();
+    }
+
+    let status = match service.record_recon_results(&event.data).await {
+        Ok(()) => DaprEventStatus::Success,
+        Err(e) if matches!(e.kind, AppErrorKind::NotFound | AppErrorKind::BadClientRequest) => {
+            DaprEventStatus::Drop
+        }
+        Err(_) => DaprEventStatus::Retry,
+    };
+    HttpResponse::Ok().json(DaprEventResponse { status })
+}
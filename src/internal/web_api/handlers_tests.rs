@@ -1,48 +1,85 @@
 use actix_web::{
     App,
+    http::StatusCode,
     test::{self, TestRequest},
     web::Data,
 };
+use actix_web_httpauth::middleware::HttpAuthentication;
+use chrono::Utc;
+use futures_util::StreamExt;
+use serde_json::{json, Value};
+use tokio::sync::mpsc::unbounded_channel;
 
 use crate::internal::{
     interfaces::recon_tasks_aggregator::MockReconTaskAggregationServiceInterface,
     interfaces::recon_tasks_aggregator::ReconTaskAggregationServiceInterface,
-    shared_reconciler_rust_libraries::models::{
-        entities::recon_tasks_models::{
-            ReconciliationConfigs, ReconFileMetaData, ReconFileType, ReconTaskDetails,
+    models::{
+        entities::{
+            authenticated_user::UserRole,
+            file_format::FileFormatDescriptor,
+            file_verification::FileVerification,
+            recon_file::ReconFile,
+            recon_results::ReconResultsSummary,
+            recon_task::{ReconTask, ReconTaskStatus},
+            recon_task_audit::{ReconTaskAction, ReconTaskAuditEntry},
+            rejection_reason::RejectionReason,
+            service_error::ServiceError,
+            row_key::RowKeyDefinition,
+            upload_session::{UploadSessionStatus, UploadedFileRole},
         },
-        view_models::recon_task_response_details::ReconTaskResponseDetails,
+        view_models::responses::{ReconTaskResponseDetails, UploadSessionResponse},
+    },
+    shared_reconciler_rust_libraries::models::entities::recon_tasks_models::{
+        ReconciliationConfigs, ReconFileMetaData, ReconFileType, ReconTaskDetails,
     },
     shared_reconciler_rust_libraries::models::entities::{
-        app_errors::{AppError, AppErrorKind},
+        app_errors::AppErrorKind,
         file_chunk_queue::FileChunkQueue,
     },
-    web_api::handlers::get_task_details,
+    shared_reconciler_rust_libraries::models::view_models::recon_task_response_details::FileResponseSummary,
+    web_api::{
+        auth::validate_bearer_token,
+        dapr_subscriber::{DaprSubscriberSettings, DAPR_API_TOKEN_HEADER},
+        handlers::{
+            cancel_task, get_task_details, get_task_history, list_all_tasks,
+            list_dapr_subscriptions, receive_recon_results, upload_file_chunk, upload_primary_file,
+        },
+        server::{upload_chunk_config, upload_form_config},
+        test_tokens::{bearer_token_for, bearer_token_with_roles, test_token_validator},
+        test_uploads::{multipart_content_type, multipart_file_body, TEST_MULTIPART_BOUNDARY},
+    },
 };
 
+//path ids have to be well formed to reach the handlers
+const TEST_TASK_ID: &str = "RECON-TASK-123e4567-e89b-12d3-a456-426614174000";
+const TEST_UPLOAD_ID: &str = "UPLOAD-SESSION-01ARZ3NDEKTSV4RRFFQ69G5FAV";
+
 #[actix_web::test]
 async fn test_get_task_details_calls_correct_dependencies_and_returns_success() {
-    let mut app = test::init_service((move || {
+    let app = test::init_service({
         // Create some global state prior to running the handler thread
         let mut mock_recon_task_aggregation_service =
             Box::new(MockReconTaskAggregationServiceInterface::new());
 
         mock_recon_task_aggregation_service
             .expect_get_recon_task()
-            .returning(|_y| Ok(get_dummy_recon_task_response_details()));
+            .returning(|_y, _| Ok(get_dummy_recon_task_response_details()));
 
         let service: Box<dyn ReconTaskAggregationServiceInterface> =
             mock_recon_task_aggregation_service;
 
         App::new()
             .app_data(Data::new(service)) // add shared state
+            .app_data(Data::new(test_token_validator()))
+            .wrap(HttpAuthentication::bearer(validate_bearer_token))
             .service(get_task_details)
-    })())
+    })
         .await;
 
     let resp = TestRequest::get()
-        .uri(&format!("/recon-tasks/123456"))
-        .send_request(&mut app)
+        .uri(&format!("/recon-tasks/{}", TEST_TASK_ID))
+        .insert_header(("Authorization", bearer_token_for("test-user-id")))
+        .send_request(&app)
         .await;
 
     assert!(resp.status().is_success());
@@ -50,15 +87,15 @@ async fn test_get_task_details_calls_correct_dependencies_and_returns_success()
 
 #[actix_web::test]
 async fn test_get_task_details_when_invalid_request_returns_bad_request() {
-    let mut app = test::init_service((move || {
+    let app = test::init_service({
         // Create some global state prior to running the handler thread
         let mut mock_recon_task_aggregation_service =
             Box::new(MockReconTaskAggregationServiceInterface::new());
 
         mock_recon_task_aggregation_service
             .expect_get_recon_task()
-            .returning(|_y| {
-                Err(AppError::new(
+            .returning(|_y, _| {
+                Err(ServiceError::new(
                     AppErrorKind::BadClientRequest,
                     "invalid request".to_string(),
                 ))
@@ -69,13 +106,16 @@ async fn test_get_task_details_when_invalid_request_returns_bad_request() {
 
         App::new()
             .app_data(Data::new(service)) // add shared state
+            .app_data(Data::new(test_token_validator()))
+            .wrap(HttpAuthentication::bearer(validate_bearer_token))
             .service(get_task_details)
-    })())
+    })
         .await;
 
     let resp = TestRequest::get()
-        .uri(&format!("/recon-tasks/123456"))
-        .send_request(&mut app)
+        .uri(&format!("/recon-tasks/{}", TEST_TASK_ID))
+        .insert_header(("Authorization", bearer_token_for("test-user-id")))
+        .send_request(&app)
         .await;
 
     assert!(resp.status().is_client_error());
@@ -83,15 +123,15 @@ async fn test_get_task_details_when_invalid_request_returns_bad_request() {
 
 #[actix_web::test]
 async fn test_upload_file_chunk_when_service_returns_error_returns_internal_error() {
-    let mut app = test::init_service((move || {
+    let app = test::init_service({
         // Create some global state prior to running the handler thread
         let mut mock_recon_task_aggregation_service =
             Box::new(MockReconTaskAggregationServiceInterface::new());
 
         mock_recon_task_aggregation_service
             .expect_get_recon_task()
-            .returning(|_y| {
-                Err(AppError::new(
+            .returning(|_y, _| {
+                Err(ServiceError::new(
                     AppErrorKind::InternalError,
                     "Internal server error".to_string(),
                 ))
@@ -102,64 +142,713 @@ async fn test_upload_file_chunk_when_service_returns_error_returns_internal_erro
 
         App::new()
             .app_data(Data::new(service)) // add shared state
+            .app_data(Data::new(test_token_validator()))
+            .wrap(HttpAuthentication::bearer(validate_bearer_token))
             .service(get_task_details)
-    })())
+    })
         .await;
 
     let resp = TestRequest::get()
-        .uri(&format!("/recon-tasks/123456"))
-        .send_request(&mut app)
+        .uri(&format!("/recon-tasks/{}", TEST_TASK_ID))
+        .insert_header(("Authorization", bearer_token_for("test-user-id")))
+        .send_request(&app)
         .await;
 
     assert!(resp.status().is_server_error());
 }
 
+#[actix_web::test]
+async fn test_get_task_details_when_dapr_is_unavailable_returns_service_unavailable() {
+    let app = test::init_service({
+        // Create some global state prior to running the handler thread
+        let mut mock_recon_task_aggregation_service =
+            Box::new(MockReconTaskAggregationServiceInterface::new());
+
+        mock_recon_task_aggregation_service
+            .expect_get_recon_task()
+            .returning(|_y, _| {
+                Err(ServiceError::new(
+                    AppErrorKind::ConnectionError,
+                    "circuit breaker is open".to_string(),
+                ))
+            });
+
+        let service: Box<dyn ReconTaskAggregationServiceInterface> =
+            mock_recon_task_aggregation_service;
+
+        App::new()
+            .app_data(Data::new(service)) // add shared state
+            .app_data(Data::new(test_token_validator()))
+            .wrap(HttpAuthentication::bearer(validate_bearer_token))
+            .service(get_task_details)
+    })
+        .await;
+
+    let resp = TestRequest::get()
+        .uri(&format!("/recon-tasks/{}", TEST_TASK_ID))
+        .insert_header(("Authorization", bearer_token_for("test-user-id")))
+        .send_request(&app)
+        .await;
+
+    assert_eq!(resp.status(), StatusCode::SERVICE_UNAVAILABLE);
+}
+
+#[actix_web::test]
+async fn test_get_task_history_returns_audit_entries() {
+    let app = test::init_service({
+        // Create some global state prior to running the handler thread
+        let mut mock_recon_task_aggregation_service =
+            Box::new(MockReconTaskAggregationServiceInterface::new());
+
+        mock_recon_task_aggregation_service
+            .expect_get_recon_task_history()
+            .returning(|task_id, _| {
+                Ok(vec![ReconTaskAuditEntry::new(
+                    task_id,
+                    ReconTaskAction::TaskCreated,
+                    &String::from("test-user-id"),
+                    None,
+                )])
+            });
+
+        let service: Box<dyn ReconTaskAggregationServiceInterface> =
+            mock_recon_task_aggregation_service;
+
+        App::new()
+            .app_data(Data::new(service)) // add shared state
+            .app_data(Data::new(test_token_validator()))
+            .wrap(HttpAuthentication::bearer(validate_bearer_token))
+            .service(get_task_history)
+    })
+        .await;
+
+    let resp = TestRequest::get()
+        .uri(&format!("/recon-tasks/{}/history", TEST_TASK_ID))
+        .insert_header(("Authorization", bearer_token_for("test-user-id")))
+        .send_request(&app)
+        .await;
+
+    assert!(resp.status().is_success());
+}
+
+#[actix_web::test]
+async fn test_cancel_task_passes_task_id_and_reason_to_service() {
+    let app = test::init_service({
+        // Create some global state prior to running the handler thread
+        let mut mock_recon_task_aggregation_service =
+            Box::new(MockReconTaskAggregationServiceInterface::new());
+
+        mock_recon_task_aggregation_service
+            .expect_cancel_recon_task()
+            .withf(|task_id, request, caller| {
+                task_id == TEST_TASK_ID
+                    && request.reason == "wrong file uploaded"
+                    && caller.user_id == "test-user-id"
+            })
+            .times(1)
+            .returning(|_, _, _| Ok(get_dummy_recon_task_response_details()));
+
+        let service: Box<dyn ReconTaskAggregationServiceInterface> =
+            mock_recon_task_aggregation_service;
+
+        App::new()
+            .app_data(Data::new(service)) // add shared state
+            .app_data(Data::new(test_token_validator()))
+            .wrap(HttpAuthentication::bearer(validate_bearer_token))
+            .service(cancel_task)
+    })
+        .await;
+
+    let resp = TestRequest::post()
+        .uri(&format!("/recon-tasks/{}/cancel", TEST_TASK_ID))
+        .insert_header(("Authorization", bearer_token_for("test-user-id")))
+        .set_json(serde_json::json!({ "reason": "wrong file uploaded" }))
+        .send_request(&app)
+        .await;
+
+    assert!(resp.status().is_success());
+}
+
+#[actix_web::test]
+async fn test_cancel_task_changed_by_another_request_returns_conflict() {
+    let app = test::init_service({
+        // Create some global state prior to running the handler thread
+        let mut mock_recon_task_aggregation_service =
+            Box::new(MockReconTaskAggregationServiceInterface::new());
+
+        mock_recon_task_aggregation_service
+            .expect_cancel_recon_task()
+            .returning(|_, _, _| {
+                Err(RejectionReason::Conflict.error(
+                    "task was changed by another request".to_string(),
+                ))
+            });
+
+        let service: Box<dyn ReconTaskAggregationServiceInterface> =
+            mock_recon_task_aggregation_service;
+
+        App::new()
+            .app_data(Data::new(service)) // add shared state
+            .app_data(Data::new(test_token_validator()))
+            .wrap(HttpAuthentication::bearer(validate_bearer_token))
+            .service(cancel_task)
+    })
+        .await;
+
+    let resp = TestRequest::post()
+        .uri(&format!("/recon-tasks/{}/cancel", TEST_TASK_ID))
+        .insert_header(("Authorization", bearer_token_for("test-user-id")))
+        .set_json(serde_json::json!({ "reason": "wrong file uploaded" }))
+        .send_request(&app)
+        .await;
+
+    assert_eq!(resp.status(), StatusCode::CONFLICT);
+
+    let body: Value = test::read_body_json(resp).await;
+    assert_eq!(body["kind"], "Conflict");
+}
+
+#[actix_web::test]
+async fn test_upload_primary_file_passes_file_name_and_content_to_service() {
+    //the content is streamed, so it is read off on its own task while the handler keeps feeding it
+    let (content_sender, mut content_receiver) = unbounded_channel();
+
+    let app = test::init_service({
+        // Create some global state prior to running the handler thread
+        let mut mock_recon_task_aggregation_service =
+            Box::new(MockReconTaskAggregationServiceInterface::new());
+
+        mock_recon_task_aggregation_service
+            .expect_upload_primary_file_to_task()
+            .withf(|task_id, file_name, _, caller| {
+                task_id == TEST_TASK_ID
+                    && file_name == "payments.csv"
+                    && caller.user_id == "test-user-id"
+            })
+            .times(1)
+            .returning(move |task_id, _, content, _| {
+                let content_sender = content_sender.clone();
+                actix_web::rt::spawn(async move {
+                    let pieces: Vec<Result<Vec<u8>, ServiceError>> = content.collect().await;
+                    let content: Vec<u8> = pieces.into_iter().flat_map(|piece| piece.unwrap()).collect();
+                    content_sender.send(content).unwrap();
+                });
+                Ok(FileResponseSummary {
+                    file_id: String::from("file-1234"),
+                    task_id: task_id.to_string(),
+                })
+            });
+
+        let service: Box<dyn ReconTaskAggregationServiceInterface> =
+            mock_recon_task_aggregation_service;
+
+        App::new()
+            .app_data(Data::new(service)) // add shared state
+            .app_data(Data::new(test_token_validator()))
+            .app_data(upload_form_config(1024))
+            .wrap(HttpAuthentication::bearer(validate_bearer_token))
+            .service(upload_primary_file)
+    })
+        .await;
+
+    let resp = TestRequest::post()
+        .uri(&format!("/recon-tasks/{}/files/primary/upload", TEST_TASK_ID))
+        .insert_header(("Authorization", bearer_token_for("test-user-id")))
+        .insert_header(("Content-Type", multipart_content_type()))
+        .set_payload(multipart_file_body("payments.csv", b"id,amount\n1,10\n"))
+        .send_request(&app)
+        .await;
+
+    assert!(resp.status().is_success());
+    assert_eq!(content_receiver.recv().await.unwrap(), b"id,amount\n1,10\n".to_vec());
+}
+
+#[actix_web::test]
+async fn test_upload_primary_file_without_file_part_returns_bad_request() {
+    let app = test::init_service({
+        // Create some global state prior to running the handler thread
+        let mut mock_recon_task_aggregation_service =
+            Box::new(MockReconTaskAggregationServiceInterface::new());
+
+        mock_recon_task_aggregation_service
+            .expect_upload_primary_file_to_task()
+            .times(0);
+
+        let service: Box<dyn ReconTaskAggregationServiceInterface> =
+            mock_recon_task_aggregation_service;
+
+        App::new()
+            .app_data(Data::new(service)) // add shared state
+            .app_data(Data::new(test_token_validator()))
+            .app_data(upload_form_config(1024))
+            .wrap(HttpAuthentication::bearer(validate_bearer_token))
+            .service(upload_primary_file)
+    })
+        .await;
+
+    let body = format!(
+        "--{}\r\nContent-Disposition: form-data; name=\"notes\"\r\n\r\nhello\r\n--{}--\r\n",
+        TEST_MULTIPART_BOUNDARY, TEST_MULTIPART_BOUNDARY
+    );
+
+    let resp = TestRequest::post()
+        .uri(&format!("/recon-tasks/{}/files/primary/upload", TEST_TASK_ID))
+        .insert_header(("Authorization", bearer_token_for("test-user-id")))
+        .insert_header(("Content-Type", multipart_content_type()))
+        .set_payload(body)
+        .send_request(&app)
+        .await;
+
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+}
+
+#[actix_web::test]
+async fn test_upload_primary_file_above_upload_size_limit_is_rejected_before_reaching_service() {
+    let app = test::init_service({
+        // Create some global state prior to running the handler thread
+        let mut mock_recon_task_aggregation_service =
+            Box::new(MockReconTaskAggregationServiceInterface::new());
+
+        mock_recon_task_aggregation_service
+            .expect_upload_primary_file_to_task()
+            .times(0);
+
+        let service: Box<dyn ReconTaskAggregationServiceInterface> =
+            mock_recon_task_aggregation_service;
+
+        App::new()
+            .app_data(Data::new(service)) // add shared state
+            .app_data(Data::new(test_token_validator()))
+            .app_data(upload_form_config(8))
+            .wrap(HttpAuthentication::bearer(validate_bearer_token))
+            .service(upload_primary_file)
+    })
+        .await;
+
+    let resp = TestRequest::post()
+        .uri(&format!("/recon-tasks/{}/files/primary/upload", TEST_TASK_ID))
+        .insert_header(("Authorization", bearer_token_for("test-user-id")))
+        .insert_header(("Content-Type", multipart_content_type()))
+        .set_payload(multipart_file_body("payments.csv", b"id,amount\n1,10\n2,20\n"))
+        .send_request(&app)
+        .await;
+
+    assert!(resp.status().is_client_error());
+}
+
+#[actix_web::test]
+async fn test_upload_file_chunk_passes_chunk_number_and_body_to_service() {
+    let app = test::init_service({
+        // Create some global state prior to running the handler thread
+        let mut mock_recon_task_aggregation_service =
+            Box::new(MockReconTaskAggregationServiceInterface::new());
+
+        mock_recon_task_aggregation_service
+            .expect_upload_file_chunk()
+            .withf(|task_id, upload_id, chunk_number, content, _| {
+                task_id == TEST_TASK_ID
+                    && upload_id == TEST_UPLOAD_ID
+                    && *chunk_number == 1
+                    && content == b"1,10\n"
+            })
+            .times(1)
+            .returning(|task_id, upload_id, _, _, _| {
+                Ok(UploadSessionResponse {
+                    upload_id: upload_id.to_string(),
+                    task_id: task_id.to_string(),
+                    file_role: UploadedFileRole::Primary,
+                    file_name: String::from("payments.csv"),
+                    total_size_bytes: 15,
+                    chunk_size_bytes: 10,
+                    chunk_count: 2,
+                    received_ranges: vec![],
+                    missing_chunks: vec![0],
+                    status: UploadSessionStatus::Open,
+                    file_id: None,
+                })
+            });
+
+        let service: Box<dyn ReconTaskAggregationServiceInterface> =
+            mock_recon_task_aggregation_service;
+
+        App::new()
+            .app_data(Data::new(service)) // add shared state
+            .app_data(Data::new(test_token_validator()))
+            .app_data(upload_chunk_config(1024))
+            .wrap(HttpAuthentication::bearer(validate_bearer_token))
+            .service(upload_file_chunk)
+    })
+        .await;
+
+    let resp = TestRequest::put()
+        .uri(&format!("/recon-tasks/{}/uploads/{}/chunks/1", TEST_TASK_ID, TEST_UPLOAD_ID))
+        .insert_header(("Authorization", bearer_token_for("test-user-id")))
+        .insert_header(("Content-Type", "application/octet-stream"))
+        .set_payload(&b"1,10\n"[..])
+        .send_request(&app)
+        .await;
+
+    assert!(resp.status().is_success());
+}
+
+#[actix_web::test]
+async fn test_get_task_details_without_bearer_token_returns_unauthorized() {
+    let app = test::init_service({
+        // Create some global state prior to running the handler thread
+        let mut mock_recon_task_aggregation_service =
+            Box::new(MockReconTaskAggregationServiceInterface::new());
+
+        mock_recon_task_aggregation_service
+            .expect_get_recon_task()
+            .times(0);
+
+        let service: Box<dyn ReconTaskAggregationServiceInterface> =
+            mock_recon_task_aggregation_service;
+
+        App::new()
+            .app_data(Data::new(service)) // add shared state
+            .app_data(Data::new(test_token_validator()))
+            .wrap(HttpAuthentication::bearer(validate_bearer_token))
+            .service(get_task_details)
+    })
+        .await;
+
+    let resp = TestRequest::get()
+        .uri(&format!("/recon-tasks/{}", TEST_TASK_ID))
+        .send_request(&app)
+        .await;
+
+    assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+}
+
+#[actix_web::test]
+async fn test_get_task_details_with_invalid_bearer_token_returns_unauthorized() {
+    let app = test::init_service({
+        // Create some global state prior to running the handler thread
+        let mut mock_recon_task_aggregation_service =
+            Box::new(MockReconTaskAggregationServiceInterface::new());
+
+        mock_recon_task_aggregation_service
+            .expect_get_recon_task()
+            .times(0);
+
+        let service: Box<dyn ReconTaskAggregationServiceInterface> =
+            mock_recon_task_aggregation_service;
+
+        App::new()
+            .app_data(Data::new(service)) // add shared state
+            .app_data(Data::new(test_token_validator()))
+            .wrap(HttpAuthentication::bearer(validate_bearer_token))
+            .service(get_task_details)
+    })
+        .await;
+
+    let resp = TestRequest::get()
+        .uri(&format!("/recon-tasks/{}", TEST_TASK_ID))
+        .insert_header(("Authorization", "Bearer not-a-jwt"))
+        .send_request(&app)
+        .await;
+
+    assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+}
+
+#[actix_web::test]
+async fn test_get_task_details_for_task_owned_by_another_user_returns_forbidden() {
+    let app = test::init_service({
+        // Create some global state prior to running the handler thread
+        let mut mock_recon_task_aggregation_service =
+            Box::new(MockReconTaskAggregationServiceInterface::new());
+
+        mock_recon_task_aggregation_service
+            .expect_get_recon_task()
+            .withf(|_, caller| caller.user_id == "another-user-id")
+            .returning(|_y, _| {
+                Err(RejectionReason::Forbidden.error(
+                    "task does not belong to the caller".to_string(),
+                ))
+            });
+
+        let service: Box<dyn ReconTaskAggregationServiceInterface> =
+            mock_recon_task_aggregation_service;
+
+        App::new()
+            .app_data(Data::new(service)) // add shared state
+            .app_data(Data::new(test_token_validator()))
+            .wrap(HttpAuthentication::bearer(validate_bearer_token))
+            .service(get_task_details)
+    })
+        .await;
+
+    let resp = TestRequest::get()
+        .uri(&format!("/recon-tasks/{}", TEST_TASK_ID))
+        .insert_header(("Authorization", bearer_token_for("another-user-id")))
+        .send_request(&app)
+        .await;
+
+    assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+
+    let body: Value = test::read_body_json(resp).await;
+    assert_eq!(body["kind"], "Forbidden");
+    assert_eq!(body["message"], "task does not belong to the caller");
+}
+
+#[actix_web::test]
+async fn test_upload_primary_file_above_row_quota_returns_payload_too_large() {
+    let app = test::init_service({
+        // Create some global state prior to running the handler thread
+        let mut mock_recon_task_aggregation_service =
+            Box::new(MockReconTaskAggregationServiceInterface::new());
+
+        mock_recon_task_aggregation_service
+            .expect_upload_primary_file_to_task()
+            .returning(|_, _, _, _| {
+                Err(RejectionReason::FileTooLarge.error(
+                    "file has 2 rows, the maximum is 1".to_string(),
+                ))
+            });
+
+        let service: Box<dyn ReconTaskAggregationServiceInterface> =
+            mock_recon_task_aggregation_service;
+
+        App::new()
+            .app_data(Data::new(service)) // add shared state
+            .app_data(Data::new(test_token_validator()))
+            .app_data(upload_form_config(1024))
+            .wrap(HttpAuthentication::bearer(validate_bearer_token))
+            .service(upload_primary_file)
+    })
+        .await;
+
+    let resp = TestRequest::post()
+        .uri(&format!("/recon-tasks/{}/files/primary/upload", TEST_TASK_ID))
+        .insert_header(("Authorization", bearer_token_for("test-user-id")))
+        .insert_header(("Content-Type", multipart_content_type()))
+        .set_payload(multipart_file_body("payments.csv", b"id,amount\n1,10\n2,20\n"))
+        .send_request(&app)
+        .await;
+
+    assert_eq!(resp.status(), StatusCode::PAYLOAD_TOO_LARGE);
+}
+
+#[actix_web::test]
+async fn test_list_all_tasks_passes_roles_from_token_to_service() {
+    let app = test::init_service({
+        // Create some global state prior to running the handler thread
+        let mut mock_recon_task_aggregation_service =
+            Box::new(MockReconTaskAggregationServiceInterface::new());
+
+        mock_recon_task_aggregation_service
+            .expect_list_all_recon_tasks()
+            .withf(|caller| caller.roles == vec![UserRole::Admin])
+            .returning(|_| Ok(vec![get_dummy_recon_task_response_details().task_details]));
+
+        let service: Box<dyn ReconTaskAggregationServiceInterface> =
+            mock_recon_task_aggregation_service;
+
+        App::new()
+            .app_data(Data::new(service)) // add shared state
+            .app_data(Data::new(test_token_validator()))
+            .wrap(HttpAuthentication::bearer(validate_bearer_token))
+            .service(list_all_tasks)
+    })
+        .await;
+
+    let resp = TestRequest::get()
+        .uri("/admin/recon-tasks")
+        .insert_header(("Authorization", bearer_token_with_roles("admin-user-id", &["admin"])))
+        .send_request(&app)
+        .await;
+
+    assert!(resp.status().is_success());
+}
+
+#[actix_web::test]
+async fn test_list_dapr_subscriptions_routes_the_one_results_topic_to_receive_recon_results() {
+    let app = test::init_service(App::new()
+            .app_data(Data::new(DaprSubscriberSettings {
+                pubsub_name: String::from("pubsub"),
+                results_topic: String::from("recon-results"),
+                app_api_token: Some(String::from("sidecar-token")),
+            }))
+            .service(list_dapr_subscriptions))
+        .await;
+
+    let subscriptions: Value = test::read_body_json(
+        TestRequest::get()
+            .uri("/dapr/subscribe")
+            .insert_header((DAPR_API_TOKEN_HEADER, "sidecar-token"))
+            .send_request(&app)
+            .await,
+    )
+    .await;
+
+    let unauthorized_resp = TestRequest::get()
+        .uri("/dapr/subscribe")
+        .send_request(&app)
+        .await;
+
+    assert_eq!(
+        subscriptions,
+        json!([{ "pubsubname": "pubsub", "topic": "recon-results", "route": "/dapr/recon-results" }])
+    );
+    assert_eq!(unauthorized_resp.status(), StatusCode::UNAUTHORIZED);
+}
+
+#[actix_web::test]
+async fn test_receive_recon_results_drops_results_for_unknown_tasks_and_redelivers_on_other_errors() {
+    let app = test::init_service({
+        // Create some global state prior to running the handler thread
+        let mut mock_recon_task_aggregation_service =
+            Box::new(MockReconTaskAggregationServiceInterface::new());
+
+        mock_recon_task_aggregation_service
+            .expect_record_recon_results()
+            .returning(|results| {
+                let kind = match results.task_id.as_str() {
+                    "unknown-task" => AppErrorKind::NotFound,
+                    _ => AppErrorKind::ConnectionError,
+                };
+                Err(ServiceError::new(kind, format!("task [{}]", results.task_id)))
+            });
+
+        let service: Box<dyn ReconTaskAggregationServiceInterface> =
+            mock_recon_task_aggregation_service;
+
+        App::new()
+            .app_data(Data::new(service)) // add shared state
+            .app_data(Data::new(DaprSubscriberSettings {
+                pubsub_name: String::from("pubsub"),
+                results_topic: String::from("recon-results"),
+                app_api_token: Some(String::from("sidecar-token")),
+            }))
+            .service(receive_recon_results)
+    })
+        .await;
+
+    let mut statuses = vec![];
+    for task_id in ["unknown-task", "task-1234"] {
+        let resp: Value = test::read_body_json(
+            TestRequest::post()
+                .uri("/dapr/recon-results")
+                .insert_header((DAPR_API_TOKEN_HEADER, "sidecar-token"))
+                .set_json(get_dummy_recon_results_cloud_event(task_id))
+                .send_request(&app)
+                .await,
+        )
+        .await;
+        statuses.push(resp["status"].clone());
+    }
+
+    let unauthorized_resp = TestRequest::post()
+        .uri("/dapr/recon-results")
+        .set_json(get_dummy_recon_results_cloud_event("task-1234"))
+        .send_request(&app)
+        .await;
+
+    assert_eq!(statuses, vec![json!("DROP"), json!("RETRY")]);
+    assert_eq!(unauthorized_resp.status(), StatusCode::UNAUTHORIZED);
+}
+
+fn get_dummy_recon_results_cloud_event(task_id: &str) -> Value {
+    json!({
+        "specversion": "1.0",
+        "data": {
+            "task_id": task_id,
+            "file_role": "primary",
+            "chunk_sequence_number": 1,
+            "total_chunks": 1,
+            "counts": { "matched": 10 }
+        }
+    })
+}
+
 fn get_dummy_recon_task_response_details() -> ReconTaskResponseDetails {
     ReconTaskResponseDetails {
         task_id: String::from("task-1234"),
-        task_details: ReconTaskDetails {
-            id: String::from("task-1234"),
-            primary_file_id: Some(String::from("src-file-1234")),
-            comparison_file_id: Some(String::from("cmp-file-1234")),
-            is_done: false,
-            has_begun: true,
-            comparison_pairs: vec![],
-            recon_config: ReconciliationConfigs {
-                should_check_for_duplicate_records_in_comparison_file: true,
-                should_reconciliation_be_case_sensitive: true,
-                should_ignore_white_space: true,
-                should_do_reverse_reconciliation: true,
-            },
-            recon_results_queue_info: FileChunkQueue {
-                topic_id: String::from("test-topic"),
-                last_acknowledged_id: None,
-            },
-            primary_file_chunks_queue_info: FileChunkQueue {
-                topic_id: String::from("test-topic"),
-                last_acknowledged_id: None,
-            },
-            comparison_file_chunks_queue_info: FileChunkQueue {
-                topic_id: String::from("test-topic"),
-                last_acknowledged_id: None,
+        row_key: RowKeyDefinition {
+            key_separator: String::from("|"),
+            identifier_columns: vec![],
+            is_complete: true,
+        },
+        task_details: ReconTask {
+            details: ReconTaskDetails {
+                id: String::from("task-1234"),
+                primary_file_id: Some(String::from("src-file-1234")),
+                comparison_file_id: Some(String::from("cmp-file-1234")),
+                is_done: false,
+                has_begun: true,
+                comparison_pairs: vec![],
+                recon_config: ReconciliationConfigs {
+                    should_check_for_duplicate_records_in_comparison_file: true,
+                    should_reconciliation_be_case_sensitive: true,
+                    should_ignore_white_space: true,
+                    should_do_reverse_reconciliation: true,
+                },
+                recon_results_queue_info: FileChunkQueue {
+                    topic_id: String::from("test-topic"),
+                    last_acknowledged_id: None,
+                },
+                primary_file_chunks_queue_info: FileChunkQueue {
+                    topic_id: String::from("test-topic"),
+                    last_acknowledged_id: None,
+                },
+                comparison_file_chunks_queue_info: FileChunkQueue {
+                    topic_id: String::from("test-topic"),
+                    last_acknowledged_id: None,
+                },
             },
+            created_by: String::from("test-user-id"),
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            cancellation: None,
+            reconciliation_start: None,
+            template: None,
+            pending_comparison_pairs: vec![],
+            resolved_comparison_pairs: vec![],
+            accepted_comparison_pairs: vec![],
+            comparison_pair_rules: vec![],
+            row_key_positions: vec![],
+            row_key_separator: String::from("|"),
+            column_type_mismatches: vec![],
+            status: ReconTaskStatus::AwaitingFiles,
+            results_summary: ReconResultsSummary::default(),
+            etag: None,
         },
-        primary_file_metadata: Some(ReconFileMetaData {
-            id: String::from("src-file-1234"),
-            file_name: String::from("src-file-1234"),
-            row_count: 1000,
-            column_delimiters: vec![],
-            recon_file_type: ReconFileType::PrimaryFile,
-            column_headers: vec![],
-            file_hash: String::from("src-file-1234"),
+        primary_file_metadata: Some(ReconFile {
+            format: FileFormatDescriptor::default(),
+            column_schema: vec![],
+            stored_file_parts: vec![],
+            verification: FileVerification::default(),
+            metadata: ReconFileMetaData {
+                id: String::from("src-file-1234"),
+                file_name: String::from("src-file-1234"),
+                row_count: 1000,
+                column_delimiters: vec![],
+                recon_file_type: ReconFileType::PrimaryFile,
+                column_headers: vec![],
+                file_hash: String::from("src-file-1234"),
+            },
+            created_by: String::from("test-user-id"),
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
         }),
-        comparison_file_metadata: Some(ReconFileMetaData {
-            id: String::from("cmp-file-1234"),
-            file_name: String::from("cmp-file-1234"),
-            row_count: 1000,
-            column_delimiters: vec![],
-            recon_file_type: ReconFileType::ComparisonFile,
-            column_headers: vec![],
-            file_hash: String::from("cmp-file-1234"),
+        comparison_file_metadata: Some(ReconFile {
+            format: FileFormatDescriptor::default(),
+            column_schema: vec![],
+            stored_file_parts: vec![],
+            verification: FileVerification::default(),
+            metadata: ReconFileMetaData {
+                id: String::from("cmp-file-1234"),
+                file_name: String::from("cmp-file-1234"),
+                row_count: 1000,
+                column_delimiters: vec![],
+                recon_file_type: ReconFileType::ComparisonFile,
+                column_headers: vec![],
+                file_hash: String::from("cmp-file-1234"),
+            },
+            created_by: String::from("test-user-id"),
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
         }),
     }
 }
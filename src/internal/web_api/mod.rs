@@ -1,7 +1,33 @@
+pub mod auth;
+pub mod dapr_subscriber;
 pub mod handlers;
+pub mod multipart_upload;
+pub mod rate_limiter;
 pub mod server;
 
+#[cfg(test)]
+pub mod test_tokens;
+
+#[cfg(test)]
+pub mod test_uploads;
+
+#[cfg(test)]
+#[path = "./auth_tests.rs"]
+mod auth_tests;
+
+#[cfg(test)]
+#[path = "./dapr_subscriber_tests.rs"]
+mod dapr_subscriber_tests;
+
+#[cfg(test)]
+#[path = "./rate_limiter_tests.rs"]
+mod rate_limiter_tests;
+
 #[cfg(test)]
 #[path = "./handlers_tests.rs"]
 mod handlers_tests;
+
+#[cfg(test)]
+#[path = "./server_tests.rs"]
+mod server_tests;
 mod utils;
@@ -0,0 +1,125 @@
+use std::future::Future;
+
+use actix_multipart::{Field, Multipart};
+use actix_web::{http::header::CONTENT_LENGTH, HttpRequest};
+use futures_util::{future, stream, StreamExt};
+use tokio::sync::mpsc;
+
+use crate::internal::{
+    interfaces::recon_tasks_aggregator::UploadedContent,
+    models::entities::{rejection_reason::RejectionReason, service_error::ServiceError},
+    shared_reconciler_rust_libraries::models::entities::app_errors::AppErrorKind,
+};
+
+//the multipart part holding the file, its name is read from the part's content disposition
+const FILE_PART_NAME: &str = "file";
+
+//pieces read off the request wait here until the service takes them, which holds back reading when it falls behind
+const UPLOAD_CHANNEL_CAPACITY: usize = 16;
+
+#[derive(Debug, Clone)]
+pub struct UploadFormConfig {
+    pub max_upload_size_bytes: usize,
+}
+
+//hands the file part of a multipart/form-data body to the upload as it is read off the request,
+//multipart fields cannot leave the request's thread, so the pieces are passed across a channel
+pub(crate) async fn stream_uploaded_file<T, F, Fut>(
+    request: &HttpRequest,
+    mut payload: Multipart,
+    config: &UploadFormConfig,
+    upload: F,
+) -> Result<T, ServiceError>
+where
+    F: FnOnce(String, UploadedContent) -> Fut,
+    Fut: Future<Output = Result<T, ServiceError>>,
+{
+    ensure_declared_size_within_limit(request, config)?;
+
+    let field = read_file_field(&mut payload).await?;
+    let file_name = field
+        .content_disposition()
+        .get_filename()
+        .unwrap_or_default()
+        .to_string();
+
+    let (sender, receiver) = mpsc::channel(UPLOAD_CHANNEL_CAPACITY);
+    let forward_file = forward_file_field(field, sender, config.max_upload_size_bytes);
+    let content = stream::unfold(receiver, |mut receiver| async move {
+        let piece = receiver.recv().await?;
+        Some((piece, receiver))
+    })
+    .boxed();
+
+    let (response, _) = future::join(upload(file_name, content), forward_file).await;
+    response
+}
+
+//a body that says up front it is too large is turned away before anything is read
+fn ensure_declared_size_within_limit(
+    request: &HttpRequest,
+    config: &UploadFormConfig,
+) -> Result<(), ServiceError> {
+    let declared_size = request
+        .headers()
+        .get(CONTENT_LENGTH)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.parse::<usize>().ok());
+
+    match declared_size {
+        Some(size) if size > config.max_upload_size_bytes => Err(too_large(config.max_upload_size_bytes)),
+        _ => Ok(()),
+    }
+}
+
+async fn read_file_field(payload: &mut Multipart) -> Result<Field, ServiceError> {
+    while let Some(field) = payload.next().await {
+        let field = field.map_err(|e| ServiceError::new(AppErrorKind::BadClientRequest, e.to_string()))?;
+
+        //any other part is skipped over
+        if field.name() == FILE_PART_NAME {
+            return Ok(field);
+        }
+    }
+
+    Err(ServiceError::new(
+        AppErrorKind::BadClientRequest,
+        format!("please send the file in a multipart part named [{}]", FILE_PART_NAME),
+    ))
+}
+
+//the size is counted as the pieces arrive too, since a chunked body declares no length
+async fn forward_file_field(
+    mut field: Field,
+    sender: mpsc::Sender<Result<Vec<u8>, ServiceError>>,
+    max_upload_size_bytes: usize,
+) {
+    let mut received_bytes = 0;
+
+    while let Some(piece) = field.next().await {
+        let piece = match piece {
+            Ok(bytes) => {
+                received_bytes += bytes.len();
+                match received_bytes > max_upload_size_bytes {
+                    true => Err(too_large(max_upload_size_bytes)),
+                    false => Ok(bytes.to_vec()),
+                }
+            }
+            Err(e) => Err(ServiceError::new(AppErrorKind::BadClientRequest, e.to_string())),
+        };
+
+        let is_error = piece.is_err();
+
+        //the upload stops reading once it has rejected the file, so there is no one left to send to
+        if sender.send(piece).await.is_err() || is_error {
+            return;
+        }
+    }
+}
+
+fn too_large(max_upload_size_bytes: usize) -> ServiceError {
+    RejectionReason::FileTooLarge.error(format!(
+        "the upload is larger than the maximum of {} bytes",
+        max_upload_size_bytes
+    ))
+}
@@ -0,0 +1,172 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::future::Future;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use actix_web::{
+    body::{BoxBody, MessageBody},
+    dev::{Service, ServiceRequest, ServiceResponse},
+    http::{header::RETRY_AFTER, Method, StatusCode},
+    web::Data,
+    Error, HttpMessage, HttpResponse, ResponseError,
+};
+
+use crate::internal::models::entities::{
+    authenticated_user::AuthenticatedUser, rejection_reason::RejectionReason,
+};
+
+//never ask a client to wait longer than this, even with a tiny refill rate
+const MAX_RETRY_AFTER_SECS: f64 = 3600.0;
+
+//how often, at most, the buckets are swept for idle users
+const EVICTION_INTERVAL: Duration = Duration::from_secs(60);
+
+struct TokenBucket {
+    tokens: f64,
+    last_refill: Instant,
+}
+
+//a token bucket per user: each request takes a token, tokens refill at a fixed rate up to the capacity
+pub struct RateLimiter {
+    capacity: f64,
+    refill_per_second: f64,
+    buckets: Mutex<HashMap<String, TokenBucket>>,
+    last_eviction: Mutex<Option<Instant>>,
+}
+
+impl RateLimiter {
+    pub fn new(capacity: u32, refill_per_second: f64) -> Self {
+        RateLimiter {
+            capacity: capacity as f64,
+            refill_per_second: refill_per_second.max(0.0),
+            buckets: Mutex::new(HashMap::new()),
+            last_eviction: Mutex::new(None),
+        }
+    }
+
+    #[cfg(test)]
+    pub fn tracked_users(&self) -> usize {
+        return self.buckets.lock().unwrap().len();
+    }
+
+    pub fn try_acquire(&self, user_id: &str) -> Result<(), Duration> {
+        self.try_acquire_at(user_id, Instant::now())
+    }
+
+    //returns how long to wait for the next token when the bucket is empty
+    pub fn try_acquire_at(&self, user_id: &str, now: Instant) -> Result<(), Duration> {
+        let mut buckets = self.buckets.lock().unwrap();
+        self.evict_idle_buckets(&mut buckets, now);
+
+        let bucket = buckets
+            .entry(String::from(user_id))
+            .or_insert(TokenBucket {
+                tokens: self.capacity,
+                last_refill: now,
+            });
+
+        let elapsed = now.saturating_duration_since(bucket.last_refill).as_secs_f64();
+        bucket.tokens = (bucket.tokens + elapsed * self.refill_per_second).min(self.capacity);
+        bucket.last_refill = now;
+
+        if bucket.tokens >= 1.0 {
+            bucket.tokens -= 1.0;
+            return Ok(());
+        }
+
+        let wait_secs = ((1.0 - bucket.tokens) / self.refill_per_second).min(MAX_RETRY_AFTER_SECS);
+        Err(Duration::from_secs_f64(wait_secs))
+    }
+
+    //a bucket that has refilled to capacity is no different from a new one, so it can be dropped
+    //without this every user that ever made a request would keep a bucket for the life of the process
+    fn evict_idle_buckets(&self, buckets: &mut HashMap<String, TokenBucket>, now: Instant) {
+        let mut last_eviction = self.last_eviction.lock().unwrap();
+        let is_due = match *last_eviction {
+            Some(last_eviction) => now.saturating_duration_since(last_eviction) >= EVICTION_INTERVAL,
+            None => true,
+        };
+
+        if !is_due {
+            return;
+        }
+
+        *last_eviction = Some(now);
+        buckets.retain(|_, bucket| {
+            let elapsed = now.saturating_duration_since(bucket.last_refill).as_secs_f64();
+            bucket.tokens + elapsed * self.refill_per_second < self.capacity
+        });
+    }
+}
+
+#[derive(Debug)]
+pub struct RateLimitExceeded {
+    pub retry_after: Duration,
+}
+
+impl fmt::Display for RateLimitExceeded {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "too many requests, retry after {:?}", self.retry_after)
+    }
+}
+
+impl ResponseError for RateLimitExceeded {
+    fn status_code(&self) -> StatusCode {
+        StatusCode::TOO_MANY_REQUESTS
+    }
+
+    fn error_response(&self) -> HttpResponse {
+        //Retry-After is in whole seconds, so round up to never invite an early retry
+        let retry_after_secs = self.retry_after.as_secs_f64().ceil().max(1.0) as u64;
+
+        HttpResponse::TooManyRequests()
+            .insert_header((RETRY_AFTER, retry_after_secs.to_string()))
+            .json(RejectionReason::RateLimited.error(self.to_string()))
+    }
+}
+
+//wrap_fn middleware, must run after authentication since buckets are per user
+//reads are never limited, and apps without a RateLimiter are not limited at all
+//a limited request is answered here rather than returned as an error, so it never reaches the handler
+pub fn enforce_rate_limit<S, B>(
+    req: ServiceRequest,
+    srv: &S,
+) -> impl Future<Output = Result<ServiceResponse<BoxBody>, Error>>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error>,
+    B: MessageBody + 'static,
+{
+    let outcome = match check_rate_limit(&req) {
+        Ok(()) => Ok(srv.call(req)),
+        Err(e) => Err(req.into_response(e.error_response())),
+    };
+
+    async move {
+        match outcome {
+            Ok(response) => response.await.map(ServiceResponse::map_into_boxed_body),
+            Err(limited_response) => Ok(limited_response),
+        }
+    }
+}
+
+fn check_rate_limit(req: &ServiceRequest) -> Result<(), RateLimitExceeded> {
+    let method = req.method();
+    if method == Method::GET || method == Method::HEAD || method == Method::OPTIONS {
+        return Ok(());
+    }
+
+    let rate_limiter = match req.app_data::<Data<RateLimiter>>() {
+        Some(rate_limiter) => rate_limiter,
+        None => return Ok(()),
+    };
+
+    let user_id = match req.extensions().get::<AuthenticatedUser>() {
+        Some(user) => user.user_id.clone(),
+        None => return Ok(()),
+    };
+
+    rate_limiter
+        .try_acquire(&user_id)
+        .map_err(|retry_after| RateLimitExceeded { retry_after })
+}
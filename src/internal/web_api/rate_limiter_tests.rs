@@ -0,0 +1,172 @@
+use std::time::{Duration, Instant};
+
+use actix_web::{
+    App,
+    get,
+    http::{header::RETRY_AFTER, StatusCode},
+    post,
+    test::{self, TestRequest},
+    web::{self, Data},
+    HttpResponse,
+};
+use actix_web_httpauth::middleware::HttpAuthentication;
+
+use crate::internal::web_api::{
+    auth::validate_bearer_token,
+    rate_limiter::{enforce_rate_limit, RateLimiter},
+    test_tokens::{bearer_token_for, test_token_validator},
+};
+
+#[test]
+fn given_tokens_left_try_acquire_allows_request() {
+    //setup
+    let rate_limiter = RateLimiter::new(2, 1.0);
+    let now = Instant::now();
+
+    //act
+    let first = rate_limiter.try_acquire_at("user-1", now);
+    let second = rate_limiter.try_acquire_at("user-1", now);
+
+    //assert
+    assert!(first.is_ok());
+    assert!(second.is_ok());
+}
+
+#[test]
+fn given_empty_bucket_try_acquire_returns_time_until_next_token() {
+    //setup
+    let rate_limiter = RateLimiter::new(1, 0.5);
+    let now = Instant::now();
+    rate_limiter.try_acquire_at("user-1", now).unwrap();
+
+    //act
+    let result = rate_limiter.try_acquire_at("user-1", now);
+
+    //assert
+    assert_eq!(result.err().unwrap(), Duration::from_secs(2));
+}
+
+#[test]
+fn given_time_has_passed_try_acquire_refills_bucket() {
+    //setup
+    let rate_limiter = RateLimiter::new(1, 1.0);
+    let now = Instant::now();
+    rate_limiter.try_acquire_at("user-1", now).unwrap();
+
+    //act
+    let result = rate_limiter.try_acquire_at("user-1", now + Duration::from_secs(1));
+
+    //assert
+    assert!(result.is_ok());
+}
+
+#[test]
+fn given_one_user_is_limited_try_acquire_still_allows_other_users() {
+    //setup
+    let rate_limiter = RateLimiter::new(1, 1.0);
+    let now = Instant::now();
+    rate_limiter.try_acquire_at("user-1", now).unwrap();
+
+    //act
+    let result = rate_limiter.try_acquire_at("user-2", now);
+
+    //assert
+    assert!(result.is_ok());
+}
+
+#[test]
+fn given_buckets_that_refilled_try_acquire_evicts_them() {
+    //setup
+    let rate_limiter = RateLimiter::new(2, 0.02);
+    let now = Instant::now();
+    rate_limiter.try_acquire_at("user-1", now).unwrap();
+    rate_limiter.try_acquire_at("user-2", now).unwrap();
+    rate_limiter.try_acquire_at("user-2", now).unwrap();
+
+    //act
+    rate_limiter
+        .try_acquire_at("user-3", now + Duration::from_secs(61))
+        .unwrap();
+
+    //assert
+    //user-1 is back to a full bucket, user-2 is still refilling
+    assert_eq!(rate_limiter.tracked_users(), 2);
+}
+
+#[actix_web::test]
+async fn test_mutating_request_over_the_limit_returns_too_many_requests_with_retry_after() {
+    //setup
+    let app = test::init_service(setup_app(RateLimiter::new(1, 0.1))).await;
+
+    //act
+    TestRequest::post()
+        .uri("/mutate")
+        .insert_header(("Authorization", bearer_token_for("user-1")))
+        .send_request(&app)
+        .await;
+
+    let resp = TestRequest::post()
+        .uri("/mutate")
+        .insert_header(("Authorization", bearer_token_for("user-1")))
+        .send_request(&app)
+        .await;
+
+    //assert
+    assert_eq!(resp.status(), StatusCode::TOO_MANY_REQUESTS);
+    assert_eq!(resp.headers().get(RETRY_AFTER).unwrap(), "10");
+}
+
+#[actix_web::test]
+async fn test_read_requests_are_not_rate_limited() {
+    //setup
+    let app = test::init_service(setup_app(RateLimiter::new(1, 0.1))).await;
+
+    //act
+    TestRequest::get()
+        .uri("/read")
+        .insert_header(("Authorization", bearer_token_for("user-1")))
+        .send_request(&app)
+        .await;
+
+    let resp = TestRequest::get()
+        .uri("/read")
+        .insert_header(("Authorization", bearer_token_for("user-1")))
+        .send_request(&app)
+        .await;
+
+    //assert
+    assert!(resp.status().is_success());
+}
+
+#[post("/mutate")]
+async fn mutate() -> HttpResponse {
+    HttpResponse::Ok().finish()
+}
+
+#[get("/read")]
+async fn read() -> HttpResponse {
+    HttpResponse::Ok().finish()
+}
+
+fn setup_app(
+    rate_limiter: RateLimiter,
+) -> App<
+    impl actix_service::ServiceFactory<
+        actix_web::dev::ServiceRequest,
+        Config = (),
+        Response = actix_web::dev::ServiceResponse,
+        Error = actix_web::Error,
+        InitError = (),
+    >,
+> {
+    App::new()
+        .app_data(Data::new(test_token_validator()))
+        .app_data(Data::new(rate_limiter))
+        .service(
+            web::scope("")
+                .wrap_fn(enforce_rate_limit)
+                .wrap(HttpAuthentication::bearer(validate_bearer_token))
+                .service(mutate)
+                .service(read),
+        )
+}
@@ -1,13 +1,26 @@
-use std::sync::Mutex;
+use actix_web::error::InternalError;
+use actix_web::{App, HttpResponse, HttpServer};
+use actix_web::web::{self, Data, ServiceConfig};
+use actix_web_httpauth::middleware::HttpAuthentication;
 
-use actix_web::{App, HttpServer};
-use actix_web::web::Data;
-
-use crate::internal::interfaces::recon_tasks_aggregator::ReconTaskAggregationServiceInterface;
-use crate::internal::shared_reconciler_rust_libraries::models::entities::app_errors::AppError;
+use crate::external::dapr::dapr_client::connect_to_dapr;
+use crate::external::repositories::recon_file_details_repo::ReconFileDetailsRepositoryManager;
+use crate::external::repositories::recon_task_details_repo::ReconTaskDetailsRepositoryManager;
+use crate::internal::web_api::auth::{read_token_validator, validate_bearer_token};
+use crate::internal::web_api::dapr_subscriber::DaprSubscriberSettings;
+use crate::internal::web_api::multipart_upload::UploadFormConfig;
+use crate::internal::web_api::rate_limiter::{enforce_rate_limit, RateLimiter};
 use crate::internal::web_api::handlers::{
-    attach_comparison_file_to_task, attach_primary_file_to_task, create_task_details,
-    get_task_details,
+    accept_suggested_pairs, attach_comparison_file_to_task, attach_primary_file_to_task,
+    cancel_task, clone_task, complete_upload_session, create_task_details, create_template,
+    create_upload_session, delete_orphaned_files, delete_template, export_results,
+    force_task_status, get_suggested_pairs, get_task_details, get_task_history, get_template,
+    get_upload_session, list_all_tasks, list_dapr_subscriptions, list_templates, preview_file,
+    receive_recon_results, start_task, update_template, upload_comparison_file, upload_file_chunk,
+    upload_primary_file, verify_task_files,
+};
+use crate::internal::shared_reconciler_rust_libraries::models::entities::app_errors::{
+    AppError, AppErrorKind,
 };
 use crate::internal::web_api::utils;
 use crate::internal::web_api::utils::setup_service;
@@ -21,23 +34,136 @@ pub async fn run_async() -> Result<(), std::io::Error> {
     //just for logging purposes
     println!("App is listening on: {:?}", app_listen_url);
 
-    HttpServer::new(async move
-        {
-            let service = match setup_service().await {
-                Ok(s) => s,
-                Err(_) => {
-                    panic!("cant setup service")
-                }
-            };
-            App::new() // add shared state
-                .app_data(Data::new(Mutex::new(service)))
-                .service(get_task_details)
-                .service(create_task_details)
-                .service(attach_primary_file_to_task)
-                .service(attach_comparison_file_to_task)
-        })
+    let service = match setup_service(&app_settings).await {
+        Ok(s) => Data::new(s),
+        Err(e) => {
+            panic!("cant setup service: {}", e.message)
+        }
+    };
+
+    let token_validator = match read_token_validator(&app_settings) {
+        Ok(v) => Data::new(v),
+        Err(e) => {
+            panic!("cant setup token validation: {}", e.message)
+        }
+    };
+
+    let rate_limiter = Data::new(RateLimiter::new(
+        app_settings.rate_limit_burst,
+        app_settings.rate_limit_refill_per_second,
+    ));
+
+    let subscriber_settings = Data::new(DaprSubscriberSettings::new(&app_settings));
+    if subscriber_settings.app_api_token.is_none() {
+        println!("APP_API_TOKEN is not set, reconciliation results from the sidecar will be turned away");
+    }
+
+    let upload_limits = upload_form_config(app_settings.max_upload_size_bytes);
+    let chunk_limits = upload_chunk_config(app_settings.max_upload_size_bytes);
+
+    HttpServer::new(move || {
+        App::new() // add shared state
+            .app_data(service.clone())
+            .app_data(token_validator.clone())
+            .app_data(rate_limiter.clone())
+            .app_data(subscriber_settings.clone())
+            .app_data(upload_limits.clone())
+            .app_data(chunk_limits.clone())
+            .configure(configure_routes)
+    })
         .bind(app_listen_url)?
         .run()
         .await
 }
 
+//rewrites every stored task and file record to the latest schema version
+pub async fn run_migrations_async() -> Result<(), std::io::Error> {
+    let app_settings = utils::read_app_settings();
+
+    let migration_result = async {
+        let recon_tasks_repo = ReconTaskDetailsRepositoryManager::new(
+            app_settings.dapr_state_store_name.clone(),
+            connect_to_dapr(&app_settings.dapr_grpc_server_address).await?,
+            app_settings.dapr_retryable_status_codes.clone(),
+        );
+
+        let recon_files_repo = ReconFileDetailsRepositoryManager::new(
+            app_settings.dapr_state_store_name.clone(),
+            connect_to_dapr(&app_settings.dapr_grpc_server_address).await?,
+            app_settings.dapr_retryable_status_codes.clone(),
+        );
+
+        let tasks_report = recon_tasks_repo.migrate_records().await?;
+        println!("Recon tasks migration: {:?}", tasks_report);
+
+        let files_report = recon_files_repo.migrate_records().await?;
+        println!("Recon files migration: {:?}", files_report);
+
+        Ok(())
+    };
+
+    return migration_result
+        .await
+        .map_err(|e: AppError| std::io::Error::other(e.message));
+}
+
+//every route requires a valid bearer token, the caller is read from its claims
+//the last wrap runs first, so the rate limit sees the authenticated user
+//the sidecar's routes check its app api token instead and must come before the scope, which takes every path
+pub(crate) fn configure_routes(cfg: &mut ServiceConfig) {
+    cfg.app_data(path_config());
+
+    cfg.service(list_dapr_subscriptions)
+        .service(receive_recon_results);
+
+    cfg.service(
+        web::scope("")
+            .wrap_fn(enforce_rate_limit)
+            .wrap(HttpAuthentication::bearer(validate_bearer_token))
+            .service(get_task_details)
+            .service(get_task_history)
+            .service(create_task_details)
+            .service(attach_primary_file_to_task)
+            .service(attach_comparison_file_to_task)
+            .service(upload_primary_file)
+            .service(upload_comparison_file)
+            .service(create_upload_session)
+            .service(upload_file_chunk)
+            .service(get_upload_session)
+            .service(complete_upload_session)
+            .service(verify_task_files)
+            .service(preview_file)
+            .service(start_task)
+            .service(export_results)
+            .service(cancel_task)
+            .service(clone_task)
+            .service(get_suggested_pairs)
+            .service(accept_suggested_pairs)
+            .service(list_all_tasks)
+            .service(force_task_status)
+            .service(delete_orphaned_files)
+            .service(create_template)
+            .service(list_templates)
+            .service(get_template)
+            .service(update_template)
+            .service(delete_template),
+    );
+}
+
+//uploaded files are streamed to the blob store, so the limit is on what is read rather than what is held
+pub(crate) fn upload_form_config(max_upload_size_bytes: usize) -> Data<UploadFormConfig> {
+    Data::new(UploadFormConfig { max_upload_size_bytes })
+}
+
+//a chunk is read into memory whole, so it can be no larger than a single upload
+pub(crate) fn upload_chunk_config(max_upload_size_bytes: usize) -> web::PayloadConfig {
+    web::PayloadConfig::new(max_upload_size_bytes)
+}
+
+//a path that fails to deserialize is a 404 by default, but a malformed id is the caller's mistake
+pub(crate) fn path_config() -> web::PathConfig {
+    web::PathConfig::default().error_handler(|err, _req| {
+        let app_error = AppError::new(AppErrorKind::BadClientRequest, err.to_string());
+        InternalError::from_response(err, HttpResponse::BadRequest().json(app_error)).into()
+    })
+}
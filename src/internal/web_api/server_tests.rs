@@ -0,0 +1,2091 @@
+use actix_web::{
+    App,
+    http::StatusCode,
+    test::{self, TestRequest},
+    web::Data,
+};
+use serde_json::{json, Value};
+use tonic::Code;
+
+use crate::external::dapr::fake_dapr_sidecar::FakeDaprSidecar;
+
+use super::dapr_subscriber::{DaprSubscriberSettings, DAPR_API_TOKEN_HEADER};
+use super::rate_limiter::RateLimiter;
+use super::server::{configure_routes, upload_chunk_config, upload_form_config};
+use super::test_tokens::{bearer_token_for, bearer_token_with_roles, test_token_validator};
+use super::test_uploads::{multipart_content_type, multipart_file_body};
+use super::utils::{read_app_settings, setup_service};
+
+const TEST_USER_ID: &str = "test-user-id";
+
+//what the sidecar sends on its calls to the app
+const TEST_SIDECAR_TOKEN: &str = "test-sidecar-token";
+
+//the sha-256 of id;amount\n1;10\n2;20\n
+const PAYMENTS_FILE_HASH: &str =
+    "c4c0b4fa35961fe406eeb87f86ad9de64f405b992dd6e76332748b4d5e2c80f1";
+
+#[actix_web::test]
+async fn test_create_then_get_recon_task_round_trips_through_the_state_store() {
+    //setup
+    let sidecar = FakeDaprSidecar::new();
+    let app = test::init_service(setup_app(&sidecar).await).await;
+
+    //act
+    let created: Value = test::read_body_json(
+        TestRequest::post()
+            .uri("/recon-tasks")
+            .insert_header(("Authorization", bearer_token_for(TEST_USER_ID)))
+            .set_json(get_dummy_create_recon_task_json())
+            .send_request(&app)
+            .await,
+    )
+    .await;
+
+    let task_id = created["task_id"].as_str().unwrap();
+
+    let resp = TestRequest::get()
+        .uri(&format!("/recon-tasks/{}", task_id))
+        .insert_header(("Authorization", bearer_token_for(TEST_USER_ID)))
+        .send_request(&app)
+        .await;
+
+    //assert
+    assert!(resp.status().is_success());
+    assert!(task_id.starts_with("RECON-TASK-"));
+}
+
+#[actix_web::test]
+async fn test_attach_primary_file_links_file_metadata_to_task() {
+    //setup
+    let sidecar = FakeDaprSidecar::new();
+    let app = test::init_service(setup_app(&sidecar).await).await;
+
+    let created: Value = test::read_body_json(
+        TestRequest::post()
+            .uri("/recon-tasks")
+            .insert_header(("Authorization", bearer_token_for(TEST_USER_ID)))
+            .set_json(get_dummy_create_recon_task_json())
+            .send_request(&app)
+            .await,
+    )
+    .await;
+    let task_id = created["task_id"].as_str().unwrap();
+
+    //act
+    let attach_resp = TestRequest::post()
+        .uri("/recon-tasks/attach-primary-file")
+        .insert_header(("Authorization", bearer_token_for(TEST_USER_ID)))
+        .set_json(json!({
+            "task_id": task_id,
+            "primary_file_name": "primary.csv",
+            "primary_file_hash": "primary-hash",
+            "primary_file_row_count": 10,
+            "primary_file_headers": ["id", "amount"],
+            "primary_file_delimiters": [","]
+        }))
+        .send_request(&app)
+        .await;
+
+    let task: Value = test::read_body_json(
+        TestRequest::get()
+            .uri(&format!("/recon-tasks/{}", task_id))
+            .insert_header(("Authorization", bearer_token_for(TEST_USER_ID)))
+            .send_request(&app)
+            .await,
+    )
+    .await;
+
+    //assert
+    assert!(attach_resp.status().is_success());
+    assert_eq!(task["primary_file_metadata"]["file_hash"], "primary-hash");
+}
+
+#[actix_web::test]
+async fn test_attach_primary_file_stores_its_format_descriptor() {
+    //setup
+    let sidecar = FakeDaprSidecar::new();
+    let app = test::init_service(setup_app(&sidecar).await).await;
+
+    let created: Value = test::read_body_json(
+        TestRequest::post()
+            .uri("/recon-tasks")
+            .insert_header(("Authorization", bearer_token_for(TEST_USER_ID)))
+            .set_json(get_dummy_create_recon_task_json())
+            .send_request(&app)
+            .await,
+    )
+    .await;
+    let task_id = created["task_id"].as_str().unwrap();
+
+    let file_format = json!({
+        "layout": {"type": "fixed_width", "column_offsets": [0, 12]},
+        "encoding": "windows-1252",
+        "has_header_row": true,
+        "rows_to_skip": 3
+    });
+
+    //act
+    let attach_resp = TestRequest::post()
+        .uri("/recon-tasks/attach-primary-file")
+        .insert_header(("Authorization", bearer_token_for(TEST_USER_ID)))
+        .set_json(json!({
+            "task_id": task_id,
+            "primary_file_name": "primary.txt",
+            "primary_file_hash": "primary-hash",
+            "primary_file_row_count": 10,
+            "primary_file_headers": ["id", "amount"],
+            "primary_file_delimiters": [],
+            "primary_file_format": file_format
+        }))
+        .send_request(&app)
+        .await;
+
+    let task: Value = test::read_body_json(
+        TestRequest::get()
+            .uri(&format!("/recon-tasks/{}", task_id))
+            .insert_header(("Authorization", bearer_token_for(TEST_USER_ID)))
+            .send_request(&app)
+            .await,
+    )
+    .await;
+
+    //assert
+    assert!(attach_resp.status().is_success());
+    assert_eq!(task["primary_file_metadata"]["format"], file_format);
+}
+
+#[actix_web::test]
+async fn test_attach_primary_file_with_unsupported_encoding_returns_bad_request() {
+    //setup
+    let sidecar = FakeDaprSidecar::new();
+    let app = test::init_service(setup_app(&sidecar).await).await;
+
+    let created: Value = test::read_body_json(
+        TestRequest::post()
+            .uri("/recon-tasks")
+            .insert_header(("Authorization", bearer_token_for(TEST_USER_ID)))
+            .set_json(get_dummy_create_recon_task_json())
+            .send_request(&app)
+            .await,
+    )
+    .await;
+
+    //act
+    let attach_resp = TestRequest::post()
+        .uri("/recon-tasks/attach-primary-file")
+        .insert_header(("Authorization", bearer_token_for(TEST_USER_ID)))
+        .set_json(json!({
+            "task_id": created["task_id"],
+            "primary_file_name": "primary.csv",
+            "primary_file_hash": "primary-hash",
+            "primary_file_row_count": 10,
+            "primary_file_headers": ["id", "amount"],
+            "primary_file_delimiters": [","],
+            "primary_file_format": {"encoding": "ebcdic"}
+        }))
+        .send_request(&app)
+        .await;
+
+    //assert
+    assert_eq!(attach_resp.status(), StatusCode::BAD_REQUEST);
+}
+
+#[actix_web::test]
+async fn test_attaching_files_with_column_schemas_reports_pairs_of_incompatible_types() {
+    //setup
+    let sidecar = FakeDaprSidecar::new();
+    let app = test::init_service(setup_app(&sidecar).await).await;
+
+    let mut create_task_json = get_dummy_create_recon_task_json();
+    create_task_json["comparison_pairs"] = json!([
+        {"primary_file_column_index": 0, "comparison_file_column_index": 0, "is_row_identifier": true},
+        {"primary_file_column_index": 1, "comparison_file_column_index": 1}
+    ]);
+
+    let created: Value = test::read_body_json(
+        TestRequest::post()
+            .uri("/recon-tasks")
+            .insert_header(("Authorization", bearer_token_for(TEST_USER_ID)))
+            .set_json(&create_task_json)
+            .send_request(&app)
+            .await,
+    )
+    .await;
+    let task_id = created["task_id"].as_str().unwrap();
+
+    //act
+    TestRequest::post()
+        .uri("/recon-tasks/attach-primary-file")
+        .insert_header(("Authorization", bearer_token_for(TEST_USER_ID)))
+        .set_json(json!({
+            "task_id": task_id,
+            "primary_file_name": "primary.csv",
+            "primary_file_hash": "primary-hash",
+            "primary_file_row_count": 10,
+            "primary_file_headers": ["id", "amount"],
+            "primary_file_delimiters": [","],
+            "primary_file_column_schema": [
+                {"column_type": "string"},
+                {"column_type": "decimal", "nullable": true}
+            ]
+        }))
+        .send_request(&app)
+        .await;
+
+    TestRequest::post()
+        .uri("/recon-tasks/attach-comparison-file")
+        .insert_header(("Authorization", bearer_token_for(TEST_USER_ID)))
+        .set_json(json!({
+            "task_id": task_id,
+            "comparison_file_name": "comparison.csv",
+            "comparison_file_hash": "comparison-hash",
+            "comparison_file_row_count": 10,
+            "comparison_file_headers": ["reference", "value_date"],
+            "comparison_file_delimiters": [","],
+            "comparison_file_column_schema": [
+                {"column_type": "string"},
+                {"column_type": "date", "format": "%Y-%m-%d"}
+            ]
+        }))
+        .send_request(&app)
+        .await;
+
+    let task: Value = test::read_body_json(
+        TestRequest::get()
+            .uri(&format!("/recon-tasks/{}", task_id))
+            .insert_header(("Authorization", bearer_token_for(TEST_USER_ID)))
+            .send_request(&app)
+            .await,
+    )
+    .await;
+
+    //assert
+    assert_eq!(task["primary_file_metadata"]["column_schema"][1]["column_type"], "decimal");
+    assert_eq!(
+        task["task_details"]["column_type_mismatches"],
+        json!([{
+            "primary_file_column_index": 1,
+            "primary_file_column_type": "decimal",
+            "comparison_file_column_index": 1,
+            "comparison_file_column_type": "date"
+        }])
+    );
+}
+
+#[actix_web::test]
+async fn test_uploading_primary_file_stores_it_in_the_blob_binding_and_attaches_its_metadata() {
+    //setup
+    let sidecar = FakeDaprSidecar::new();
+    let app = test::init_service(setup_app(&sidecar).await).await;
+    let content = b"id;amount\n1;10\n2;20\n";
+
+    let created: Value = test::read_body_json(
+        TestRequest::post()
+            .uri("/recon-tasks")
+            .insert_header(("Authorization", bearer_token_for(TEST_USER_ID)))
+            .set_json(get_dummy_create_recon_task_json())
+            .send_request(&app)
+            .await,
+    )
+    .await;
+    let task_id = created["task_id"].as_str().unwrap();
+
+    //act
+    let upload_resp = TestRequest::post()
+        .uri(&format!("/recon-tasks/{}/files/primary/upload", task_id))
+        .insert_header(("Authorization", bearer_token_for(TEST_USER_ID)))
+        .insert_header(("Content-Type", multipart_content_type()))
+        .set_payload(multipart_file_body("payments.csv", content))
+        .send_request(&app)
+        .await;
+    let upload_status = upload_resp.status();
+
+    let task: Value = test::read_body_json(
+        TestRequest::get()
+            .uri(&format!("/recon-tasks/{}", task_id))
+            .insert_header(("Authorization", bearer_token_for(TEST_USER_ID)))
+            .send_request(&app)
+            .await,
+    )
+    .await;
+
+    //assert
+    let primary_file = &task["primary_file_metadata"];
+    let stored_file_name = primary_file["stored_file_parts"][0].as_str().unwrap();
+
+    assert!(upload_status.is_success());
+    assert_eq!(primary_file["file_name"], "payments.csv");
+    assert_eq!(primary_file["file_hash"], PAYMENTS_FILE_HASH);
+    assert_eq!(primary_file["row_count"], 3);
+    assert_eq!(primary_file["column_headers"], json!(["id", "amount"]));
+    assert_eq!(primary_file["column_delimiters"], json!([";"]));
+    assert_eq!(
+        sidecar.get_binding_file("blobstore", stored_file_name),
+        Some(content.to_vec())
+    );
+}
+
+#[actix_web::test]
+async fn test_resumable_upload_takes_chunks_in_any_order_and_attaches_the_file_on_completion() {
+    //setup
+    let sidecar = FakeDaprSidecar::new();
+    let app = test::init_service(setup_app(&sidecar).await).await;
+    let content = b"id;amount\n1;10\n2;20\n";
+
+    let created: Value = test::read_body_json(
+        TestRequest::post()
+            .uri("/recon-tasks")
+            .insert_header(("Authorization", bearer_token_for(TEST_USER_ID)))
+            .set_json(get_dummy_create_recon_task_json())
+            .send_request(&app)
+            .await,
+    )
+    .await;
+    let task_id = created["task_id"].as_str().unwrap();
+
+    //act
+    let session: Value = test::read_body_json(
+        TestRequest::post()
+            .uri(&format!("/recon-tasks/{}/files/primary/uploads", task_id))
+            .insert_header(("Authorization", bearer_token_for(TEST_USER_ID)))
+            .set_json(json!({
+                "file_name": "payments.csv",
+                "total_size_bytes": content.len(),
+                "chunk_size_bytes": 8
+            }))
+            .send_request(&app)
+            .await,
+    )
+    .await;
+    let upload_id = session["upload_id"].as_str().unwrap();
+
+    for chunk_number in [2, 0] {
+        let chunk_start = chunk_number * 8;
+        let chunk_end = (chunk_start + 8).min(content.len());
+        let chunk_resp = TestRequest::put()
+            .uri(&format!(
+                "/recon-tasks/{}/uploads/{}/chunks/{}",
+                task_id, upload_id, chunk_number
+            ))
+            .insert_header(("Authorization", bearer_token_for(TEST_USER_ID)))
+            .set_payload(content[chunk_start..chunk_end].to_vec())
+            .send_request(&app)
+            .await;
+        assert!(chunk_resp.status().is_success());
+    }
+
+    let partial_upload: Value = test::read_body_json(
+        TestRequest::get()
+            .uri(&format!("/recon-tasks/{}/uploads/{}", task_id, upload_id))
+            .insert_header(("Authorization", bearer_token_for(TEST_USER_ID)))
+            .send_request(&app)
+            .await,
+    )
+    .await;
+
+    let early_completion_status = TestRequest::post()
+        .uri(&format!("/recon-tasks/{}/uploads/{}/complete", task_id, upload_id))
+        .insert_header(("Authorization", bearer_token_for(TEST_USER_ID)))
+        .set_json(json!({ "file_hash": PAYMENTS_FILE_HASH }))
+        .send_request(&app)
+        .await
+        .status();
+
+    let last_chunk_status = TestRequest::put()
+        .uri(&format!("/recon-tasks/{}/uploads/{}/chunks/1", task_id, upload_id))
+        .insert_header(("Authorization", bearer_token_for(TEST_USER_ID)))
+        .set_payload(content[8..16].to_vec())
+        .send_request(&app)
+        .await
+        .status();
+
+    let completion_status = TestRequest::post()
+        .uri(&format!("/recon-tasks/{}/uploads/{}/complete", task_id, upload_id))
+        .insert_header(("Authorization", bearer_token_for(TEST_USER_ID)))
+        .set_json(json!({ "file_hash": PAYMENTS_FILE_HASH }))
+        .send_request(&app)
+        .await
+        .status();
+
+    let task: Value = test::read_body_json(
+        TestRequest::get()
+            .uri(&format!("/recon-tasks/{}", task_id))
+            .insert_header(("Authorization", bearer_token_for(TEST_USER_ID)))
+            .send_request(&app)
+            .await,
+    )
+    .await;
+
+    //assert
+    assert_eq!(session["chunk_count"], 3);
+    assert_eq!(
+        partial_upload["received_ranges"],
+        json!([
+            { "first_byte": 0, "last_byte": 7 },
+            { "first_byte": 16, "last_byte": 19 }
+        ])
+    );
+    assert_eq!(partial_upload["missing_chunks"], json!([1]));
+    assert_eq!(early_completion_status, StatusCode::BAD_REQUEST);
+    assert!(last_chunk_status.is_success());
+    assert!(completion_status.is_success());
+
+    let primary_file = &task["primary_file_metadata"];
+    assert_eq!(primary_file["file_hash"], PAYMENTS_FILE_HASH);
+    assert_eq!(primary_file["row_count"], 3);
+    assert_eq!(primary_file["column_headers"], json!(["id", "amount"]));
+
+    let stored_content: Vec<u8> = primary_file["stored_file_parts"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .flat_map(|part| {
+            sidecar
+                .get_binding_file("blobstore", part.as_str().unwrap())
+                .unwrap()
+        })
+        .collect();
+    assert_eq!(stored_content, content.to_vec());
+}
+
+#[actix_web::test]
+async fn test_uploading_both_files_verifies_them_and_readies_the_task() {
+    //setup
+    let sidecar = FakeDaprSidecar::new();
+    let app = test::init_service(setup_app(&sidecar).await).await;
+
+    let created: Value = test::read_body_json(
+        TestRequest::post()
+            .uri("/recon-tasks")
+            .insert_header(("Authorization", bearer_token_for(TEST_USER_ID)))
+            .set_json(get_dummy_create_recon_task_json())
+            .send_request(&app)
+            .await,
+    )
+    .await;
+    let task_id = created["task_id"].as_str().unwrap();
+
+    //act
+    for (file_role, file_name) in [("primary", "payments.csv"), ("comparison", "ledger.csv")] {
+        let upload_resp = TestRequest::post()
+            .uri(&format!("/recon-tasks/{}/files/{}/upload", task_id, file_role))
+            .insert_header(("Authorization", bearer_token_for(TEST_USER_ID)))
+            .insert_header(("Content-Type", multipart_content_type()))
+            .set_payload(multipart_file_body(file_name, b"id;amount\n1;10\n2;20\n"))
+            .send_request(&app)
+            .await;
+        assert!(upload_resp.status().is_success());
+    }
+
+    let task: Value = test::read_body_json(
+        TestRequest::get()
+            .uri(&format!("/recon-tasks/{}", task_id))
+            .insert_header(("Authorization", bearer_token_for(TEST_USER_ID)))
+            .send_request(&app)
+            .await,
+    )
+    .await;
+
+    //assert
+    assert_eq!(created["task_details"]["status"], "awaiting_files");
+    assert_eq!(task["task_details"]["status"], "ready");
+    assert_eq!(task["primary_file_metadata"]["verification"]["status"], "verified");
+    assert_eq!(task["comparison_file_metadata"]["verification"]["status"], "verified");
+}
+
+#[actix_web::test]
+async fn test_verify_task_files_checks_metadata_attached_files_against_the_blob_binding() {
+    //setup
+    let sidecar = FakeDaprSidecar::new();
+    let app = test::init_service(setup_app(&sidecar).await).await;
+
+    let created: Value = test::read_body_json(
+        TestRequest::post()
+            .uri("/recon-tasks")
+            .insert_header(("Authorization", bearer_token_for(TEST_USER_ID)))
+            .set_json(get_dummy_create_recon_task_json())
+            .send_request(&app)
+            .await,
+    )
+    .await;
+    let task_id = created["task_id"].as_str().unwrap();
+
+    let _ = TestRequest::post()
+        .uri("/recon-tasks/attach-primary-file")
+        .insert_header(("Authorization", bearer_token_for(TEST_USER_ID)))
+        .set_json(json!({
+            "task_id": task_id,
+            "primary_file_name": "payments.csv",
+            "primary_file_hash": PAYMENTS_FILE_HASH,
+            "primary_file_row_count": 3,
+            "primary_file_headers": ["id", "amount"],
+            "primary_file_delimiters": [";"]
+        }))
+        .send_request(&app)
+        .await;
+
+    let _ = TestRequest::post()
+        .uri("/recon-tasks/attach-comparison-file")
+        .insert_header(("Authorization", bearer_token_for(TEST_USER_ID)))
+        .set_json(json!({
+            "task_id": task_id,
+            "comparison_file_name": "ledger.csv",
+            "comparison_file_hash": PAYMENTS_FILE_HASH,
+            "comparison_file_row_count": 3,
+            "comparison_file_headers": ["id", "amount"],
+            "comparison_file_delimiters": [";"]
+        }))
+        .send_request(&app)
+        .await;
+
+    //files attached with their metadata only are looked for under their name within the task
+    sidecar.put_binding_file(
+        "blobstore",
+        &format!("{}-payments.csv", task_id),
+        b"id;amount\n1;10\n2;20\n".to_vec(),
+    );
+    sidecar.put_binding_file(
+        "blobstore",
+        &format!("{}-ledger.csv", task_id),
+        b"id;amount\n1;10\n".to_vec(),
+    );
+
+    //act
+    let verify_resp = TestRequest::post()
+        .uri(&format!("/recon-tasks/{}/files/verify", task_id))
+        .insert_header(("Authorization", bearer_token_for(TEST_USER_ID)))
+        .send_request(&app)
+        .await;
+    let verify_status = verify_resp.status();
+    let task: Value = test::read_body_json(verify_resp).await;
+
+    //assert
+    assert!(verify_status.is_success());
+    assert_eq!(task["primary_file_metadata"]["verification"]["status"], "verified");
+    assert_eq!(task["comparison_file_metadata"]["verification"]["status"], "mismatch");
+    assert_eq!(task["comparison_file_metadata"]["verification"]["actual_row_count"], 2);
+    assert_eq!(task["task_details"]["status"], "awaiting_files");
+}
+
+#[actix_web::test]
+async fn test_verify_task_files_leaves_files_outside_the_task_unread_and_pending() {
+    //setup
+    let sidecar = FakeDaprSidecar::new();
+    let app = test::init_service(setup_app(&sidecar).await).await;
+
+    let created: Value = test::read_body_json(
+        TestRequest::post()
+            .uri("/recon-tasks")
+            .insert_header(("Authorization", bearer_token_for(TEST_USER_ID)))
+            .set_json(get_dummy_create_recon_task_json())
+            .send_request(&app)
+            .await,
+    )
+    .await;
+    let task_id = created["task_id"].as_str().unwrap();
+
+    let _ = TestRequest::post()
+        .uri("/recon-tasks/attach-primary-file")
+        .insert_header(("Authorization", bearer_token_for(TEST_USER_ID)))
+        .set_json(json!({
+            "task_id": task_id,
+            "primary_file_name": "payments.csv",
+            "primary_file_hash": PAYMENTS_FILE_HASH,
+            "primary_file_row_count": 3,
+            "primary_file_headers": ["id", "amount"],
+            "primary_file_delimiters": [";"]
+        }))
+        .send_request(&app)
+        .await;
+
+    //e.g. another task's file, the binding answers for a missing file the way the real sidecar does
+    sidecar.put_binding_file("blobstore", "payments.csv", b"id;amount\n1;10\n2;20\n".to_vec());
+
+    //act
+    let verify_resp = TestRequest::post()
+        .uri(&format!("/recon-tasks/{}/files/verify", task_id))
+        .insert_header(("Authorization", bearer_token_for(TEST_USER_ID)))
+        .send_request(&app)
+        .await;
+    let verify_status = verify_resp.status();
+    let task: Value = test::read_body_json(verify_resp).await;
+
+    //assert
+    assert!(verify_status.is_success());
+    assert_eq!(task["primary_file_metadata"]["verification"]["status"], "pending");
+}
+
+#[actix_web::test]
+async fn test_attach_primary_file_with_a_path_in_its_name_returns_bad_request() {
+    //setup
+    let sidecar = FakeDaprSidecar::new();
+    let app = test::init_service(setup_app(&sidecar).await).await;
+
+    let created: Value = test::read_body_json(
+        TestRequest::post()
+            .uri("/recon-tasks")
+            .insert_header(("Authorization", bearer_token_for(TEST_USER_ID)))
+            .set_json(get_dummy_create_recon_task_json())
+            .send_request(&app)
+            .await,
+    )
+    .await;
+    let task_id = created["task_id"].as_str().unwrap();
+
+    //act
+    let resp = TestRequest::post()
+        .uri("/recon-tasks/attach-primary-file")
+        .insert_header(("Authorization", bearer_token_for(TEST_USER_ID)))
+        .set_json(json!({
+            "task_id": task_id,
+            "primary_file_name": "../payments.csv",
+            "primary_file_hash": PAYMENTS_FILE_HASH,
+            "primary_file_row_count": 3,
+            "primary_file_headers": ["id", "amount"],
+            "primary_file_delimiters": [";"]
+        }))
+        .send_request(&app)
+        .await;
+
+    //assert
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+}
+
+#[actix_web::test]
+async fn test_start_recon_task_publishes_each_file_on_its_chunks_topic_and_locks_the_task() {
+    //setup
+    let sidecar = FakeDaprSidecar::new();
+    let app = test::init_service(setup_app(&sidecar).await).await;
+
+    let created: Value = test::read_body_json(
+        TestRequest::post()
+            .uri("/recon-tasks")
+            .insert_header(("Authorization", bearer_token_for(TEST_USER_ID)))
+            .set_json(get_dummy_create_recon_task_json())
+            .send_request(&app)
+            .await,
+    )
+    .await;
+    let task_id = created["task_id"].as_str().unwrap();
+    let task_details = &created["task_details"];
+
+    let early_start_resp = TestRequest::post()
+        .uri(&format!("/recon-tasks/{}/start", task_id))
+        .insert_header(("Authorization", bearer_token_for(TEST_USER_ID)))
+        .send_request(&app)
+        .await;
+
+    for (file_role, file_name) in [("primary", "payments.csv"), ("comparison", "ledger.csv")] {
+        let upload_resp = TestRequest::post()
+            .uri(&format!("/recon-tasks/{}/files/{}/upload", task_id, file_role))
+            .insert_header(("Authorization", bearer_token_for(TEST_USER_ID)))
+            .insert_header(("Content-Type", multipart_content_type()))
+            .set_payload(multipart_file_body(file_name, b"id;amount\n1;10\n2;20\n"))
+            .send_request(&app)
+            .await;
+        assert!(upload_resp.status().is_success());
+    }
+
+    //act
+    let started: Value = test::read_body_json(
+        TestRequest::post()
+            .uri(&format!("/recon-tasks/{}/start", task_id))
+            .insert_header(("Authorization", bearer_token_for(TEST_USER_ID)))
+            .send_request(&app)
+            .await,
+    )
+    .await;
+
+    let second_start_resp = TestRequest::post()
+        .uri(&format!("/recon-tasks/{}/start", task_id))
+        .insert_header(("Authorization", bearer_token_for(TEST_USER_ID)))
+        .send_request(&app)
+        .await;
+
+    let upload_resp = TestRequest::post()
+        .uri(&format!("/recon-tasks/{}/files/primary/upload", task_id))
+        .insert_header(("Authorization", bearer_token_for(TEST_USER_ID)))
+        .insert_header(("Content-Type", multipart_content_type()))
+        .set_payload(multipart_file_body("payments.csv", b"id;amount\n1;10\n"))
+        .send_request(&app)
+        .await;
+
+    //assert
+    let primary_topic = task_details["primary_file_chunks_queue_info"]["topic_id"]
+        .as_str()
+        .unwrap();
+    let comparison_topic = task_details["comparison_file_chunks_queue_info"]["topic_id"]
+        .as_str()
+        .unwrap();
+    let primary_events = sidecar.published_events_on(primary_topic);
+    let comparison_events = sidecar.published_events_on(comparison_topic);
+    let primary_event: Value = serde_json::from_slice(&primary_events[0].data).unwrap();
+    let comparison_event: Value = serde_json::from_slice(&comparison_events[0].data).unwrap();
+
+    assert_eq!(early_start_resp.status(), StatusCode::BAD_REQUEST);
+    assert_eq!(started["task_details"]["status"], "reconciling");
+    assert_eq!(primary_events.len(), 1);
+    assert_eq!(comparison_events.len(), 1);
+    assert_eq!(primary_event["event_type"], "ReconciliationStarted");
+    assert_eq!(primary_event["file"]["file_name"], "payments.csv");
+    assert_eq!(primary_event["file"]["row_count"], 3);
+    assert_eq!(comparison_event["file"]["file_name"], "ledger.csv");
+    assert_eq!(primary_event["recon_results_topic"], "recon-results");
+    assert_eq!(primary_event["recon_config"], task_details["recon_config"]);
+    assert_eq!(second_start_resp.status(), StatusCode::BAD_REQUEST);
+    assert_eq!(upload_resp.status(), StatusCode::BAD_REQUEST);
+}
+
+#[actix_web::test]
+async fn test_results_of_every_chunk_complete_the_task_with_their_summed_counts() {
+    //setup
+    let sidecar = FakeDaprSidecar::new();
+    let app = test::init_service(setup_app(&sidecar).await).await;
+
+    let created: Value = test::read_body_json(
+        TestRequest::post()
+            .uri("/recon-tasks")
+            .insert_header(("Authorization", bearer_token_for(TEST_USER_ID)))
+            .set_json(get_dummy_create_recon_task_json())
+            .send_request(&app)
+            .await,
+    )
+    .await;
+    let task_id = created["task_id"].as_str().unwrap();
+
+    for (file_role, file_name) in [("primary", "payments.csv"), ("comparison", "ledger.csv")] {
+        let upload_resp = TestRequest::post()
+            .uri(&format!("/recon-tasks/{}/files/{}/upload", task_id, file_role))
+            .insert_header(("Authorization", bearer_token_for(TEST_USER_ID)))
+            .insert_header(("Content-Type", multipart_content_type()))
+            .set_payload(multipart_file_body(file_name, b"id;amount\n1;10\n2;20\n"))
+            .send_request(&app)
+            .await;
+        assert!(upload_resp.status().is_success());
+    }
+
+    let start_resp = TestRequest::post()
+        .uri(&format!("/recon-tasks/{}/start", task_id))
+        .insert_header(("Authorization", bearer_token_for(TEST_USER_ID)))
+        .send_request(&app)
+        .await;
+    assert!(start_resp.status().is_success());
+
+    //act
+    let subscriptions: Value = test::read_body_json(
+        TestRequest::get()
+            .uri("/dapr/subscribe")
+            .insert_header((DAPR_API_TOKEN_HEADER, TEST_SIDECAR_TOKEN))
+            .send_request(&app)
+            .await,
+    )
+    .await;
+
+    //the primary chunk is delivered twice, as pubsub may do
+    let mut deliveries = vec![];
+    for (file_role, matched) in [("primary", 2), ("primary", 2), ("comparison", 0)] {
+        let delivery: Value = test::read_body_json(
+            TestRequest::post()
+                .uri("/dapr/recon-results")
+                .insert_header((DAPR_API_TOKEN_HEADER, TEST_SIDECAR_TOKEN))
+                .insert_header(("Content-Type", "application/cloudevents+json"))
+                .set_payload(
+                    json!({
+                        "specversion": "1.0",
+                        "topic": "recon-results",
+                        "data": {
+                            "task_id": task_id,
+                            "file_role": file_role,
+                            "chunk_sequence_number": 1,
+                            "total_chunks": 1,
+                            "counts": { "matched": matched, "field_mismatches": 1 }
+                        }
+                    })
+                    .to_string(),
+                )
+                .send_request(&app)
+                .await,
+        )
+        .await;
+        deliveries.push(delivery);
+    }
+
+    let task: Value = test::read_body_json(
+        TestRequest::get()
+            .uri(&format!("/recon-tasks/{}", task_id))
+            .insert_header(("Authorization", bearer_token_for(TEST_USER_ID)))
+            .send_request(&app)
+            .await,
+    )
+    .await;
+
+    //assert
+    let results_summary = &task["task_details"]["results_summary"];
+
+    assert_eq!(subscriptions.as_array().unwrap().len(), 1);
+    assert_eq!(subscriptions[0]["topic"], "recon-results");
+    assert_eq!(subscriptions[0]["route"], "/dapr/recon-results");
+    assert!(deliveries.iter().all(|delivery| delivery["status"] == "SUCCESS"));
+    assert_eq!(task["task_details"]["status"], "completed");
+    assert_eq!(task["task_details"]["is_done"], true);
+    assert_eq!(results_summary["counts"]["matched"], 2);
+    assert_eq!(results_summary["counts"]["field_mismatches"], 2);
+    assert!(results_summary["completed_at"].is_string());
+}
+
+#[actix_web::test]
+async fn test_results_of_completed_task_export_as_csv_and_jsonl_filtered_by_outcome() {
+    //setup
+    let sidecar = FakeDaprSidecar::new();
+    let app = test::init_service(setup_app(&sidecar).await).await;
+
+    let created: Value = test::read_body_json(
+        TestRequest::post()
+            .uri("/recon-tasks")
+            .insert_header(("Authorization", bearer_token_for(TEST_USER_ID)))
+            .set_json(get_dummy_create_recon_task_json())
+            .send_request(&app)
+            .await,
+    )
+    .await;
+    let task_id = created["task_id"].as_str().unwrap();
+
+    for (file_role, file_name) in [("primary", "payments.csv"), ("comparison", "ledger.csv")] {
+        let upload_resp = TestRequest::post()
+            .uri(&format!("/recon-tasks/{}/files/{}/upload", task_id, file_role))
+            .insert_header(("Authorization", bearer_token_for(TEST_USER_ID)))
+            .insert_header(("Content-Type", multipart_content_type()))
+            .set_payload(multipart_file_body(file_name, b"id;amount\n1;10\n2;20\n"))
+            .send_request(&app)
+            .await;
+        assert!(upload_resp.status().is_success());
+    }
+
+    let early_export_resp = TestRequest::get()
+        .uri(&format!("/recon-tasks/{}/results", task_id))
+        .insert_header(("Authorization", bearer_token_for(TEST_USER_ID)))
+        .send_request(&app)
+        .await;
+
+    let start_resp = TestRequest::post()
+        .uri(&format!("/recon-tasks/{}/start", task_id))
+        .insert_header(("Authorization", bearer_token_for(TEST_USER_ID)))
+        .send_request(&app)
+        .await;
+    assert!(start_resp.status().is_success());
+
+    let results_files = [
+        (
+            "primary",
+            format!(
+                "{}\n{}\n",
+                json!({ "outcome": "matched", "file_role": "primary", "row_key": "1", "values": ["1", "10"] }),
+                json!({ "outcome": "unmatched_in_primary", "file_role": "primary", "row_key": "2", "values": ["2", "20"] })
+            ),
+        ),
+        (
+            "comparison",
+            format!(
+                "{}\n",
+                json!({ "outcome": "unmatched_in_comparison", "file_role": "comparison", "row_key": "3", "values": ["3", "30"] })
+            ),
+        ),
+    ];
+    for (file_role, results_file) in results_files {
+        let results_file_name = format!("{}-{}-results-1", task_id, file_role);
+        sidecar.put_binding_file("blobstore", &results_file_name, results_file.into_bytes());
+
+        let delivery_resp = TestRequest::post()
+            .uri("/dapr/recon-results")
+            .insert_header((DAPR_API_TOKEN_HEADER, TEST_SIDECAR_TOKEN))
+            .set_json(json!({
+                "data": {
+                    "task_id": task_id,
+                    "file_role": file_role,
+                    "chunk_sequence_number": 1,
+                    "total_chunks": 1,
+                    "counts": { "matched": 1 },
+                    "results_file_name": results_file_name
+                }
+            }))
+            .send_request(&app)
+            .await;
+        assert!(delivery_resp.status().is_success());
+    }
+
+    //a results message cannot point the export at a file it was not derived from
+    let misnamed_delivery: Value = test::read_body_json(
+        TestRequest::post()
+            .uri("/dapr/recon-results")
+            .insert_header((DAPR_API_TOKEN_HEADER, TEST_SIDECAR_TOKEN))
+            .set_json(json!({
+                "data": {
+                    "task_id": task_id,
+                    "file_role": "primary",
+                    "chunk_sequence_number": 2,
+                    "total_chunks": 1,
+                    "counts": { "matched": 1 },
+                    "results_file_name": format!("{}-payments.csv", task_id)
+                }
+            }))
+            .send_request(&app)
+            .await,
+    )
+    .await;
+
+    //act
+    let csv_resp = TestRequest::get()
+        .uri(&format!("/recon-tasks/{}/results?format=csv&status=unmatched", task_id))
+        .insert_header(("Authorization", bearer_token_for(TEST_USER_ID)))
+        .send_request(&app)
+        .await;
+    let csv_content_type = csv_resp.headers().get("content-type").unwrap().clone();
+    let csv_body = test::read_body(csv_resp).await;
+
+    let jsonl_body = test::read_body(
+        TestRequest::get()
+            .uri(&format!("/recon-tasks/{}/results?format=jsonl&status=matched", task_id))
+            .insert_header(("Authorization", bearer_token_for(TEST_USER_ID)))
+            .send_request(&app)
+            .await,
+    )
+    .await;
+
+    //assert
+    let jsonl_rows: Vec<Value> = String::from_utf8(jsonl_body.to_vec())
+        .unwrap()
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect();
+
+    assert_eq!(early_export_resp.status(), StatusCode::BAD_REQUEST);
+    assert_eq!(misnamed_delivery["status"], "DROP");
+    assert_eq!(csv_content_type, "text/csv; charset=utf-8");
+    assert_eq!(
+        String::from_utf8(csv_body.to_vec()).unwrap(),
+        "outcome,file_role,row_key,id,amount,mismatched_columns\n\
+         unmatched_in_primary,primary,2,2,20,\n\
+         unmatched_in_comparison,comparison,3,3,30,\n"
+    );
+    assert_eq!(jsonl_rows.len(), 1);
+    assert_eq!(jsonl_rows[0]["values"], json!({ "id": "1", "amount": "10" }));
+}
+
+#[actix_web::test]
+async fn test_preview_of_uploaded_file_returns_first_rows_keyed_by_header() {
+    //setup
+    let sidecar = FakeDaprSidecar::new();
+    let app = test::init_service(setup_app(&sidecar).await).await;
+
+    let created: Value = test::read_body_json(
+        TestRequest::post()
+            .uri("/recon-tasks")
+            .insert_header(("Authorization", bearer_token_for(TEST_USER_ID)))
+            .set_json(get_dummy_create_recon_task_json())
+            .send_request(&app)
+            .await,
+    )
+    .await;
+    let task_id = created["task_id"].as_str().unwrap();
+
+    let uploaded: Value = test::read_body_json(
+        TestRequest::post()
+            .uri(&format!("/recon-tasks/{}/files/primary/upload", task_id))
+            .insert_header(("Authorization", bearer_token_for(TEST_USER_ID)))
+            .insert_header(("Content-Type", multipart_content_type()))
+            .set_payload(multipart_file_body("payments.csv", b"id;amount\n1;10\n2;20\n"))
+            .send_request(&app)
+            .await,
+    )
+    .await;
+    let file_id = uploaded["file_id"].as_str().unwrap();
+
+    //act
+    let preview: Value = test::read_body_json(
+        TestRequest::get()
+            .uri(&format!("/recon-tasks/{}/files/{}/preview?rows=1", task_id, file_id))
+            .insert_header(("Authorization", bearer_token_for(TEST_USER_ID)))
+            .send_request(&app)
+            .await,
+    )
+    .await;
+
+    //assert
+    assert_eq!(preview["rows"], json!([{ "id": "1", "amount": "10" }]));
+    assert_eq!(
+        preview["columns"],
+        json!([
+            { "header": "id", "is_compared": true, "is_row_identifier": true },
+            { "header": "amount", "is_compared": false, "is_row_identifier": false }
+        ])
+    );
+}
+
+#[actix_web::test]
+async fn test_task_history_records_creation_and_file_attachment_in_order() {
+    //setup
+    let sidecar = FakeDaprSidecar::new();
+    let app = test::init_service(setup_app(&sidecar).await).await;
+
+    let created: Value = test::read_body_json(
+        TestRequest::post()
+            .uri("/recon-tasks")
+            .insert_header(("Authorization", bearer_token_for(TEST_USER_ID)))
+            .set_json(get_dummy_create_recon_task_json())
+            .send_request(&app)
+            .await,
+    )
+    .await;
+    let task_id = created["task_id"].as_str().unwrap();
+
+    TestRequest::post()
+        .uri("/recon-tasks/attach-primary-file")
+        .insert_header(("Authorization", bearer_token_for(TEST_USER_ID)))
+        .set_json(json!({
+            "task_id": task_id,
+            "primary_file_name": "primary.csv",
+            "primary_file_hash": "primary-hash",
+            "primary_file_row_count": 10,
+            "primary_file_headers": ["id", "amount"],
+            "primary_file_delimiters": [","]
+        }))
+        .send_request(&app)
+        .await;
+
+    //act
+    let history: Value = test::read_body_json(
+        TestRequest::get()
+            .uri(&format!("/recon-tasks/{}/history", task_id))
+            .insert_header(("Authorization", bearer_token_for(TEST_USER_ID)))
+            .send_request(&app)
+            .await,
+    )
+    .await;
+
+    //assert
+    assert_eq!(history[0]["action"], "TaskCreated");
+    assert_eq!(history[1]["action"], "PrimaryFileAttached");
+    assert_eq!(created["task_details"]["created_by"], history[0]["performed_by"]);
+}
+
+#[actix_web::test]
+async fn test_create_recon_task_takes_owner_from_token_not_body() {
+    //setup
+    let sidecar = FakeDaprSidecar::new();
+    let app = test::init_service(setup_app(&sidecar).await).await;
+
+    let mut request = get_dummy_create_recon_task_json();
+    request["user_id"] = json!("someone-else");
+
+    //act
+    let created: Value = test::read_body_json(
+        TestRequest::post()
+            .uri("/recon-tasks")
+            .insert_header(("Authorization", bearer_token_for(TEST_USER_ID)))
+            .set_json(&request)
+            .send_request(&app)
+            .await,
+    )
+    .await;
+
+    //assert
+    assert_eq!(created["task_details"]["created_by"], TEST_USER_ID);
+}
+
+#[actix_web::test]
+async fn test_get_recon_task_owned_by_another_user_returns_forbidden() {
+    //setup
+    let sidecar = FakeDaprSidecar::new();
+    let app = test::init_service(setup_app(&sidecar).await).await;
+
+    let created: Value = test::read_body_json(
+        TestRequest::post()
+            .uri("/recon-tasks")
+            .insert_header(("Authorization", bearer_token_for(TEST_USER_ID)))
+            .set_json(get_dummy_create_recon_task_json())
+            .send_request(&app)
+            .await,
+    )
+    .await;
+    let task_id = created["task_id"].as_str().unwrap();
+
+    //act
+    let resp = TestRequest::get()
+        .uri(&format!("/recon-tasks/{}", task_id))
+        .insert_header(("Authorization", bearer_token_for("another-user-id")))
+        .send_request(&app)
+        .await;
+
+    //assert
+    assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+}
+
+#[actix_web::test]
+async fn test_request_without_bearer_token_returns_unauthorized() {
+    //setup
+    let sidecar = FakeDaprSidecar::new();
+    let app = test::init_service(setup_app(&sidecar).await).await;
+
+    //act
+    let resp = TestRequest::post()
+        .uri("/recon-tasks")
+        .set_json(get_dummy_create_recon_task_json())
+        .send_request(&app)
+        .await;
+
+    //assert
+    assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+}
+
+#[actix_web::test]
+async fn test_admin_delete_orphaned_files_keeps_files_attached_to_tasks() {
+    //setup
+    let sidecar = FakeDaprSidecar::new();
+    let app = test::init_service(setup_app(&sidecar).await).await;
+
+    let created: Value = test::read_body_json(
+        TestRequest::post()
+            .uri("/recon-tasks")
+            .insert_header(("Authorization", bearer_token_for(TEST_USER_ID)))
+            .set_json(get_dummy_create_recon_task_json())
+            .send_request(&app)
+            .await,
+    )
+    .await;
+    let task_id = created["task_id"].as_str().unwrap();
+
+    let attached: Value = test::read_body_json(
+        TestRequest::post()
+            .uri("/recon-tasks/attach-primary-file")
+            .insert_header(("Authorization", bearer_token_for(TEST_USER_ID)))
+            .set_json(json!({
+                "task_id": task_id,
+                "primary_file_name": "primary.csv",
+                "primary_file_hash": "primary-hash",
+                "primary_file_row_count": 10,
+                "primary_file_headers": ["id", "amount"],
+                "primary_file_delimiters": [","]
+            }))
+            .send_request(&app)
+            .await,
+    )
+    .await;
+
+    sidecar.put_raw("statestore", "RECON-FILE-orphan", b"{}".to_vec());
+    sidecar.put_raw(
+        "statestore",
+        "RECON-FILES-INDEX",
+        serde_json::to_vec(&vec![attached["file_id"].clone(), json!("RECON-FILE-orphan")]).unwrap(),
+    );
+
+    //act
+    let cleanup: Value = test::read_body_json(
+        TestRequest::delete()
+            .uri("/admin/recon-files/orphaned")
+            .insert_header(("Authorization", bearer_token_with_roles("admin-user-id", &["admin"])))
+            .send_request(&app)
+            .await,
+    )
+    .await;
+
+    //assert
+    assert_eq!(cleanup["deleted_file_ids"], json!(["RECON-FILE-orphan"]));
+    assert!(sidecar.get_raw("statestore", "RECON-FILE-orphan").is_none());
+}
+
+#[actix_web::test]
+async fn test_admin_endpoint_called_by_owner_returns_forbidden() {
+    //setup
+    let sidecar = FakeDaprSidecar::new();
+    let app = test::init_service(setup_app(&sidecar).await).await;
+
+    //act
+    let resp = TestRequest::get()
+        .uri("/admin/recon-tasks")
+        .insert_header(("Authorization", bearer_token_for(TEST_USER_ID)))
+        .send_request(&app)
+        .await;
+
+    //assert
+    assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+}
+
+#[actix_web::test]
+async fn test_cancel_recon_task_publishes_event_and_rejects_further_attachments() {
+    //setup
+    let sidecar = FakeDaprSidecar::new();
+    let app = test::init_service(setup_app(&sidecar).await).await;
+
+    let created: Value = test::read_body_json(
+        TestRequest::post()
+            .uri("/recon-tasks")
+            .insert_header(("Authorization", bearer_token_for(TEST_USER_ID)))
+            .set_json(get_dummy_create_recon_task_json())
+            .send_request(&app)
+            .await,
+    )
+    .await;
+    let task_id = created["task_id"].as_str().unwrap();
+    let chunks_topics: Vec<&str> = ["primary_file_chunks_queue_info", "comparison_file_chunks_queue_info"]
+        .iter()
+        .map(|queue| created["task_details"][*queue]["topic_id"].as_str().unwrap())
+        .collect();
+
+    //act
+    let cancelled: Value = test::read_body_json(
+        TestRequest::post()
+            .uri(&format!("/recon-tasks/{}/cancel", task_id))
+            .insert_header(("Authorization", bearer_token_for(TEST_USER_ID)))
+            .set_json(json!({ "reason": "wrong file uploaded" }))
+            .send_request(&app)
+            .await,
+    )
+    .await;
+
+    let attach_resp = TestRequest::post()
+        .uri("/recon-tasks/attach-primary-file")
+        .insert_header(("Authorization", bearer_token_for(TEST_USER_ID)))
+        .set_json(json!({
+            "task_id": task_id,
+            "primary_file_name": "primary.csv",
+            "primary_file_hash": "primary-hash",
+            "primary_file_row_count": 10,
+            "primary_file_headers": ["id", "amount"],
+            "primary_file_delimiters": [","]
+        }))
+        .send_request(&app)
+        .await;
+
+    //assert
+    assert_eq!(cancelled["task_details"]["status"], "cancelled");
+    assert_eq!(cancelled["task_details"]["cancellation"]["cancelled_by"], TEST_USER_ID);
+    for chunks_topic in chunks_topics {
+        let published = sidecar.published_events_on(chunks_topic);
+        let event: Value = serde_json::from_slice(&published[0].data).unwrap();
+
+        assert_eq!(event["event_type"], "TaskCancelled");
+        assert_eq!(event["reason"], "wrong file uploaded");
+    }
+    assert_eq!(attach_resp.status(), StatusCode::BAD_REQUEST);
+}
+
+#[actix_web::test]
+async fn test_cancel_recon_task_twice_records_a_single_cancellation_in_history() {
+    //setup
+    let sidecar = FakeDaprSidecar::new();
+    let app = test::init_service(setup_app(&sidecar).await).await;
+
+    let created: Value = test::read_body_json(
+        TestRequest::post()
+            .uri("/recon-tasks")
+            .insert_header(("Authorization", bearer_token_for(TEST_USER_ID)))
+            .set_json(get_dummy_create_recon_task_json())
+            .send_request(&app)
+            .await,
+    )
+    .await;
+    let task_id = created["task_id"].as_str().unwrap();
+
+    //act
+    for reason in ["wrong file uploaded", "cancelling again"] {
+        let resp = TestRequest::post()
+            .uri(&format!("/recon-tasks/{}/cancel", task_id))
+            .insert_header(("Authorization", bearer_token_for(TEST_USER_ID)))
+            .set_json(json!({ "reason": reason }))
+            .send_request(&app)
+            .await;
+        assert!(resp.status().is_success());
+    }
+
+    let history: Value = test::read_body_json(
+        TestRequest::get()
+            .uri(&format!("/recon-tasks/{}/history", task_id))
+            .insert_header(("Authorization", bearer_token_for(TEST_USER_ID)))
+            .send_request(&app)
+            .await,
+    )
+    .await;
+
+    //assert
+    let cancellations: Vec<&Value> = history
+        .as_array()
+        .unwrap()
+        .iter()
+        .filter(|entry| entry["action"] == "TaskCancelled")
+        .collect();
+
+    assert_eq!(cancellations.len(), 1);
+    assert_eq!(cancellations[0]["details"], "wrong file uploaded");
+}
+
+#[actix_web::test]
+async fn test_clone_recon_task_reuses_configuration_with_new_ids_and_topics() {
+    //setup
+    let sidecar = FakeDaprSidecar::new();
+    let app = test::init_service(setup_app(&sidecar).await).await;
+
+    let created: Value = test::read_body_json(
+        TestRequest::post()
+            .uri("/recon-tasks")
+            .insert_header(("Authorization", bearer_token_for(TEST_USER_ID)))
+            .set_json(get_dummy_create_recon_task_json())
+            .send_request(&app)
+            .await,
+    )
+    .await;
+    let task_id = created["task_id"].as_str().unwrap();
+
+    //act
+    let cloned: Value = test::read_body_json(
+        TestRequest::post()
+            .uri(&format!("/recon-tasks/{}/clone", task_id))
+            .insert_header(("Authorization", bearer_token_for(TEST_USER_ID)))
+            .send_request(&app)
+            .await,
+    )
+    .await;
+
+    //assert
+    assert_ne!(cloned["task_id"], created["task_id"]);
+    assert_ne!(
+        cloned["task_details"]["recon_results_queue_info"]["topic_id"],
+        created["task_details"]["recon_results_queue_info"]["topic_id"]
+    );
+    assert_eq!(cloned["task_details"]["comparison_pairs"], created["task_details"]["comparison_pairs"]);
+    assert_eq!(cloned["task_details"]["recon_config"], created["task_details"]["recon_config"]);
+    assert!(cloned["primary_file_metadata"].is_null());
+}
+
+#[actix_web::test]
+async fn test_clone_recon_task_with_copy_file_metadata_attaches_copies_of_the_files() {
+    //setup
+    let sidecar = FakeDaprSidecar::new();
+    let app = test::init_service(setup_app(&sidecar).await).await;
+
+    let created: Value = test::read_body_json(
+        TestRequest::post()
+            .uri("/recon-tasks")
+            .insert_header(("Authorization", bearer_token_for(TEST_USER_ID)))
+            .set_json(get_dummy_create_recon_task_json())
+            .send_request(&app)
+            .await,
+    )
+    .await;
+    let task_id = created["task_id"].as_str().unwrap();
+
+    for (file_role, file_name) in [("primary", "payments.csv"), ("comparison", "ledger.csv")] {
+        TestRequest::post()
+            .uri(&format!("/recon-tasks/attach-{}-file", file_role))
+            .insert_header(("Authorization", bearer_token_for(TEST_USER_ID)))
+            .set_json(json!({
+                "task_id": task_id,
+                format!("{}_file_name", file_role): file_name,
+                format!("{}_file_hash", file_role): PAYMENTS_FILE_HASH,
+                format!("{}_file_row_count", file_role): 3,
+                format!("{}_file_headers", file_role): ["id", "amount"],
+                format!("{}_file_delimiters", file_role): [";"]
+            }))
+            .send_request(&app)
+            .await;
+
+        //files attached with their metadata only are stored under their name within the source task
+        sidecar.put_binding_file(
+            "blobstore",
+            &format!("{}-{}", task_id, file_name),
+            b"id;amount\n1;10\n2;20\n".to_vec(),
+        );
+    }
+
+    TestRequest::post()
+        .uri(&format!("/recon-tasks/{}/files/verify", task_id))
+        .insert_header(("Authorization", bearer_token_for(TEST_USER_ID)))
+        .send_request(&app)
+        .await;
+
+    //act
+    let cloned: Value = test::read_body_json(
+        TestRequest::post()
+            .uri(&format!("/recon-tasks/{}/clone", task_id))
+            .insert_header(("Authorization", bearer_token_for(TEST_USER_ID)))
+            .set_json(json!({ "copy_file_metadata": true }))
+            .send_request(&app)
+            .await,
+    )
+    .await;
+    let cloned_task_id = cloned["task_id"].as_str().unwrap();
+
+    let start_resp = TestRequest::post()
+        .uri(&format!("/recon-tasks/{}/start", cloned_task_id))
+        .insert_header(("Authorization", bearer_token_for(TEST_USER_ID)))
+        .send_request(&app)
+        .await;
+
+    //assert
+    let primary_topic = cloned["task_details"]["primary_file_chunks_queue_info"]["topic_id"]
+        .as_str()
+        .unwrap();
+    let primary_events = sidecar.published_events_on(primary_topic);
+    let primary_event: Value = serde_json::from_slice(&primary_events[0].data).unwrap();
+
+    assert_eq!(cloned["primary_file_metadata"]["file_hash"], PAYMENTS_FILE_HASH);
+    assert_eq!(cloned["comparison_file_metadata"]["file_name"], "ledger.csv");
+    assert!(start_resp.status().is_success());
+    assert_eq!(
+        primary_event["file"]["stored_file_parts"],
+        json!([format!("{}-payments.csv", task_id)])
+    );
+}
+
+#[actix_web::test]
+async fn test_task_created_from_template_resolves_header_names_once_both_files_are_attached() {
+    //setup
+    let sidecar = FakeDaprSidecar::new();
+    let app = test::init_service(setup_app(&sidecar).await).await;
+
+    let template: Value = test::read_body_json(
+        TestRequest::post()
+            .uri("/recon-templates")
+            .insert_header(("Authorization", bearer_token_for(TEST_USER_ID)))
+            .set_json(get_dummy_recon_template_json())
+            .send_request(&app)
+            .await,
+    )
+    .await;
+
+    //act
+    let created: Value = test::read_body_json(
+        TestRequest::post()
+            .uri("/recon-tasks")
+            .insert_header(("Authorization", bearer_token_for(TEST_USER_ID)))
+            .set_json(json!({ "template_id": template["id"] }))
+            .send_request(&app)
+            .await,
+    )
+    .await;
+    let task_id = created["task_id"].as_str().unwrap();
+
+    TestRequest::post()
+        .uri("/recon-tasks/attach-primary-file")
+        .insert_header(("Authorization", bearer_token_for(TEST_USER_ID)))
+        .set_json(json!({
+            "task_id": task_id,
+            "primary_file_name": "primary.csv",
+            "primary_file_hash": "primary-hash",
+            "primary_file_row_count": 10,
+            "primary_file_headers": ["amount", "id"],
+            "primary_file_delimiters": [","]
+        }))
+        .send_request(&app)
+        .await;
+
+    TestRequest::post()
+        .uri("/recon-tasks/attach-comparison-file")
+        .insert_header(("Authorization", bearer_token_for(TEST_USER_ID)))
+        .set_json(json!({
+            "task_id": task_id,
+            "comparison_file_name": "comparison.csv",
+            "comparison_file_hash": "comparison-hash",
+            "comparison_file_row_count": 10,
+            "comparison_file_headers": ["reference", "date", "total"],
+            "comparison_file_delimiters": [","]
+        }))
+        .send_request(&app)
+        .await;
+
+    let task: Value = test::read_body_json(
+        TestRequest::get()
+            .uri(&format!("/recon-tasks/{}", task_id))
+            .insert_header(("Authorization", bearer_token_for(TEST_USER_ID)))
+            .send_request(&app)
+            .await,
+    )
+    .await;
+
+    //assert
+    assert_eq!(created["task_details"]["template"]["template_version"], 1);
+    assert_eq!(created["task_details"]["template"]["template_id"], template["id"]);
+    assert_eq!(
+        task["task_details"]["comparison_pairs"],
+        json!([
+            {"primary_file_column_index": 1, "comparison_file_column_index": 0, "is_row_identifier": true},
+            {"primary_file_column_index": 0, "comparison_file_column_index": 2, "is_row_identifier": false}
+        ])
+    );
+    assert_eq!(task["task_details"]["pending_comparison_pairs"], json!([]));
+}
+
+#[actix_web::test]
+async fn test_task_with_header_name_pairs_shows_resolved_indexes_once_both_files_are_attached() {
+    //setup
+    let sidecar = FakeDaprSidecar::new();
+    let app = test::init_service(setup_app(&sidecar).await).await;
+
+    let mut create_task_json = get_dummy_create_recon_task_json();
+    create_task_json["comparison_pairs"] = json!([
+        {"primary_file_column_index": 0, "comparison_file_column_index": 0, "is_row_identifier": true},
+        {"primary_file_column": "AMOUNT", "comparison_file_column": "Total", "ignore_header_case": true}
+    ]);
+
+    let created: Value = test::read_body_json(
+        TestRequest::post()
+            .uri("/recon-tasks")
+            .insert_header(("Authorization", bearer_token_for(TEST_USER_ID)))
+            .set_json(&create_task_json)
+            .send_request(&app)
+            .await,
+    )
+    .await;
+    let task_id = created["task_id"].as_str().unwrap();
+
+    //act
+    TestRequest::post()
+        .uri("/recon-tasks/attach-primary-file")
+        .insert_header(("Authorization", bearer_token_for(TEST_USER_ID)))
+        .set_json(json!({
+            "task_id": task_id,
+            "primary_file_name": "primary.csv",
+            "primary_file_hash": "primary-hash",
+            "primary_file_row_count": 10,
+            "primary_file_headers": ["id", "amount"],
+            "primary_file_delimiters": [","]
+        }))
+        .send_request(&app)
+        .await;
+
+    TestRequest::post()
+        .uri("/recon-tasks/attach-comparison-file")
+        .insert_header(("Authorization", bearer_token_for(TEST_USER_ID)))
+        .set_json(json!({
+            "task_id": task_id,
+            "comparison_file_name": "comparison.csv",
+            "comparison_file_hash": "comparison-hash",
+            "comparison_file_row_count": 10,
+            "comparison_file_headers": ["reference", "date", "total"],
+            "comparison_file_delimiters": [","]
+        }))
+        .send_request(&app)
+        .await;
+
+    let task: Value = test::read_body_json(
+        TestRequest::get()
+            .uri(&format!("/recon-tasks/{}", task_id))
+            .insert_header(("Authorization", bearer_token_for(TEST_USER_ID)))
+            .send_request(&app)
+            .await,
+    )
+    .await;
+
+    //assert
+    assert_eq!(created["task_details"]["comparison_pairs"].as_array().unwrap().len(), 1);
+    assert_eq!(
+        task["task_details"]["comparison_pairs"][1],
+        json!({"primary_file_column_index": 1, "comparison_file_column_index": 2, "is_row_identifier": false})
+    );
+    assert_eq!(
+        task["task_details"]["resolved_comparison_pairs"],
+        json!([{
+            "named_pair": {
+                "primary_file_column": "AMOUNT",
+                "comparison_file_column": "Total",
+                "is_row_identifier": false,
+                "ignore_header_case": true
+            },
+            "primary_file_column_index": 1,
+            "comparison_file_column_index": 2
+        }])
+    );
+}
+
+#[actix_web::test]
+async fn test_create_recon_task_with_comparison_rules_shows_them_against_the_pair_columns() {
+    //setup
+    let sidecar = FakeDaprSidecar::new();
+    let app = test::init_service(setup_app(&sidecar).await).await;
+
+    let rules = json!({
+        "numeric_tolerance": {"type": "absolute", "value": 0.01},
+        "date_format": null,
+        "trim_values": true,
+        "cleanup_patterns": [{"pattern": "^REF-", "replacement": ""}],
+        "amount_sign": "ignore_sign"
+    });
+
+    let mut create_task_json = get_dummy_create_recon_task_json();
+    create_task_json["comparison_pairs"] = json!([
+        {"primary_file_column_index": 0, "comparison_file_column_index": 0, "is_row_identifier": true},
+        {"primary_file_column_index": 2, "comparison_file_column_index": 3, "rules": rules}
+    ]);
+
+    //act
+    let created: Value = test::read_body_json(
+        TestRequest::post()
+            .uri("/recon-tasks")
+            .insert_header(("Authorization", bearer_token_for(TEST_USER_ID)))
+            .set_json(&create_task_json)
+            .send_request(&app)
+            .await,
+    )
+    .await;
+
+    //assert
+    assert_eq!(
+        created["task_details"]["comparison_pair_rules"],
+        json!([{"primary_file_column_index": 2, "comparison_file_column_index": 3, "rules": rules}])
+    );
+}
+
+#[actix_web::test]
+async fn test_create_recon_task_with_invalid_cleanup_pattern_returns_bad_request() {
+    //setup
+    let sidecar = FakeDaprSidecar::new();
+    let app = test::init_service(setup_app(&sidecar).await).await;
+
+    let mut create_task_json = get_dummy_create_recon_task_json();
+    create_task_json["comparison_pairs"][0]["rules"] =
+        json!({"cleanup_patterns": [{"pattern": "[0-9"}]});
+
+    //act
+    let resp = TestRequest::post()
+        .uri("/recon-tasks")
+        .insert_header(("Authorization", bearer_token_for(TEST_USER_ID)))
+        .set_json(&create_task_json)
+        .send_request(&app)
+        .await;
+
+    //assert
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+}
+
+#[actix_web::test]
+async fn test_create_recon_task_with_composite_row_identifier_shows_the_row_key_definition() {
+    //setup
+    let sidecar = FakeDaprSidecar::new();
+    let app = test::init_service(setup_app(&sidecar).await).await;
+
+    let mut create_task_json = get_dummy_create_recon_task_json();
+    create_task_json["row_key_separator"] = json!("#");
+    create_task_json["comparison_pairs"] = json!([
+        {"primary_file_column_index": 2, "comparison_file_column_index": 0, "is_row_identifier": true, "row_key_position": 1},
+        {"primary_file_column_index": 1, "comparison_file_column_index": 1},
+        {"primary_file_column_index": 0, "comparison_file_column_index": 3, "is_row_identifier": true, "row_key_position": 0}
+    ]);
+
+    //act
+    let created: Value = test::read_body_json(
+        TestRequest::post()
+            .uri("/recon-tasks")
+            .insert_header(("Authorization", bearer_token_for(TEST_USER_ID)))
+            .set_json(&create_task_json)
+            .send_request(&app)
+            .await,
+    )
+    .await;
+
+    //assert
+    assert_eq!(
+        created["row_key"],
+        json!({
+            "key_separator": "#",
+            "identifier_columns": [
+                {"primary_file_column_index": 0, "comparison_file_column_index": 3},
+                {"primary_file_column_index": 2, "comparison_file_column_index": 0}
+            ],
+            "is_complete": true
+        })
+    );
+}
+
+#[actix_web::test]
+async fn test_create_recon_task_with_duplicated_row_identifier_returns_bad_request() {
+    //setup
+    let sidecar = FakeDaprSidecar::new();
+    let app = test::init_service(setup_app(&sidecar).await).await;
+
+    let mut create_task_json = get_dummy_create_recon_task_json();
+    create_task_json["comparison_pairs"] = json!([
+        {"primary_file_column_index": 0, "comparison_file_column_index": 0, "is_row_identifier": true},
+        {"primary_file_column_index": 0, "comparison_file_column_index": 1, "is_row_identifier": true}
+    ]);
+
+    //act
+    let resp = TestRequest::post()
+        .uri("/recon-tasks")
+        .insert_header(("Authorization", bearer_token_for(TEST_USER_ID)))
+        .set_json(&create_task_json)
+        .send_request(&app)
+        .await;
+
+    //assert
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+}
+
+#[actix_web::test]
+async fn test_suggested_pairs_can_be_listed_and_accepted_once_both_files_are_attached() {
+    //setup
+    let sidecar = FakeDaprSidecar::new();
+    let app = test::init_service(setup_app(&sidecar).await).await;
+
+    let mut create_task_json = get_dummy_create_recon_task_json();
+    create_task_json["comparison_pairs"] = json!([
+        {"primary_file_column_index": 0, "comparison_file_column_index": 1, "is_row_identifier": true}
+    ]);
+
+    let created: Value = test::read_body_json(
+        TestRequest::post()
+            .uri("/recon-tasks")
+            .insert_header(("Authorization", bearer_token_for(TEST_USER_ID)))
+            .set_json(&create_task_json)
+            .send_request(&app)
+            .await,
+    )
+    .await;
+    let task_id = created["task_id"].as_str().unwrap();
+
+    TestRequest::post()
+        .uri("/recon-tasks/attach-primary-file")
+        .insert_header(("Authorization", bearer_token_for(TEST_USER_ID)))
+        .set_json(json!({
+            "task_id": task_id,
+            "primary_file_name": "primary.csv",
+            "primary_file_hash": "primary-hash",
+            "primary_file_row_count": 10,
+            "primary_file_headers": ["Transaction ID", "Amount"],
+            "primary_file_delimiters": [","]
+        }))
+        .send_request(&app)
+        .await;
+
+    TestRequest::post()
+        .uri("/recon-tasks/attach-comparison-file")
+        .insert_header(("Authorization", bearer_token_for(TEST_USER_ID)))
+        .set_json(json!({
+            "task_id": task_id,
+            "comparison_file_name": "comparison.csv",
+            "comparison_file_hash": "comparison-hash",
+            "comparison_file_row_count": 10,
+            "comparison_file_headers": ["amount", "transaction_id", "value_date"],
+            "comparison_file_delimiters": [","]
+        }))
+        .send_request(&app)
+        .await;
+
+    //act
+    let suggestions: Value = test::read_body_json(
+        TestRequest::get()
+            .uri(&format!("/recon-tasks/{}/suggested-pairs", task_id))
+            .insert_header(("Authorization", bearer_token_for(TEST_USER_ID)))
+            .send_request(&app)
+            .await,
+    )
+    .await;
+
+    let accepted: Value = test::read_body_json(
+        TestRequest::post()
+            .uri(&format!("/recon-tasks/{}/suggested-pairs/accept", task_id))
+            .insert_header(("Authorization", bearer_token_for(TEST_USER_ID)))
+            .send_request(&app)
+            .await,
+    )
+    .await;
+
+    //assert
+    assert_eq!(suggestions["suggested_pairs"][0]["comparison_file_column_header"], "transaction_id");
+    assert_eq!(suggestions["suggested_pairs"][0]["is_row_identifier"], true);
+    assert_eq!(suggestions["suggested_pairs"][1]["comparison_file_column_header"], "amount");
+    assert_eq!(
+        accepted["task_details"]["comparison_pairs"],
+        json!([
+            {"primary_file_column_index": 0, "comparison_file_column_index": 1, "is_row_identifier": true},
+            {"primary_file_column_index": 1, "comparison_file_column_index": 0, "is_row_identifier": false}
+        ])
+    );
+}
+
+#[actix_web::test]
+async fn test_suggested_pairs_before_files_are_attached_returns_bad_request() {
+    //setup
+    let sidecar = FakeDaprSidecar::new();
+    let app = test::init_service(setup_app(&sidecar).await).await;
+
+    let created: Value = test::read_body_json(
+        TestRequest::post()
+            .uri("/recon-tasks")
+            .insert_header(("Authorization", bearer_token_for(TEST_USER_ID)))
+            .set_json(get_dummy_create_recon_task_json())
+            .send_request(&app)
+            .await,
+    )
+    .await;
+
+    //act
+    let resp = TestRequest::get()
+        .uri(&format!("/recon-tasks/{}/suggested-pairs", created["task_id"].as_str().unwrap()))
+        .insert_header(("Authorization", bearer_token_for(TEST_USER_ID)))
+        .send_request(&app)
+        .await;
+
+    //assert
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+}
+
+#[actix_web::test]
+async fn test_update_recon_template_bumps_version_and_keeps_previous_version_readable() {
+    //setup
+    let sidecar = FakeDaprSidecar::new();
+    let app = test::init_service(setup_app(&sidecar).await).await;
+
+    let template: Value = test::read_body_json(
+        TestRequest::post()
+            .uri("/recon-templates")
+            .insert_header(("Authorization", bearer_token_for(TEST_USER_ID)))
+            .set_json(get_dummy_recon_template_json())
+            .send_request(&app)
+            .await,
+    )
+    .await;
+    let template_id = template["id"].as_str().unwrap();
+
+    let mut changed_template = get_dummy_recon_template_json();
+    changed_template["name"] = json!("daily bank recon v2");
+
+    //act
+    let updated: Value = test::read_body_json(
+        TestRequest::put()
+            .uri(&format!("/recon-templates/{}", template_id))
+            .insert_header(("Authorization", bearer_token_for(TEST_USER_ID)))
+            .set_json(&changed_template)
+            .send_request(&app)
+            .await,
+    )
+    .await;
+
+    let first_version: Value = test::read_body_json(
+        TestRequest::get()
+            .uri(&format!("/recon-templates/{}?version=1", template_id))
+            .insert_header(("Authorization", bearer_token_for(TEST_USER_ID)))
+            .send_request(&app)
+            .await,
+    )
+    .await;
+
+    let update_by_other_user = TestRequest::put()
+        .uri(&format!("/recon-templates/{}", template_id))
+        .insert_header(("Authorization", bearer_token_for("another-user-id")))
+        .set_json(&changed_template)
+        .send_request(&app)
+        .await;
+
+    //assert
+    assert_eq!(updated["version"], 2);
+    assert_eq!(updated["name"], "daily bank recon v2");
+    assert_eq!(first_version["name"], "daily bank recon");
+    assert_eq!(update_by_other_user.status(), StatusCode::FORBIDDEN);
+}
+
+#[actix_web::test]
+async fn test_get_unknown_recon_task_returns_not_found() {
+    //setup
+    let sidecar = FakeDaprSidecar::new();
+    let app = test::init_service(setup_app(&sidecar).await).await;
+
+    //act
+    let resp = TestRequest::get()
+        .uri("/recon-tasks/RECON-TASK-00000000-0000-0000-0000-000000000000")
+        .insert_header(("Authorization", bearer_token_for(TEST_USER_ID)))
+        .send_request(&app)
+        .await;
+
+    //assert
+    assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+}
+
+#[actix_web::test]
+async fn test_get_recon_task_with_malformed_id_returns_bad_request_without_reading_the_store() {
+    //setup
+    let sidecar = FakeDaprSidecar::new();
+    let app = test::init_service(setup_app(&sidecar).await).await;
+    sidecar.fail_with(Some(Code::Unavailable));
+
+    //act
+    let resp = TestRequest::get()
+        .uri("/recon-tasks/not-an-id")
+        .insert_header(("Authorization", bearer_token_for(TEST_USER_ID)))
+        .send_request(&app)
+        .await;
+
+    //assert
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+}
+
+#[actix_web::test]
+async fn test_create_recon_task_with_malformed_template_id_returns_bad_request_without_reading_the_store() {
+    //setup
+    let sidecar = FakeDaprSidecar::new();
+    let app = test::init_service(setup_app(&sidecar).await).await;
+    sidecar.fail_with(Some(Code::Unavailable));
+
+    let create_task_json = json!({
+        "user_id": "test-user-id",
+        "template_id": "not-an-id"
+    });
+
+    //act
+    let resp = TestRequest::post()
+        .uri("/recon-tasks")
+        .insert_header(("Authorization", bearer_token_for(TEST_USER_ID)))
+        .set_json(&create_task_json)
+        .send_request(&app)
+        .await;
+
+    //assert
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+}
+
+#[actix_web::test]
+async fn test_attach_files_with_malformed_task_id_returns_bad_request_without_reading_the_store() {
+    //setup
+    let sidecar = FakeDaprSidecar::new();
+    let app = test::init_service(setup_app(&sidecar).await).await;
+    sidecar.fail_with(Some(Code::Unavailable));
+
+    //act
+    let primary_resp = TestRequest::post()
+        .uri("/recon-tasks/attach-primary-file")
+        .insert_header(("Authorization", bearer_token_for(TEST_USER_ID)))
+        .set_json(json!({
+            "task_id": "../RECON-TASK-1234",
+            "primary_file_name": "payments.csv",
+            "primary_file_hash": PAYMENTS_FILE_HASH,
+            "primary_file_row_count": 3,
+            "primary_file_headers": ["id", "amount"],
+            "primary_file_delimiters": [";"]
+        }))
+        .send_request(&app)
+        .await;
+    let comparison_resp = TestRequest::post()
+        .uri("/recon-tasks/attach-comparison-file")
+        .insert_header(("Authorization", bearer_token_for(TEST_USER_ID)))
+        .set_json(json!({
+            "task_id": "../RECON-TASK-1234",
+            "comparison_file_name": "payments.csv",
+            "comparison_file_hash": PAYMENTS_FILE_HASH,
+            "comparison_file_row_count": 3,
+            "comparison_file_headers": ["id", "amount"],
+            "comparison_file_delimiters": [";"]
+        }))
+        .send_request(&app)
+        .await;
+
+    //assert
+    assert_eq!(primary_resp.status(), StatusCode::BAD_REQUEST);
+    assert_eq!(comparison_resp.status(), StatusCode::BAD_REQUEST);
+}
+
+#[actix_web::test]
+async fn test_get_recon_task_when_sidecar_is_unavailable_returns_service_unavailable() {
+    //setup
+    let sidecar = FakeDaprSidecar::new();
+    let app = test::init_service(setup_app(&sidecar).await).await;
+    sidecar.fail_with(Some(Code::Unavailable));
+
+    //act
+    let resp = TestRequest::get()
+        .uri("/recon-tasks/RECON-TASK-00000000-0000-0000-0000-000000000001")
+        .insert_header(("Authorization", bearer_token_for(TEST_USER_ID)))
+        .send_request(&app)
+        .await;
+
+    //assert
+    assert_eq!(resp.status(), StatusCode::SERVICE_UNAVAILABLE);
+}
+
+#[actix_web::test]
+async fn test_unknown_or_seeded_id_generator_fails_service_setup() {
+    for id_generator in ["snowflake", "seeded"] {
+        //setup
+        let sidecar = FakeDaprSidecar::new();
+        let mut app_settings = read_app_settings();
+        app_settings.dapr_grpc_server_address = sidecar.start().await;
+        app_settings.id_generator = String::from(id_generator);
+
+        //act
+        let result = setup_service(&app_settings).await;
+
+        //assert
+        assert!(result.is_err(), "[{}] was accepted", id_generator);
+    }
+}
+
+async fn setup_app(
+    sidecar: &FakeDaprSidecar,
+) -> App<
+    impl actix_service::ServiceFactory<
+        actix_web::dev::ServiceRequest,
+        Config = (),
+        Response = actix_web::dev::ServiceResponse,
+        Error = actix_web::Error,
+        InitError = (),
+    >,
+> {
+    let mut app_settings = read_app_settings();
+    app_settings.dapr_grpc_server_address = sidecar.start().await;
+    app_settings.dapr_max_retries = 0;
+    app_settings.dapr_app_api_token = Some(String::from(TEST_SIDECAR_TOKEN));
+
+    let service = setup_service(&app_settings).await.unwrap();
+
+    App::new()
+        .app_data(Data::new(service))
+        .app_data(Data::new(test_token_validator()))
+        .app_data(Data::new(RateLimiter::new(
+            app_settings.rate_limit_burst,
+            app_settings.rate_limit_refill_per_second,
+        )))
+        .app_data(Data::new(DaprSubscriberSettings::new(&app_settings)))
+        .app_data(upload_form_config(app_settings.max_upload_size_bytes))
+        .app_data(upload_chunk_config(app_settings.max_upload_size_bytes))
+        .configure(configure_routes)
+}
+
+fn get_dummy_create_recon_task_json() -> Value {
+    json!({
+        "user_id": "test-user-id",
+        "recon_configurations": {
+            "should_check_for_duplicate_records_in_comparison_file": false,
+            "should_reconciliation_be_case_sensitive": true,
+            "should_ignore_white_space": true,
+            "should_do_reverse_reconciliation": false
+        },
+        "comparison_pairs": [
+            {
+                "primary_file_column_index": 0,
+                "comparison_file_column_index": 0,
+                "is_row_identifier": true
+            }
+        ]
+    })
+}
+
+fn get_dummy_recon_template_json() -> Value {
+    json!({
+        "name": "daily bank recon",
+        "recon_configurations": {
+            "should_check_for_duplicate_records_in_comparison_file": false,
+            "should_reconciliation_be_case_sensitive": true,
+            "should_ignore_white_space": true,
+            "should_do_reverse_reconciliation": false
+        },
+        "comparison_pairs": [
+            {
+                "primary_file_column": "id",
+                "comparison_file_column": "reference",
+                "is_row_identifier": true
+            },
+            {
+                "primary_file_column": "amount",
+                "comparison_file_column": "total"
+            }
+        ]
+    })
+}
@@ -0,0 +1,52 @@
+use chrono::{Duration, Utc};
+use jsonwebtoken::{encode, Algorithm, DecodingKey, EncodingKey, Header};
+
+use crate::internal::web_api::auth::{Claims, TokenValidator};
+
+//shared by the web api tests so every request can carry a real signed token
+pub const TEST_JWT_SECRET: &str = "test-jwt-secret";
+
+pub fn test_token_validator() -> TokenValidator {
+    TokenValidator::from_static_key(
+        DecodingKey::from_secret(TEST_JWT_SECRET.as_bytes()),
+        Algorithm::HS256,
+    )
+}
+
+pub fn sign_test_token(claims: &Claims, secret: &str) -> String {
+    encode(
+        &Header::new(Algorithm::HS256),
+        claims,
+        &EncodingKey::from_secret(secret.as_bytes()),
+    )
+    .unwrap()
+}
+
+pub fn test_claims_for(user_id: &str) -> Claims {
+    Claims {
+        sub: String::from(user_id),
+        exp: (Utc::now() + Duration::hours(1)).timestamp() as usize,
+        roles: None,
+    }
+}
+
+pub fn test_claims_with_roles(user_id: &str, roles: &[&str]) -> Claims {
+    let mut claims = test_claims_for(user_id);
+    claims.roles = Some(roles.iter().map(|role| String::from(*role)).collect());
+    claims
+}
+
+//the value of an Authorization header for the given user
+pub fn bearer_token_for(user_id: &str) -> String {
+    format!(
+        "Bearer {}",
+        sign_test_token(&test_claims_for(user_id), TEST_JWT_SECRET)
+    )
+}
+
+pub fn bearer_token_with_roles(user_id: &str, roles: &[&str]) -> String {
+    format!(
+        "Bearer {}",
+        sign_test_token(&test_claims_with_roles(user_id, roles), TEST_JWT_SECRET)
+    )
+}
@@ -0,0 +1,18 @@
+//shared by the web api tests to send files the way a browser form would
+pub const TEST_MULTIPART_BOUNDARY: &str = "recon-test-boundary";
+
+pub fn multipart_content_type() -> String {
+    format!("multipart/form-data; boundary={}", TEST_MULTIPART_BOUNDARY)
+}
+
+//a multipart/form-data body holding one file in the part named "file"
+pub fn multipart_file_body(file_name: &str, content: &[u8]) -> Vec<u8> {
+    let mut body = format!(
+        "--{}\r\nContent-Disposition: form-data; name=\"file\"; filename=\"{}\"\r\nContent-Type: text/csv\r\n\r\n",
+        TEST_MULTIPART_BOUNDARY, file_name
+    )
+    .into_bytes();
+    body.extend_from_slice(content);
+    body.extend_from_slice(format!("\r\n--{}--\r\n", TEST_MULTIPART_BOUNDARY).as_bytes());
+    body
+}
@@ -1,15 +1,62 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use actix_web::HttpResponse;
+use jsonwebtoken::Algorithm;
+use serde::Serialize;
+use tonic::Code;
+
+use crate::external::blob_store::dapr_binding_blob_store::DaprBindingBlobStore;
+use crate::external::blob_store::resilient_blob_store::ResilientBlobStore;
 use crate::external::dapr;
+use crate::external::dapr::dapr_errors::{parse_status_codes, DEFAULT_RETRYABLE_STATUS_CODES};
+use crate::external::events::recon_task_event_publisher::ReconTaskEventPublisher;
+use crate::external::events::resilient_recon_task_event_publisher::ResilientReconTaskEventPublisher;
 use crate::external::repositories::recon_file_details_repo::ReconFileDetailsRepositoryManager;
+use crate::external::repositories::recon_task_audit_repo::ReconTaskAuditRepositoryManager;
 use crate::external::repositories::recon_task_details_repo::ReconTaskDetailsRepositoryManager;
+use crate::external::repositories::recon_template_repo::ReconTemplateRepositoryManager;
+use crate::external::repositories::resilient_recon_file_details_repo::ResilientReconFileDetailsRepository;
+use crate::external::repositories::resilient_recon_task_audit_repo::ResilientReconTaskAuditRepository;
+use crate::external::repositories::resilient_recon_task_details_repo::ResilientReconTaskDetailsRepository;
+use crate::external::repositories::resilient_recon_template_repo::ResilientReconTemplateRepository;
+use crate::external::repositories::resilient_upload_session_repo::ResilientUploadSessionRepository;
+use crate::external::repositories::upload_session_repo::UploadSessionRepositoryManager;
+use crate::external::resilience::circuit_breaker::CircuitBreaker;
+use crate::external::resilience::retry_policy::RetryPolicy;
 use crate::internal::interfaces::recon_tasks_aggregator::ReconTaskAggregationServiceInterface;
+use crate::internal::models::entities::rejection_reason::RejectionReason;
+use crate::internal::models::entities::service_error::ServiceError;
+use crate::internal::services::core_logic::id_generators::{
+    new_id_generator, IdGeneratorKind, IdPrefixes,
+};
 use crate::internal::services::core_logic::transfomer::Transformer;
-use crate::internal::services::recon_tasks_aggregator_service::ReconTaskAggregationService;
-use crate::internal::shared_reconciler_rust_libraries::models::entities::app_errors::AppError;
+use crate::internal::services::recon_tasks_aggregator_service::{
+    ReconTaskAggregationService, ReconTaskQuotas,
+};
+use crate::internal::shared_reconciler_rust_libraries::models::entities::app_errors::{
+    AppError, AppErrorKind,
+};
+use crate::internal::shared_reconciler_rust_libraries::web_api::utils as shared_utils;
 
-const DEFAULT_DAPR_CONNECTION_URL: &'static str = "http://localhost:5005";
-const DEFAULT_DAPR_STORE_NAME: &'static str = "statestore";
-const DEFAULT_APP_LISTEN_IP: &'static str = "0.0.0.0";
+const DEFAULT_DAPR_CONNECTION_URL: &str = "http://localhost:5005";
+const DEFAULT_DAPR_STORE_NAME: &str = "statestore";
+const DEFAULT_DAPR_PUBSUB_NAME: &str = "pubsub";
+const DEFAULT_DAPR_RECON_RESULTS_TOPIC: &str = "recon-results";
+const DEFAULT_DAPR_BLOB_BINDING_NAME: &str = "blobstore";
+const DEFAULT_APP_LISTEN_IP: &str = "0.0.0.0";
 const DEFAULT_APP_LISTEN_PORT: u16 = 8080;
+const DEFAULT_DAPR_MAX_RETRIES: u32 = 3;
+const DEFAULT_DAPR_RETRY_BASE_DELAY_MS: u64 = 100;
+const DEFAULT_DAPR_RETRY_MAX_DELAY_MS: u64 = 2000;
+const DEFAULT_CIRCUIT_BREAKER_FAILURE_THRESHOLD: u32 = 5;
+const DEFAULT_CIRCUIT_BREAKER_OPEN_DURATION_MS: u64 = 30000;
+const DEFAULT_RATE_LIMIT_BURST: u32 = 20;
+const DEFAULT_RATE_LIMIT_REFILL_PER_SECOND: f64 = 1.0;
+const DEFAULT_MAX_ACTIVE_TASKS_PER_USER: usize = 50;
+const DEFAULT_MAX_FILE_ROW_COUNT: u64 = 10_000_000;
+const DEFAULT_ID_GENERATOR: &str = "uuid_v4";
+const DEFAULT_MAX_UPLOAD_SIZE_BYTES: usize = 512 * 1024 * 1024;
 
 #[derive(Clone, Debug)]
 pub struct AppSettings {
@@ -19,30 +66,185 @@ pub struct AppSettings {
 
     pub dapr_state_store_name: String,
 
+    pub dapr_pubsub_name: String,
+
+    //every worker reports on this one topic, the results name the task they belong to
+    pub dapr_recon_results_topic: String,
+
+    //the token the sidecar sends on its calls to the app, see DaprSubscriberSettings
+    pub dapr_app_api_token: Option<String>,
+
+    pub dapr_blob_binding_name: String,
+
     pub dapr_grpc_server_address: String,
+
+    pub dapr_max_retries: u32,
+
+    pub dapr_retry_base_delay_ms: u64,
+
+    pub dapr_retry_max_delay_ms: u64,
+
+    pub dapr_retryable_status_codes: Vec<Code>,
+
+    pub circuit_breaker_failure_threshold: u32,
+
+    pub circuit_breaker_open_duration_ms: u64,
+
+    pub jwt_jwks_file_path: Option<String>,
+
+    pub jwt_static_key: Option<String>,
+
+    pub jwt_algorithm: Option<Algorithm>,
+
+    pub jwt_issuer: Option<String>,
+
+    pub jwt_audience: Option<String>,
+
+    pub rate_limit_burst: u32,
+
+    pub rate_limit_refill_per_second: f64,
+
+    pub max_active_tasks_per_user: usize,
+
+    pub max_file_row_count: u64,
+
+    //uploads are held in memory while they are inspected and saved
+    pub max_upload_size_bytes: usize,
+
+    //checked when the service is set up, see IdGeneratorKind::from_setting
+    pub id_generator: String,
+
+    pub id_prefixes: IdPrefixes,
 }
 
-pub async fn setup_service() -> Result<Box<dyn ReconTaskAggregationServiceInterface>, AppError> {
-    let app_settings = read_app_settings();
+pub async fn setup_service(
+    app_settings: &AppSettings,
+) -> Result<Box<dyn ReconTaskAggregationServiceInterface>, AppError> {
+    app_settings.id_prefixes.ensure_well_formed()?;
+    let id_generator_kind = IdGeneratorKind::from_setting(&app_settings.id_generator)?;
+
+    //the clients below are clones of this one and share its connection to the sidecar
     let dapr_client = dapr::dapr_client::connect_to_dapr(&app_settings.dapr_grpc_server_address.clone()).await?;
-    let dapr_client2 = dapr::dapr_client::connect_to_dapr(&app_settings.dapr_grpc_server_address.clone()).await?;
+
     let recon_tasks_repo = ReconTaskDetailsRepositoryManager::new(
         app_settings.dapr_state_store_name.clone(),
-        dapr_client,
+        dapr_client.clone(),
+        app_settings.dapr_retryable_status_codes.clone(),
     );
 
     let recon_files_repo = ReconFileDetailsRepositoryManager::new(
         app_settings.dapr_state_store_name.clone(),
-        dapr_client2,
+        dapr_client.clone(),
+        app_settings.dapr_retryable_status_codes.clone(),
+    );
+
+    let recon_task_audit_repo = ReconTaskAuditRepositoryManager::new(
+        app_settings.dapr_state_store_name.clone(),
+        dapr_client.clone(),
+        app_settings.dapr_retryable_status_codes.clone(),
+    );
+
+    let recon_template_repo = ReconTemplateRepositoryManager::new(
+        app_settings.dapr_state_store_name.clone(),
+        dapr_client.clone(),
+        app_settings.dapr_retryable_status_codes.clone(),
+    );
+
+    let upload_session_repo = UploadSessionRepositoryManager::new(
+        app_settings.dapr_state_store_name.clone(),
+        dapr_client.clone(),
+        app_settings.dapr_retryable_status_codes.clone(),
+    );
+
+    let event_publisher = ReconTaskEventPublisher::new(
+        app_settings.dapr_pubsub_name.clone(),
+        dapr_client.clone(),
+        app_settings.dapr_retryable_status_codes.clone(),
+    );
+
+    let blob_store = DaprBindingBlobStore::new(
+        app_settings.dapr_blob_binding_name.clone(),
+        dapr_client,
+        app_settings.dapr_retryable_status_codes.clone(),
+    );
+
+    let retry_policy = RetryPolicy::new(
+        app_settings.dapr_max_retries,
+        Duration::from_millis(app_settings.dapr_retry_base_delay_ms),
+        Duration::from_millis(app_settings.dapr_retry_max_delay_ms),
+    );
+
+    //all repositories talk to the same sidecar so they share its health
+    let circuit_breaker = Arc::new(CircuitBreaker::new(
+        app_settings.circuit_breaker_failure_threshold,
+        Duration::from_millis(app_settings.circuit_breaker_open_duration_ms),
+    ));
+
+    let recon_tasks_repo = ResilientReconTaskDetailsRepository::new(
+        Box::new(recon_tasks_repo),
+        retry_policy.clone(),
+        circuit_breaker.clone(),
+    );
+
+    let recon_files_repo = ResilientReconFileDetailsRepository::new(
+        Box::new(recon_files_repo),
+        retry_policy.clone(),
+        circuit_breaker.clone(),
+    );
+
+    let recon_task_audit_repo = ResilientReconTaskAuditRepository::new(
+        Box::new(recon_task_audit_repo),
+        retry_policy.clone(),
+        circuit_breaker.clone(),
     );
 
+    let recon_template_repo = ResilientReconTemplateRepository::new(
+        Box::new(recon_template_repo),
+        retry_policy.clone(),
+        circuit_breaker.clone(),
+    );
+
+    let upload_session_repo = ResilientUploadSessionRepository::new(
+        Box::new(upload_session_repo),
+        retry_policy.clone(),
+        circuit_breaker.clone(),
+    );
+
+    let event_publisher = ResilientReconTaskEventPublisher::new(
+        Box::new(event_publisher),
+        retry_policy.clone(),
+        circuit_breaker.clone(),
+    );
+
+    let blob_store = ResilientBlobStore::new(Box::new(blob_store), retry_policy, circuit_breaker);
+
     let service: Box<dyn ReconTaskAggregationServiceInterface> =
         Box::new(ReconTaskAggregationService {
             recon_task_details_repo: Box::new(recon_tasks_repo),
 
             recon_file_details_repo: Box::new(recon_files_repo),
 
-            transformer: Box::new(Transformer {}),
+            recon_task_audit_repo: Box::new(recon_task_audit_repo),
+
+            recon_template_repo: Box::new(recon_template_repo),
+
+            transformer: Box::new(Transformer {
+                id_generator: new_id_generator(id_generator_kind),
+                id_prefixes: app_settings.id_prefixes.clone(),
+            }),
+
+            event_publisher: Box::new(event_publisher),
+
+            upload_session_repo: Box::new(upload_session_repo),
+
+            blob_store: Box::new(blob_store),
+
+            quotas: ReconTaskQuotas {
+                max_active_tasks_per_user: app_settings.max_active_tasks_per_user,
+                max_file_row_count: app_settings.max_file_row_count,
+            },
+
+            recon_results_topic: app_settings.dapr_recon_results_topic.clone(),
         });
 
     Ok(service)
@@ -59,5 +261,130 @@ pub fn read_app_settings() -> AppSettings {
 
         dapr_state_store_name: std::env::var("DAPR_RECON_TASKS_STORE_NAME")
             .unwrap_or(DEFAULT_DAPR_STORE_NAME.to_string()),
+
+        dapr_pubsub_name: std::env::var("DAPR_PUBSUB_NAME")
+            .unwrap_or(DEFAULT_DAPR_PUBSUB_NAME.to_string()),
+
+        dapr_recon_results_topic: std::env::var("DAPR_RECON_RESULTS_TOPIC")
+            .unwrap_or(DEFAULT_DAPR_RECON_RESULTS_TOPIC.to_string()),
+
+        dapr_app_api_token: std::env::var("APP_API_TOKEN").ok(),
+
+        dapr_blob_binding_name: std::env::var("DAPR_BLOB_BINDING_NAME")
+            .unwrap_or(DEFAULT_DAPR_BLOB_BINDING_NAME.to_string()),
+
+        dapr_max_retries: read_env_number("DAPR_MAX_RETRIES", DEFAULT_DAPR_MAX_RETRIES),
+
+        dapr_retry_base_delay_ms: read_env_number(
+            "DAPR_RETRY_BASE_DELAY_MS",
+            DEFAULT_DAPR_RETRY_BASE_DELAY_MS,
+        ),
+
+        dapr_retry_max_delay_ms: read_env_number(
+            "DAPR_RETRY_MAX_DELAY_MS",
+            DEFAULT_DAPR_RETRY_MAX_DELAY_MS,
+        ),
+
+        dapr_retryable_status_codes: std::env::var("DAPR_RETRYABLE_STATUS_CODES")
+            .map(|codes| parse_status_codes(&codes))
+            .unwrap_or(DEFAULT_RETRYABLE_STATUS_CODES.to_vec()),
+
+        circuit_breaker_failure_threshold: read_env_number(
+            "DAPR_CIRCUIT_BREAKER_FAILURE_THRESHOLD",
+            DEFAULT_CIRCUIT_BREAKER_FAILURE_THRESHOLD,
+        ),
+
+        circuit_breaker_open_duration_ms: read_env_number(
+            "DAPR_CIRCUIT_BREAKER_OPEN_DURATION_MS",
+            DEFAULT_CIRCUIT_BREAKER_OPEN_DURATION_MS,
+        ),
+
+        jwt_jwks_file_path: std::env::var("JWT_JWKS_FILE").ok(),
+
+        jwt_static_key: std::env::var("JWT_STATIC_KEY").ok(),
+
+        jwt_algorithm: std::env::var("JWT_ALGORITHM")
+            .ok()
+            .and_then(|algorithm| algorithm.parse::<Algorithm>().ok()),
+
+        jwt_issuer: std::env::var("JWT_ISSUER").ok(),
+
+        jwt_audience: std::env::var("JWT_AUDIENCE").ok(),
+
+        rate_limit_burst: read_env_number("RATE_LIMIT_BURST", DEFAULT_RATE_LIMIT_BURST),
+
+        rate_limit_refill_per_second: read_env_number(
+            "RATE_LIMIT_REFILL_PER_SECOND",
+            DEFAULT_RATE_LIMIT_REFILL_PER_SECOND,
+        ),
+
+        max_active_tasks_per_user: read_env_number(
+            "QUOTA_MAX_ACTIVE_TASKS_PER_USER",
+            DEFAULT_MAX_ACTIVE_TASKS_PER_USER,
+        ),
+
+        max_file_row_count: read_env_number("QUOTA_MAX_FILE_ROW_COUNT", DEFAULT_MAX_FILE_ROW_COUNT),
+
+        max_upload_size_bytes: read_env_number(
+            "QUOTA_MAX_UPLOAD_SIZE_BYTES",
+            DEFAULT_MAX_UPLOAD_SIZE_BYTES,
+        ),
+
+        id_generator: std::env::var("ID_GENERATOR")
+            .unwrap_or(DEFAULT_ID_GENERATOR.to_string()),
+
+        id_prefixes: read_id_prefixes(),
+    }
+}
+
+fn read_id_prefixes() -> IdPrefixes {
+    let defaults = IdPrefixes::default();
+    IdPrefixes {
+        recon_task: std::env::var("ID_PREFIX_RECON_TASK").unwrap_or(defaults.recon_task),
+
+        recon_file: std::env::var("ID_PREFIX_RECON_FILE").unwrap_or(defaults.recon_file),
+
+        recon_template: std::env::var("ID_PREFIX_RECON_TEMPLATE")
+            .unwrap_or(defaults.recon_template),
+
+        upload_session: std::env::var("ID_PREFIX_UPLOAD_SESSION")
+            .unwrap_or(defaults.upload_session),
+
+        primary_file_queue: std::env::var("ID_PREFIX_PRIMARY_FILE_QUEUE")
+            .unwrap_or(defaults.primary_file_queue),
+
+        comparison_file_queue: std::env::var("ID_PREFIX_COMPARISON_FILE_QUEUE")
+            .unwrap_or(defaults.comparison_file_queue),
+
+        recon_results_queue: std::env::var("ID_PREFIX_RECON_RESULTS_QUEUE")
+            .unwrap_or(defaults.recon_results_queue),
+    }
+}
+
+fn read_env_number<T: std::str::FromStr>(name: &str, default: T) -> T {
+    std::env::var(name)
+        .ok()
+        .and_then(|value| value.parse::<T>().ok())
+        .unwrap_or(default)
+}
+
+//connection errors mean the dapr sidecar is down or the circuit is open, so we
+//report them as 503s; rejections get the status that fits their reason,
+//everything else keeps the shared error mapping
+pub fn ok_or_error<T: Serialize>(response: Result<T, ServiceError>) -> HttpResponse {
+    match response {
+        Err(e) => match e.rejection {
+            Some(RejectionReason::Forbidden) => HttpResponse::Forbidden().json(e),
+            Some(RejectionReason::TooManyActiveTasks) | Some(RejectionReason::RateLimited) => {
+                HttpResponse::TooManyRequests().json(e)
+            }
+            Some(RejectionReason::FileTooLarge) => HttpResponse::PayloadTooLarge().json(e),
+            Some(RejectionReason::Conflict) => HttpResponse::Conflict().json(e),
+            None if matches!(e.kind, AppErrorKind::ConnectionError) => {
+                HttpResponse::ServiceUnavailable().json(e)
+            }
+            None => shared_utils::ok_or_error::<T>(Err(e.error)),
+        },
+        Ok(value) => shared_utils::ok_or_error(Ok(value)),
     }
 }
\ No newline at end of file
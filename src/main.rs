@@ -5,5 +5,9 @@ use crate::internal::web_api::server;
 
 #[actix_web::main]
 async fn main() -> std::io::Result<()> {
-    server::run_async().await
+    //`svc-task-details-repository-manager migrate` upgrades stored records and exits
+    match std::env::args().nth(1).as_deref() {
+        Some("migrate") => server::run_migrations_async().await,
+        _ => server::run_async().await,
+    }
 }